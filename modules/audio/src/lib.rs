@@ -126,6 +126,8 @@ mod inside {
         unsafe {
             debug_assert!(CTX.is_null(), "duplicated setup of audio system.");
 
+            #[cfg(feature = "alloc-tracking")]
+            let _scope = crayon::memory::scope("audio");
             let ctx = AudioSystem::new()?;
             CTX = Box::into_raw(Box::new(ctx));
             Ok(())
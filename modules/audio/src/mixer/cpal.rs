@@ -28,6 +28,8 @@ pub fn run(rx: Arc<RwLock<Vec<Command>>>) -> Result<()> {
     Builder::new()
         .name("Audio".into())
         .spawn(move || {
+            #[cfg(feature = "alloc-tracking")]
+            let _scope = crayon::memory::scope("audio");
             let mut bufs = Vec::new();
 
             events.run(move |id, buffer| {
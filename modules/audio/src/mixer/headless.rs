@@ -10,7 +10,9 @@ pub fn run(rx: Arc<RwLock<Vec<Command>>>) -> Result<()> {
     Builder::new()
         .name("Audio".into())
         .spawn(move || {
-            //
+            #[cfg(feature = "alloc-tracking")]
+            let _scope = crayon::memory::scope("audio");
+
             loop {
                 {
                     let mut rx = rx.write().unwrap();
@@ -3,6 +3,7 @@ use std::sync::{Arc, RwLock};
 use crayon::application::prelude::{LifecycleListener, LifecycleListenerHandle};
 use crayon::errors::Result;
 use crayon::math::prelude::Vector3;
+use crayon::res::gc::GarbageCollectorHandle;
 use crayon::res::utils::prelude::{ResourcePool, ResourceState};
 use crayon::uuid::Uuid;
 
@@ -13,6 +14,7 @@ use super::source::{AudioSource, AudioSourceHandle};
 /// The centralized management of audio sub-system.
 pub struct AudioSystem {
     lis: LifecycleListenerHandle,
+    collector: GarbageCollectorHandle,
     clips: Arc<RwLock<ResourcePool<AudioClipHandle, AudioClipLoader>>>,
     mixer: Mixer,
 }
@@ -23,6 +25,8 @@ struct AudioState {
 
 impl LifecycleListener for AudioState {
     fn on_pre_update(&mut self) -> Result<()> {
+        #[cfg(feature = "alloc-tracking")]
+        let _scope = crayon::memory::scope("audio");
         self.clips.write().unwrap().advance()?;
         Ok(())
     }
@@ -30,6 +34,7 @@ impl LifecycleListener for AudioState {
 
 impl Drop for AudioSystem {
     fn drop(&mut self) {
+        crayon::res::detach_collector(self.collector);
         crayon::application::detach(self.lis);
     }
 }
@@ -47,8 +52,16 @@ impl AudioSystem {
             clips: clips.clone(),
         };
 
+        let collector = {
+            let clips = clips.clone();
+            crayon::res::attach_collector(move |report| {
+                report.add("audio clips", clips.write().unwrap().collect_garbage());
+            })
+        };
+
         Ok(AudioSystem {
             lis: crayon::application::attach(state),
+            collector: collector,
             clips: clips,
             mixer: mixer,
         })
@@ -0,0 +1,242 @@
+//! Custom derives of crayon game framework.
+//!
+//! # Vertex
+//!
+//! `#[derive(Vertex)]` is an alternative of `impl_vertex!` which works with plain struct
+//! declarations. Every field should be tagged with the shader attribute it feeds, and
+//! optionally `normalized`. The vertex format and size are inferred from the field type,
+//! which should be an array of `i8`, `u8`, `i16`, `u16` or `f32` with 1 to 4 elements.
+//!
+//! ```rust,ignore
+//! #[repr(C)]
+//! #[derive(Debug, Copy, Clone, Default, Vertex)]
+//! struct Vertex {
+//!     #[vertex(Position)]
+//!     position: [f32; 3],
+//!     #[vertex(Color0, normalized)]
+//!     color: [u8; 4],
+//! }
+//!
+//! let mut params = MeshParams::default();
+//! params.layout = Vertex::layout();
+//! ```
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Expr, Fields, Ident, Lit, Meta, NestedMeta, Type};
+
+#[proc_macro_derive(Vertex, attributes(vertex))]
+pub fn derive_vertex(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match vertex(&input) {
+        Ok(v) => v.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+struct VertexField {
+    ident: Ident,
+    attribute: Ident,
+    format: Ident,
+    size: u8,
+    normalized: bool,
+}
+
+fn vertex(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "Vertex could only be derived for structs with named fields.",
+                ));
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "Vertex could only be derived for structs.",
+            ));
+        }
+    };
+
+    if !input.attrs.iter().any(is_repr_c) {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "Vertex requires #[repr(C)] to keep the field offsets stable.",
+        ));
+    }
+
+    let mut vertex_fields = Vec::new();
+    for field in fields {
+        let ident = field.ident.clone().unwrap();
+        let (attribute, normalized) = parse_field_attrs(field)?;
+        let (format, size) = parse_field_type(&field.ty)?;
+
+        vertex_fields.push(VertexField {
+            ident,
+            attribute,
+            format,
+            size,
+            normalized,
+        });
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let layouts = vertex_fields.iter().map(|v| {
+        let (ident, attribute, format) = (&v.ident, &v.attribute, &v.format);
+        let (size, normalized) = (v.size, v.normalized);
+
+        quote! {
+            builder.with(
+                ::crayon::video::assets::shader::Attribute::#attribute,
+                ::crayon::video::assets::mesh::VertexFormat::#format,
+                #size,
+                #normalized,
+                {
+                    let ptr: *const #name #ty_generics = ::std::ptr::null();
+                    unsafe { &(*ptr).#ident as *const _ as usize as u8 }
+                },
+            );
+        }
+    });
+
+    let attributes = vertex_fields.iter().map(|v| {
+        let (attribute, size) = (&v.attribute, v.size);
+
+        quote! {
+            let builder = builder.with(
+                ::crayon::video::assets::shader::Attribute::#attribute,
+                #size,
+            );
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #[allow(dead_code)]
+            pub fn layout() -> ::crayon::video::assets::mesh::VertexLayout {
+                let mut builder =
+                    ::crayon::video::assets::mesh::macros::CustomVertexLayoutBuilder::new();
+                #(#layouts)*
+                builder.finish(::std::mem::size_of::<Self>() as u8)
+            }
+
+            #[allow(dead_code)]
+            pub fn attributes() -> ::crayon::video::assets::shader::AttributeLayout {
+                let builder = ::crayon::video::assets::shader::AttributeLayoutBuilder::new();
+                #(#attributes)*
+                builder.finish()
+            }
+
+            #[allow(dead_code)]
+            pub fn encode(values: &[Self]) -> &[u8] {
+                let len = values.len() * ::std::mem::size_of::<Self>();
+                unsafe { ::std::slice::from_raw_parts(values.as_ptr() as *const u8, len) }
+            }
+        }
+    })
+}
+
+fn is_repr_c(attr: &syn::Attribute) -> bool {
+    match attr.parse_meta() {
+        Ok(Meta::List(ref list)) if list.ident == "repr" => list.nested.iter().any(|v| match *v {
+            NestedMeta::Meta(Meta::Word(ref ident)) => ident == "C",
+            _ => false,
+        }),
+        _ => false,
+    }
+}
+
+fn parse_field_attrs(field: &syn::Field) -> syn::Result<(Ident, bool)> {
+    let mut attribute = None;
+    let mut normalized = false;
+
+    for attr in &field.attrs {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => {
+                if list.ident != "vertex" {
+                    continue;
+                }
+
+                list
+            }
+            ref meta if meta.name() == "vertex" => {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "Expected #[vertex(Attribute)] or #[vertex(Attribute, normalized)].",
+                ));
+            }
+            _ => continue,
+        };
+
+        for nested in list.nested.iter() {
+            match *nested {
+                NestedMeta::Meta(Meta::Word(ref ident)) if ident == "normalized" => {
+                    normalized = true;
+                }
+                NestedMeta::Meta(Meta::Word(ref ident)) if attribute.is_none() => {
+                    attribute = Some(ident.clone());
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        nested,
+                        "Unexpected argument of #[vertex(..)].",
+                    ));
+                }
+            }
+        }
+    }
+
+    match attribute {
+        Some(attribute) => Ok((attribute, normalized)),
+        None => Err(syn::Error::new(
+            field.span(),
+            "Vertex field should be tagged with its attribute, e.g. #[vertex(Position)].",
+        )),
+    }
+}
+
+fn parse_field_type(ty: &Type) -> syn::Result<(Ident, u8)> {
+    let err = || {
+        syn::Error::new_spanned(
+            ty,
+            "Vertex field should be an array of i8, u8, i16, u16 or f32 with 1 to 4 elements.",
+        )
+    };
+
+    let array = match *ty {
+        Type::Array(ref array) => array,
+        _ => return Err(err()),
+    };
+
+    let elem = &array.elem;
+    let format = match quote!(#elem).to_string().as_str() {
+        "i8" => "Byte",
+        "u8" => "UByte",
+        "i16" => "Short",
+        "u16" => "UShort",
+        "f32" => "Float",
+        _ => return Err(err()),
+    };
+
+    let size = match array.len {
+        Expr::Lit(ref v) => match v.lit {
+            Lit::Int(ref v) if v.value() >= 1 && v.value() <= 4 => v.value() as u8,
+            _ => return Err(err()),
+        },
+        _ => return Err(err()),
+    };
+
+    Ok((Ident::new(format, Span::call_site()), size))
+}
@@ -0,0 +1,43 @@
+#[macro_use]
+extern crate crayon_derive;
+extern crate crayon;
+
+use crayon::video::assets::prelude::*;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Vertex)]
+struct Vertex {
+    #[vertex(Position)]
+    position: [f32; 3],
+    #[vertex(Color0, normalized)]
+    color: [u8; 4],
+    #[vertex(Texcoord0)]
+    texcoord: [i16; 2],
+}
+
+#[test]
+fn layout() {
+    let layout = Vertex::layout();
+    assert_eq!(layout.stride() as usize, ::std::mem::size_of::<Vertex>());
+    assert_eq!(layout.offset(Attribute::Position), Some(0));
+    assert_eq!(layout.offset(Attribute::Color0), Some(12));
+    assert_eq!(layout.offset(Attribute::Texcoord0), Some(16));
+    assert_eq!(layout.offset(Attribute::Normal), None);
+
+    let element = layout.element(Attribute::Color0).unwrap();
+    assert_eq!(element.format, VertexFormat::UByte);
+    assert_eq!(element.size, 4);
+    assert_eq!(element.normalized, true);
+
+    let element = layout.element(Attribute::Texcoord0).unwrap();
+    assert_eq!(element.format, VertexFormat::Short);
+    assert_eq!(element.size, 2);
+    assert_eq!(element.normalized, false);
+}
+
+#[test]
+fn encode() {
+    let verts = [Vertex::default(), Vertex::default()];
+    let bytes = Vertex::encode(&verts);
+    assert_eq!(bytes.len(), ::std::mem::size_of::<Vertex>() * 2);
+}
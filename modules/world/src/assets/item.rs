@@ -0,0 +1,94 @@
+use crayon::errors::*;
+use crayon::res::utils::prelude::ResourceState;
+use crayon::sched::prelude::LatchProbe;
+use crayon::utils::hash::FastHashMap;
+
+impl_handle!(ItemCatalogHandle);
+
+/// The definition of an item, which is shared by all the instances of it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ItemDefinition {
+    /// The unique name of this item.
+    pub name: String,
+    /// The maximum number of items in a stack.
+    pub max_stack: u32,
+    /// The tags which are checked by the filters of inventory slots.
+    pub tags: Vec<String>,
+    /// The custom numeric properties, e.g. weight and price.
+    pub properties: FastHashMap<String, f32>,
+}
+
+impl ItemDefinition {
+    /// Creates a definition which could not be stacked.
+    pub fn new<T: Into<String>>(name: T) -> Self {
+        ItemDefinition {
+            name: name.into(),
+            max_stack: 1,
+            tags: Vec::new(),
+            properties: FastHashMap::default(),
+        }
+    }
+
+    /// Checks if this item has the tag.
+    #[inline]
+    pub fn has_tag<T: AsRef<str>>(&self, tag: T) -> bool {
+        let tag = tag.as_ref();
+        self.tags.iter().any(|v| v == tag)
+    }
+
+    /// Gets the custom property.
+    #[inline]
+    pub fn property<T: AsRef<str>>(&self, name: T) -> Option<f32> {
+        self.properties.get(name.as_ref()).cloned()
+    }
+}
+
+/// A catalog asset of item definitions.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ItemCatalog {
+    pub items: Vec<ItemDefinition>,
+
+    #[serde(skip)]
+    remap: FastHashMap<String, usize>,
+}
+
+impl ItemCatalog {
+    pub fn new(items: Vec<ItemDefinition>) -> Result<Self> {
+        let mut catalog = ItemCatalog {
+            items,
+            remap: FastHashMap::default(),
+        };
+
+        catalog.validate()?;
+        Ok(catalog)
+    }
+
+    /// Validates the definitions and builds the index of names.
+    pub fn validate(&mut self) -> Result<()> {
+        self.remap.clear();
+
+        for (i, v) in self.items.iter().enumerate() {
+            if v.max_stack == 0 {
+                bail!("Item {} should have a positive max_stack.", v.name);
+            }
+
+            if self.remap.insert(v.name.clone(), i).is_some() {
+                bail!("Item {} is defined more than once.", v.name);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Gets the definition of item with the name.
+    #[inline]
+    pub fn get<T: AsRef<str>>(&self, name: T) -> Option<&ItemDefinition> {
+        self.remap.get(name.as_ref()).map(|&i| &self.items[i])
+    }
+}
+
+impl LatchProbe for ItemCatalogHandle {
+    fn is_set(&self) -> bool {
+        ResourceState::NotReady != crate::item_catalog_state(*self)
+    }
+}
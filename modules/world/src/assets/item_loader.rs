@@ -0,0 +1,54 @@
+use std::io::Cursor;
+use std::sync::Arc;
+
+use crayon::bincode;
+use crayon::errors::Result;
+use crayon::res::utils::prelude::ResourceLoader;
+
+use super::item::*;
+
+pub const MAGIC: [u8; 8] = [
+    'I' as u8, 'T' as u8, 'E' as u8, 'M' as u8, ' ' as u8, 0, 0, 1,
+];
+
+#[derive(Clone)]
+pub struct ItemCatalogLoader {}
+
+impl ItemCatalogLoader {
+    pub fn new() -> Self {
+        ItemCatalogLoader {}
+    }
+}
+
+impl ResourceLoader for ItemCatalogLoader {
+    type Handle = ItemCatalogHandle;
+    type Intermediate = ItemCatalog;
+    type Resource = Arc<ItemCatalog>;
+
+    fn load(&self, handle: Self::Handle, bytes: &[u8]) -> Result<Self::Intermediate> {
+        if bytes.len() < 8 || &bytes[0..8] != &MAGIC[..] {
+            bail!("[ItemCatalogLoader] MAGIC number not match.");
+        }
+
+        let mut file = Cursor::new(&bytes[8..]);
+        let mut catalog: ItemCatalog = bincode::deserialize_from(&mut file)?;
+        catalog.validate()?;
+
+        info!(
+            "[ItemCatalogLoader] load {:?}. (Items: {})",
+            handle,
+            catalog.items.len()
+        );
+
+        Ok(catalog)
+    }
+
+    fn create(&self, handle: Self::Handle, item: Self::Intermediate) -> Result<Self::Resource> {
+        info!("[ItemCatalogLoader] create {:?}.", handle);
+        Ok(Arc::new(item))
+    }
+
+    fn delete(&self, handle: Self::Handle, _: Self::Resource) {
+        info!("[ItemCatalogLoader] delete {:?}.", handle);
+    }
+}
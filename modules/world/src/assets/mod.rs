@@ -1,10 +1,20 @@
+pub mod item;
+pub mod item_loader;
 pub mod prefab;
 pub mod prefab_loader;
+pub mod quest;
+pub mod quest_loader;
 
 pub mod mesh_builder;
 pub mod texture_builder;
 
 pub mod prelude {
+    pub use super::item::{ItemCatalog, ItemCatalogHandle, ItemDefinition};
+    pub use super::item_loader::ItemCatalogLoader;
     pub use super::prefab::{Prefab, PrefabHandle};
     pub use super::prefab_loader::PrefabLoader;
+    pub use super::quest::{
+        ObjectiveDefinition, QuestBook, QuestBookHandle, QuestDefinition, QuestReward,
+    };
+    pub use super::quest_loader::QuestBookLoader;
 }
@@ -0,0 +1,124 @@
+use crayon::errors::*;
+use crayon::res::utils::prelude::ResourceState;
+use crayon::sched::prelude::LatchProbe;
+use crayon::utils::hash::FastHashMap;
+
+impl_handle!(QuestBookHandle);
+
+/// An objective of quest, which is completed once its progress reaches `required`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ObjectiveDefinition {
+    pub name: String,
+    pub description: String,
+    pub required: u32,
+}
+
+/// A reward which is granted when the quest is completed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct QuestReward {
+    /// The name of rewarded item, currency or experience.
+    pub item: String,
+    pub count: u32,
+}
+
+/// The definition of quest.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct QuestDefinition {
+    /// The unique name of this quest.
+    pub name: String,
+    pub description: String,
+    /// The quests which should be completed before starting this one.
+    pub prerequisites: Vec<String>,
+    pub objectives: Vec<ObjectiveDefinition>,
+    pub rewards: Vec<QuestReward>,
+}
+
+/// A book asset of quest definitions.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct QuestBook {
+    pub quests: Vec<QuestDefinition>,
+
+    #[serde(skip)]
+    remap: FastHashMap<String, usize>,
+}
+
+impl QuestBook {
+    pub fn new(quests: Vec<QuestDefinition>) -> Result<Self> {
+        let mut book = QuestBook {
+            quests,
+            remap: FastHashMap::default(),
+        };
+
+        book.validate()?;
+        Ok(book)
+    }
+
+    /// Validates the definitions and builds the index of names.
+    pub fn validate(&mut self) -> Result<()> {
+        self.remap.clear();
+
+        for (i, v) in self.quests.iter().enumerate() {
+            if v.objectives.is_empty() {
+                bail!("Quest {} should have at least one objective.", v.name);
+            }
+
+            for (j, w) in v.objectives.iter().enumerate() {
+                if w.required == 0 {
+                    bail!("Objective {} of quest {} requires nothing.", w.name, v.name);
+                }
+
+                if v.objectives[..j].iter().any(|o| o.name == w.name) {
+                    bail!(
+                        "Objective {} of quest {} is defined more than once.",
+                        w.name,
+                        v.name
+                    );
+                }
+            }
+
+            if self.remap.insert(v.name.clone(), i).is_some() {
+                bail!("Quest {} is defined more than once.", v.name);
+            }
+        }
+
+        for v in &self.quests {
+            for p in &v.prerequisites {
+                if !self.remap.contains_key(p) {
+                    bail!("Quest {} requires undefined quest {}.", v.name, p);
+                }
+            }
+        }
+
+        // Resolves the quests whose prerequisites are resolved, until nothing changes.
+        // The remaining ones are in cycles.
+        let mut resolved = vec![false; self.quests.len()];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (i, v) in self.quests.iter().enumerate() {
+                if !resolved[i] && v.prerequisites.iter().all(|p| resolved[self.remap[p]]) {
+                    resolved[i] = true;
+                    changed = true;
+                }
+            }
+        }
+
+        if let Some(i) = resolved.iter().position(|&v| !v) {
+            bail!("Quest {} has circular prerequisites.", self.quests[i].name);
+        }
+
+        Ok(())
+    }
+
+    /// Gets the definition of quest with the name.
+    #[inline]
+    pub fn get<T: AsRef<str>>(&self, name: T) -> Option<&QuestDefinition> {
+        self.remap.get(name.as_ref()).map(|&i| &self.quests[i])
+    }
+}
+
+impl LatchProbe for QuestBookHandle {
+    fn is_set(&self) -> bool {
+        ResourceState::NotReady != crate::quest_book_state(*self)
+    }
+}
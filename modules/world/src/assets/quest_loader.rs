@@ -0,0 +1,54 @@
+use std::io::Cursor;
+use std::sync::Arc;
+
+use crayon::bincode;
+use crayon::errors::Result;
+use crayon::res::utils::prelude::ResourceLoader;
+
+use super::quest::*;
+
+pub const MAGIC: [u8; 8] = [
+    'Q' as u8, 'U' as u8, 'S' as u8, 'T' as u8, ' ' as u8, 0, 0, 1,
+];
+
+#[derive(Clone)]
+pub struct QuestBookLoader {}
+
+impl QuestBookLoader {
+    pub fn new() -> Self {
+        QuestBookLoader {}
+    }
+}
+
+impl ResourceLoader for QuestBookLoader {
+    type Handle = QuestBookHandle;
+    type Intermediate = QuestBook;
+    type Resource = Arc<QuestBook>;
+
+    fn load(&self, handle: Self::Handle, bytes: &[u8]) -> Result<Self::Intermediate> {
+        if bytes.len() < 8 || &bytes[0..8] != &MAGIC[..] {
+            bail!("[QuestBookLoader] MAGIC number not match.");
+        }
+
+        let mut file = Cursor::new(&bytes[8..]);
+        let mut book: QuestBook = bincode::deserialize_from(&mut file)?;
+        book.validate()?;
+
+        info!(
+            "[QuestBookLoader] load {:?}. (Quests: {})",
+            handle,
+            book.quests.len()
+        );
+
+        Ok(book)
+    }
+
+    fn create(&self, handle: Self::Handle, item: Self::Intermediate) -> Result<Self::Resource> {
+        info!("[QuestBookLoader] create {:?}.", handle);
+        Ok(Arc::new(item))
+    }
+
+    fn delete(&self, handle: Self::Handle, _: Self::Resource) {
+        info!("[QuestBookLoader] delete {:?}.", handle);
+    }
+}
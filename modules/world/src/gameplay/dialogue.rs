@@ -0,0 +1,550 @@
+//! Branching dialogues, with the lines and choices presented by the UI of game.
+//!
+//! A `Dialogue` is a graph of named nodes, which is parsed from a simple authoring format:
+//!
+//! ```text
+//! # Comments start with '#'.
+//! == start
+//! guard: Halt! Who goes there?
+//! ! play_sound alert
+//! * Just a traveler. -> traveler
+//! * {has_pass} Show the pass. -> pass
+//! * Leave. -> END
+//!
+//! == traveler
+//! guard: Move along then.
+//! {is_night} -> night
+//! ```
+//!
+//! * `== name` starts a node.
+//! * `speaker: text` or `text` is a line.
+//! * `* text -> target` is a choice. Consecutive choices are presented together.
+//! * `-> target` jumps to another node, and `-> END` ends the dialogue.
+//! * `! command` invokes the command callback, e.g. to give items or play sounds.
+//! * Choices and jumps could be guarded with `{condition}`, which is evaluated by the
+//!   condition callback.
+//!
+//! A node ends the dialogue once all of its steps are played. Nodes which jump to each
+//! other without any line or choices in between are rejected, since playing them would
+//! never stop.
+//!
+//! The `DialoguePlayer` plays a dialogue and emits `DialogueEvent`s for the UI. It stops
+//! at every line until `advance` is called, and at every group of choices until one of
+//! them is chosen. Texts are passed through the localizer if there is one, with the
+//! source text as key.
+//!
+//! ```rust,ignore
+//! let mut player = DialoguePlayer::new(Arc::new(Dialogue::parse(source)?));
+//! player.set_conditions(|name| flags.contains(name));
+//! player.start("start")?;
+//!
+//! for v in player.drain_events() {
+//!     match v {
+//!         DialogueEvent::Line { speaker, text } => ui.show_line(speaker, text),
+//!         DialogueEvent::Choices(choices) => ui.show_choices(choices),
+//!         _ => {}
+//!     }
+//! }
+//! ```
+
+use std::sync::Arc;
+use std::vec::Drain;
+
+use crayon::errors::Result;
+use crayon::utils::hash::FastHashMap;
+
+/// The name of target which ends the dialogue.
+pub const END: &str = "END";
+
+/// The maximum number of steps played without stopping at a line or choices. Cycles of
+/// guarded jumps could not be detected when parsing, so the player gives up after it.
+pub const MAX_STEPS: usize = 1024;
+
+/// A choice of dialogue.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DialogueChoice {
+    pub text: String,
+    pub target: String,
+    pub condition: Option<String>,
+}
+
+/// A step of dialogue node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DialogueStep {
+    Line {
+        speaker: Option<String>,
+        text: String,
+    },
+    Choices(Vec<DialogueChoice>),
+    Jump {
+        target: String,
+        condition: Option<String>,
+    },
+    Command(String),
+}
+
+/// A node of dialogue.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DialogueNode {
+    pub name: String,
+    pub steps: Vec<DialogueStep>,
+}
+
+/// A graph of dialogue nodes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Dialogue {
+    pub nodes: Vec<DialogueNode>,
+}
+
+impl Dialogue {
+    /// Parses a dialogue from the authoring format.
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut dialogue = Dialogue::default();
+
+        for (i, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if line.starts_with("==") {
+                let name = line[2..].trim();
+                if name.is_empty() || name == END {
+                    bail!("Line {}: invalid node name {:?}.", i + 1, name);
+                }
+
+                dialogue.nodes.push(DialogueNode {
+                    name: name.to_owned(),
+                    steps: Vec::new(),
+                });
+
+                continue;
+            }
+
+            let node = match dialogue.nodes.last_mut() {
+                Some(v) => v,
+                None => bail!("Line {}: steps should be placed in a node.", i + 1),
+            };
+
+            let step = match Self::parse_step(line) {
+                Some(v) => v,
+                None => bail!("Line {}: could not parse {:?}.", i + 1, line),
+            };
+
+            // Merges the consecutive choices into one group.
+            if let DialogueStep::Choices(mut choices) = step {
+                if let Some(&mut DialogueStep::Choices(ref mut v)) = node.steps.last_mut() {
+                    v.append(&mut choices);
+                    continue;
+                }
+
+                node.steps.push(DialogueStep::Choices(choices));
+            } else {
+                node.steps.push(step);
+            }
+        }
+
+        dialogue.validate()?;
+        Ok(dialogue)
+    }
+
+    /// Checks if the names of nodes are unique, every target exists, and no nodes jump
+    /// to each other unconditionally without any line or choices in between.
+    pub fn validate(&self) -> Result<()> {
+        let mut names = FastHashMap::default();
+        for (i, v) in self.nodes.iter().enumerate() {
+            if names.insert(v.name.as_str(), i).is_some() {
+                bail!("Dialogue node {} is defined more than once.", v.name);
+            }
+        }
+
+        let check = |target: &str| -> Result<()> {
+            if target != END && !names.contains_key(target) {
+                bail!("Dialogue node {} is not defined.", target);
+            }
+
+            Ok(())
+        };
+
+        for node in &self.nodes {
+            for step in &node.steps {
+                match *step {
+                    DialogueStep::Choices(ref choices) => {
+                        for v in choices {
+                            check(&v.target)?;
+                        }
+                    }
+                    DialogueStep::Jump { ref target, .. } => check(target)?,
+                    _ => {}
+                }
+            }
+        }
+
+        // Every node has at most one node that it always jumps to before stopping, so
+        // a cycle is found by following the jumps no more than the number of nodes.
+        let jumps: Vec<_> = self
+            .nodes
+            .iter()
+            .map(|v| Self::unconditional_jump(v).and_then(|w| names.get(w).cloned()))
+            .collect();
+
+        for (i, v) in self.nodes.iter().enumerate() {
+            let mut next = jumps[i];
+            for _ in 0..self.nodes.len() {
+                next = match next {
+                    Some(n) if n == i => bail!(
+                        "Dialogue node {} jumps back to itself without any line or choices.",
+                        v.name
+                    ),
+                    Some(n) => jumps[n],
+                    None => break,
+                };
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Gets the index of node with the name.
+    #[inline]
+    pub fn find<T: AsRef<str>>(&self, name: T) -> Option<usize> {
+        let name = name.as_ref();
+        self.nodes.iter().position(|v| v.name == name)
+    }
+
+    /// Gets the target of the first unconditional jump, if the node reaches it without
+    /// stopping at a line or choices.
+    fn unconditional_jump(node: &DialogueNode) -> Option<&str> {
+        for step in &node.steps {
+            match *step {
+                DialogueStep::Line { .. } | DialogueStep::Choices(_) => return None,
+                DialogueStep::Jump {
+                    ref target,
+                    condition: None,
+                } => return Some(target),
+                _ => {}
+            }
+        }
+
+        None
+    }
+
+    fn parse_step(line: &str) -> Option<DialogueStep> {
+        if line.starts_with('!') {
+            let command = line[1..].trim();
+            if command.is_empty() {
+                return None;
+            }
+
+            return Some(DialogueStep::Command(command.to_owned()));
+        }
+
+        if line.starts_with('*') {
+            let (condition, rest) = Self::parse_condition(line[1..].trim())?;
+            let (text, target) = Self::parse_target(rest)?;
+            if text.is_empty() {
+                return None;
+            }
+
+            return Some(DialogueStep::Choices(vec![DialogueChoice {
+                text: text.to_owned(),
+                target,
+                condition,
+            }]));
+        }
+
+        let (condition, rest) = Self::parse_condition(line)?;
+        if rest.starts_with("->") {
+            let (_, target) = Self::parse_target(rest)?;
+            return Some(DialogueStep::Jump { target, condition });
+        }
+
+        if condition.is_some() {
+            return None;
+        }
+
+        // Speakers are single words before the first colon.
+        let (speaker, text) = match line.find(':') {
+            Some(i) if i > 0 && !line[..i].contains(char::is_whitespace) => {
+                (Some(line[..i].to_owned()), line[i + 1..].trim())
+            }
+            _ => (None, line),
+        };
+
+        Some(DialogueStep::Line {
+            speaker,
+            text: text.to_owned(),
+        })
+    }
+
+    fn parse_condition(line: &str) -> Option<(Option<String>, &str)> {
+        if !line.starts_with('{') {
+            return Some((None, line));
+        }
+
+        let end = line.find('}')?;
+        let condition = line[1..end].trim();
+        if condition.is_empty() {
+            return None;
+        }
+
+        Some((Some(condition.to_owned()), line[end + 1..].trim()))
+    }
+
+    fn parse_target(line: &str) -> Option<(&str, String)> {
+        let i = line.rfind("->")?;
+        let target = line[i + 2..].trim();
+        if target.is_empty() {
+            return None;
+        }
+
+        Some((line[..i].trim(), target.to_owned()))
+    }
+}
+
+/// The events of dialogue playback.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DialogueEvent {
+    /// A line is presented, and the player waits until `advance` is called.
+    Line {
+        speaker: Option<String>,
+        text: String,
+    },
+    /// The available choices are presented, and the player waits until one of them is
+    /// chosen.
+    Choices(Vec<String>),
+    /// A command is invoked.
+    Command(String),
+    /// The dialogue is ended.
+    Ended,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Waiting {
+    Line,
+    Choices(Vec<String>),
+}
+
+/// Plays a dialogue step by step.
+pub struct DialoguePlayer {
+    dialogue: Arc<Dialogue>,
+    cursor: Option<(usize, usize)>,
+    waiting: Option<Waiting>,
+    conditions: Option<Box<dyn FnMut(&str) -> bool>>,
+    commands: Option<Box<dyn FnMut(&str)>>,
+    localizer: Option<Box<dyn Fn(&str) -> Option<String>>>,
+    events: Vec<DialogueEvent>,
+}
+
+impl DialoguePlayer {
+    pub fn new(dialogue: Arc<Dialogue>) -> Self {
+        DialoguePlayer {
+            dialogue,
+            cursor: None,
+            waiting: None,
+            conditions: None,
+            commands: None,
+            localizer: None,
+            events: Vec::new(),
+        }
+    }
+
+    /// Sets the callback which evaluates conditions. Conditions are false if there is no
+    /// callback.
+    pub fn set_conditions<T>(&mut self, func: T)
+    where
+        T: FnMut(&str) -> bool + 'static,
+    {
+        self.conditions = Some(Box::new(func));
+    }
+
+    /// Sets the callback which is invoked with commands.
+    pub fn set_commands<T>(&mut self, func: T)
+    where
+        T: FnMut(&str) + 'static,
+    {
+        self.commands = Some(Box::new(func));
+    }
+
+    /// Sets the localizer which translates texts, e.g. with the string tables of game.
+    /// Texts are kept as is if it returns None.
+    pub fn set_localizer<T>(&mut self, func: T)
+    where
+        T: Fn(&str) -> Option<String> + 'static,
+    {
+        self.localizer = Some(Box::new(func));
+    }
+
+    /// Checks if the dialogue is playing.
+    #[inline]
+    pub fn is_playing(&self) -> bool {
+        self.cursor.is_some()
+    }
+
+    /// Gets the name of the current node.
+    #[inline]
+    pub fn node(&self) -> Option<&str> {
+        self.cursor
+            .map(|(node, _)| self.dialogue.nodes[node].name.as_str())
+    }
+
+    /// Starts playing from the node.
+    pub fn start<T: AsRef<str>>(&mut self, node: T) -> Result<()> {
+        let node = node.as_ref();
+        let index = match self.dialogue.find(node) {
+            Some(v) => v,
+            None => bail!("Dialogue node {} is not defined.", node),
+        };
+
+        self.cursor = Some((index, 0));
+        self.waiting = None;
+        self.run()
+    }
+
+    /// Continues after a line. Returns false if the player is not waiting on a line.
+    pub fn advance(&mut self) -> Result<bool> {
+        if self.waiting != Some(Waiting::Line) {
+            return Ok(false);
+        }
+
+        self.waiting = None;
+        self.run()?;
+        Ok(true)
+    }
+
+    /// Chooses one of the presented choices. Returns false if the player is not waiting
+    /// on choices or the index is out of range.
+    pub fn choose(&mut self, index: usize) -> Result<bool> {
+        let target = match self.waiting {
+            Some(Waiting::Choices(ref targets)) if index < targets.len() => targets[index].clone(),
+            _ => return Ok(false),
+        };
+
+        self.waiting = None;
+        self.jump(&target);
+        self.run()?;
+        Ok(true)
+    }
+
+    /// Stops playing.
+    pub fn stop(&mut self) {
+        if self.cursor.is_some() {
+            self.end();
+        }
+    }
+
+    /// Drains the events since the events were drained last time.
+    #[inline]
+    pub fn drain_events(&mut self) -> Drain<DialogueEvent> {
+        self.events.drain(..)
+    }
+
+    /// Plays until a line or choices, and ends the dialogue with an error if it takes
+    /// more than `MAX_STEPS` steps.
+    fn run(&mut self) -> Result<()> {
+        let dialogue = self.dialogue.clone();
+        let mut steps = 0;
+
+        while let Some((node, index)) = self.cursor {
+            let step = match dialogue.nodes[node].steps.get(index) {
+                Some(v) => v,
+                None => {
+                    self.end();
+                    return Ok(());
+                }
+            };
+
+            steps += 1;
+            if steps > MAX_STEPS {
+                self.end();
+                bail!(
+                    "Dialogue node {} is played more than {} steps without any line or choices.",
+                    dialogue.nodes[node].name,
+                    MAX_STEPS
+                );
+            }
+
+            self.cursor = Some((node, index + 1));
+
+            match *step {
+                DialogueStep::Line {
+                    ref speaker,
+                    ref text,
+                } => {
+                    let text = self.localize(text);
+                    self.events.push(DialogueEvent::Line {
+                        speaker: speaker.clone(),
+                        text,
+                    });
+
+                    self.waiting = Some(Waiting::Line);
+                    return Ok(());
+                }
+                DialogueStep::Choices(ref choices) => {
+                    let mut texts = Vec::new();
+                    let mut targets = Vec::new();
+                    for v in choices {
+                        if self.test(&v.condition) {
+                            texts.push(self.localize(&v.text));
+                            targets.push(v.target.clone());
+                        }
+                    }
+
+                    // Skips the choices if none of them is available.
+                    if !targets.is_empty() {
+                        self.events.push(DialogueEvent::Choices(texts));
+                        self.waiting = Some(Waiting::Choices(targets));
+                        return Ok(());
+                    }
+                }
+                DialogueStep::Jump {
+                    ref target,
+                    ref condition,
+                } => {
+                    if self.test(condition) {
+                        self.jump(target);
+                    }
+                }
+                DialogueStep::Command(ref command) => {
+                    if let Some(ref mut func) = self.commands {
+                        func(command.as_str());
+                    }
+
+                    self.events.push(DialogueEvent::Command(command.clone()));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn jump(&mut self, target: &str) {
+        match self.dialogue.find(target) {
+            Some(node) => self.cursor = Some((node, 0)),
+            None => self.end(),
+        }
+    }
+
+    fn end(&mut self) {
+        self.cursor = None;
+        self.waiting = None;
+        self.events.push(DialogueEvent::Ended);
+    }
+
+    fn test(&mut self, condition: &Option<String>) -> bool {
+        match *condition {
+            Some(ref v) => match self.conditions {
+                Some(ref mut func) => func(v.as_str()),
+                None => false,
+            },
+            None => true,
+        }
+    }
+
+    fn localize(&self, text: &str) -> String {
+        self.localizer
+            .as_ref()
+            .and_then(|func| func(text))
+            .unwrap_or_else(|| text.to_owned())
+    }
+}
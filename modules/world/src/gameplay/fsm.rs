@@ -0,0 +1,246 @@
+//! Finite state machines for the gameplay logic of entities.
+//!
+//! An `Fsm` holds the current state, which is usually a plain enum, and runs the callbacks
+//! registered on the exit and enter of states when it transitions. Every transition is
+//! also recorded as a `StateTransition` event, which could be drained by the systems
+//! interested in it at the end of frame.
+//!
+//! It's named apart from `crayon::application::prelude::StateMachine`, the stack of game
+//! screens, so both preludes could be glob imported together.
+//!
+//! ```rust,ignore
+//! #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+//! enum Door { Closed, Opening, Open }
+//!
+//! let mut fsm = Fsm::new(Door::Closed);
+//! fsm.allow(Door::Closed, Door::Opening);
+//! fsm.allow(Door::Opening, Door::Open);
+//! fsm.on_enter(Door::Open, |_| println!("The door is open."));
+//!
+//! assert!(!fsm.transition(Door::Open));
+//! assert!(fsm.transition(Door::Opening));
+//! ```
+
+use std::fmt;
+use std::hash::Hash;
+use std::vec::Drain;
+
+use crayon::utils::hash::{FastHashMap, FastHashSet};
+
+use utils::prelude::Component;
+use Entity;
+
+/// The event of a transition from a state to another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StateTransition<S> {
+    pub from: S,
+    pub to: S,
+}
+
+/// A finite state machine with callbacks on transitions.
+pub struct Fsm<S> {
+    state: S,
+    previous: Option<S>,
+    allowed: FastHashSet<(S, S)>,
+    on_enter: FastHashMap<S, Vec<Box<dyn FnMut(S)>>>,
+    on_exit: FastHashMap<S, Vec<Box<dyn FnMut(S)>>>,
+    on_transition: Vec<Box<dyn FnMut(S, S)>>,
+    events: Vec<StateTransition<S>>,
+}
+
+impl<S: fmt::Debug> fmt::Debug for Fsm<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Fsm")
+            .field("state", &self.state)
+            .field("previous", &self.previous)
+            .finish()
+    }
+}
+
+impl<S: Copy + Eq + Hash> Fsm<S> {
+    /// Creates a state machine in the `initial` state. The callbacks of `initial` are
+    /// not invoked.
+    pub fn new(initial: S) -> Self {
+        Fsm {
+            state: initial,
+            previous: None,
+            allowed: FastHashSet::default(),
+            on_enter: FastHashMap::default(),
+            on_exit: FastHashMap::default(),
+            on_transition: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Gets the current state.
+    #[inline]
+    pub fn state(&self) -> S {
+        self.state
+    }
+
+    /// Gets the state before the last transition.
+    #[inline]
+    pub fn previous(&self) -> Option<S> {
+        self.previous
+    }
+
+    /// Checks if the state machine is in `state`.
+    #[inline]
+    pub fn is(&self, state: S) -> bool {
+        self.state == state
+    }
+
+    /// Allows the transition from `from` to `to`. Once any transition is allowed, the
+    /// transitions which have not been allowed explicitly are rejected.
+    pub fn allow(&mut self, from: S, to: S) {
+        self.allowed.insert((from, to));
+    }
+
+    /// Checks if it could transition from the current state to `to`.
+    pub fn can_transition(&self, to: S) -> bool {
+        to != self.state && (self.allowed.is_empty() || self.allowed.contains(&(self.state, to)))
+    }
+
+    /// Registers a callback which is invoked with the previous state when entering
+    /// `state`.
+    pub fn on_enter<T>(&mut self, state: S, func: T)
+    where
+        T: FnMut(S) + 'static,
+    {
+        self.on_enter
+            .entry(state)
+            .or_insert_with(Vec::new)
+            .push(Box::new(func));
+    }
+
+    /// Registers a callback which is invoked with the next state when exiting `state`.
+    pub fn on_exit<T>(&mut self, state: S, func: T)
+    where
+        T: FnMut(S) + 'static,
+    {
+        self.on_exit
+            .entry(state)
+            .or_insert_with(Vec::new)
+            .push(Box::new(func));
+    }
+
+    /// Registers a callback which is invoked with both of the states on every
+    /// transition, after the exit callbacks and before the enter callbacks.
+    pub fn on_transition<T>(&mut self, func: T)
+    where
+        T: FnMut(S, S) + 'static,
+    {
+        self.on_transition.push(Box::new(func));
+    }
+
+    /// Transitions to `to`, and returns false if the transition is not allowed or the
+    /// state machine is in `to` already.
+    pub fn transition(&mut self, to: S) -> bool {
+        if !self.can_transition(to) {
+            return false;
+        }
+
+        let from = self.state;
+
+        if let Some(funcs) = self.on_exit.get_mut(&from) {
+            for func in funcs {
+                func(to);
+            }
+        }
+
+        for func in &mut self.on_transition {
+            func(from, to);
+        }
+
+        self.previous = Some(from);
+        self.state = to;
+
+        if let Some(funcs) = self.on_enter.get_mut(&to) {
+            for func in funcs {
+                func(from);
+            }
+        }
+
+        self.events.push(StateTransition { from, to });
+        true
+    }
+
+    /// Gets the transitions since the events were drained last time.
+    #[inline]
+    pub fn events(&self) -> &[StateTransition<S>] {
+        &self.events
+    }
+
+    /// Drains the transitions since the events were drained last time.
+    #[inline]
+    pub fn drain_events(&mut self) -> Drain<StateTransition<S>> {
+        self.events.drain(..)
+    }
+}
+
+/// The state machines of entities.
+pub struct Fsms<S> {
+    machines: Component<Fsm<S>>,
+}
+
+impl<S: Copy + Eq + Hash> Fsms<S> {
+    pub fn new() -> Self {
+        Fsms {
+            machines: Component::new(),
+        }
+    }
+
+    /// Attaches a state machine to the entity, and returns the previous one if exists.
+    #[inline]
+    pub fn add(&mut self, ent: Entity, fsm: Fsm<S>) -> Option<Fsm<S>> {
+        self.machines.add(ent, fsm)
+    }
+
+    #[inline]
+    pub fn remove(&mut self, ent: Entity) {
+        self.machines.remove(ent);
+    }
+
+    #[inline]
+    pub fn has(&self, ent: Entity) -> bool {
+        self.machines.has(ent)
+    }
+
+    #[inline]
+    pub fn get(&self, ent: Entity) -> Option<&Fsm<S>> {
+        self.machines.get(ent)
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, ent: Entity) -> Option<&mut Fsm<S>> {
+        self.machines.get_mut(ent)
+    }
+
+    /// Gets the current state of the entity.
+    #[inline]
+    pub fn state(&self, ent: Entity) -> Option<S> {
+        self.machines.get(ent).map(|v| v.state())
+    }
+
+    /// Transitions the state machine of the entity to `to`. Returns false if the entity
+    /// has no state machine or the transition is not allowed.
+    #[inline]
+    pub fn transition(&mut self, ent: Entity, to: S) -> bool {
+        self.machines
+            .get_mut(ent)
+            .map(|v| v.transition(to))
+            .unwrap_or(false)
+    }
+
+    /// Drains the transitions of all the entities. Entities are visited in the order they
+    /// are stored, which changes as machines are removed, and the transitions of each
+    /// entity stay in the order they happened.
+    pub fn drain_events(&mut self) -> Vec<(Entity, StateTransition<S>)> {
+        let mut events = Vec::new();
+        for (&ent, fsm) in self.machines.entities.iter().zip(&mut self.machines.data) {
+            events.extend(fsm.drain_events().map(|v| (ent, v)));
+        }
+
+        events
+    }
+}
@@ -0,0 +1,225 @@
+//! Slot based containers of items, e.g. the backpacks of characters and the chests.
+//!
+//! The rules of items are defined by `ItemDefinition`s, which are usually loaded from an
+//! `ItemCatalog` asset. Items are stacked up to `max_stack` in a slot, and slots could
+//! be restricted to the items with a tag, e.g. the equipment slots. Every change of
+//! slots is recorded as an `InventoryEvent`, which could be drained at the end of frame.
+//!
+//! ```rust,ignore
+//! let catalog = crayon_world::item_catalog(handle).unwrap();
+//! let potion = catalog.get("potion").unwrap();
+//!
+//! let mut bag = Inventory::new(16);
+//! let rest = bag.add(potion, 30);
+//! assert_eq!(bag.count("potion") + rest, 30);
+//! ```
+
+use std::vec::Drain;
+
+use assets::prelude::ItemDefinition;
+
+/// A stack of items in slot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemStack {
+    /// The name of item.
+    pub item: String,
+    pub count: u32,
+}
+
+/// The changes of inventory slots.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InventoryEvent {
+    Added {
+        slot: usize,
+        item: String,
+        count: u32,
+    },
+    Removed {
+        slot: usize,
+        item: String,
+        count: u32,
+    },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct Slot {
+    stack: Option<ItemStack>,
+    filter: Option<String>,
+}
+
+/// A container with fixed number of slots.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Inventory {
+    slots: Vec<Slot>,
+    #[serde(skip)]
+    events: Vec<InventoryEvent>,
+}
+
+impl Inventory {
+    /// Creates an inventory with `len` empty slots.
+    pub fn new(len: usize) -> Self {
+        Inventory {
+            slots: vec![Slot::default(); len],
+            events: Vec::new(),
+        }
+    }
+
+    /// Gets the number of slots.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Checks if all the slots are empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.slots.iter().all(|v| v.stack.is_none())
+    }
+
+    /// Gets the stack in slot.
+    #[inline]
+    pub fn slot(&self, slot: usize) -> Option<&ItemStack> {
+        self.slots.get(slot).and_then(|v| v.stack.as_ref())
+    }
+
+    /// Iterates the non-empty slots.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &ItemStack)> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(i, v)| v.stack.as_ref().map(|s| (i, s)))
+    }
+
+    /// Restricts the slot to the items with `tag`, or removes the restriction with None.
+    /// The items in slot are not affected.
+    pub fn set_filter<T: Into<String>>(&mut self, slot: usize, tag: Option<T>) {
+        if let Some(v) = self.slots.get_mut(slot) {
+            v.filter = tag.map(|v| v.into());
+        }
+    }
+
+    /// Checks if the item could be placed in the slot.
+    pub fn accepts(&self, slot: usize, item: &ItemDefinition) -> bool {
+        match self.slots.get(slot) {
+            Some(v) => match v.filter {
+                Some(ref tag) => item.has_tag(tag),
+                None => true,
+            },
+            None => false,
+        }
+    }
+
+    /// Gets the number of items with the name in all the slots.
+    pub fn count<T: AsRef<str>>(&self, item: T) -> u32 {
+        let item = item.as_ref();
+        self.iter()
+            .filter(|v| v.1.item == item)
+            .map(|v| v.1.count)
+            .sum()
+    }
+
+    /// Adds items to the existing stacks first, and then to the empty slots. Returns the
+    /// number of items which could not be added.
+    pub fn add(&mut self, item: &ItemDefinition, mut count: u32) -> u32 {
+        for i in 0..self.slots.len() {
+            if count > 0 && self.slot(i).map(|v| v.item == item.name) == Some(true) {
+                count = self.add_to(i, item, count);
+            }
+        }
+
+        for i in 0..self.slots.len() {
+            if count > 0 && self.slots[i].stack.is_none() {
+                count = self.add_to(i, item, count);
+            }
+        }
+
+        count
+    }
+
+    /// Adds items to the slot. Returns the number of items which could not be added.
+    pub fn add_to(&mut self, slot: usize, item: &ItemDefinition, count: u32) -> u32 {
+        if count == 0 || !self.accepts(slot, item) {
+            return count;
+        }
+
+        let v = &mut self.slots[slot];
+        let stack = v.stack.get_or_insert_with(|| ItemStack {
+            item: item.name.clone(),
+            count: 0,
+        });
+
+        if stack.item != item.name {
+            return count;
+        }
+
+        let n = count.min(item.max_stack.max(1).saturating_sub(stack.count));
+        if n == 0 {
+            return count;
+        }
+
+        stack.count += n;
+        self.events.push(InventoryEvent::Added {
+            slot,
+            item: item.name.clone(),
+            count: n,
+        });
+
+        count - n
+    }
+
+    /// Removes items with the name from the last slots first. Returns the number of
+    /// items removed.
+    pub fn remove<T: AsRef<str>>(&mut self, item: T, count: u32) -> u32 {
+        let item = item.as_ref();
+        let mut removed = 0;
+
+        for i in (0..self.slots.len()).rev() {
+            if removed < count && self.slot(i).map(|v| v.item == item) == Some(true) {
+                removed += self.remove_from(i, count - removed).map_or(0, |v| v.count);
+            }
+        }
+
+        removed
+    }
+
+    /// Removes at most `count` items from the slot, and returns them.
+    pub fn remove_from(&mut self, slot: usize, count: u32) -> Option<ItemStack> {
+        let v = match self.slots.get_mut(slot) {
+            Some(v) => v,
+            None => return None,
+        };
+
+        let (item, n, empty) = match v.stack {
+            Some(ref mut stack) if count > 0 => {
+                let n = count.min(stack.count);
+                stack.count -= n;
+                (stack.item.clone(), n, stack.count == 0)
+            }
+            _ => return None,
+        };
+
+        if empty {
+            v.stack = None;
+        }
+
+        self.events.push(InventoryEvent::Removed {
+            slot,
+            item: item.clone(),
+            count: n,
+        });
+
+        Some(ItemStack { item, count: n })
+    }
+
+    /// Removes all the items from the slot, and returns them.
+    #[inline]
+    pub fn take(&mut self, slot: usize) -> Option<ItemStack> {
+        self.remove_from(slot, ::std::u32::MAX)
+    }
+
+    /// Drains the changes since the events were drained last time.
+    #[inline]
+    pub fn drain_events(&mut self) -> Drain<InventoryEvent> {
+        self.events.drain(..)
+    }
+}
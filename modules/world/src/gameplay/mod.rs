@@ -0,0 +1,13 @@
+pub mod dialogue;
+pub mod fsm;
+pub mod inventory;
+pub mod quest;
+pub mod stats;
+
+pub mod prelude {
+    pub use super::dialogue::{Dialogue, DialogueEvent, DialoguePlayer};
+    pub use super::fsm::{Fsm, Fsms, StateTransition};
+    pub use super::inventory::{Inventory, InventoryEvent, ItemStack};
+    pub use super::quest::{QuestEvent, QuestLog, QuestProgress, QuestState, QuestStatus};
+    pub use super::stats::{Modifier, ModifierKind, Stat, Stats};
+}
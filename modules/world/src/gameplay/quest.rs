@@ -0,0 +1,251 @@
+//! Tracks the progress of quests defined in a `QuestBook`.
+//!
+//! A `QuestLog` starts quests once their prerequisites are completed, counts the progress
+//! of objectives, and completes the quest when all of its objectives are completed. Every
+//! change is recorded as a `QuestEvent`, which could be drained by the UI and the systems
+//! granting rewards.
+//!
+//! The progress is kept in `QuestProgress`, which is serializable and should be written
+//! into the saves of game.
+//!
+//! ```rust,ignore
+//! let mut log = QuestLog::new(crayon_world::quest_book(handle).unwrap());
+//! log.start("rats")?;
+//! log.advance("rats", "kill", 1);
+//!
+//! let saved = bincode::serialize(log.progress())?;
+//! ```
+
+use std::sync::Arc;
+use std::vec::Drain;
+
+use crayon::errors::Result;
+use crayon::utils::hash::FastHashMap;
+
+use assets::prelude::QuestBook;
+use assets::quest::QuestReward;
+
+/// The status of quest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QuestStatus {
+    /// The quest is not started, and its prerequisites are not completed.
+    Locked,
+    /// The quest could be started.
+    Available,
+    Active,
+    Completed,
+    Failed,
+}
+
+/// The events of quest tracking.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuestEvent {
+    Started {
+        quest: String,
+    },
+    Progressed {
+        quest: String,
+        objective: String,
+        count: u32,
+        required: u32,
+    },
+    ObjectiveCompleted {
+        quest: String,
+        objective: String,
+    },
+    Completed {
+        quest: String,
+        rewards: Vec<QuestReward>,
+    },
+    Failed {
+        quest: String,
+    },
+}
+
+/// The serializable state of a quest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuestState {
+    pub status: QuestStatus,
+    /// The progress of objectives, by the names of objectives.
+    pub objectives: FastHashMap<String, u32>,
+}
+
+/// The serializable progress of all the started quests.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuestProgress {
+    pub quests: FastHashMap<String, QuestState>,
+}
+
+/// The runtime tracking of quests.
+pub struct QuestLog {
+    book: Arc<QuestBook>,
+    progress: QuestProgress,
+    events: Vec<QuestEvent>,
+}
+
+impl QuestLog {
+    pub fn new(book: Arc<QuestBook>) -> Self {
+        QuestLog::with_progress(book, QuestProgress::default())
+    }
+
+    /// Creates a quest log with the progress restored from saves.
+    pub fn with_progress(book: Arc<QuestBook>, progress: QuestProgress) -> Self {
+        QuestLog {
+            book,
+            progress,
+            events: Vec::new(),
+        }
+    }
+
+    /// Gets the serializable progress.
+    #[inline]
+    pub fn progress(&self) -> &QuestProgress {
+        &self.progress
+    }
+
+    /// Gets the status of quest, or None if the quest is not defined.
+    pub fn status<T: AsRef<str>>(&self, quest: T) -> Option<QuestStatus> {
+        let quest = quest.as_ref();
+        let def = self.book.get(quest)?;
+
+        if let Some(v) = self.progress.quests.get(quest) {
+            return Some(v.status);
+        }
+
+        let completed = def
+            .prerequisites
+            .iter()
+            .all(|v| self.status(v) == Some(QuestStatus::Completed));
+
+        if completed {
+            Some(QuestStatus::Available)
+        } else {
+            Some(QuestStatus::Locked)
+        }
+    }
+
+    /// Gets the progress and required count of objective.
+    pub fn objective<T1, T2>(&self, quest: T1, objective: T2) -> Option<(u32, u32)>
+    where
+        T1: AsRef<str>,
+        T2: AsRef<str>,
+    {
+        let (quest, objective) = (quest.as_ref(), objective.as_ref());
+        let def = self.book.get(quest)?;
+        let required = def
+            .objectives
+            .iter()
+            .find(|v| v.name == objective)?
+            .required;
+
+        let count = self
+            .progress
+            .quests
+            .get(quest)
+            .and_then(|v| v.objectives.get(objective).cloned())
+            .unwrap_or(0);
+
+        Some((count, required))
+    }
+
+    /// Starts an available quest.
+    pub fn start<T: AsRef<str>>(&mut self, quest: T) -> Result<()> {
+        let quest = quest.as_ref();
+        match self.status(quest) {
+            Some(QuestStatus::Available) => {}
+            Some(status) => bail!("Quest {} could not be started as it's {:?}.", quest, status),
+            None => bail!("Quest {} is not defined.", quest),
+        }
+
+        let state = QuestState {
+            status: QuestStatus::Active,
+            objectives: FastHashMap::default(),
+        };
+
+        self.progress.quests.insert(quest.to_owned(), state);
+        self.events.push(QuestEvent::Started {
+            quest: quest.to_owned(),
+        });
+
+        Ok(())
+    }
+
+    /// Adds progress to the objective of an active quest, and completes the quest if all
+    /// of its objectives are completed. Returns false if the quest is not active or the
+    /// objective is completed already.
+    pub fn advance<T1, T2>(&mut self, quest: T1, objective: T2, count: u32) -> bool
+    where
+        T1: AsRef<str>,
+        T2: AsRef<str>,
+    {
+        let (quest, objective) = (quest.as_ref(), objective.as_ref());
+        let (current, required) = match self.objective(quest, objective) {
+            Some(v) => v,
+            None => return false,
+        };
+
+        if self.status(quest) != Some(QuestStatus::Active) || current >= required {
+            return false;
+        }
+
+        let current = current.saturating_add(count).min(required);
+        {
+            let state = self.progress.quests.get_mut(quest).unwrap();
+            state.objectives.insert(objective.to_owned(), current);
+        }
+
+        self.events.push(QuestEvent::Progressed {
+            quest: quest.to_owned(),
+            objective: objective.to_owned(),
+            count: current,
+            required,
+        });
+
+        if current < required {
+            return true;
+        }
+
+        self.events.push(QuestEvent::ObjectiveCompleted {
+            quest: quest.to_owned(),
+            objective: objective.to_owned(),
+        });
+
+        let book = self.book.clone();
+        let def = book.get(quest).unwrap();
+        let completed = def
+            .objectives
+            .iter()
+            .all(|v| self.objective(quest, &v.name).map(|p| p.0 >= p.1) == Some(true));
+
+        if completed {
+            self.progress.quests.get_mut(quest).unwrap().status = QuestStatus::Completed;
+            self.events.push(QuestEvent::Completed {
+                quest: quest.to_owned(),
+                rewards: def.rewards.clone(),
+            });
+        }
+
+        true
+    }
+
+    /// Fails an active quest. Returns false if the quest is not active.
+    pub fn fail<T: AsRef<str>>(&mut self, quest: T) -> bool {
+        let quest = quest.as_ref();
+        if self.status(quest) != Some(QuestStatus::Active) {
+            return false;
+        }
+
+        self.progress.quests.get_mut(quest).unwrap().status = QuestStatus::Failed;
+        self.events.push(QuestEvent::Failed {
+            quest: quest.to_owned(),
+        });
+
+        true
+    }
+
+    /// Drains the events since the events were drained last time.
+    #[inline]
+    pub fn drain_events(&mut self) -> Drain<QuestEvent> {
+        self.events.drain(..)
+    }
+}
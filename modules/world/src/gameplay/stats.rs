@@ -0,0 +1,279 @@
+//! Named numeric stats with modifiers, e.g. the health, speed and armor of characters.
+//!
+//! The value of a stat is its base value with the modifiers applied:
+//!
+//! ```text
+//! value = (base + sum(additive)) * product(multiplicative)
+//! ```
+//!
+//! Modifiers are tagged with their sources, e.g. the item or buff which grants them, so
+//! they could be removed together once the source goes away. Modifiers with durations
+//! expire as `advance` is called every frame. Values are recalculated lazily only after
+//! the modifiers or base values are changed.
+//!
+//! ```rust,ignore
+//! let mut stats = Stats::new();
+//! stats.set_base("speed", 5.0);
+//!
+//! let haste = Modifier::multiplicative(1.5, "haste").with_duration(Duration::from_secs(10));
+//! stats.add_modifier("speed", haste);
+//!
+//! assert_eq!(stats.value("speed"), Some(7.5));
+//! ```
+
+use std::cell::Cell;
+use std::time::Duration;
+
+use inlinable_string::InlinableString;
+
+use crayon::utils::hash::FastHashMap;
+
+/// The ways modifiers are applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ModifierKind {
+    /// Adds to the base value.
+    Additive,
+    /// Multiplies the sum of base value and additive modifiers.
+    Multiplicative,
+}
+
+/// A modifier of stat.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Modifier {
+    pub kind: ModifierKind,
+    pub value: f32,
+    /// The source which grants this modifier.
+    pub source: InlinableString,
+    /// The remaining duration of this modifier, or None if it lasts until removed.
+    pub duration: Option<Duration>,
+}
+
+impl Modifier {
+    /// Creates a permanent modifier which adds `value`.
+    pub fn additive<T: Into<InlinableString>>(value: f32, source: T) -> Self {
+        Modifier {
+            kind: ModifierKind::Additive,
+            value,
+            source: source.into(),
+            duration: None,
+        }
+    }
+
+    /// Creates a permanent modifier which multiplies by `value`.
+    pub fn multiplicative<T: Into<InlinableString>>(value: f32, source: T) -> Self {
+        Modifier {
+            kind: ModifierKind::Multiplicative,
+            value,
+            source: source.into(),
+            duration: None,
+        }
+    }
+
+    /// Expires this modifier after `duration`.
+    #[inline]
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+}
+
+/// A stat with its base value and modifiers.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Stat {
+    base: f32,
+    modifiers: Vec<Modifier>,
+    #[serde(skip)]
+    cache: Cell<Option<f32>>,
+}
+
+impl Stat {
+    pub fn new(base: f32) -> Self {
+        Stat {
+            base,
+            modifiers: Vec::new(),
+            cache: Cell::new(None),
+        }
+    }
+
+    #[inline]
+    pub fn base(&self) -> f32 {
+        self.base
+    }
+
+    #[inline]
+    pub fn set_base(&mut self, base: f32) {
+        self.base = base;
+        self.cache.set(None);
+    }
+
+    #[inline]
+    pub fn modifiers(&self) -> &[Modifier] {
+        &self.modifiers
+    }
+
+    #[inline]
+    pub fn add_modifier(&mut self, modifier: Modifier) {
+        self.modifiers.push(modifier);
+        self.cache.set(None);
+    }
+
+    /// Removes the modifiers granted by `source`, and returns the number of them.
+    pub fn remove_modifiers<T: AsRef<str>>(&mut self, source: T) -> usize {
+        let source = source.as_ref();
+        let len = self.modifiers.len();
+        self.modifiers
+            .retain(|v| AsRef::<str>::as_ref(&v.source) != source);
+
+        let removed = len - self.modifiers.len();
+        if removed > 0 {
+            self.cache.set(None);
+        }
+
+        removed
+    }
+
+    /// Gets the value with modifiers applied.
+    pub fn value(&self) -> f32 {
+        if let Some(v) = self.cache.get() {
+            return v;
+        }
+
+        let mut add = 0.0;
+        let mut mul = 1.0;
+        for v in &self.modifiers {
+            match v.kind {
+                ModifierKind::Additive => add += v.value,
+                ModifierKind::Multiplicative => mul *= v.value,
+            }
+        }
+
+        let v = (self.base + add) * mul;
+        self.cache.set(Some(v));
+        v
+    }
+
+    /// Advances the durations of modifiers, and removes the expired ones.
+    pub fn advance(&mut self, dt: Duration) {
+        let len = self.modifiers.len();
+        self.modifiers.retain(|v| match v.duration {
+            Some(d) => d > dt,
+            None => true,
+        });
+
+        for v in &mut self.modifiers {
+            if let Some(ref mut d) = v.duration {
+                *d -= dt;
+            }
+        }
+
+        if self.modifiers.len() != len {
+            self.cache.set(None);
+        }
+    }
+}
+
+impl PartialEq for Stat {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.base == rhs.base && self.modifiers == rhs.modifiers
+    }
+}
+
+/// A set of named stats.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    stats: FastHashMap<InlinableString, Stat>,
+}
+
+impl Stats {
+    pub fn new() -> Self {
+        Stats {
+            stats: FastHashMap::default(),
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.stats.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.stats.is_empty()
+    }
+
+    #[inline]
+    pub fn contains<T: AsRef<str>>(&self, name: T) -> bool {
+        self.stats.contains_key(name.as_ref())
+    }
+
+    #[inline]
+    pub fn get<T: AsRef<str>>(&self, name: T) -> Option<&Stat> {
+        self.stats.get(name.as_ref())
+    }
+
+    #[inline]
+    pub fn get_mut<T: AsRef<str>>(&mut self, name: T) -> Option<&mut Stat> {
+        self.stats.get_mut(name.as_ref())
+    }
+
+    /// Gets the value of stat with modifiers applied.
+    #[inline]
+    pub fn value<T: AsRef<str>>(&self, name: T) -> Option<f32> {
+        self.get(name).map(|v| v.value())
+    }
+
+    /// Gets the base value of stat.
+    #[inline]
+    pub fn base<T: AsRef<str>>(&self, name: T) -> Option<f32> {
+        self.get(name).map(|v| v.base())
+    }
+
+    /// Sets the base value of stat, and adds the stat if it does not exist.
+    pub fn set_base<T: AsRef<str>>(&mut self, name: T, base: f32) {
+        self.stats
+            .entry(name.as_ref().into())
+            .or_insert_with(Stat::default)
+            .set_base(base);
+    }
+
+    /// Removes a stat along with its modifiers.
+    #[inline]
+    pub fn remove<T: AsRef<str>>(&mut self, name: T) -> Option<Stat> {
+        self.stats.remove(name.as_ref())
+    }
+
+    /// Adds a modifier to stat. Returns false if the stat does not exist.
+    pub fn add_modifier<T: AsRef<str>>(&mut self, name: T, modifier: Modifier) -> bool {
+        match self.stats.get_mut(name.as_ref()) {
+            Some(v) => {
+                v.add_modifier(modifier);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the modifiers granted by `source` from all the stats, and returns
+    /// the number of them.
+    pub fn remove_modifiers<T: AsRef<str>>(&mut self, source: T) -> usize {
+        let source = source.as_ref();
+        self.stats
+            .values_mut()
+            .map(|v| v.remove_modifiers(source))
+            .sum()
+    }
+
+    /// Advances the durations of modifiers, and removes the expired ones.
+    pub fn advance(&mut self, dt: Duration) {
+        for v in self.stats.values_mut() {
+            v.advance(dt);
+        }
+    }
+
+    /// Iterates the names and values of stats.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, f32)> {
+        self.stats
+            .iter()
+            .map(|(k, v)| (AsRef::<str>::as_ref(k), v.value()))
+    }
+}
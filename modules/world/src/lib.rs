@@ -8,6 +8,7 @@ extern crate serde;
 extern crate inlinable_string;
 
 pub mod assets;
+pub mod gameplay;
 pub mod renderable;
 pub mod scene;
 pub mod spatial;
@@ -16,6 +17,7 @@ pub mod utils;
 
 pub mod prelude {
     pub use super::assets::prelude::*;
+    pub use super::gameplay::prelude::*;
     pub use super::renderable::prelude::*;
     pub use super::scene::Scene;
     pub use super::spatial::prelude::*;
@@ -30,7 +32,9 @@ pub use self::system::WorldDefaultResources;
 use crayon::res::utils::prelude::ResourceState;
 use std::sync::Arc;
 
-use self::assets::prelude::{Prefab, PrefabHandle};
+use self::assets::prelude::{
+    ItemCatalog, ItemCatalogHandle, Prefab, PrefabHandle, QuestBook, QuestBookHandle,
+};
 use self::inside::ctx;
 
 pub type Result<T> = ::std::result::Result<T, failure::Error>;
@@ -75,6 +79,72 @@ pub fn delete_prefab(handle: PrefabHandle) {
     ctx().delete_prefab(handle);
 }
 
+/// Create an item catalog from file asynchronously.
+///
+/// An item catalog asset contains the definitions of items, which are shared by the
+/// inventories.
+#[inline]
+pub fn create_item_catalog_from<T: AsRef<str>>(url: T) -> Result<ItemCatalogHandle> {
+    ctx().create_item_catalog_from(url)
+}
+
+/// Creates an item catalog.
+#[inline]
+pub fn create_item_catalog(catalog: ItemCatalog) -> Result<ItemCatalogHandle> {
+    ctx().create_item_catalog(catalog)
+}
+
+/// Return the item catalog if exists.
+#[inline]
+pub fn item_catalog(handle: ItemCatalogHandle) -> Option<Arc<ItemCatalog>> {
+    ctx().item_catalog(handle)
+}
+
+/// Query the resource state of specified item catalog.
+#[inline]
+pub fn item_catalog_state(handle: ItemCatalogHandle) -> ResourceState {
+    ctx().item_catalog_state(handle)
+}
+
+/// Delete an item catalog from this world.
+#[inline]
+pub fn delete_item_catalog(handle: ItemCatalogHandle) {
+    ctx().delete_item_catalog(handle);
+}
+
+/// Create a quest book from file asynchronously.
+///
+/// A quest book asset contains the definitions of quests, which are tracked by the
+/// quest logs.
+#[inline]
+pub fn create_quest_book_from<T: AsRef<str>>(url: T) -> Result<QuestBookHandle> {
+    ctx().create_quest_book_from(url)
+}
+
+/// Creates a quest book.
+#[inline]
+pub fn create_quest_book(book: QuestBook) -> Result<QuestBookHandle> {
+    ctx().create_quest_book(book)
+}
+
+/// Return the quest book if exists.
+#[inline]
+pub fn quest_book(handle: QuestBookHandle) -> Option<Arc<QuestBook>> {
+    ctx().quest_book(handle)
+}
+
+/// Query the resource state of specified quest book.
+#[inline]
+pub fn quest_book_state(handle: QuestBookHandle) -> ResourceState {
+    ctx().quest_book_state(handle)
+}
+
+/// Delete a quest book from this world.
+#[inline]
+pub fn delete_quest_book(handle: QuestBookHandle) {
+    ctx().delete_quest_book(handle);
+}
+
 /// Return the default resources in this world.
 #[inline]
 pub fn default() -> WorldDefaultResources {
@@ -103,6 +173,8 @@ mod inside {
         unsafe {
             debug_assert!(CTX.is_null(), "duplicated setup of world system.");
 
+            #[cfg(feature = "alloc-tracking")]
+            let _scope = crayon::memory::scope("world");
             let ctx = WorldSystem::new()?;
             CTX = Box::into_raw(Box::new(ctx));
             Ok(())
@@ -5,11 +5,14 @@ use crayon::video::assets::surface::SurfaceHandle;
 
 use spatial::prelude::Transform;
 
+use super::fog::Fog;
+
 /// A `Camera` is a device through which the player views the world.
 #[derive(Debug, Clone, Copy)]
 pub struct Camera {
     frustum: Frustum<f32>,
     surface: Option<SurfaceHandle>,
+    fog: Option<Fog>,
 
     #[doc(hidden)]
     pub(crate) transform: Transform,
@@ -34,6 +37,7 @@ impl Camera {
         Camera {
             frustum: Frustum::new(projection),
             surface: None,
+            fog: None,
             transform: Transform::default(),
         }
     }
@@ -79,6 +83,21 @@ impl Camera {
         self.surface
     }
 
+    /// Sets the fog of the environment seen through this camera, or disables it with `None`.
+    #[inline]
+    pub fn set_fog<T>(&mut self, fog: T)
+    where
+        T: Into<Option<Fog>>,
+    {
+        self.fog = fog.into();
+    }
+
+    /// Gets the fog of the environment seen through this camera.
+    #[inline]
+    pub fn fog(&self) -> Option<Fog> {
+        self.fog
+    }
+
     /// Sets the near/far clipping plane distances.
     #[inline]
     pub fn set_clip_plane(&mut self, near: f32, far: f32) {
@@ -0,0 +1,34 @@
+use crayon::math::prelude::Color;
+
+/// The distance and height based fog applied by the standard forward shading, which
+/// blends the shaded color of fragments into the fog color. It's a part of the environment
+/// settings of `Camera`, so each view could have its own fog.
+#[derive(Debug, Clone, Copy)]
+pub struct Fog {
+    /// Color of the fog.
+    pub color: Color<f32>,
+    /// The view distance where the fog starts.
+    pub start: f32,
+    /// The view distance where the fog becomes opaque.
+    pub end: f32,
+    /// The world height below which the fog has full density.
+    pub height: f32,
+    /// How fast the fog fades out above `height`. Zero disables the height fog.
+    pub height_falloff: f32,
+    /// Tints the fog with the color of the directional light when looking towards it,
+    /// which simulates the in-scattering of atmosphere outdoors. Zero disables it.
+    pub scattering: f32,
+}
+
+impl Default for Fog {
+    fn default() -> Self {
+        Fog {
+            color: Color::gray(),
+            start: 10.0,
+            end: 100.0,
+            height: 0.0,
+            height_falloff: 0.0,
+            scattering: 0.0,
+        }
+    }
+}
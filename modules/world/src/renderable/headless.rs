@@ -1,7 +1,7 @@
 use utils::prelude::Component;
 use Entity;
 
-use super::prelude::{Camera, Lit, MeshRenderer, Renderer};
+use super::prelude::{Camera, Lit, MeshRenderer, Renderer, TrailRenderer};
 
 pub struct HeadlessRenderer {
     materials: Component<()>,
@@ -34,5 +34,5 @@ impl Renderer for HeadlessRenderer {
         self.materials.remove(ent);
     }
 
-    fn submit(&mut self, _: &Camera, _: &[Lit], _: &[MeshRenderer]) {}
+    fn submit(&mut self, _: &Camera, _: &[Lit], _: &[MeshRenderer], _: &[TrailRenderer]) {}
 }
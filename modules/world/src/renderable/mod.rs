@@ -1,15 +1,19 @@
 mod camera;
+mod fog;
 mod lit;
 mod mesh_renderer;
 mod simple;
+mod trail;
 
 pub mod headless;
 
 pub mod prelude {
     pub use super::camera::Camera;
+    pub use super::fog::Fog;
     pub use super::lit::{Lit, LitSource};
     pub use super::mesh_renderer::MeshRenderer;
     pub use super::simple::{SimpleMaterial, SimpleRenderer};
+    pub use super::trail::{Curve, TrailRenderer};
     pub use super::{Renderable, Renderer};
 }
 
@@ -18,8 +22,10 @@ use utils::prelude::Component;
 use Entity;
 
 use self::camera::Camera;
+use self::fog::Fog;
 use self::lit::{Lit, LitSource};
 use self::mesh_renderer::MeshRenderer;
+use self::trail::TrailRenderer;
 
 pub trait Renderer {
     type Mtl;
@@ -29,13 +35,23 @@ pub trait Renderer {
     fn mtl_mut(&mut self, ent: Entity) -> Option<&mut Self::Mtl>;
     fn remove_mtl(&mut self, ent: Entity);
 
-    fn submit(&mut self, camera: &Camera, lits: &[Lit], meshes: &[MeshRenderer]);
+    /// Called every frame before the cameras are submitted one by one.
+    fn prepare(&mut self, _cameras: usize) {}
+
+    fn submit(
+        &mut self,
+        camera: &Camera,
+        lits: &[Lit],
+        meshes: &[MeshRenderer],
+        trails: &[TrailRenderer],
+    );
 }
 
 pub struct Renderable {
     cameras: Component<Camera>,
     lits: Component<Lit>,
     meshes: Component<MeshRenderer>,
+    trails: Component<TrailRenderer>,
 }
 
 impl Renderable {
@@ -44,6 +60,7 @@ impl Renderable {
             cameras: Component::new(),
             lits: Component::new(),
             meshes: Component::new(),
+            trails: Component::new(),
         }
     }
 
@@ -106,6 +123,26 @@ impl Renderable {
     pub fn remove_mesh(&mut self, ent: Entity) {
         self.meshes.remove(ent);
     }
+
+    #[inline]
+    pub fn add_trail(&mut self, ent: Entity, trail: TrailRenderer) {
+        self.trails.add(ent, trail);
+    }
+
+    #[inline]
+    pub fn trail(&self, ent: Entity) -> Option<&TrailRenderer> {
+        self.trails.get(ent)
+    }
+
+    #[inline]
+    pub fn trail_mut(&mut self, ent: Entity) -> Option<&mut TrailRenderer> {
+        self.trails.get_mut(ent)
+    }
+
+    #[inline]
+    pub fn remove_trail(&mut self, ent: Entity) {
+        self.trails.remove(ent);
+    }
 }
 
 impl Renderable {
@@ -129,8 +166,20 @@ impl Renderable {
             }
         }
 
+        if !self.trails.data.is_empty() {
+            let duration = crayon::application::frame_duration();
+            let dt = duration.as_secs() as f32 + duration.subsec_nanos() as f32 * 1e-9;
+
+            for (i, v) in self.trails.data.iter_mut().enumerate() {
+                if let Some(position) = sg.position(self.trails.entities[i]) {
+                    v.advance(position, dt);
+                }
+            }
+        }
+
+        renderer.prepare(self.cameras.data.len());
         for v in &self.cameras.data {
-            renderer.submit(&v, &self.lits.data, &self.meshes.data);
+            renderer.submit(&v, &self.lits.data, &self.meshes.data, &self.trails.data);
         }
     }
 }
@@ -7,10 +7,15 @@ use failure::Error;
 use utils::prelude::Component;
 use Entity;
 
-use super::{Camera, Lit, LitSource, MeshRenderer};
+use super::trail::TrailVertex;
+use super::{Camera, Fog, Lit, LitSource, MeshRenderer, TrailRenderer};
 
 pub const MAX_DIR_LITS: usize = 1;
 pub const MAX_POINT_LITS: usize = 4;
+pub const MAX_TRAIL_VERTS: usize = 4096;
+
+/// The number of frames the trail vertices of each camera are kept in flight.
+const TRAIL_FRAMES: usize = 2;
 
 /// A simple renderer that draws some color into mesh objects.
 pub struct SimpleRenderer {
@@ -18,6 +23,11 @@ pub struct SimpleRenderer {
 
     surface: SurfaceHandle,
     shader: ShaderHandle,
+    trail_shader: ShaderHandle,
+    trail_mesh: Option<DynamicMesh>,
+    trail_cameras: usize,
+    trail_verts: Vec<TrailVertex>,
+    trail_idxes: Vec<u32>,
     drawcalls: DrawCommandBuffer<DrawOrder>,
 
     global_ambient: Color<f32>,
@@ -29,6 +39,7 @@ impl Drop for SimpleRenderer {
     fn drop(&mut self) {
         video::delete_surface(self.surface);
         video::delete_shader(self.shader);
+        video::delete_shader(self.trail_shader);
     }
 }
 
@@ -43,6 +54,7 @@ impl SimpleRenderer {
             .finish();
 
         let mut uniforms = UniformVariableLayout::build()
+            .with("u_ModelMatrix", UniformVariableType::Matrix4f)
             .with("u_ModelViewMatrix", UniformVariableType::Matrix4f)
             .with("u_MVPMatrix", UniformVariableType::Matrix4f)
             .with("u_ViewNormalMatrix", UniformVariableType::Matrix4f)
@@ -51,7 +63,10 @@ impl SimpleRenderer {
             .with("u_DiffuseTexture", UniformVariableType::Texture)
             .with("u_Specular", UniformVariableType::Vector3f)
             .with("u_SpecularTexture", UniformVariableType::Texture)
-            .with("u_Shininess", UniformVariableType::F32);
+            .with("u_Shininess", UniformVariableType::F32)
+            .with("u_FogColor", UniformVariableType::Vector3f)
+            .with("u_FogParams", UniformVariableType::Vector4f)
+            .with("u_FogScattering", UniformVariableType::F32);
 
         let mut dir_lits = Vec::new();
         let mut point_lits = Vec::new();
@@ -120,6 +135,39 @@ impl SimpleRenderer {
 
         let shader = video::create_shader(params, vs, fs)?;
 
+        // Trails are unlit, and blended over the opaque objects.
+        let mut params = ShaderParams::default();
+        params.state.depth_test = Comparison::Less;
+        params.state.color_blend = Some((
+            Equation::Add,
+            BlendFactor::Value(BlendValue::SourceAlpha),
+            BlendFactor::OneMinusValue(BlendValue::SourceAlpha),
+        ));
+        params.attributes = TrailVertex::attributes();
+        params.uniforms = UniformVariableLayout::build()
+            .with("u_ViewProjectionMatrix", UniformVariableType::Matrix4f)
+            .finish();
+
+        let vs = format!(
+            "
+            #version 100
+            precision lowp float;
+            {0}
+            ",
+            include_str!("shaders/trail.vs")
+        );
+
+        let fs = format!(
+            "
+            #version 100
+            precision lowp float;
+            {0}
+            ",
+            include_str!("shaders/trail.fs")
+        );
+
+        let trail_shader = video::create_shader(params, vs, fs)?;
+
         let params = SurfaceParams::default();
         let surface = video::create_surface(params)?;
 
@@ -127,6 +175,11 @@ impl SimpleRenderer {
             materials: Component::new(),
             surface: surface,
             shader: shader,
+            trail_shader: trail_shader,
+            trail_mesh: None,
+            trail_cameras: 1,
+            trail_verts: Vec::new(),
+            trail_idxes: Vec::new(),
             drawcalls: DrawCommandBuffer::new(),
             dir_lits: dir_lits,
             point_lits: point_lits,
@@ -184,13 +237,32 @@ impl super::Renderer for SimpleRenderer {
         self.remove(ent);
     }
 
-    fn submit(&mut self, camera: &Camera, lits: &[Lit], meshes: &[MeshRenderer]) {
+    fn prepare(&mut self, cameras: usize) {
+        self.trail_cameras = cameras.max(1);
+    }
+
+    fn submit(
+        &mut self,
+        camera: &Camera,
+        lits: &[Lit],
+        meshes: &[MeshRenderer],
+        trails: &[TrailRenderer],
+    ) {
         use crayon::math::prelude::{InnerSpace, Matrix, MetricSpace, SquareMatrix};
 
         let view_matrix = camera.transform.view_matrix();
         let projection_matrix = camera.frustum().to_matrix();
         let mut lits = Vec::from(lits);
 
+        // The fog is disabled by an empty distance range.
+        let fog = camera.fog().unwrap_or(Fog {
+            start: 0.0,
+            end: 0.0,
+            ..Fog::default()
+        });
+
+        let fog_params = [fog.start, fog.end, fog.height, fog.height_falloff];
+
         for mesh in meshes {
             let model_matrix = mesh.transform.matrix();
             let mv = view_matrix * model_matrix;
@@ -198,6 +270,7 @@ impl super::Renderer for SimpleRenderer {
             let vn = mv.invert().and_then(|v| Some(v.transpose())).unwrap_or(mv);
 
             let mut dc = Draw::new(self.shader, mesh.mesh);
+            dc.set_uniform_variable("u_ModelMatrix", model_matrix);
             dc.set_uniform_variable("u_ModelViewMatrix", mv);
             dc.set_uniform_variable("u_MVPMatrix", mvp);
             dc.set_uniform_variable("u_ViewNormalMatrix", vn);
@@ -217,6 +290,9 @@ impl super::Renderer for SimpleRenderer {
             dc.set_uniform_variable("u_Specular", mat.specular.rgb());
             dc.set_uniform_variable("u_SpecularTexture", specular);
             dc.set_uniform_variable("u_Shininess", mat.shininess);
+            dc.set_uniform_variable("u_FogColor", fog.color.rgb());
+            dc.set_uniform_variable("u_FogParams", fog_params);
+            dc.set_uniform_variable("u_FogScattering", fog.scattering);
 
             lits.sort_by_key(|v| mesh.transform.position.distance2(v.transform.position) as u32);
 
@@ -268,11 +344,75 @@ impl super::Renderer for SimpleRenderer {
             self.drawcalls.draw(order, dc);
         }
 
+        if let Err(err) = self.submit_trails(camera, trails) {
+            warn!("Failed to draw trails: {}", err);
+        }
+
         let surface = camera.surface().unwrap_or(self.surface);
         self.drawcalls.submit(surface).unwrap();
     }
 }
 
+impl SimpleRenderer {
+    fn submit_trails(&mut self, camera: &Camera, trails: &[TrailRenderer]) -> Result<(), Error> {
+        self.trail_verts.clear();
+        self.trail_idxes.clear();
+
+        let eye = camera.transform.position;
+        for v in trails {
+            if !v.build(
+                eye,
+                MAX_TRAIL_VERTS,
+                &mut self.trail_verts,
+                &mut self.trail_idxes,
+            ) {
+                warn!("Trails are out of capacity ({} vertices).", MAX_TRAIL_VERTS);
+                break;
+            }
+        }
+
+        if self.trail_idxes.is_empty() {
+            return Ok(());
+        }
+
+        // Every camera takes a region of the ring buffer in one frame, so the regions are
+        // only reused after the frames in flight. The mesh grows with the cameras.
+        let frames = TRAIL_FRAMES * self.trail_cameras;
+        let outgrown = match self.trail_mesh {
+            Some(ref v) => v.params().frames < frames,
+            None => true,
+        };
+
+        if outgrown {
+            let mut params = DynamicMeshParams::default();
+            params.layout = TrailVertex::layout();
+            if MAX_TRAIL_VERTS * frames >= u16::max_value() as usize {
+                params.index_format = IndexFormat::U32;
+            }
+            params.max_verts = MAX_TRAIL_VERTS;
+            params.max_idxes = MAX_TRAIL_VERTS * 3;
+            params.frames = frames;
+            self.trail_mesh = Some(DynamicMesh::new(params)?);
+        }
+
+        let mesh = self.trail_mesh.as_mut().unwrap();
+        mesh.begin();
+        mesh.write(TrailVertex::encode(&self.trail_verts), &self.trail_idxes)?;
+
+        let view_matrix = camera.transform.view_matrix();
+        let projection_matrix = camera.frustum().to_matrix();
+
+        let mut dc = Draw::new(self.trail_shader, mesh.handle());
+        dc.mesh_index = mesh.end()?;
+        dc.set_uniform_variable("u_ViewProjectionMatrix", projection_matrix * view_matrix);
+
+        // Trails are drawn after all the opaque objects.
+        let order = DrawOrder::new(self.trail_shader, true, 0);
+        self.drawcalls.draw(order, dc);
+        Ok(())
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 struct DrawOrder(u64);
 
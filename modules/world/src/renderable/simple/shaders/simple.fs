@@ -1,6 +1,7 @@
 varying vec3 v_EyeFragPos;
 varying vec3 v_EyeNormal;
 varying vec2 v_Texcoord;
+varying float v_WorldHeight;
 
 uniform vec3 u_DirLitViewDir[MAX_DIR_LITS];
 uniform vec3 u_DirLitColor[MAX_DIR_LITS];
@@ -19,6 +20,11 @@ uniform sampler2D u_SpecularTexture;
 
 uniform float u_Shininess;
 
+// (start, end, height, height falloff)
+uniform vec4 u_FogParams;
+uniform vec3 u_FogColor;
+uniform float u_FogScattering;
+
 vec3 Calculate(vec3 normal, vec3 viewDir, vec3 lightDir, vec3 reflectDir, vec3 d, vec3 s)
 {
     vec3 diffuse = max(dot(normal, -lightDir), 0.0) * u_Diffuse * d;
@@ -26,6 +32,28 @@ vec3 Calculate(vec3 normal, vec3 viewDir, vec3 lightDir, vec3 reflectDir, vec3 d
     return diffuse + specular;
 }
 
+vec3 CalculateFog(vec3 color, vec3 viewDir)
+{
+    if (u_FogParams.y <= u_FogParams.x)
+    {
+        return color;
+    }
+
+    float distance = length(v_EyeFragPos);
+    float amount = clamp((distance - u_FogParams.x) / (u_FogParams.y - u_FogParams.x), 0.0, 1.0);
+
+    if (u_FogParams.w > 0.0)
+    {
+        amount *= exp(-max(v_WorldHeight - u_FogParams.z, 0.0) * u_FogParams.w);
+    }
+
+    // Brightens the fog towards the directional light.
+    float sun = pow(max(dot(viewDir, -u_DirLitViewDir[0]), 0.0), 8.0) * u_FogScattering;
+    vec3 fog = mix(u_FogColor, u_DirLitColor[0], clamp(sun, 0.0, 1.0));
+
+    return mix(color, fog, amount);
+}
+
 void main()
 {
     vec3 normal = normalize(v_EyeNormal);
@@ -64,5 +92,5 @@ void main()
         result += max(power * attenuation, vec3(0.0, 0.0, 0.0));
     }
 
-    gl_FragColor = vec4(result, 1.0);
+    gl_FragColor = vec4(CalculateFog(result, viewDir), 1.0);
 }
\ No newline at end of file
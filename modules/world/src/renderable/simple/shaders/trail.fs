@@ -0,0 +1,6 @@
+varying vec4 v_Color;
+
+void main()
+{
+    gl_FragColor = v_Color;
+}
\ No newline at end of file
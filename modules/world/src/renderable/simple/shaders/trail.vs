@@ -0,0 +1,11 @@
+attribute vec3 Position;
+attribute vec4 Color0;
+
+uniform mat4 u_ViewProjectionMatrix;
+
+varying vec4 v_Color;
+
+void main() {
+    gl_Position = u_ViewProjectionMatrix * vec4(Position, 1.0);
+    v_Color = Color0;
+}
@@ -0,0 +1,214 @@
+//! A camera-facing ribbon which follows the recent positions of a moving entity.
+
+use std::collections::VecDeque;
+
+use crayon::math::prelude::*;
+
+impl_vertex! {
+    TrailVertex {
+        position => [Position; Float; 3; false],
+        color => [Color0; UByte; 4; true],
+    }
+}
+
+/// A piecewise linear curve over `[0, 1]`, which is used to shape trails over their
+/// lifetime.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Curve {
+    keys: Vec<(f32, f32)>,
+}
+
+impl Curve {
+    /// Creates a curve which has the same value everywhere.
+    pub fn constant(value: f32) -> Self {
+        Curve {
+            keys: vec![(0.0, value)],
+        }
+    }
+
+    /// Creates a curve which goes from `start` to `end` linearly.
+    pub fn linear(start: f32, end: f32) -> Self {
+        Curve {
+            keys: vec![(0.0, start), (1.0, end)],
+        }
+    }
+
+    /// Adds a key to the curve, replacing the key at the same time if there is one.
+    pub fn with_key(mut self, time: f32, value: f32) -> Self {
+        let time = time.max(0.0).min(1.0);
+        match self.keys.iter().position(|v| v.0 >= time) {
+            Some(i) if self.keys[i].0 == time => self.keys[i].1 = value,
+            Some(i) => self.keys.insert(i, (time, value)),
+            None => self.keys.push((time, value)),
+        }
+
+        self
+    }
+
+    /// Evaluates the curve at `time`, which is clamped into `[0, 1]`.
+    pub fn evaluate(&self, time: f32) -> f32 {
+        let time = time.max(0.0).min(1.0);
+
+        let mut prev = match self.keys.first() {
+            Some(&v) if time > v.0 => v,
+            Some(&v) => return v.1,
+            None => return 0.0,
+        };
+
+        for &next in &self.keys[1..] {
+            if time <= next.0 {
+                let t = (time - prev.0) / (next.0 - prev.0);
+                return prev.1 + (next.1 - prev.1) * t;
+            }
+
+            prev = next;
+        }
+
+        prev.1
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TrailPoint {
+    position: Vector3<f32>,
+    age: f32,
+}
+
+/// A `TrailRenderer` records the recent positions of its entity, and renders them as a
+/// ribbon which always faces the camera.
+#[derive(Debug, Clone)]
+pub struct TrailRenderer {
+    /// How long a recorded position lasts in seconds.
+    pub lifetime: f32,
+    /// The minimum distance the entity has to move before a new position is recorded.
+    pub min_distance: f32,
+    /// The width of ribbon, where `0` is the head and `1` is the end of lifetime.
+    pub width: Curve,
+    /// The alpha of ribbon, where `0` is the head and `1` is the end of lifetime.
+    pub alpha: Curve,
+    /// The color of ribbon.
+    pub color: Color<f32>,
+    /// Is this renderer visible.
+    pub visible: bool,
+
+    points: VecDeque<TrailPoint>,
+}
+
+impl Default for TrailRenderer {
+    fn default() -> Self {
+        TrailRenderer {
+            lifetime: 1.0,
+            min_distance: 0.1,
+            width: Curve::linear(1.0, 0.0),
+            alpha: Curve::linear(1.0, 0.0),
+            color: Color::white(),
+            visible: true,
+            points: VecDeque::new(),
+        }
+    }
+}
+
+impl TrailRenderer {
+    /// Creates a new `TrailRenderer` with the lifetime of positions, and the width
+    /// of its head.
+    pub fn new(lifetime: f32, width: f32) -> Self {
+        TrailRenderer {
+            lifetime: lifetime,
+            width: Curve::linear(width, 0.0),
+            ..Default::default()
+        }
+    }
+
+    /// Ages the recorded positions by `dt` seconds, and records the current position of
+    /// entity. The newest point always follows the entity, and is left behind once the
+    /// entity moves further than `min_distance`.
+    pub fn advance<T: Into<Vector3<f32>>>(&mut self, position: T, dt: f32) {
+        let position = position.into();
+
+        for v in &mut self.points {
+            v.age += dt;
+        }
+
+        while self.points.front().map(|v| v.age >= self.lifetime) == Some(true) {
+            self.points.pop_front();
+        }
+
+        let head = TrailPoint {
+            position: position,
+            age: 0.0,
+        };
+
+        let len = self.points.len();
+        if len >= 2 && self.points[len - 2].position.distance(position) < self.min_distance {
+            self.points[len - 1] = head;
+        } else {
+            self.points.push_back(head);
+        }
+    }
+
+    /// Removes all the recorded positions.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.points.clear();
+    }
+
+    /// Gets the number of recorded positions.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns an iterator of the recorded positions, from the oldest to the newest.
+    #[inline]
+    pub fn positions<'a>(&'a self) -> impl Iterator<Item = Vector3<f32>> + 'a {
+        self.points.iter().map(|v| v.position)
+    }
+
+    /// Appends the ribbon which faces `eye` to `verts` and `idxes`. Returns false if the
+    /// ribbon does not fit into `max_verts`.
+    pub(crate) fn build(
+        &self,
+        eye: Vector3<f32>,
+        max_verts: usize,
+        verts: &mut Vec<TrailVertex>,
+        idxes: &mut Vec<u32>,
+    ) -> bool {
+        let len = self.points.len();
+        if !self.visible || len < 2 {
+            return true;
+        }
+
+        if verts.len() + len * 2 > max_verts {
+            return false;
+        }
+
+        let base = verts.len() as u32;
+        for i in 0..len {
+            let v = self.points[i];
+            let prev = self.points[i.saturating_sub(1)].position;
+            let next = self.points[(i + 1).min(len - 1)].position;
+
+            let t = v.age / self.lifetime;
+            let side = (next - prev).cross(eye - v.position);
+            let side = if side.magnitude2() > ::std::f32::EPSILON {
+                side.normalize() * (self.width.evaluate(t) * 0.5)
+            } else {
+                Vector3::new(0.0, 0.0, 0.0)
+            };
+
+            let mut color = self.color;
+            color.a *= self.alpha.evaluate(t).max(0.0).min(1.0);
+            let color: [u8; 4] = color.into();
+
+            verts.push(TrailVertex::new((v.position - side).into(), color));
+            verts.push(TrailVertex::new((v.position + side).into(), color));
+        }
+
+        for i in 0..(len as u32 - 1) {
+            let v = base + i * 2;
+            idxes.extend_from_slice(&[v, v + 1, v + 3, v, v + 3, v + 2]);
+        }
+
+        true
+    }
+}
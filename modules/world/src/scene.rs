@@ -5,7 +5,7 @@ use crayon::math::prelude::{Quaternion, Vector3};
 use crayon::utils::prelude::HandlePool;
 
 use assets::prelude::PrefabHandle;
-use renderable::prelude::{Camera, Lit, MeshRenderer, Renderable, Renderer};
+use renderable::prelude::{Camera, Lit, MeshRenderer, Renderable, Renderer, TrailRenderer};
 use spatial::prelude::{SceneGraph, Transform};
 use tags::Tags;
 use Entity;
@@ -23,9 +23,28 @@ pub struct Scene<R: Renderer> {
 }
 
 impl<R: Renderer> Scene<R> {
+    /// Creates a scene which holds at most `Settings::max_entities` entities of the
+    /// application.
     pub fn new(renderer: R) -> Self {
+        let limit = if crayon::application::valid() {
+            crayon::application::settings().max_entities
+        } else {
+            0
+        };
+
+        Scene::with_limit(renderer, limit)
+    }
+
+    /// Creates a scene which holds at most `limit` entities, unbounded if zero.
+    pub fn with_limit(renderer: R, limit: usize) -> Self {
+        let entities = if limit == 0 {
+            HandlePool::new()
+        } else {
+            HandlePool::with_limit(limit)
+        };
+
         Scene {
-            entities: HandlePool::new(),
+            entities: entities,
             tags: Tags::new(),
             nodes: SceneGraph::new(),
             renderables: Renderable::new(),
@@ -47,6 +66,10 @@ impl<R: Renderer> Scene<R> {
     }
 
     /// Create a new Entity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the scene is full, use `try_create` for scenes with limits.
     #[inline]
     pub fn create<T: AsRef<str>>(&mut self, name: T) -> Entity {
         let e = self.entities.create().into();
@@ -55,6 +78,14 @@ impl<R: Renderer> Scene<R> {
         e
     }
 
+    /// Create a new Entity. It fails only if the scene has reached its limit.
+    pub fn try_create<T: AsRef<str>>(&mut self, name: T) -> Result<Entity> {
+        let e = self.entities.try_create()?.into();
+        self.nodes.add(e);
+        self.tags.add(e, name.as_ref());
+        Ok(e)
+    }
+
     /// Get the name of this Entity.
     #[inline]
     pub fn name(&self, ent: Entity) -> Option<&str> {
@@ -76,6 +107,7 @@ impl<R: Renderer> Scene<R> {
                 self.renderables.remove_mesh(v);
                 self.renderables.remove_lit(v);
                 self.renderables.remove_camera(v);
+                self.renderables.remove_trail(v);
             }
 
             Some(deletions)
@@ -183,7 +215,7 @@ impl<R: Renderer> Scene<R> {
 
             while let Some((parent, idx)) = nodes.pop() {
                 let n = &prefab.nodes[idx];
-                let e = self.create(&n.name);
+                let e = self.try_create(&n.name)?;
                 self.nodes.set_local_transform(e, n.local_transform);
 
                 if let Some(parent) = parent {
@@ -289,6 +321,28 @@ impl<R: Renderer> Scene<R> {
         self.renderables.remove_mesh(ent);
     }
 
+    /// Add trail component to this Entity.
+    #[inline]
+    pub fn add_trail(&mut self, ent: Entity, trail: TrailRenderer) {
+        self.renderables.add_trail(ent, trail);
+    }
+
+    #[inline]
+    pub fn trail(&self, ent: Entity) -> Option<&TrailRenderer> {
+        self.renderables.trail(ent)
+    }
+
+    #[inline]
+    pub fn trail_mut(&mut self, ent: Entity) -> Option<&mut TrailRenderer> {
+        self.renderables.trail_mut(ent)
+    }
+
+    /// Remove trail component from this Entity.
+    #[inline]
+    pub fn remove_trail(&mut self, ent: Entity) {
+        self.renderables.remove_trail(ent);
+    }
+
     /// Add material component to this Entity.
     #[inline]
     pub fn add_mtl(&mut self, ent: Entity, mtl: R::Mtl) {
@@ -0,0 +1,480 @@
+use std::f32;
+
+use crayon::math::prelude::*;
+use crayon::utils::hash::FastHashMap;
+
+use Entity;
+
+const NULL: usize = ::std::usize::MAX;
+
+#[derive(Debug, Clone, Copy)]
+struct BvhNode {
+    /// The enlarged bounds of leaves, or the union of children.
+    bounds: Aabb3<f32>,
+    /// The exact bounds of leaves.
+    tight: Aabb3<f32>,
+    parent: usize,
+    left: usize,
+    right: usize,
+    ent: Option<Entity>,
+}
+
+/// A dynamic bounding volume hierarchy of entities, which accelerates the frustum
+/// culling, ray casts and area queries in 3D.
+///
+/// Leaves are enlarged by a margin, so an entity moving inside its enlarged bounds only
+/// refits its leaf, and the tree is restructured only when it moves further.
+pub struct Bvh {
+    nodes: Vec<BvhNode>,
+    frees: Vec<usize>,
+    root: usize,
+    leaves: FastHashMap<Entity, usize>,
+    margin: f32,
+}
+
+impl Default for Bvh {
+    fn default() -> Self {
+        Bvh::new(0.1)
+    }
+}
+
+impl Bvh {
+    /// Creates a new and empty `Bvh`, where the bounds of leaves are enlarged by `margin`.
+    pub fn new(margin: f32) -> Self {
+        Bvh {
+            nodes: Vec::new(),
+            frees: Vec::new(),
+            root: NULL,
+            leaves: FastHashMap::default(),
+            margin: margin.max(0.0),
+        }
+    }
+
+    /// Gets the number of entities in this tree.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Checks if this tree is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Checks if the entity is in this tree.
+    #[inline]
+    pub fn contains(&self, ent: Entity) -> bool {
+        self.leaves.contains_key(&ent)
+    }
+
+    /// Gets the bounds of the entity.
+    #[inline]
+    pub fn bounds(&self, ent: Entity) -> Option<Aabb3<f32>> {
+        self.leaves.get(&ent).map(|&v| self.nodes[v].tight)
+    }
+
+    /// Gets the height of tree, which is zero if the tree is empty.
+    pub fn height(&self) -> usize {
+        let mut height = 0;
+        let mut stack = Vec::new();
+        if self.root != NULL {
+            stack.push((self.root, 1));
+        }
+
+        while let Some((index, depth)) = stack.pop() {
+            let node = self.nodes[index];
+            height = height.max(depth);
+            if node.ent.is_none() {
+                stack.push((node.left, depth + 1));
+                stack.push((node.right, depth + 1));
+            }
+        }
+
+        height
+    }
+
+    /// Inserts an entity with its bounds, or moves it if it's in this tree already.
+    pub fn insert(&mut self, ent: Entity, bounds: Aabb3<f32>) {
+        if self.update(ent, bounds) {
+            return;
+        }
+
+        let enlarged = self.enlarge(bounds);
+        let leaf = self.alloc(BvhNode {
+            bounds: enlarged,
+            tight: bounds,
+            parent: NULL,
+            left: NULL,
+            right: NULL,
+            ent: Some(ent),
+        });
+
+        self.leaves.insert(ent, leaf);
+        self.link(leaf);
+    }
+
+    /// Moves an entity to new bounds. Returns false if the entity is not in this tree.
+    pub fn update(&mut self, ent: Entity, bounds: Aabb3<f32>) -> bool {
+        let leaf = match self.leaves.get(&ent) {
+            Some(&v) => v,
+            None => return false,
+        };
+
+        self.nodes[leaf].tight = bounds;
+        if !contains(self.nodes[leaf].bounds, bounds) {
+            let enlarged = self.enlarge(bounds);
+            self.unlink(leaf);
+            self.nodes[leaf].bounds = enlarged;
+            self.link(leaf);
+        }
+
+        true
+    }
+
+    /// Removes an entity. Returns false if the entity is not in this tree.
+    pub fn remove(&mut self, ent: Entity) -> bool {
+        match self.leaves.remove(&ent) {
+            Some(leaf) => {
+                self.unlink(leaf);
+                self.frees.push(leaf);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes all the entities.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.frees.clear();
+        self.leaves.clear();
+        self.root = NULL;
+    }
+
+    /// Returns the entities whose bounds intersect with `area`.
+    pub fn query(&self, area: Aabb3<f32>) -> Vec<Entity> {
+        let mut result = Vec::new();
+        self.traverse(
+            |bounds| intersects(bounds, area),
+            |node| intersects(node.tight, area),
+            &mut result,
+        );
+
+        result
+    }
+
+    /// Returns the entities whose bounds are inside or crossing the frustum. The `view`
+    /// matrix transforms the bounds into the space of frustum, e.g.
+    /// `camera.transform.view_matrix()` along with `camera.frustum()`.
+    pub fn query_frustum(&self, frustum: &Frustum<f32>, view: Matrix4<f32>) -> Vec<Entity> {
+        let mut result = Vec::new();
+        let mut stack = Vec::new();
+        if self.root != NULL {
+            stack.push((self.root, false));
+        }
+
+        while let Some((index, inside)) = stack.pop() {
+            let node = self.nodes[index];
+
+            // Everything under a node inside the frustum is visible without tests.
+            let inside = inside
+                || match frustum.contains(&node.bounds.transform(&view)) {
+                    PlaneRelation::Out => continue,
+                    PlaneRelation::In => true,
+                    PlaneRelation::Cross => false,
+                };
+
+            match node.ent {
+                Some(ent) => {
+                    if inside
+                        || frustum.contains(&node.tight.transform(&view)) != PlaneRelation::Out
+                    {
+                        result.push(ent);
+                    }
+                }
+                None => {
+                    stack.push((node.left, inside));
+                    stack.push((node.right, inside));
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Casts a ray from `origin` along `dir`, and returns the first entity it hits within
+    /// `max_distance` along with the distance to it.
+    pub fn raycast(
+        &self,
+        origin: Vector3<f32>,
+        dir: Vector3<f32>,
+        max_distance: f32,
+    ) -> Option<(Entity, f32)> {
+        if self.root == NULL || dir.magnitude2() <= f32::EPSILON {
+            return None;
+        }
+
+        let dir = dir.normalize();
+        let inv = Vector3::new(1.0 / dir.x, 1.0 / dir.y, 1.0 / dir.z);
+
+        let mut hit: Option<(Entity, f32)> = None;
+        let mut stack = vec![self.root];
+
+        while let Some(index) = stack.pop() {
+            let node = self.nodes[index];
+            let limit = hit.map(|v| v.1).unwrap_or(max_distance);
+
+            match node.ent {
+                Some(ent) => {
+                    if let Some(d) = slab(node.tight, origin, inv) {
+                        if d <= limit && hit.map_or(true, |v| d < v.1) {
+                            hit = Some((ent, d));
+                        }
+                    }
+                }
+                None => {
+                    // Visits the nearer child first, so the farther one is likely to be
+                    // pruned by the hit found.
+                    let lhs = slab(self.nodes[node.left].bounds, origin, inv);
+                    let rhs = slab(self.nodes[node.right].bounds, origin, inv);
+
+                    let mut children = [(node.left, lhs), (node.right, rhs)];
+                    if rhs.unwrap_or(f32::INFINITY) > lhs.unwrap_or(f32::INFINITY) {
+                        children.swap(0, 1);
+                    }
+
+                    for &(child, d) in &children {
+                        if let Some(d) = d {
+                            if d <= limit {
+                                stack.push(child);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        hit
+    }
+
+    fn traverse<T1, T2>(&self, node_func: T1, leaf_func: T2, result: &mut Vec<Entity>)
+    where
+        T1: Fn(Aabb3<f32>) -> bool,
+        T2: Fn(&BvhNode) -> bool,
+    {
+        let mut stack = Vec::new();
+        if self.root != NULL {
+            stack.push(self.root);
+        }
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !node_func(node.bounds) {
+                continue;
+            }
+
+            match node.ent {
+                Some(ent) => {
+                    if leaf_func(node) {
+                        result.push(ent);
+                    }
+                }
+                None => {
+                    stack.push(node.left);
+                    stack.push(node.right);
+                }
+            }
+        }
+    }
+
+    fn enlarge(&self, bounds: Aabb3<f32>) -> Aabb3<f32> {
+        bounds.add_margin(Vector3::new(self.margin, self.margin, self.margin))
+    }
+
+    fn alloc(&mut self, node: BvhNode) -> usize {
+        match self.frees.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    /// Links the leaf into the tree, next to the sibling which enlarges the surface area
+    /// least.
+    fn link(&mut self, leaf: usize) {
+        if self.root == NULL {
+            self.root = leaf;
+            self.nodes[leaf].parent = NULL;
+            return;
+        }
+
+        let bounds = self.nodes[leaf].bounds;
+        let mut index = self.root;
+        while self.nodes[index].ent.is_none() {
+            let node = self.nodes[index];
+            let combined = area(union(node.bounds, bounds));
+
+            // The cost of creating a new parent for this node and the leaf.
+            let cost = 2.0 * combined;
+            // The minimum cost of pushing the leaf further down the tree.
+            let inheritance = 2.0 * (combined - area(node.bounds));
+
+            let descend = |child: BvhNode| {
+                let v = area(union(child.bounds, bounds));
+                if child.ent.is_some() {
+                    v + inheritance
+                } else {
+                    v - area(child.bounds) + inheritance
+                }
+            };
+
+            let lhs = descend(self.nodes[node.left]);
+            let rhs = descend(self.nodes[node.right]);
+
+            if cost < lhs && cost < rhs {
+                break;
+            }
+
+            index = if lhs < rhs { node.left } else { node.right };
+        }
+
+        let sibling = index;
+        let old_parent = self.nodes[sibling].parent;
+        let combined = union(self.nodes[sibling].bounds, bounds);
+        let parent = self.alloc(BvhNode {
+            bounds: combined,
+            tight: bounds,
+            parent: old_parent,
+            left: sibling,
+            right: leaf,
+            ent: None,
+        });
+
+        self.nodes[sibling].parent = parent;
+        self.nodes[leaf].parent = parent;
+
+        if old_parent == NULL {
+            self.root = parent;
+        } else if self.nodes[old_parent].left == sibling {
+            self.nodes[old_parent].left = parent;
+        } else {
+            self.nodes[old_parent].right = parent;
+        }
+
+        self.refit(old_parent);
+    }
+
+    /// Unlinks the leaf from the tree, and frees its parent.
+    fn unlink(&mut self, leaf: usize) {
+        if leaf == self.root {
+            self.root = NULL;
+            return;
+        }
+
+        let parent = self.nodes[leaf].parent;
+        let grand_parent = self.nodes[parent].parent;
+        let sibling = if self.nodes[parent].left == leaf {
+            self.nodes[parent].right
+        } else {
+            self.nodes[parent].left
+        };
+
+        self.nodes[sibling].parent = grand_parent;
+        if grand_parent == NULL {
+            self.root = sibling;
+        } else if self.nodes[grand_parent].left == parent {
+            self.nodes[grand_parent].left = sibling;
+        } else {
+            self.nodes[grand_parent].right = sibling;
+        }
+
+        self.frees.push(parent);
+        self.refit(grand_parent);
+    }
+
+    /// Refits the bounds of the node and its ancestors.
+    fn refit(&mut self, mut index: usize) {
+        while index != NULL {
+            let node = self.nodes[index];
+            let bounds = union(self.nodes[node.left].bounds, self.nodes[node.right].bounds);
+            self.nodes[index].bounds = bounds;
+            index = node.parent;
+        }
+    }
+}
+
+#[inline]
+fn union(lhs: Aabb3<f32>, rhs: Aabb3<f32>) -> Aabb3<f32> {
+    Aabb3::new(
+        Point3::new(
+            lhs.min.x.min(rhs.min.x),
+            lhs.min.y.min(rhs.min.y),
+            lhs.min.z.min(rhs.min.z),
+        ),
+        Point3::new(
+            lhs.max.x.max(rhs.max.x),
+            lhs.max.y.max(rhs.max.y),
+            lhs.max.z.max(rhs.max.z),
+        ),
+    )
+}
+
+#[inline]
+fn area(v: Aabb3<f32>) -> f32 {
+    let d = v.dim();
+    2.0 * (d.x * d.y + d.y * d.z + d.z * d.x)
+}
+
+#[inline]
+fn contains(outer: Aabb3<f32>, inner: Aabb3<f32>) -> bool {
+    outer.min.x <= inner.min.x
+        && outer.min.y <= inner.min.y
+        && outer.min.z <= inner.min.z
+        && outer.max.x >= inner.max.x
+        && outer.max.y >= inner.max.y
+        && outer.max.z >= inner.max.z
+}
+
+#[inline]
+fn intersects(lhs: Aabb3<f32>, rhs: Aabb3<f32>) -> bool {
+    lhs.min.x <= rhs.max.x
+        && lhs.max.x >= rhs.min.x
+        && lhs.min.y <= rhs.max.y
+        && lhs.max.y >= rhs.min.y
+        && lhs.min.z <= rhs.max.z
+        && lhs.max.z >= rhs.min.z
+}
+
+/// Returns the distance along the ray to the bounds, or zero if the origin is inside.
+fn slab(bounds: Aabb3<f32>, origin: Vector3<f32>, inv: Vector3<f32>) -> Option<f32> {
+    let mut near = 0.0f32;
+    let mut far = f32::INFINITY;
+
+    for &(o, inv, min, max) in &[
+        (origin.x, inv.x, bounds.min.x, bounds.max.x),
+        (origin.y, inv.y, bounds.min.y, bounds.max.y),
+        (origin.z, inv.z, bounds.min.z, bounds.max.z),
+    ] {
+        if inv.is_infinite() {
+            if o < min || o > max {
+                return None;
+            }
+        } else {
+            let (t0, t1) = ((min - o) * inv, (max - o) * inv);
+            near = near.max(t0.min(t1));
+            far = far.min(t0.max(t1));
+        }
+    }
+
+    if near <= far {
+        Some(near)
+    } else {
+        None
+    }
+}
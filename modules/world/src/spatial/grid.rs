@@ -0,0 +1,360 @@
+use std::collections::BTreeMap;
+
+use crayon::math::prelude::*;
+use crayon::utils::hash::{FastHashMap, FastHashSet};
+
+use Entity;
+
+/// A uniform grid which buckets the bounds of entities on the xy plane, so the entities
+/// near a point, in an area or along a ray are found without visiting everything. It
+/// works best when most of the entities are about the size of a cell, e.g. the
+/// characters and projectiles of 2D games.
+pub struct SpatialHashGrid {
+    cell_size: f32,
+    cells: FastHashMap<(i32, i32), Vec<Entity>>,
+    bounds: FastHashMap<Entity, Aabb2<f32>>,
+    // The number of occupied cells in each column and row, which keeps the range of
+    // occupied cells without visiting them.
+    columns: BTreeMap<i32, usize>,
+    rows: BTreeMap<i32, usize>,
+}
+
+impl SpatialHashGrid {
+    /// Creates a new and empty grid with the size of its cells.
+    pub fn new(cell_size: f32) -> Self {
+        assert!(cell_size > 0.0, "The cell size of grid should be positive.");
+
+        SpatialHashGrid {
+            cell_size,
+            cells: FastHashMap::default(),
+            bounds: FastHashMap::default(),
+            columns: BTreeMap::new(),
+            rows: BTreeMap::new(),
+        }
+    }
+
+    /// Gets the size of cells.
+    #[inline]
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Gets the number of entities in this grid.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bounds.len()
+    }
+
+    /// Checks if this grid is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bounds.is_empty()
+    }
+
+    /// Checks if the entity is in this grid.
+    #[inline]
+    pub fn contains(&self, ent: Entity) -> bool {
+        self.bounds.contains_key(&ent)
+    }
+
+    /// Gets the bounds of the entity.
+    #[inline]
+    pub fn bounds(&self, ent: Entity) -> Option<Aabb2<f32>> {
+        self.bounds.get(&ent).cloned()
+    }
+
+    /// Inserts an entity with its bounds, or moves it if it's in this grid already.
+    pub fn insert(&mut self, ent: Entity, bounds: Aabb2<f32>) {
+        if !self.update(ent, bounds) {
+            self.bounds.insert(ent, bounds);
+            self.link(ent, bounds);
+        }
+    }
+
+    /// Moves an entity to new bounds. Returns false if the entity is not in this grid.
+    pub fn update(&mut self, ent: Entity, bounds: Aabb2<f32>) -> bool {
+        let prev = match self.bounds.get_mut(&ent) {
+            Some(v) => ::std::mem::replace(v, bounds),
+            None => return false,
+        };
+
+        // Most of the moves happen inside the same cells.
+        if self.cell_range(prev) != self.cell_range(bounds) {
+            self.unlink(ent, prev);
+            self.link(ent, bounds);
+        }
+
+        true
+    }
+
+    /// Removes an entity. Returns false if the entity is not in this grid.
+    pub fn remove(&mut self, ent: Entity) -> bool {
+        match self.bounds.remove(&ent) {
+            Some(bounds) => {
+                self.unlink(ent, bounds);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes all the entities.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.bounds.clear();
+        self.columns.clear();
+        self.rows.clear();
+    }
+
+    /// Returns the entities whose bounds intersect with `area`.
+    pub fn query(&self, area: Aabb2<f32>) -> Vec<Entity> {
+        self.query_with(area, |bounds| intersects(bounds, area))
+    }
+
+    /// Returns the entities whose bounds intersect with the circle.
+    pub fn query_circle(&self, center: Vector2<f32>, radius: f32) -> Vec<Entity> {
+        let area = Aabb2::new(
+            Point2::new(center.x - radius, center.y - radius),
+            Point2::new(center.x + radius, center.y + radius),
+        );
+
+        self.query_with(area, |bounds| {
+            let x = center.x.max(bounds.min.x).min(bounds.max.x);
+            let y = center.y.max(bounds.min.y).min(bounds.max.y);
+            Vector2::new(x - center.x, y - center.y).magnitude2() <= radius * radius
+        })
+    }
+
+    /// Casts a ray from `origin` along `dir`, and returns the first entity it hits within
+    /// `max_distance` along with the distance to it.
+    pub fn raycast(
+        &self,
+        origin: Vector2<f32>,
+        dir: Vector2<f32>,
+        max_distance: f32,
+    ) -> Option<(Entity, f32)> {
+        if dir.magnitude2() <= ::std::f32::EPSILON {
+            return None;
+        }
+
+        // Nothing could be hit once the ray leaves the occupied cells, which also ends
+        // the walk when `max_distance` is infinite.
+        let (lo, hi) = self.occupied_range()?;
+        let outside =
+            |v: i32, step: i32, lo: i32, hi: i32| (step >= 0 && v > hi) || (step <= 0 && v < lo);
+
+        let dir = dir.normalize();
+        let mut cell = self.cell(origin.x, origin.y);
+
+        // Walks through the cells along the ray.
+        let step = (sign(dir.x), sign(dir.y));
+        let boundary = |v: i32, step: i32| (v + if step > 0 { 1 } else { 0 }) as f32;
+        let t_max = |o: f32, d: f32, v: i32, step: i32| {
+            if step == 0 {
+                ::std::f32::INFINITY
+            } else {
+                (boundary(v, step) * self.cell_size - o) / d
+            }
+        };
+
+        let mut next = (
+            t_max(origin.x, dir.x, cell.0, step.0),
+            t_max(origin.y, dir.y, cell.1, step.1),
+        );
+
+        let delta = (
+            (self.cell_size / dir.x).abs(),
+            (self.cell_size / dir.y).abs(),
+        );
+
+        let mut hit: Option<(Entity, f32)> = None;
+        let mut t = 0.0;
+
+        while t <= max_distance {
+            if outside(cell.0, step.0, lo.0, hi.0) || outside(cell.1, step.1, lo.1, hi.1) {
+                break;
+            }
+
+            if let Some(ents) = self.cells.get(&cell) {
+                for &ent in ents {
+                    let d = match slab(self.bounds[&ent], origin, dir) {
+                        Some(d) if d <= max_distance => d,
+                        _ => continue,
+                    };
+
+                    if hit.map_or(true, |v| d < v.1) {
+                        hit = Some((ent, d));
+                    }
+                }
+            }
+
+            // Entities spanning multiple cells might be hit later in another cell, so
+            // stops only if the hit is inside the visited cells.
+            let exit = next.0.min(next.1);
+            if let Some(v) = hit {
+                if v.1 <= exit {
+                    return Some(v);
+                }
+            }
+
+            if next.0 < next.1 {
+                cell.0 += step.0;
+                next.0 += delta.0;
+            } else {
+                cell.1 += step.1;
+                next.1 += delta.1;
+            }
+
+            t = exit;
+        }
+
+        hit
+    }
+
+    fn query_with<T>(&self, area: Aabb2<f32>, func: T) -> Vec<Entity>
+    where
+        T: Fn(Aabb2<f32>) -> bool,
+    {
+        let mut visited = FastHashSet::default();
+        let mut result = Vec::new();
+
+        let (min, max) = self.cell_range(area);
+        for x in min.0..=max.0 {
+            for y in min.1..=max.1 {
+                if let Some(ents) = self.cells.get(&(x, y)) {
+                    for &ent in ents {
+                        if visited.insert(ent) && func(self.bounds[&ent]) {
+                            result.push(ent);
+                        }
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    fn link(&mut self, ent: Entity, bounds: Aabb2<f32>) {
+        let (min, max) = self.cell_range(bounds);
+        for x in min.0..=max.0 {
+            for y in min.1..=max.1 {
+                let ents = self.cells.entry((x, y)).or_insert_with(Vec::new);
+                if ents.is_empty() {
+                    *self.columns.entry(x).or_insert(0) += 1;
+                    *self.rows.entry(y).or_insert(0) += 1;
+                }
+
+                ents.push(ent);
+            }
+        }
+    }
+
+    fn unlink(&mut self, ent: Entity, bounds: Aabb2<f32>) {
+        let (min, max) = self.cell_range(bounds);
+        for x in min.0..=max.0 {
+            for y in min.1..=max.1 {
+                let empty = match self.cells.get_mut(&(x, y)) {
+                    Some(ents) => {
+                        if let Some(i) = ents.iter().position(|&v| v == ent) {
+                            ents.swap_remove(i);
+                        }
+
+                        ents.is_empty()
+                    }
+                    None => false,
+                };
+
+                if empty {
+                    self.cells.remove(&(x, y));
+                    release(&mut self.columns, x);
+                    release(&mut self.rows, y);
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn cell(&self, x: f32, y: f32) -> (i32, i32) {
+        (
+            (x / self.cell_size).floor() as i32,
+            (y / self.cell_size).floor() as i32,
+        )
+    }
+
+    fn occupied_range(&self) -> Option<((i32, i32), (i32, i32))> {
+        let lo = (*self.columns.keys().next()?, *self.rows.keys().next()?);
+        let hi = (
+            *self.columns.keys().next_back()?,
+            *self.rows.keys().next_back()?,
+        );
+        Some((lo, hi))
+    }
+
+    #[inline]
+    fn cell_range(&self, bounds: Aabb2<f32>) -> ((i32, i32), (i32, i32)) {
+        (
+            self.cell(bounds.min.x, bounds.min.y),
+            self.cell(bounds.max.x, bounds.max.y),
+        )
+    }
+}
+
+fn release(counts: &mut BTreeMap<i32, usize>, key: i32) {
+    let empty = match counts.get_mut(&key) {
+        Some(v) => {
+            *v -= 1;
+            *v == 0
+        }
+        None => false,
+    };
+
+    if empty {
+        counts.remove(&key);
+    }
+}
+
+#[inline]
+fn sign(v: f32) -> i32 {
+    if v > 0.0 {
+        1
+    } else if v < 0.0 {
+        -1
+    } else {
+        0
+    }
+}
+
+#[inline]
+fn intersects(lhs: Aabb2<f32>, rhs: Aabb2<f32>) -> bool {
+    lhs.min.x <= rhs.max.x
+        && lhs.max.x >= rhs.min.x
+        && lhs.min.y <= rhs.max.y
+        && lhs.max.y >= rhs.min.y
+}
+
+/// Returns the distance along the ray to the bounds, or zero if the origin is inside.
+fn slab(bounds: Aabb2<f32>, origin: Vector2<f32>, dir: Vector2<f32>) -> Option<f32> {
+    let mut near = 0.0f32;
+    let mut far = ::std::f32::INFINITY;
+
+    for &(o, d, min, max) in &[
+        (origin.x, dir.x, bounds.min.x, bounds.max.x),
+        (origin.y, dir.y, bounds.min.y, bounds.max.y),
+    ] {
+        if d == 0.0 {
+            if o < min || o > max {
+                return None;
+            }
+        } else {
+            let (t0, t1) = ((min - o) / d, (max - o) / d);
+            near = near.max(t0.min(t1));
+            far = far.min(t0.max(t1));
+        }
+    }
+
+    if near <= far {
+        Some(near)
+    } else {
+        None
+    }
+}
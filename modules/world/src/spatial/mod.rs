@@ -1,9 +1,15 @@
+pub mod bvh;
 pub mod graph;
+pub mod grid;
 pub mod node;
+pub mod pathfinding;
 pub mod transform;
 
 pub mod prelude {
+    pub use super::bvh::Bvh;
     pub use super::graph::SceneGraph;
+    pub use super::grid::SpatialHashGrid;
     pub use super::node::Node;
+    pub use super::pathfinding::{find_path, request_path, PathGrid, PathOptions, PathRequest};
     pub use super::transform::Transform;
 }
@@ -0,0 +1,425 @@
+//! Path finding on uniform grids, for the games which don't need full navigation meshes.
+//!
+//! Paths could be found immediately with `find_path`, or requested with `request_path`
+//! which solves it on the `sched` threads, and returns a `PathRequest` that should be
+//! polled every frame.
+//!
+//! ```rust,ignore
+//! let mut grid = PathGrid::new(64, 64);
+//! grid.set_cost(10, 10, 0);
+//!
+//! let mut req = request_path(Arc::new(grid), (0, 0), (63, 63), PathOptions::default());
+//! // ... later
+//! if req.poll() {
+//!     let path = req.path();
+//! }
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::sync::Arc;
+
+use crayon::sched::prelude::{LatchProbe, LockLatch};
+use crayon::utils::hash::FastHashMap;
+
+const STRAIGHT: u32 = 10;
+const DIAGONAL: u32 = 14;
+
+/// A grid of cells, where each cell has a cost of entering it. Cells with zero cost
+/// are blocked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathGrid {
+    width: i32,
+    height: i32,
+    costs: Vec<u8>,
+}
+
+impl PathGrid {
+    /// Creates a grid where every cell is walkable with a cost of 1.
+    pub fn new(width: u32, height: u32) -> Self {
+        PathGrid {
+            width: width as i32,
+            height: height as i32,
+            costs: vec![1; (width * height) as usize],
+        }
+    }
+
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width as u32
+    }
+
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height as u32
+    }
+
+    /// Sets the cost of entering a cell, where zero blocks the cell.
+    pub fn set_cost(&mut self, x: i32, y: i32, cost: u8) {
+        if let Some(index) = self.index(x, y) {
+            self.costs[index] = cost;
+        }
+    }
+
+    /// Gets the cost of entering a cell, which is zero for blocked cells and the cells
+    /// outside of grid.
+    #[inline]
+    pub fn cost(&self, x: i32, y: i32) -> u8 {
+        self.index(x, y).map(|v| self.costs[v]).unwrap_or(0)
+    }
+
+    /// Checks if the cell is walkable.
+    #[inline]
+    pub fn is_walkable(&self, x: i32, y: i32) -> bool {
+        self.cost(x, y) > 0
+    }
+
+    #[inline]
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        if x >= 0 && y >= 0 && x < self.width && y < self.height {
+            Some((y * self.width + x) as usize)
+        } else {
+            None
+        }
+    }
+}
+
+/// The options of path finding.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PathOptions {
+    /// Allows moving diagonally, as long as both of the adjacent cells are walkable.
+    pub diagonal: bool,
+    /// The weight of heuristic. Weights greater than 1 find paths faster, but the paths
+    /// might be up to `weight` times longer than the shortest ones.
+    pub weight: f32,
+    /// Uses jump point search, which skips most of the cells on open areas. It treats
+    /// every walkable cell as cost 1, and it's only used when `diagonal` is enabled.
+    pub jump_point: bool,
+}
+
+impl Default for PathOptions {
+    fn default() -> Self {
+        PathOptions {
+            diagonal: true,
+            weight: 1.0,
+            jump_point: false,
+        }
+    }
+}
+
+/// Finds a path from `start` to `goal`, which includes both of them. Returns None if
+/// there is no path.
+pub fn find_path(
+    grid: &PathGrid,
+    start: (i32, i32),
+    goal: (i32, i32),
+    options: PathOptions,
+) -> Option<Vec<(i32, i32)>> {
+    if !grid.is_walkable(start.0, start.1) || !grid.is_walkable(goal.0, goal.1) {
+        return None;
+    }
+
+    Solver {
+        grid,
+        goal,
+        options,
+        jump_point: options.jump_point && options.diagonal,
+    }
+    .solve(start)
+}
+
+/// An asynchronous path request. You should check the completion status with `poll`
+/// method, and fetch the result by `path` once it returns true.
+pub enum PathRequest {
+    NotReady(Arc<LockLatch<Option<Vec<(i32, i32)>>>>),
+    Ok(Option<Vec<(i32, i32)>>),
+}
+
+impl PathRequest {
+    /// Attempts to resolve the request, and returns true if the path is ready.
+    pub fn poll(&mut self) -> bool {
+        let path = match *self {
+            PathRequest::Ok(_) => return true,
+            PathRequest::NotReady(ref latch) => {
+                if !latch.is_set() {
+                    return false;
+                }
+
+                latch.take()
+            }
+        };
+
+        *self = PathRequest::Ok(path);
+        true
+    }
+
+    /// Returns the path if the request is resolved and a path is found.
+    pub fn path(&self) -> Option<&[(i32, i32)]> {
+        match *self {
+            PathRequest::Ok(Some(ref path)) => Some(path),
+            _ => None,
+        }
+    }
+}
+
+/// Finds a path from `start` to `goal` on the `sched` threads.
+pub fn request_path(
+    grid: Arc<PathGrid>,
+    start: (i32, i32),
+    goal: (i32, i32),
+    options: PathOptions,
+) -> PathRequest {
+    let latch = Arc::new(LockLatch::new());
+    let clone = latch.clone();
+
+    crayon::sched::spawn(move || {
+        #[cfg(feature = "alloc-tracking")]
+        let _scope = crayon::memory::scope("world");
+        clone.set(find_path(&grid, start, goal, options));
+    });
+
+    PathRequest::NotReady(latch)
+}
+
+#[derive(PartialEq, Eq)]
+struct Open {
+    f: u32,
+    g: u32,
+    pos: (i32, i32),
+}
+
+impl Ord for Open {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        // Reversed for the min-heap, and prefers the nodes closer to goal on ties.
+        rhs.f
+            .cmp(&self.f)
+            .then_with(|| self.g.cmp(&rhs.g))
+            .then_with(|| self.pos.cmp(&rhs.pos))
+    }
+}
+
+impl PartialOrd for Open {
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+struct Solver<'a> {
+    grid: &'a PathGrid,
+    goal: (i32, i32),
+    options: PathOptions,
+    jump_point: bool,
+}
+
+impl<'a> Solver<'a> {
+    fn solve(&self, start: (i32, i32)) -> Option<Vec<(i32, i32)>> {
+        let mut open = BinaryHeap::new();
+        let mut parents: FastHashMap<(i32, i32), ((i32, i32), u32)> = FastHashMap::default();
+
+        parents.insert(start, (start, 0));
+        open.push(Open {
+            f: self.heuristic(start),
+            g: 0,
+            pos: start,
+        });
+
+        let mut neighbors = Vec::new();
+        while let Some(v) = open.pop() {
+            if v.pos == self.goal {
+                return Some(self.build(&parents, start));
+            }
+
+            // Skips the stale entries of nodes which have been reached with lower costs.
+            if parents[&v.pos].1 < v.g {
+                continue;
+            }
+
+            neighbors.clear();
+            let parent = parents[&v.pos].0;
+            if self.jump_point {
+                self.jump_neighbors(v.pos, parent, &mut neighbors);
+            } else {
+                self.neighbors(v.pos, &mut neighbors);
+            }
+
+            for &(pos, cost) in &neighbors {
+                let g = v.g + cost;
+                if parents.get(&pos).map(|p| g < p.1) == Some(false) {
+                    continue;
+                }
+
+                parents.insert(pos, (v.pos, g));
+                open.push(Open {
+                    f: g + self.heuristic(pos),
+                    g,
+                    pos,
+                });
+            }
+        }
+
+        None
+    }
+
+    fn heuristic(&self, pos: (i32, i32)) -> u32 {
+        let h = if self.options.diagonal {
+            octile(pos, self.goal)
+        } else {
+            ((pos.0 - self.goal.0).abs() + (pos.1 - self.goal.1).abs()) as u32 * STRAIGHT
+        };
+
+        (h as f32 * self.options.weight.max(1.0)) as u32
+    }
+
+    fn neighbors(&self, pos: (i32, i32), out: &mut Vec<((i32, i32), u32)>) {
+        let (x, y) = pos;
+        for &(dx, dy) in &[(1, 0), (-1, 0), (0, 1), (0, -1)] {
+            let cost = u32::from(self.grid.cost(x + dx, y + dy));
+            if cost > 0 {
+                out.push(((x + dx, y + dy), cost * STRAIGHT));
+            }
+        }
+
+        if !self.options.diagonal {
+            return;
+        }
+
+        for &(dx, dy) in &[(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+            let cost = u32::from(self.grid.cost(x + dx, y + dy));
+            if cost > 0 && self.walkable(x + dx, y) && self.walkable(x, y + dy) {
+                out.push(((x + dx, y + dy), cost * DIAGONAL));
+            }
+        }
+    }
+
+    fn jump_neighbors(
+        &self,
+        pos: (i32, i32),
+        parent: (i32, i32),
+        out: &mut Vec<((i32, i32), u32)>,
+    ) {
+        let mut dirs = Vec::new();
+        let (x, y) = pos;
+
+        if pos == parent {
+            for &(dx, dy) in &[
+                (1, 0),
+                (-1, 0),
+                (0, 1),
+                (0, -1),
+                (1, 1),
+                (1, -1),
+                (-1, 1),
+                (-1, -1),
+            ] {
+                dirs.push((dx, dy));
+            }
+        } else {
+            let dx = (x - parent.0).signum();
+            let dy = (y - parent.1).signum();
+
+            if dx != 0 && dy != 0 {
+                dirs.push((0, dy));
+                dirs.push((dx, 0));
+                dirs.push((dx, dy));
+            } else if dx != 0 {
+                dirs.push((dx, 0));
+                dirs.push((0, 1));
+                dirs.push((0, -1));
+                dirs.push((dx, 1));
+                dirs.push((dx, -1));
+            } else {
+                dirs.push((0, dy));
+                dirs.push((1, 0));
+                dirs.push((-1, 0));
+                dirs.push((1, dy));
+                dirs.push((-1, dy));
+            }
+        }
+
+        for (dx, dy) in dirs {
+            if dx != 0 && dy != 0 && !(self.walkable(x + dx, y) && self.walkable(x, y + dy)) {
+                continue;
+            }
+
+            if let Some(v) = self.jump(x + dx, y + dy, dx, dy) {
+                out.push((v, octile(pos, v)));
+            }
+        }
+    }
+
+    /// Walks from `(x, y)` along the direction until it reaches a jump point.
+    fn jump(&self, mut x: i32, mut y: i32, dx: i32, dy: i32) -> Option<(i32, i32)> {
+        loop {
+            if !self.walkable(x, y) {
+                return None;
+            }
+
+            if (x, y) == self.goal {
+                return Some((x, y));
+            }
+
+            if dx != 0 && dy != 0 {
+                // Moving diagonally, stops if there is a jump point horizontally or
+                // vertically.
+                if self.jump(x + dx, y, dx, 0).is_some() || self.jump(x, y + dy, 0, dy).is_some() {
+                    return Some((x, y));
+                }
+            } else if dx != 0 {
+                let up = self.walkable(x, y + 1) && !self.walkable(x - dx, y + 1);
+                let down = self.walkable(x, y - 1) && !self.walkable(x - dx, y - 1);
+                if up || down {
+                    return Some((x, y));
+                }
+            } else {
+                let right = self.walkable(x + 1, y) && !self.walkable(x + 1, y - dy);
+                let left = self.walkable(x - 1, y) && !self.walkable(x - 1, y - dy);
+                if right || left {
+                    return Some((x, y));
+                }
+            }
+
+            if !(self.walkable(x + dx, y) && self.walkable(x, y + dy)) {
+                return None;
+            }
+
+            x += dx;
+            y += dy;
+        }
+    }
+
+    #[inline]
+    fn walkable(&self, x: i32, y: i32) -> bool {
+        self.grid.is_walkable(x, y)
+    }
+
+    fn build(
+        &self,
+        parents: &FastHashMap<(i32, i32), ((i32, i32), u32)>,
+        start: (i32, i32),
+    ) -> Vec<(i32, i32)> {
+        let mut path = vec![self.goal];
+        let mut pos = self.goal;
+
+        while pos != start {
+            let parent = parents[&pos].0;
+
+            // Fills the cells between jump points.
+            let dx = (parent.0 - pos.0).signum();
+            let dy = (parent.1 - pos.1).signum();
+            while pos != parent {
+                pos = (pos.0 + dx, pos.1 + dy);
+                path.push(pos);
+            }
+        }
+
+        path.reverse();
+        path
+    }
+}
+
+fn octile(lhs: (i32, i32), rhs: (i32, i32)) -> u32 {
+    let dx = (lhs.0 - rhs.0).abs() as u32;
+    let dy = (lhs.1 - rhs.1).abs() as u32;
+    let (min, max) = if dx < dy { (dx, dy) } else { (dy, dx) };
+    min * DIAGONAL + (max - min) * STRAIGHT
+}
@@ -1,6 +1,7 @@
 use std::sync::{Arc, RwLock};
 
 use crayon::application::prelude::*;
+use crayon::res::gc::{GarbageCollectorHandle, GarbageReport};
 use crayon::res::utils::prelude::*;
 use crayon::video::assets::prelude::*;
 use failure::Error;
@@ -18,24 +19,49 @@ pub struct WorldDefaultResources {
 
 pub struct WorldSystem {
     prefabs: Arc<RwLock<ResourcePool<PrefabHandle, PrefabLoader>>>,
+    items: Arc<RwLock<ResourcePool<ItemCatalogHandle, ItemCatalogLoader>>>,
+    quests: Arc<RwLock<ResourcePool<QuestBookHandle, QuestBookLoader>>>,
     lis: LifecycleListenerHandle,
+    collector: GarbageCollectorHandle,
 
     pub default: WorldDefaultResources,
 }
 
+#[derive(Clone)]
 struct WorldState {
     prefabs: Arc<RwLock<ResourcePool<PrefabHandle, PrefabLoader>>>,
+    items: Arc<RwLock<ResourcePool<ItemCatalogHandle, ItemCatalogLoader>>>,
+    quests: Arc<RwLock<ResourcePool<QuestBookHandle, QuestBookLoader>>>,
 }
 
 impl LifecycleListener for WorldState {
     fn on_pre_update(&mut self) -> Result<(), Error> {
+        #[cfg(feature = "alloc-tracking")]
+        let _scope = crayon::memory::scope("world");
         self.prefabs.write().unwrap().advance()?;
+        self.items.write().unwrap().advance()?;
+        self.quests.write().unwrap().advance()?;
         Ok(())
     }
 }
 
+impl WorldState {
+    fn collect_garbage(&self, report: &mut GarbageReport) {
+        // Prefabs are freed before the meshes they own, which are collected by video later.
+        let prefabs = self.prefabs.write().unwrap().collect_garbage();
+        report.add("prefabs", prefabs);
+
+        let items = self.items.write().unwrap().collect_garbage();
+        report.add("item catalogs", items);
+
+        let quests = self.quests.write().unwrap().collect_garbage();
+        report.add("quest books", quests);
+    }
+}
+
 impl Drop for WorldSystem {
     fn drop(&mut self) {
+        crayon::res::detach_collector(self.collector);
         crayon::application::detach(self.lis);
     }
 }
@@ -50,10 +76,24 @@ impl WorldSystem {
         };
 
         let prefabs = Arc::new(RwLock::new(ResourcePool::new(PrefabLoader::new())));
+        let items = Arc::new(RwLock::new(ResourcePool::new(ItemCatalogLoader::new())));
+        let quests = Arc::new(RwLock::new(ResourcePool::new(QuestBookLoader::new())));
 
-        let shared = WorldSystem {
+        let state = WorldState {
             prefabs: prefabs.clone(),
-            lis: crayon::application::attach(WorldState { prefabs }),
+            items: items.clone(),
+            quests: quests.clone(),
+        };
+
+        let clone = state.clone();
+        let collector = crayon::res::attach_collector(move |report| clone.collect_garbage(report));
+
+        let shared = WorldSystem {
+            prefabs: prefabs,
+            items: items,
+            quests: quests,
+            lis: crayon::application::attach(state),
+            collector: collector,
             default: default,
         };
 
@@ -93,4 +133,71 @@ impl WorldSystem {
     pub fn delete_prefab(&self, handle: PrefabHandle) {
         self.prefabs.write().unwrap().delete(handle);
     }
+
+    /// Create an item catalog from file asynchronously.
+    #[inline]
+    pub fn create_item_catalog_from<T: AsRef<str>>(
+        &self,
+        url: T,
+    ) -> Result<ItemCatalogHandle, Error> {
+        let handle = self.items.write().unwrap().create_from(url)?;
+        Ok(handle)
+    }
+
+    /// Creates an item catalog.
+    #[inline]
+    pub fn create_item_catalog(&self, catalog: ItemCatalog) -> Result<ItemCatalogHandle, Error> {
+        let handle = self.items.write().unwrap().create(catalog)?;
+        Ok(handle)
+    }
+
+    /// Return the item catalog if exists.
+    #[inline]
+    pub fn item_catalog(&self, handle: ItemCatalogHandle) -> Option<Arc<ItemCatalog>> {
+        self.items.read().unwrap().resource(handle).cloned()
+    }
+
+    /// Query the resource state of specified item catalog.
+    #[inline]
+    pub fn item_catalog_state(&self, handle: ItemCatalogHandle) -> ResourceState {
+        self.items.read().unwrap().state(handle)
+    }
+
+    /// Delete an item catalog from this world.
+    #[inline]
+    pub fn delete_item_catalog(&self, handle: ItemCatalogHandle) {
+        self.items.write().unwrap().delete(handle);
+    }
+
+    /// Create a quest book from file asynchronously.
+    #[inline]
+    pub fn create_quest_book_from<T: AsRef<str>>(&self, url: T) -> Result<QuestBookHandle, Error> {
+        let handle = self.quests.write().unwrap().create_from(url)?;
+        Ok(handle)
+    }
+
+    /// Creates a quest book.
+    #[inline]
+    pub fn create_quest_book(&self, book: QuestBook) -> Result<QuestBookHandle, Error> {
+        let handle = self.quests.write().unwrap().create(book)?;
+        Ok(handle)
+    }
+
+    /// Return the quest book if exists.
+    #[inline]
+    pub fn quest_book(&self, handle: QuestBookHandle) -> Option<Arc<QuestBook>> {
+        self.quests.read().unwrap().resource(handle).cloned()
+    }
+
+    /// Query the resource state of specified quest book.
+    #[inline]
+    pub fn quest_book_state(&self, handle: QuestBookHandle) -> ResourceState {
+        self.quests.read().unwrap().state(handle)
+    }
+
+    /// Delete a quest book from this world.
+    #[inline]
+    pub fn delete_quest_book(&self, handle: QuestBookHandle) {
+        self.quests.write().unwrap().delete(handle);
+    }
 }
@@ -0,0 +1,193 @@
+extern crate crayon;
+extern crate crayon_world;
+extern crate rand;
+
+use crayon::math::prelude::*;
+use crayon::utils::prelude::HandleLike;
+use crayon_world::prelude::*;
+
+fn cube(x: f32, y: f32, z: f32, size: f32) -> Aabb3<f32> {
+    Aabb3::new(
+        Point3::new(x, y, z),
+        Point3::new(x + size, y + size, z + size),
+    )
+}
+
+fn random_cube() -> Aabb3<f32> {
+    let v = || rand::random::<f32>() * 100.0 - 50.0;
+    cube(v(), v(), v(), rand::random::<f32>() * 4.0 + 0.1)
+}
+
+fn intersects(lhs: Aabb3<f32>, rhs: Aabb3<f32>) -> bool {
+    lhs.min.x <= rhs.max.x
+        && lhs.max.x >= rhs.min.x
+        && lhs.min.y <= rhs.max.y
+        && lhs.max.y >= rhs.min.y
+        && lhs.min.z <= rhs.max.z
+        && lhs.max.z >= rhs.min.z
+}
+
+fn slab(bounds: Aabb3<f32>, origin: Vector3<f32>, dir: Vector3<f32>) -> Option<f32> {
+    let mut near = 0.0f32;
+    let mut far = ::std::f32::INFINITY;
+
+    for i in 0..3 {
+        let t0 = (bounds.min[i] - origin[i]) / dir[i];
+        let t1 = (bounds.max[i] - origin[i]) / dir[i];
+        near = near.max(t0.min(t1));
+        far = far.min(t0.max(t1));
+    }
+
+    if near <= far {
+        Some(near)
+    } else {
+        None
+    }
+}
+
+fn sorted(mut v: Vec<Entity>) -> Vec<Entity> {
+    v.sort();
+    v
+}
+
+#[test]
+fn basic() {
+    let (e1, e2) = (Entity::new(1, 1), Entity::new(2, 1));
+
+    let mut bvh = Bvh::new(0.5);
+    assert_eq!(bvh.height(), 0);
+
+    bvh.insert(e1, cube(0.0, 0.0, 0.0, 1.0));
+    bvh.insert(e2, cube(5.0, 0.0, 0.0, 1.0));
+    assert_eq!(bvh.len(), 2);
+    assert_eq!(bvh.height(), 2);
+
+    assert_eq!(bvh.query(cube(-1.0, -1.0, -1.0, 1.5)), vec![e1]);
+    assert_eq!(
+        sorted(bvh.query(cube(-1.0, -1.0, -1.0, 10.0))),
+        vec![e1, e2]
+    );
+
+    // Moves inside the enlarged bounds.
+    assert!(bvh.update(e1, cube(0.2, 0.0, 0.0, 1.0)));
+    assert_eq!(bvh.bounds(e1).unwrap().min.x, 0.2);
+    assert!(bvh.query(cube(-1.0, -1.0, -1.0, 1.1)).is_empty());
+
+    // Moves outside the enlarged bounds.
+    bvh.insert(e1, cube(10.0, 0.0, 0.0, 1.0));
+    assert_eq!(bvh.len(), 2);
+    assert_eq!(bvh.query(cube(9.5, -1.0, -1.0, 2.0)), vec![e1]);
+
+    let (ent, d) = bvh
+        .raycast(
+            Vector3::new(0.0, 0.5, 0.5),
+            Vector3::new(1.0, 0.0, 0.0),
+            100.0,
+        )
+        .unwrap();
+    assert_eq!((ent, d), (e2, 5.0));
+    assert!(bvh
+        .raycast(
+            Vector3::new(0.0, 0.5, 0.5),
+            Vector3::new(1.0, 0.0, 0.0),
+            4.0
+        )
+        .is_none());
+
+    assert!(bvh.remove(e2));
+    assert!(!bvh.remove(e2));
+    assert_eq!(bvh.height(), 1);
+
+    let (ent, d) = bvh
+        .raycast(
+            Vector3::new(0.0, 0.5, 0.5),
+            Vector3::new(1.0, 0.0, 0.0),
+            100.0,
+        )
+        .unwrap();
+    assert_eq!((ent, d), (e1, 10.0));
+
+    bvh.clear();
+    assert!(bvh.is_empty());
+}
+
+#[test]
+fn frustum() {
+    let (e1, e2) = (Entity::new(1, 1), Entity::new(2, 1));
+
+    let mut bvh = Bvh::default();
+    bvh.insert(e1, cube(-0.5, -0.5, -10.0, 1.0));
+    bvh.insert(e2, cube(-0.5, -0.5, 10.0, 1.0));
+
+    let projection = Projection::Ortho {
+        width: 4.0,
+        height: 4.0,
+        near: 0.1,
+        far: 100.0,
+    };
+
+    let frustum = Frustum::new(projection);
+    let visible = bvh.query_frustum(&frustum, Matrix4::identity());
+    assert_eq!(visible.len(), 1);
+
+    let other = bvh.query_frustum(&frustum, Matrix4::from_angle_y(Deg(180.0)));
+    assert_eq!(other.len(), 1);
+    assert_ne!(visible, other);
+}
+
+#[test]
+fn brute_force() {
+    let mut bvh = Bvh::new(0.5);
+    let mut bounds = Vec::new();
+
+    for i in 0..500 {
+        let ent = Entity::new(i, 1);
+        let v = random_cube();
+        bvh.insert(ent, v);
+        bounds.push((ent, v));
+    }
+
+    // Moves and removes some of the entities.
+    for i in 0..200 {
+        let v = random_cube();
+        bvh.update(bounds[i].0, v);
+        bounds[i].1 = v;
+    }
+
+    for (ent, _) in bounds.drain(400..) {
+        bvh.remove(ent);
+    }
+
+    assert_eq!(bvh.len(), bounds.len());
+    assert!(bvh.height() < 40);
+
+    for _ in 0..100 {
+        let area = random_cube().add_margin(Vector3::new(5.0, 5.0, 5.0));
+        let expected: Vec<_> = bounds
+            .iter()
+            .filter(|v| intersects(v.1, area))
+            .map(|v| v.0)
+            .collect();
+
+        assert_eq!(sorted(bvh.query(area)), sorted(expected));
+    }
+
+    for _ in 0..100 {
+        let origin = random_cube().center().to_vec();
+        let dir = random_cube().center().to_vec() - origin;
+
+        let expected = bounds
+            .iter()
+            .filter_map(|v| slab(v.1, origin, dir.normalize()))
+            .filter(|&d| d <= 1000.0)
+            .fold(None, |acc: Option<f32>, d| {
+                Some(acc.map_or(d, |v| v.min(d)))
+            });
+
+        match (bvh.raycast(origin, dir, 1000.0), expected) {
+            (Some((_, d)), Some(expected)) => assert!((d - expected).abs() < 0.001),
+            (None, None) => {}
+            (hit, expected) => panic!("{:?} != {:?}", hit, expected),
+        }
+    }
+}
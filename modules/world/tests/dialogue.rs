@@ -0,0 +1,124 @@
+extern crate crayon_world;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crayon_world::gameplay::dialogue::MAX_STEPS;
+use crayon_world::prelude::*;
+
+const SOURCE: &str = "
+# The guard at the gate.
+== start
+guard: Halt! Who goes there?
+! play_sound alert
+* Just a traveler. -> traveler
+* {has_pass} Show the pass. -> gate
+* Leave. -> END
+
+== traveler
+guard: Move along then.
+{is_night} -> gate
+
+== gate
+The gate opens.
+";
+
+fn line(speaker: Option<&str>, text: &str) -> DialogueEvent {
+    DialogueEvent::Line {
+        speaker: speaker.map(|v| v.to_owned()),
+        text: text.to_owned(),
+    }
+}
+
+#[test]
+fn parse() {
+    let dialogue = Dialogue::parse(SOURCE).unwrap();
+    assert_eq!(dialogue.nodes.len(), 3);
+    assert_eq!(dialogue.nodes[0].steps.len(), 3);
+    assert_eq!(dialogue.find("gate"), Some(2));
+
+    assert!(Dialogue::parse("orphan line").is_err());
+    assert!(Dialogue::parse("== a\n-> b").is_err());
+    assert!(Dialogue::parse("== a\n== a").is_err());
+    assert!(Dialogue::parse("== a\n* no target").is_err());
+
+    // Jumps which never stop at a line or choices.
+    assert!(Dialogue::parse("== a\n-> a").is_err());
+    assert!(Dialogue::parse("== a\n! cmd\n-> b\n== b\n-> a").is_err());
+    assert!(Dialogue::parse("== a\n-> b\n== b\n-> c\n== c\n-> b").is_err());
+    assert!(Dialogue::parse("== a\ntext\n-> a").is_ok());
+    assert!(Dialogue::parse("== a\n* text -> a").is_ok());
+    assert!(Dialogue::parse("== a\n{flag} -> a\ntext").is_ok());
+}
+
+#[test]
+fn playback() {
+    let commands = Rc::new(RefCell::new(Vec::new()));
+    let clone = commands.clone();
+
+    let mut player = DialoguePlayer::new(Arc::new(Dialogue::parse(SOURCE).unwrap()));
+    player.set_commands(move |v| clone.borrow_mut().push(v.to_owned()));
+    player.set_conditions(|v| v == "is_night");
+    player.set_localizer(|v| {
+        if v == "Leave." {
+            Some("Partir.".to_owned())
+        } else {
+            None
+        }
+    });
+
+    assert!(player.start("missing").is_err());
+    player.start("start").unwrap();
+    assert!(!player.choose(0).unwrap());
+    assert_eq!(
+        player.drain_events().collect::<Vec<_>>(),
+        vec![line(Some("guard"), "Halt! Who goes there?")]
+    );
+
+    assert!(player.advance().unwrap());
+    assert_eq!(
+        player.drain_events().collect::<Vec<_>>(),
+        vec![
+            DialogueEvent::Command("play_sound alert".to_owned()),
+            DialogueEvent::Choices(vec!["Just a traveler.".to_owned(), "Partir.".to_owned()]),
+        ]
+    );
+    assert_eq!(*commands.borrow(), vec!["play_sound alert"]);
+
+    assert!(!player.choose(2).unwrap());
+    assert!(player.choose(0).unwrap());
+    assert_eq!(player.node(), Some("traveler"));
+    assert!(player.advance().unwrap());
+    assert_eq!(player.node(), Some("gate"));
+    assert!(player.advance().unwrap());
+    assert!(!player.is_playing());
+    assert_eq!(
+        player.drain_events().collect::<Vec<_>>(),
+        vec![
+            line(Some("guard"), "Move along then."),
+            line(None, "The gate opens."),
+            DialogueEvent::Ended,
+        ]
+    );
+
+    player.start("start").unwrap();
+    player.advance().unwrap();
+    assert!(player.choose(1).unwrap());
+    assert!(!player.is_playing());
+    assert_eq!(player.drain_events().last(), Some(DialogueEvent::Ended));
+}
+
+#[test]
+fn guarded_loop() {
+    let dialogue = Dialogue::parse("== a\n! tick\n{loop} -> a\ntext").unwrap();
+    let mut player = DialoguePlayer::new(Arc::new(dialogue));
+    player.set_conditions(|v| v == "loop");
+
+    assert!(player.start("a").is_err());
+    assert!(!player.is_playing());
+
+    let events: Vec<_> = player.drain_events().collect();
+    assert_eq!(events.len(), MAX_STEPS / 2 + 1);
+    assert_eq!(events.last(), Some(&DialogueEvent::Ended));
+}
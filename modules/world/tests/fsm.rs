@@ -0,0 +1,104 @@
+extern crate crayon;
+extern crate crayon_world;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crayon::utils::prelude::HandleLike;
+use crayon_world::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Door {
+    Closed,
+    Opening,
+    Open,
+}
+
+#[test]
+fn transitions() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut fsm = Fsm::new(Door::Closed);
+
+    let l = log.clone();
+    fsm.on_exit(Door::Closed, move |to| {
+        l.borrow_mut().push(format!("exit {:?}", to))
+    });
+    let l = log.clone();
+    fsm.on_transition(move |from, to| l.borrow_mut().push(format!("{:?}->{:?}", from, to)));
+    let l = log.clone();
+    fsm.on_enter(Door::Opening, move |from| {
+        l.borrow_mut().push(format!("enter {:?}", from))
+    });
+
+    assert!(!fsm.transition(Door::Closed));
+    assert!(fsm.transition(Door::Opening));
+    assert!(fsm.is(Door::Opening));
+    assert_eq!(fsm.previous(), Some(Door::Closed));
+    assert_eq!(
+        *log.borrow(),
+        vec!["exit Opening", "Closed->Opening", "enter Closed"]
+    );
+
+    assert!(fsm.transition(Door::Open));
+    assert_eq!(log.borrow().len(), 4);
+
+    let events: Vec<_> = fsm.drain_events().collect();
+    assert_eq!(
+        events,
+        vec![
+            StateTransition {
+                from: Door::Closed,
+                to: Door::Opening,
+            },
+            StateTransition {
+                from: Door::Opening,
+                to: Door::Open,
+            },
+        ]
+    );
+    assert!(fsm.events().is_empty());
+}
+
+#[test]
+fn allowed() {
+    let mut fsm = Fsm::new(Door::Closed);
+    fsm.allow(Door::Closed, Door::Opening);
+    fsm.allow(Door::Opening, Door::Open);
+
+    assert!(!fsm.can_transition(Door::Open));
+    assert!(!fsm.transition(Door::Open));
+    assert!(fsm.transition(Door::Opening));
+    assert!(fsm.transition(Door::Open));
+    assert!(!fsm.transition(Door::Closed));
+    assert_eq!(fsm.events().len(), 2);
+}
+
+#[test]
+fn entities() {
+    let (e1, e2, e3) = (Entity::new(1, 1), Entity::new(2, 1), Entity::new(3, 1));
+
+    let mut machines = Fsms::new();
+    machines.add(e1, Fsm::new(Door::Closed));
+    machines.add(e2, Fsm::new(Door::Open));
+
+    assert!(machines.transition(e1, Door::Opening));
+    assert!(machines.transition(e2, Door::Closed));
+    assert!(!machines.transition(e3, Door::Closed));
+    assert_eq!(machines.state(e1), Some(Door::Opening));
+    assert_eq!(machines.state(e3), None);
+
+    let events = machines.drain_events();
+    assert_eq!(events.len(), 2);
+    assert!(events.contains(&(
+        e2,
+        StateTransition {
+            from: Door::Open,
+            to: Door::Closed,
+        }
+    )));
+    assert!(machines.drain_events().is_empty());
+
+    machines.remove(e1);
+    assert!(!machines.has(e1));
+    assert!(machines.has(e2));
+}
@@ -0,0 +1,127 @@
+extern crate crayon;
+extern crate crayon_world;
+
+use crayon::math::prelude::*;
+use crayon::utils::prelude::HandleLike;
+use crayon_world::prelude::*;
+
+fn rect(x: f32, y: f32, w: f32, h: f32) -> Aabb2<f32> {
+    Aabb2::new(Point2::new(x, y), Point2::new(x + w, y + h))
+}
+
+fn sorted(mut v: Vec<Entity>) -> Vec<Entity> {
+    v.sort();
+    v
+}
+
+#[test]
+fn insert_and_query() {
+    let (e1, e2, e3) = (Entity::new(1, 1), Entity::new(2, 1), Entity::new(3, 1));
+
+    let mut grid = SpatialHashGrid::new(1.0);
+    grid.insert(e1, rect(0.2, 0.2, 0.5, 0.5));
+    grid.insert(e2, rect(2.5, 0.5, 3.0, 3.0));
+    grid.insert(e3, rect(-4.0, -4.0, 0.5, 0.5));
+    assert_eq!(grid.len(), 3);
+
+    assert_eq!(grid.query(rect(0.0, 0.0, 1.0, 1.0)), vec![e1]);
+    assert_eq!(grid.query(rect(4.0, 3.0, 0.1, 0.1)), vec![e2]);
+    assert_eq!(
+        sorted(grid.query(rect(-5.0, -5.0, 10.0, 10.0))),
+        vec![e1, e2, e3]
+    );
+
+    // The cell is shared, but the bounds are not.
+    assert!(grid.query(rect(0.8, 0.8, 0.1, 0.1)).is_empty());
+
+    assert_eq!(grid.query_circle(Vector2::new(0.0, 0.0), 0.5), vec![e1]);
+    assert!(grid.query_circle(Vector2::new(0.0, 0.0), 0.2).is_empty());
+
+    assert!(grid.remove(e2));
+    assert!(!grid.remove(e2));
+    assert!(!grid.contains(e2));
+    assert!(grid.query(rect(4.0, 3.0, 0.1, 0.1)).is_empty());
+}
+
+#[test]
+fn update() {
+    let e1 = Entity::new(1, 1);
+
+    let mut grid = SpatialHashGrid::new(1.0);
+    assert!(!grid.update(e1, rect(0.0, 0.0, 0.5, 0.5)));
+
+    grid.insert(e1, rect(0.0, 0.0, 0.5, 0.5));
+    assert!(grid.update(e1, rect(10.0, 10.0, 0.5, 0.5)));
+    assert!(grid.query(rect(0.0, 0.0, 1.0, 1.0)).is_empty());
+    assert_eq!(grid.query(rect(10.0, 10.0, 1.0, 1.0)), vec![e1]);
+
+    grid.insert(e1, rect(-10.0, 0.0, 0.5, 0.5));
+    assert_eq!(grid.len(), 1);
+    assert_eq!(grid.query(rect(-10.0, 0.0, 1.0, 1.0)), vec![e1]);
+
+    grid.clear();
+    assert!(grid.is_empty());
+}
+
+#[test]
+fn raycast() {
+    let (e1, e2, e3) = (Entity::new(1, 1), Entity::new(2, 1), Entity::new(3, 1));
+
+    let mut grid = SpatialHashGrid::new(1.0);
+    grid.insert(e1, rect(5.0, -0.5, 1.0, 1.0));
+    grid.insert(e2, rect(2.0, -10.0, 1.0, 20.0));
+    grid.insert(e3, rect(-3.0, 2.0, 1.0, 1.0));
+
+    let (ent, d) = grid
+        .raycast(Vector2::new(0.0, 0.0), Vector2::new(1.0, 0.0), 100.0)
+        .unwrap();
+    assert_eq!(ent, e2);
+    assert_eq!(d, 2.0);
+
+    assert!(grid
+        .raycast(Vector2::new(0.0, 0.0), Vector2::new(1.0, 0.0), 1.5)
+        .is_none());
+
+    let (ent, _) = grid
+        .raycast(Vector2::new(0.0, 0.0), Vector2::new(-1.0, 1.0), 100.0)
+        .unwrap();
+    assert_eq!(ent, e3);
+
+    assert!(grid
+        .raycast(Vector2::new(0.0, 0.0), Vector2::new(0.0, -1.0), 100.0)
+        .is_none());
+
+    // The walk ends once the ray leaves the occupied cells.
+    let inf = ::std::f32::INFINITY;
+    assert!(grid
+        .raycast(Vector2::new(0.0, 0.0), Vector2::new(0.0, -1.0), inf)
+        .is_none());
+    assert!(grid
+        .raycast(Vector2::new(20.0, 20.0), Vector2::new(1.0, 0.3), inf)
+        .is_none());
+
+    let (ent, d) = grid
+        .raycast(Vector2::new(-50.0, 0.0), Vector2::new(1.0, 0.0), inf)
+        .unwrap();
+    assert_eq!(ent, e2);
+    assert_eq!(d, 52.0);
+
+    // The occupied range follows removals.
+    grid.remove(e2);
+    let (ent, d) = grid
+        .raycast(Vector2::new(-50.0, 0.0), Vector2::new(1.0, 0.0), inf)
+        .unwrap();
+    assert_eq!(ent, e1);
+    assert_eq!(d, 55.0);
+
+    grid.remove(e1);
+    grid.remove(e3);
+    assert!(grid
+        .raycast(Vector2::new(-50.0, 0.0), Vector2::new(1.0, 0.0), inf)
+        .is_none());
+
+    let grid = SpatialHashGrid::new(1.0);
+    assert!(grid
+        .raycast(Vector2::new(0.0, 0.0), Vector2::new(1.0, 0.0), inf)
+        .is_none());
+}
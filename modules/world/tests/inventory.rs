@@ -0,0 +1,85 @@
+extern crate crayon_world;
+
+use crayon_world::prelude::*;
+
+fn catalog() -> ItemCatalog {
+    let mut potion = ItemDefinition::new("potion");
+    potion.max_stack = 10;
+    potion.properties.insert("price".into(), 5.0);
+
+    let mut sword = ItemDefinition::new("sword");
+    sword.tags.push("weapon".into());
+
+    ItemCatalog::new(vec![potion, sword]).unwrap()
+}
+
+#[test]
+fn definitions() {
+    let catalog = catalog();
+    assert_eq!(catalog.get("potion").unwrap().property("price"), Some(5.0));
+    assert!(catalog.get("sword").unwrap().has_tag("weapon"));
+    assert!(catalog.get("shield").is_none());
+
+    let items = catalog.items.clone();
+    assert!(ItemCatalog::new(vec![items[0].clone(), items[0].clone()]).is_err());
+
+    let mut empty = ItemDefinition::new("empty");
+    empty.max_stack = 0;
+    assert!(ItemCatalog::new(vec![empty]).is_err());
+}
+
+#[test]
+fn stacking() {
+    let catalog = catalog();
+    let potion = catalog.get("potion").unwrap();
+    let sword = catalog.get("sword").unwrap();
+
+    let mut bag = Inventory::new(3);
+    assert!(bag.is_empty());
+    assert_eq!(bag.add(potion, 4), 0);
+    assert_eq!(bag.add(sword, 1), 0);
+    assert_eq!(bag.add(potion, 10), 0);
+    assert_eq!(bag.slot(0).unwrap().count, 10);
+    assert_eq!(bag.slot(1).unwrap().item, "sword");
+    assert_eq!(bag.slot(2).unwrap().count, 4);
+
+    assert_eq!(bag.add(potion, 10), 4);
+    assert_eq!(bag.count("potion"), 20);
+
+    assert_eq!(bag.remove("potion", 12), 12);
+    assert_eq!(bag.slot(2), None);
+    assert_eq!(bag.slot(0).unwrap().count, 8);
+    assert_eq!(bag.take(1).unwrap().item, "sword");
+    assert_eq!(bag.iter().count(), 1);
+}
+
+#[test]
+fn filters_and_events() {
+    let catalog = catalog();
+    let potion = catalog.get("potion").unwrap();
+    let sword = catalog.get("sword").unwrap();
+
+    let mut equipment = Inventory::new(1);
+    equipment.set_filter(0, Some("weapon"));
+    assert!(!equipment.accepts(0, potion));
+    assert_eq!(equipment.add(potion, 1), 1);
+    assert_eq!(equipment.add_to(0, sword, 2), 1);
+    assert_eq!(equipment.remove_from(0, 1).unwrap().count, 1);
+
+    let events: Vec<_> = equipment.drain_events().collect();
+    assert_eq!(
+        events,
+        vec![
+            InventoryEvent::Added {
+                slot: 0,
+                item: "sword".into(),
+                count: 1,
+            },
+            InventoryEvent::Removed {
+                slot: 0,
+                item: "sword".into(),
+                count: 1,
+            },
+        ]
+    );
+}
@@ -0,0 +1,145 @@
+extern crate crayon;
+extern crate crayon_world;
+extern crate rand;
+
+use std::sync::Arc;
+
+use crayon::prelude::*;
+use crayon::testing;
+use crayon_world::prelude::*;
+
+fn length(path: &[(i32, i32)]) -> u32 {
+    path.windows(2)
+        .map(|v| {
+            let dx = (v[0].0 - v[1].0).abs();
+            let dy = (v[0].1 - v[1].1).abs();
+            assert!(dx <= 1 && dy <= 1 && dx + dy > 0);
+            if dx + dy == 2 {
+                14
+            } else {
+                10
+            }
+        })
+        .sum()
+}
+
+fn assert_walkable(grid: &PathGrid, path: &[(i32, i32)]) {
+    for v in path {
+        assert!(grid.is_walkable(v.0, v.1));
+    }
+
+    // Never cuts the corners of blocked cells.
+    for v in path.windows(2) {
+        assert!(grid.is_walkable(v[1].0, v[0].1));
+        assert!(grid.is_walkable(v[0].0, v[1].1));
+    }
+}
+
+#[test]
+fn straight() {
+    let grid = PathGrid::new(8, 8);
+    let path = find_path(&grid, (0, 0), (7, 0), PathOptions::default()).unwrap();
+    assert_eq!(path, (0..8).map(|x| (x, 0)).collect::<Vec<_>>());
+
+    let path = find_path(&grid, (3, 3), (3, 3), PathOptions::default()).unwrap();
+    assert_eq!(path, vec![(3, 3)]);
+}
+
+#[test]
+fn blocked() {
+    let mut grid = PathGrid::new(8, 8);
+    for y in 0..8 {
+        grid.set_cost(4, y, 0);
+    }
+
+    assert_eq!(
+        find_path(&grid, (0, 0), (7, 7), PathOptions::default()),
+        None
+    );
+    assert_eq!(
+        find_path(&grid, (0, 0), (4, 0), PathOptions::default()),
+        None
+    );
+    assert_eq!(
+        find_path(&grid, (0, 0), (9, 0), PathOptions::default()),
+        None
+    );
+
+    grid.set_cost(4, 7, 1);
+    let path = find_path(&grid, (0, 0), (7, 0), PathOptions::default()).unwrap();
+    assert!(path.contains(&(4, 7)));
+    assert_walkable(&grid, &path);
+}
+
+#[test]
+fn costs() {
+    let mut grid = PathGrid::new(5, 3);
+    for x in 1..4 {
+        grid.set_cost(x, 1, 9);
+    }
+
+    let mut options = PathOptions::default();
+    options.diagonal = false;
+
+    // Goes around the expensive cells.
+    let path = find_path(&grid, (0, 1), (4, 1), options).unwrap();
+    assert_eq!(path.len(), 7);
+    assert!(path.iter().all(|v| v.1 != 1 || v.0 == 0 || v.0 == 4));
+}
+
+#[test]
+fn optimal() {
+    for _ in 0..32 {
+        let mut grid = PathGrid::new(32, 32);
+        for _ in 0..256 {
+            let x = rand::random::<u32>() % 32;
+            let y = rand::random::<u32>() % 32;
+            grid.set_cost(x as i32, y as i32, 0);
+        }
+
+        grid.set_cost(0, 0, 1);
+        grid.set_cost(31, 31, 1);
+
+        let mut options = PathOptions::default();
+        let astar = find_path(&grid, (0, 0), (31, 31), options);
+
+        options.jump_point = true;
+        let jps = find_path(&grid, (0, 0), (31, 31), options);
+
+        options.jump_point = false;
+        options.weight = 2.0;
+        let weighted = find_path(&grid, (0, 0), (31, 31), options);
+
+        match astar {
+            Some(astar) => {
+                let (jps, weighted) = (jps.unwrap(), weighted.unwrap());
+                assert_walkable(&grid, &astar);
+                assert_walkable(&grid, &jps);
+                assert_walkable(&grid, &weighted);
+
+                assert_eq!(length(&jps), length(&astar));
+                assert!(length(&weighted) <= length(&astar) * 2);
+            }
+            None => {
+                assert_eq!(jps, None);
+                assert_eq!(weighted, None);
+            }
+        }
+    }
+}
+
+#[test]
+fn request() {
+    testing::setup(Params::default()).unwrap();
+
+    let mut grid = PathGrid::new(16, 16);
+    grid.set_cost(8, 8, 0);
+
+    let grid = Arc::new(grid);
+    let mut req = request_path(grid.clone(), (0, 0), (15, 15), PathOptions::default());
+    let mut none = request_path(grid, (0, 0), (8, 8), PathOptions::default());
+
+    testing::advance_until(1000, || req.poll() && none.poll()).unwrap();
+    assert_eq!(req.path().unwrap().len(), 17);
+    assert_eq!(none.path(), None);
+}
@@ -0,0 +1,99 @@
+extern crate crayon_world;
+
+use std::sync::Arc;
+
+use crayon_world::prelude::*;
+
+fn quest(name: &str, prerequisites: &[&str], objectives: &[(&str, u32)]) -> QuestDefinition {
+    QuestDefinition {
+        name: name.to_owned(),
+        description: String::new(),
+        prerequisites: prerequisites.iter().map(|v| v.to_string()).collect(),
+        objectives: objectives
+            .iter()
+            .map(|v| ObjectiveDefinition {
+                name: v.0.to_owned(),
+                description: String::new(),
+                required: v.1,
+            })
+            .collect(),
+        rewards: vec![QuestReward {
+            item: "gold".to_owned(),
+            count: 10,
+        }],
+    }
+}
+
+fn book() -> Arc<QuestBook> {
+    let rats = quest("rats", &[], &[("kill", 3), ("report", 1)]);
+    let wolves = quest("wolves", &["rats"], &[("kill", 1)]);
+    Arc::new(QuestBook::new(vec![rats, wolves]).unwrap())
+}
+
+#[test]
+fn definitions() {
+    let a = quest("a", &["b"], &[("x", 1)]);
+    let b = quest("b", &["a"], &[("x", 1)]);
+    assert!(QuestBook::new(vec![a.clone(), b]).is_err());
+    assert!(QuestBook::new(vec![a.clone()]).is_err());
+    assert!(QuestBook::new(vec![quest("c", &[], &[])]).is_err());
+    assert!(QuestBook::new(vec![quest("c", &[], &[("x", 0)])]).is_err());
+    assert!(QuestBook::new(vec![quest("c", &[], &[("x", 1), ("x", 2)])]).is_err());
+
+    // Objectives are named per quest.
+    let c = quest("c", &[], &[("x", 1), ("y", 2)]);
+    let d = quest("d", &[], &[("x", 1)]);
+    assert!(QuestBook::new(vec![c, d]).is_ok());
+    assert!(book().get("wolves").is_some());
+}
+
+#[test]
+fn tracking() {
+    let mut log = QuestLog::new(book());
+    assert_eq!(log.status("rats"), Some(QuestStatus::Available));
+    assert_eq!(log.status("wolves"), Some(QuestStatus::Locked));
+    assert_eq!(log.status("dragons"), None);
+    assert!(log.start("wolves").is_err());
+    assert!(!log.advance("rats", "kill", 1));
+
+    log.start("rats").unwrap();
+    assert!(log.start("rats").is_err());
+    assert!(log.advance("rats", "kill", 2));
+    assert!(log.advance("rats", "kill", 5));
+    assert!(!log.advance("rats", "kill", 1));
+    assert!(!log.advance("rats", "missing", 1));
+    assert_eq!(log.objective("rats", "kill"), Some((3, 3)));
+    assert_eq!(log.status("rats"), Some(QuestStatus::Active));
+
+    log.drain_events();
+    assert!(log.advance("rats", "report", 1));
+    assert_eq!(log.status("rats"), Some(QuestStatus::Completed));
+    assert_eq!(log.status("wolves"), Some(QuestStatus::Available));
+
+    let events: Vec<_> = log.drain_events().collect();
+    assert_eq!(events.len(), 3);
+    assert_eq!(
+        events[2],
+        QuestEvent::Completed {
+            quest: "rats".to_owned(),
+            rewards: vec![QuestReward {
+                item: "gold".to_owned(),
+                count: 10,
+            }],
+        }
+    );
+}
+
+#[test]
+fn restore() {
+    let mut log = QuestLog::new(book());
+    log.start("rats").unwrap();
+    log.advance("rats", "kill", 2);
+
+    let mut log = QuestLog::with_progress(book(), log.progress().clone());
+    assert_eq!(log.objective("rats", "kill"), Some((2, 3)));
+    assert!(log.fail("rats"));
+    assert!(!log.fail("rats"));
+    assert_eq!(log.status("rats"), Some(QuestStatus::Failed));
+    assert_eq!(log.status("wolves"), Some(QuestStatus::Locked));
+}
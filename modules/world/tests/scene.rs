@@ -88,3 +88,16 @@ fn instantiate() {
         scene.find("room.obj/floor/tallBox")
     );
 }
+
+#[test]
+fn limit() {
+    let mut scene = Scene::with_limit(HeadlessRenderer::new(), 2);
+
+    let e1 = scene.try_create("e1").unwrap();
+    scene.try_create("e2").unwrap();
+    assert!(scene.try_create("e3").is_err());
+    assert_eq!(scene.len(), 2);
+
+    scene.delete(e1);
+    scene.try_create("e3").unwrap();
+}
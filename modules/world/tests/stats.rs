@@ -0,0 +1,52 @@
+extern crate crayon_world;
+
+use std::time::Duration;
+
+use crayon_world::prelude::*;
+
+#[test]
+fn modifiers() {
+    let mut stats = Stats::new();
+    assert_eq!(stats.value("strength"), None);
+    assert!(!stats.add_modifier("strength", Modifier::additive(1.0, "ring")));
+
+    stats.set_base("strength", 10.0);
+    assert_eq!(stats.value("strength"), Some(10.0));
+
+    stats.add_modifier("strength", Modifier::additive(2.0, "ring"));
+    stats.add_modifier("strength", Modifier::multiplicative(2.0, "rage"));
+    stats.add_modifier("strength", Modifier::multiplicative(1.5, "ring"));
+    assert_eq!(stats.value("strength"), Some(36.0));
+    assert_eq!(stats.base("strength"), Some(10.0));
+
+    stats.set_base("strength", 8.0);
+    assert_eq!(stats.value("strength"), Some(30.0));
+
+    assert_eq!(stats.remove_modifiers("ring"), 2);
+    assert_eq!(stats.value("strength"), Some(16.0));
+    assert_eq!(stats.remove_modifiers("ring"), 0);
+}
+
+#[test]
+fn durations() {
+    let mut stats = Stats::new();
+    stats.set_base("speed", 4.0);
+    stats.set_base("armor", 1.0);
+
+    let haste = Modifier::multiplicative(2.0, "haste").with_duration(Duration::from_secs(2));
+    stats.add_modifier("speed", haste);
+    stats.add_modifier("armor", Modifier::additive(3.0, "shield"));
+    assert_eq!(stats.value("speed"), Some(8.0));
+
+    stats.advance(Duration::from_millis(1500));
+    assert_eq!(stats.value("speed"), Some(8.0));
+    assert_eq!(
+        stats.get("speed").unwrap().modifiers()[0].duration,
+        Some(Duration::from_millis(500))
+    );
+
+    stats.advance(Duration::from_millis(500));
+    assert_eq!(stats.value("speed"), Some(4.0));
+    assert_eq!(stats.value("armor"), Some(4.0));
+    assert_eq!(stats.len(), 2);
+}
@@ -0,0 +1,49 @@
+extern crate crayon;
+extern crate crayon_world;
+
+use crayon::math::prelude::Vector3;
+use crayon_world::prelude::*;
+
+#[test]
+fn curve() {
+    let curve = Curve::linear(1.0, 0.0);
+    assert_eq!(curve.evaluate(0.0), 1.0);
+    assert_eq!(curve.evaluate(0.5), 0.5);
+    assert_eq!(curve.evaluate(2.0), 0.0);
+
+    let curve = Curve::constant(2.0).with_key(0.5, 4.0);
+    assert_eq!(curve.evaluate(0.25), 3.0);
+    assert_eq!(curve.evaluate(1.0), 4.0);
+
+    let curve = Curve::linear(0.0, 1.0).with_key(1.0, 2.0);
+    assert_eq!(curve.evaluate(0.5), 1.0);
+}
+
+#[test]
+fn record() {
+    let mut trail = TrailRenderer::new(1.0, 0.5);
+    trail.min_distance = 1.0;
+
+    trail.advance([0.0, 0.0, 0.0], 0.1);
+    trail.advance([0.5, 0.0, 0.0], 0.1);
+    assert_eq!(trail.len(), 2);
+
+    // The head follows the entity until it moves far enough.
+    trail.advance([0.8, 0.0, 0.0], 0.1);
+    assert_eq!(trail.len(), 2);
+
+    trail.advance([1.5, 0.0, 0.0], 0.1);
+    assert_eq!(trail.len(), 3);
+
+    let positions: Vec<_> = trail.positions().collect();
+    assert_eq!(positions[0], Vector3::new(0.0, 0.0, 0.0));
+    assert_eq!(positions[1], Vector3::new(0.8, 0.0, 0.0));
+    assert_eq!(positions[2], Vector3::new(1.5, 0.0, 0.0));
+
+    // Expired positions are removed.
+    trail.advance([1.5, 0.0, 0.0], 0.75);
+    assert_eq!(trail.len(), 2);
+
+    trail.clear();
+    assert_eq!(trail.len(), 0);
+}
@@ -0,0 +1,23 @@
+use crate::errors::*;
+
+/// The storage of achievements and stats, e.g. a local file or the services of a
+/// platform.
+pub trait AchievementBackend: Send + 'static {
+    /// Unlocks an achievement.
+    fn unlock(&mut self, id: &str) -> Result<()>;
+
+    /// Checks if an achievement has been unlocked.
+    fn is_unlocked(&self, id: &str) -> bool;
+
+    /// Sets the value of a stat.
+    fn set_stat(&mut self, id: &str, value: f32) -> Result<()>;
+
+    /// Gets the value of a stat.
+    fn stat(&self, id: &str) -> Option<f32>;
+
+    /// Writes the pending changes. Backends which write changes immediately could leave
+    /// it as is.
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
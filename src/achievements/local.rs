@@ -0,0 +1,85 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::errors::*;
+use crate::res::saves::write_atomic;
+use crate::utils::hash::{FastHashMap, FastHashSet};
+
+use super::backend::AchievementBackend;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Storage {
+    unlocked: FastHashSet<String>,
+    stats: FastHashMap<String, f32>,
+}
+
+/// A backend which persists the achievements and stats in a local JSON file. Changes
+/// are written on `flush`, and when the achievements are discarded.
+pub struct LocalAchievements {
+    path: PathBuf,
+    storage: Storage,
+    dirty: bool,
+}
+
+impl LocalAchievements {
+    /// Loads the achievements from file, or starts from nothing if the file does not
+    /// exist.
+    pub fn load<T: AsRef<Path>>(path: T) -> Result<Self> {
+        let path = path.as_ref().to_owned();
+        let storage = if path.exists() {
+            let bytes = fs::read(&path)?;
+            serde_json::from_slice(&bytes)?
+        } else {
+            Storage::default()
+        };
+
+        Ok(LocalAchievements {
+            path,
+            storage,
+            dirty: false,
+        })
+    }
+
+    /// Gets the path of file.
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl AchievementBackend for LocalAchievements {
+    fn unlock(&mut self, id: &str) -> Result<()> {
+        self.dirty |= self.storage.unlocked.insert(id.to_owned());
+        Ok(())
+    }
+
+    fn is_unlocked(&self, id: &str) -> bool {
+        self.storage.unlocked.contains(id)
+    }
+
+    fn set_stat(&mut self, id: &str, value: f32) -> Result<()> {
+        if !value.is_finite() {
+            bail!("Stat {} could not be set to {}.", id, value);
+        }
+
+        if self.storage.stats.insert(id.to_owned(), value) != Some(value) {
+            self.dirty = true;
+        }
+
+        Ok(())
+    }
+
+    fn stat(&self, id: &str) -> Option<f32> {
+        self.storage.stats.get(id).cloned()
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        if self.dirty {
+            let bytes = serde_json::to_vec_pretty(&self.storage)?;
+            write_atomic(&self.path, &bytes)?;
+            self.dirty = false;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,137 @@
+//! A facade of achievements and stats, which are usually tracked by the platforms.
+//!
+//! Game code unlocks achievements and updates stats through the functions of this module,
+//! while the actual storage is provided by an `AchievementBackend`. The engine ships a
+//! `LocalAchievements` backend which persists everything in a JSON file, and platform
+//! integrations, e.g. Steam or consoles, could plug in their own backends without
+//! changing the game code.
+//!
+//! ```rust,ignore
+//! let dirs = UserDirs::new("shawnscode", "crayon")?;
+//! let path = dirs.create(UserDir::Saves)?.join("achievements.json");
+//! achievements::setup(LocalAchievements::load(path)?);
+//!
+//! achievements::set_stat("distance", 42.0)?;
+//! if achievements::unlock("first_steps")? {
+//!     println!("Achievement unlocked!");
+//! }
+//! ```
+
+pub mod backend;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod local;
+
+pub mod prelude {
+    pub use super::backend::AchievementBackend;
+    #[cfg(not(target_arch = "wasm32"))]
+    pub use super::local::LocalAchievements;
+}
+
+use crate::errors::*;
+
+use std::sync::Mutex;
+
+use self::backend::AchievementBackend;
+
+/// Setup the achievements with a backend, and replaces the previous one if exists.
+pub fn setup<T: AchievementBackend>(backend: T) {
+    unsafe {
+        inside::discard();
+        inside::setup(Box::new(backend));
+    }
+}
+
+/// Checks if the achievements have been setup.
+#[inline]
+pub fn valid() -> bool {
+    unsafe { !inside::CTX.is_null() }
+}
+
+/// Unlocks an achievement. Returns true if it was locked before.
+#[inline]
+pub fn unlock<T: AsRef<str>>(id: T) -> Result<bool> {
+    let id = id.as_ref();
+    let mut backend = ctx()?.lock().unwrap();
+    if backend.is_unlocked(id) {
+        return Ok(false);
+    }
+
+    backend.unlock(id)?;
+    info!("[Achievements] {} is unlocked.", id);
+    Ok(true)
+}
+
+/// Checks if an achievement has been unlocked. It's always false before `setup`.
+#[inline]
+pub fn is_unlocked<T: AsRef<str>>(id: T) -> bool {
+    match ctx() {
+        Ok(v) => v.lock().unwrap().is_unlocked(id.as_ref()),
+        Err(_) => false,
+    }
+}
+
+/// Sets the value of a stat.
+#[inline]
+pub fn set_stat<T: AsRef<str>>(id: T, value: f32) -> Result<()> {
+    ctx()?.lock().unwrap().set_stat(id.as_ref(), value)
+}
+
+/// Gets the value of a stat. It's always `None` before `setup`.
+#[inline]
+pub fn stat<T: AsRef<str>>(id: T) -> Option<f32> {
+    ctx().ok()?.lock().unwrap().stat(id.as_ref())
+}
+
+/// Adds to the value of a stat, which starts from zero.
+#[inline]
+pub fn add_stat<T: AsRef<str>>(id: T, delta: f32) -> Result<f32> {
+    let id = id.as_ref();
+    let mut backend = ctx()?.lock().unwrap();
+    let value = backend.stat(id).unwrap_or(0.0) + delta;
+    backend.set_stat(id, value)?;
+    Ok(value)
+}
+
+/// Writes the pending changes into the storage of backend.
+#[inline]
+pub fn flush() -> Result<()> {
+    ctx()?.lock().unwrap().flush()
+}
+
+/// Gets the backend, or an error if achievements have not been setup, since it's optional
+/// for games to track them.
+fn ctx() -> Result<&'static Mutex<Box<dyn AchievementBackend>>> {
+    if !valid() {
+        bail!("Achievements have not been setup.");
+    }
+
+    unsafe { Ok(&*inside::CTX) }
+}
+
+pub(crate) mod inside {
+    use std::sync::Mutex;
+
+    use super::backend::AchievementBackend;
+
+    pub static mut CTX: *const Mutex<Box<dyn AchievementBackend>> = std::ptr::null();
+
+    /// Setup the achievements.
+    pub unsafe fn setup(backend: Box<dyn AchievementBackend>) {
+        debug_assert!(CTX.is_null(), "duplicated setup of achievements.");
+        CTX = Box::into_raw(Box::new(Mutex::new(backend)));
+    }
+
+    /// Discard the achievements, and flushes the pending changes.
+    pub unsafe fn discard() {
+        if CTX.is_null() {
+            return;
+        }
+
+        let ctx = Box::from_raw(CTX as *mut Mutex<Box<dyn AchievementBackend>>);
+        CTX = std::ptr::null();
+
+        if let Err(err) = ctx.lock().unwrap().flush() {
+            warn!("[Achievements] failed to flush: {}", err);
+        }
+    }
+}
@@ -0,0 +1,420 @@
+//! Splash screens shown while the engine boots.
+//!
+//! The splashes in `BootParams` are shown in order right after the window is created, while
+//! the manifests of resources are still loading. Each one stays on screen for at least its
+//! `duration`, and the closure of `application::setup` is only invoked once all of them have
+//! been shown and the resources are ready. If loading takes longer, the last splash stays
+//! until it's finished.
+//!
+//! The resources of the first state could be streamed behind the splashes too, by
+//! requesting them in the closure of `BootParams::set_preload`, which is invoked once the
+//! manifests are loaded. The returned latch is usually passed to the `Preload` state as
+//! well, which switches to the first state right away since the latch is set already.
+//!
+//! Nothing could be loaded from the resource system before its manifests are ready, so the
+//! splash images are embedded as the bytes of PNG or JPEG files, which requires the `image`
+//! feature.
+//!
+//! ```rust,ignore
+//! let mut params = Params::default();
+//! params.boot.splashes.push(Splash::new(
+//!     &include_bytes!("logo.png")[..],
+//!     Duration::from_secs(2),
+//! ));
+//! params.boot.skippable = true;
+//!
+//! let latch = Arc::new(CountLatch::new());
+//! let clone = latch.clone();
+//! params.boot.set_preload(move || {
+//!     Level::preload(clone.clone())?;
+//!     Ok(clone.clone())
+//! });
+//!
+//! application::setup(params, move || {
+//!     Ok(StateMachine::new(Preload::new(latch, |_| Ok(Box::new(Level::new()?)))))
+//! })?;
+//! ```
+
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::errors::*;
+use crate::input::prelude::{GestureTap, Key, MouseButton};
+use crate::math::prelude::{Color, Vector2};
+use crate::sched::prelude::LatchProbe;
+use crate::video::prelude::*;
+
+crate::impl_vertex! {
+    SplashVertex {
+        position => [Position; Float; 2; false],
+    }
+}
+
+/// A splash screen, which shows an image in the center of window.
+#[derive(Debug, Clone)]
+pub struct Splash {
+    /// The bytes of a PNG or JPEG image.
+    pub image: Cow<'static, [u8]>,
+    /// The minimum duration the splash stays on screen.
+    pub duration: Duration,
+}
+
+impl Splash {
+    pub fn new<T: Into<Cow<'static, [u8]>>>(image: T, duration: Duration) -> Self {
+        Splash {
+            image: image.into(),
+            duration,
+        }
+    }
+}
+
+pub(crate) type Preloader = Arc<dyn Fn() -> Result<Box<dyn LatchProbe>> + Send + Sync>;
+
+/// The setup parameters of the boot sequence.
+#[derive(Clone, Default)]
+pub struct BootParams {
+    /// The splashes shown in order.
+    pub splashes: Vec<Splash>,
+    /// Whether a key press, click or tap moves on to the next splash before its duration
+    /// elapses.
+    pub skippable: bool,
+    preload: Option<Preloader>,
+}
+
+impl BootParams {
+    /// Sets the closure which requests the resources of the first state. It's invoked once
+    /// the manifests are loaded, and the closure of `application::setup` waits until the
+    /// returned latch is set.
+    pub fn set_preload<T, L>(&mut self, func: T)
+    where
+        T: Fn() -> Result<L> + Send + Sync + 'static,
+        L: LatchProbe + 'static,
+    {
+        self.preload = Some(Arc::new(move || {
+            let latch: Box<dyn LatchProbe> = Box::new(func()?);
+            Ok(latch)
+        }));
+    }
+
+    pub(crate) fn take_preload(&mut self) -> Option<Preloader> {
+        self.preload.take()
+    }
+}
+
+impl fmt::Debug for BootParams {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BootParams")
+            .field("splashes", &self.splashes)
+            .field("skippable", &self.skippable)
+            .field("preload", &self.preload.is_some())
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Progress {
+    /// Shows the splash at the index.
+    Show(usize),
+    /// Keeps the current splash.
+    Stay,
+    /// All the splashes have been shown, and the resources are ready.
+    Finished,
+}
+
+/// The timing of splashes, which is kept apart from drawing so it could be tested without
+/// a window.
+#[derive(Debug, Clone)]
+struct Timeline {
+    durations: Vec<Duration>,
+    started: bool,
+    current: usize,
+    elapsed: Duration,
+}
+
+impl Timeline {
+    fn new(durations: Vec<Duration>) -> Self {
+        Timeline {
+            durations,
+            started: false,
+            current: 0,
+            elapsed: Duration::from_secs(0),
+        }
+    }
+
+    /// Advances the time by `dt`. The first splash is shown at the first call, which
+    /// does not count the time spent before it.
+    fn advance(&mut self, dt: Duration, skip: bool, ready: bool) -> Progress {
+        if self.current >= self.durations.len() {
+            return if ready {
+                Progress::Finished
+            } else {
+                Progress::Stay
+            };
+        }
+
+        let mut progress = Progress::Stay;
+        if self.started {
+            self.elapsed += dt;
+        } else {
+            self.started = true;
+            progress = Progress::Show(0);
+        }
+
+        if skip || self.elapsed >= self.durations[self.current] {
+            if self.current + 1 < self.durations.len() {
+                self.current += 1;
+                self.elapsed = Duration::from_secs(0);
+                progress = Progress::Show(self.current);
+            } else if ready {
+                self.current = self.durations.len();
+                progress = Progress::Finished;
+            }
+        }
+
+        progress
+    }
+}
+
+pub(crate) struct BootSequence {
+    splashes: Vec<Splash>,
+    skippable: bool,
+    timeline: Timeline,
+    renderer: Option<Renderer>,
+}
+
+impl BootSequence {
+    #[allow(unused_mut)]
+    pub fn new(mut params: BootParams) -> Self {
+        #[cfg(not(feature = "image"))]
+        {
+            if !params.splashes.is_empty() {
+                warn!("The splashes could not be shown without the `image` feature.");
+                params.splashes.clear();
+            }
+        }
+
+        let durations = params.splashes.iter().map(|v| v.duration).collect();
+
+        BootSequence {
+            splashes: params.splashes,
+            skippable: params.skippable,
+            timeline: Timeline::new(durations),
+            renderer: None,
+        }
+    }
+
+    /// Advances the splashes by `dt` and draws the current one. Returns true once all of
+    /// them have been shown and the resources are `ready`.
+    pub fn advance(&mut self, dt: Duration, ready: bool) -> Result<bool> {
+        let skip = self.skippable && skip_requested();
+
+        match self.timeline.advance(dt, skip, ready) {
+            Progress::Finished => {
+                self.renderer = None;
+                return Ok(true);
+            }
+            Progress::Show(i) => {
+                if self.renderer.is_none() {
+                    self.renderer = Some(Renderer::new()?);
+                }
+
+                let image = &self.splashes[i].image;
+                self.renderer.as_mut().unwrap().set_image(image)?;
+            }
+            Progress::Stay => {}
+        }
+
+        if let Some(ref mut v) = self.renderer {
+            v.draw()?;
+        }
+
+        Ok(false)
+    }
+}
+
+fn skip_requested() -> bool {
+    crate::input::is_key_press(Key::Escape)
+        || crate::input::is_key_press(Key::Space)
+        || crate::input::is_key_press(Key::Return)
+        || crate::input::is_mouse_click(MouseButton::Left)
+        || match crate::input::finger_tap() {
+            GestureTap::Action { .. } => true,
+            GestureTap::None => false,
+        }
+}
+
+const VS: &str = r#"
+#version 100
+precision lowp float;
+
+attribute vec2 Position;
+uniform vec2 u_Scale;
+varying vec2 v_Texcoord;
+
+void main(){
+    gl_Position = vec4(Position * u_Scale, 0.0, 1.0);
+    v_Texcoord = vec2(Position.x + 1.0, 1.0 - Position.y) / 2.0;
+}
+"#;
+
+const FS: &str = r#"
+#version 100
+precision lowp float;
+
+varying vec2 v_Texcoord;
+uniform sampler2D u_Texture;
+
+void main() {
+    gl_FragColor = texture2D(u_Texture, v_Texcoord);
+}
+"#;
+
+struct Renderer {
+    surface: SurfaceHandle,
+    shader: ShaderHandle,
+    mesh: MeshHandle,
+    texture: Option<(TextureHandle, Vector2<u32>)>,
+    cmds: CommandBuffer,
+}
+
+impl Renderer {
+    fn new() -> Result<Self> {
+        let mut params = SurfaceParams::default();
+        params.set_clear(Color::black(), None, None);
+        let surface = crate::video::create_surface(params)?;
+
+        let mut params = ShaderParams::default();
+        params.attributes = SplashVertex::attributes();
+        params.uniforms = UniformVariableLayout::build()
+            .with("u_Scale", UniformVariableType::Vector2f)
+            .with("u_Texture", UniformVariableType::Texture)
+            .finish();
+        params.state.color_blend = Some((
+            Equation::Add,
+            BlendFactor::Value(BlendValue::SourceAlpha),
+            BlendFactor::OneMinusValue(BlendValue::SourceAlpha),
+        ));
+        let shader = crate::video::create_shader(params, VS.to_owned(), FS.to_owned())?;
+
+        let verts: [SplashVertex; 4] = [
+            SplashVertex::new([-1.0, -1.0]),
+            SplashVertex::new([1.0, -1.0]),
+            SplashVertex::new([1.0, 1.0]),
+            SplashVertex::new([-1.0, 1.0]),
+        ];
+        let idxes: [u16; 6] = [0, 1, 2, 0, 2, 3];
+
+        let mut params = MeshParams::default();
+        params.layout = SplashVertex::layout();
+        params.num_verts = 4;
+        params.num_idxes = 6;
+
+        let data = MeshData {
+            vptr: SplashVertex::encode(&verts[..]).into(),
+            iptr: IndexFormat::encode(&idxes).into(),
+        };
+        let mesh = crate::video::create_mesh(params, Some(data))?;
+
+        Ok(Renderer {
+            surface,
+            shader,
+            mesh,
+            texture: None,
+            cmds: CommandBuffer::new(),
+        })
+    }
+
+    #[cfg(feature = "image")]
+    fn set_image(&mut self, bytes: &[u8]) -> Result<()> {
+        let (params, data) = crate::video::decode_image(bytes)?;
+        let dimensions = params.dimensions;
+        let texture = crate::video::create_texture(params, data)?;
+
+        if let Some((v, _)) = self.texture.replace((texture, dimensions)) {
+            crate::video::delete_texture(v);
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "image"))]
+    fn set_image(&mut self, _: &[u8]) -> Result<()> {
+        Ok(())
+    }
+
+    fn draw(&mut self) -> Result<()> {
+        let (texture, dimensions) = match self.texture {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+
+        // Fits the image into window without changing its aspect ratio.
+        let window = crate::window::dimensions();
+        let dpr = crate::window::device_pixel_ratio();
+        let sx = dimensions.x as f32 / (window.x.max(1) as f32 * dpr);
+        let sy = dimensions.y as f32 / (window.y.max(1) as f32 * dpr);
+        let scale = 1.0 / sx.max(sy).max(1.0);
+
+        let mut dc = Draw::new(self.shader, self.mesh);
+        dc.set_uniform_variable("u_Scale", [sx * scale, sy * scale]);
+        dc.set_uniform_variable("u_Texture", texture);
+        self.cmds.draw(dc);
+        self.cmds.submit(self.surface)?;
+        Ok(())
+    }
+}
+
+impl Drop for Renderer {
+    fn drop(&mut self) {
+        if let Some((v, _)) = self.texture.take() {
+            crate::video::delete_texture(v);
+        }
+
+        crate::video::delete_mesh(self.mesh);
+        crate::video::delete_shader(self.shader);
+        crate::video::delete_surface(self.surface);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ms(v: u64) -> Duration {
+        Duration::from_millis(v)
+    }
+
+    #[test]
+    fn durations() {
+        let mut timeline = Timeline::new(vec![ms(100), ms(50)]);
+        assert_eq!(timeline.advance(ms(1000), false, true), Progress::Show(0));
+        assert_eq!(timeline.advance(ms(60), false, true), Progress::Stay);
+        assert_eq!(timeline.advance(ms(60), false, true), Progress::Show(1));
+        assert_eq!(timeline.advance(ms(30), false, true), Progress::Stay);
+        assert_eq!(timeline.advance(ms(30), false, true), Progress::Finished);
+        assert_eq!(timeline.advance(ms(30), false, true), Progress::Finished);
+    }
+
+    #[test]
+    fn skip() {
+        let mut timeline = Timeline::new(vec![ms(100), ms(100)]);
+        assert_eq!(timeline.advance(ms(10), false, true), Progress::Show(0));
+        assert_eq!(timeline.advance(ms(10), true, true), Progress::Show(1));
+        assert_eq!(timeline.advance(ms(10), true, true), Progress::Finished);
+    }
+
+    #[test]
+    fn wait_until_ready() {
+        let mut timeline = Timeline::new(vec![ms(100)]);
+        assert_eq!(timeline.advance(ms(10), false, false), Progress::Show(0));
+        assert_eq!(timeline.advance(ms(200), false, false), Progress::Stay);
+        assert_eq!(timeline.advance(ms(10), true, false), Progress::Stay);
+        assert_eq!(timeline.advance(ms(10), false, true), Progress::Finished);
+
+        let mut timeline = Timeline::new(Vec::new());
+        assert_eq!(timeline.advance(ms(10), false, false), Progress::Stay);
+        assert_eq!(timeline.advance(ms(10), false, true), Progress::Finished);
+    }
+}
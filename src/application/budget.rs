@@ -0,0 +1,146 @@
+use std::time::Duration;
+
+/// Scales the quality of optional effects based on recent frame times, so the game
+/// degrades gracefully on weak hardware instead of dropping frames.
+///
+/// The budget holds a discrete quality level, from `0` (the cheapest) to `levels - 1`
+/// (the full quality). It steps down once the smoothed frame time stays over the target
+/// for a while, and steps back up only after it stays well under the target for even
+/// longer. The gap between the two thresholds avoids flickering between two levels.
+///
+/// The engine keeps a global budget, which is set with `Params::effects_budget` or
+/// `application::set_effects_budget` and advanced every frame, so effects only query it.
+///
+/// ```rust,ignore
+/// application::set_effects_budget(EffectsBudget::new(60));
+///
+/// // Every frame.
+/// let rate = application::effects_scale() * MAX_SPAWN_RATE;
+/// let decals = application::effects_scale_count(MAX_DECALS);
+/// ```
+#[derive(Debug, Clone)]
+pub struct EffectsBudget {
+    target: f32,
+    levels: u32,
+    level: u32,
+    average: Option<f32>,
+    over: u32,
+    under: u32,
+
+    /// The weight of the latest frame in the smoothed frame time.
+    pub smoothing: f32,
+    /// Steps down if the smoothed frame time is higher than `target * degrade_ratio`.
+    pub degrade_ratio: f32,
+    /// Steps up if the smoothed frame time is lower than `target * recover_ratio`.
+    pub recover_ratio: f32,
+    /// The number of consecutive frames over budget before stepping down.
+    pub degrade_frames: u32,
+    /// The number of consecutive frames under budget before stepping up.
+    pub recover_frames: u32,
+}
+
+impl EffectsBudget {
+    /// Creates a new budget targeting the frames per second, which starts at the full
+    /// quality with 4 levels.
+    pub fn new(target_fps: u32) -> Self {
+        assert!(
+            target_fps > 0,
+            "The target fps should be greater than zero."
+        );
+
+        EffectsBudget {
+            target: 1.0 / target_fps as f32,
+            levels: 4,
+            level: 3,
+            average: None,
+            over: 0,
+            under: 0,
+            smoothing: 0.1,
+            degrade_ratio: 1.1,
+            recover_ratio: 0.85,
+            degrade_frames: 30,
+            recover_frames: 120,
+        }
+    }
+
+    /// Sets the number of quality levels, and resets to the full quality.
+    pub fn with_levels(mut self, levels: u32) -> Self {
+        assert!(levels > 0, "The budget should have at least one level.");
+
+        self.levels = levels;
+        self.level = levels - 1;
+        self
+    }
+
+    /// Feeds the duration of last frame into the budget.
+    pub fn advance(&mut self, duration: Duration) {
+        let dt = duration.as_secs() as f32 + duration.subsec_nanos() as f32 * 1e-9;
+
+        let average = match self.average {
+            Some(v) => v + (dt - v) * self.smoothing,
+            None => dt,
+        };
+
+        self.average = Some(average);
+
+        if average > self.target * self.degrade_ratio {
+            self.over += 1;
+            self.under = 0;
+        } else if average < self.target * self.recover_ratio {
+            self.under += 1;
+            self.over = 0;
+        } else {
+            self.over = 0;
+            self.under = 0;
+        }
+
+        if self.over >= self.degrade_frames && self.level > 0 {
+            self.level -= 1;
+            self.over = 0;
+        }
+
+        if self.under >= self.recover_frames && self.level < self.levels - 1 {
+            self.level += 1;
+            self.under = 0;
+        }
+    }
+
+    /// Gets the current quality level, where `0` is the cheapest.
+    #[inline]
+    pub fn level(&self) -> u32 {
+        self.level
+    }
+
+    /// Gets the number of quality levels.
+    #[inline]
+    pub fn levels(&self) -> u32 {
+        self.levels
+    }
+
+    /// Gets the current quality as a factor in `(0, 1]`, which could be used to scale
+    /// spawn rates or the quality of post effects.
+    #[inline]
+    pub fn scale(&self) -> f32 {
+        (self.level + 1) as f32 / self.levels as f32
+    }
+
+    /// Scales the maximum count of something, e.g. decals, by the current quality.
+    #[inline]
+    pub fn scale_count(&self, max: usize) -> usize {
+        (max as f32 * self.scale()).round() as usize
+    }
+
+    /// Gets the smoothed frame time.
+    #[inline]
+    pub fn average(&self) -> Duration {
+        let v = self.average.unwrap_or(self.target);
+        Duration::from_nanos((v * 1e9) as u64)
+    }
+
+    /// Forces the quality level, e.g. from the user settings.
+    pub fn set_level(&mut self, level: u32) {
+        self.level = level.min(self.levels - 1);
+        self.over = 0;
+        self.under = 0;
+    }
+}
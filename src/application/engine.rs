@@ -4,7 +4,9 @@ use std::sync::Arc;
 use crate::sched::prelude::LatchProbe;
 use crate::window::prelude::{Event, EventListener, EventListenerHandle, WindowEvent};
 
+use super::boot::{BootParams, BootSequence};
 use super::lifecycle::LifecycleListener;
+use super::settings::Settings;
 use super::Params;
 
 type Result<T> = ::std::result::Result<T, ::failure::Error>;
@@ -15,6 +17,7 @@ type Result<T> = ::std::result::Result<T, ::failure::Error>;
 pub struct EngineSystem {
     events: EventListenerHandle,
     state: Arc<EngineState>,
+    settings: Settings,
     headless: bool,
 }
 
@@ -37,6 +40,7 @@ impl Drop for EngineSystem {
         crate::window::detach(self.events);
 
         unsafe {
+            crate::achievements::inside::discard();
             crate::res::inside::discard();
             crate::input::inside::discard();
             crate::video::inside::discard();
@@ -49,16 +53,15 @@ impl Drop for EngineSystem {
 impl EngineSystem {
     /// Setup engine with specified settings.
     pub unsafe fn new(params: Params) -> Result<Self> {
-        #[cfg(not(target_arch = "wasm32"))]
-        crate::sched::inside::setup(4, None, None);
-        #[cfg(target_arch = "wasm32")]
-        crate::sched::inside::setup(0, None, None);
+        crate::sched::inside::setup(params.sched, params.settings.worker_threads, None);
 
         crate::window::inside::setup(params.window)?;
-        crate::video::inside::setup()?;
+        crate::video::inside::setup(params.video, params.settings)?;
         crate::input::inside::setup(params.input);
         crate::res::inside::setup(params.res)?;
 
+        info!("[EngineSystem] {}", super::sysinfo::SystemInfo::collect());
+
         let state = Arc::new(EngineState {
             alive: AtomicBool::new(true),
         });
@@ -66,6 +69,7 @@ impl EngineSystem {
         let sys = EngineSystem {
             events: crate::window::attach(state.clone()),
             state,
+            settings: params.settings,
             headless: false,
         };
 
@@ -73,13 +77,10 @@ impl EngineSystem {
     }
 
     pub unsafe fn new_headless(params: Params) -> Result<Self> {
-        #[cfg(not(target_arch = "wasm32"))]
-        crate::sched::inside::setup(4, None, None);
-        #[cfg(target_arch = "wasm32")]
-        crate::sched::inside::setup(0, None, None);
+        crate::sched::inside::setup(params.sched, params.settings.worker_threads, None);
 
         crate::window::inside::headless();
-        crate::video::inside::headless();
+        crate::video::inside::headless(params.video, params.settings);
         crate::input::inside::setup(params.input);
         crate::res::inside::setup(params.res)?;
 
@@ -90,6 +91,7 @@ impl EngineSystem {
         let sys = EngineSystem {
             events: crate::window::attach(state.clone()),
             state,
+            settings: params.settings,
             headless: true,
         };
 
@@ -106,6 +108,11 @@ impl EngineSystem {
         self.headless
     }
 
+    #[inline]
+    pub fn settings(&self) -> Settings {
+        self.settings
+    }
+
     pub fn run_oneshot(&self) -> Result<()> {
         super::foreach(|v| v.on_pre_update())?;
         super::foreach(|v| v.on_update())?;
@@ -114,7 +121,7 @@ impl EngineSystem {
         Ok(())
     }
 
-    pub fn run<L, T, T2>(&self, latch: L, closure: T) -> Result<()>
+    pub fn run<L, T, T2>(&self, latch: L, boot: BootParams, closure: T) -> Result<()>
     where
         L: LatchProbe + 'static,
         T: FnOnce() -> Result<T2> + 'static,
@@ -122,12 +129,45 @@ impl EngineSystem {
     {
         let state = self.state.clone();
         let mut closure = Some(closure);
+        let mut boot = boot;
+        let mut preload = boot.take_preload();
+        let mut preloaded: Option<Box<dyn LatchProbe>> = None;
+
+        // The splashes are skipped in headless mode, where nothing could be seen anyway.
+        let mut boot = if self.headless {
+            None
+        } else {
+            Some(BootSequence::new(boot))
+        };
 
         super::sys::run_forever(
             move || {
                 super::foreach(|v| v.on_pre_update())?;
+
+                // The resources of the first state could only be requested after the
+                // manifests are loaded.
+                let mut ready = latch.is_set();
+                if ready {
+                    if let Some(func) = preload.take() {
+                        preloaded = Some(func()?);
+                    }
+
+                    ready = preloaded.as_ref().map_or(true, |v| v.is_set());
+                }
+
+                let dt = crate::application::frame_duration();
+                let finished = match boot {
+                    Some(ref mut v) => v.advance(dt, ready)?,
+                    None => ready,
+                };
+
                 super::foreach_rev(|v| v.on_post_update())?;
-                Ok(!latch.is_set())
+
+                if finished {
+                    boot = None;
+                }
+
+                Ok(!finished)
             },
             move || {
                 let mut v = None;
@@ -139,9 +179,21 @@ impl EngineSystem {
                 super::sys::run_forever(
                     move || {
                         super::foreach(|v| v.on_pre_update())?;
-                        super::foreach(|v| v.on_update())?;
-                        super::foreach(|v| v.on_render())?;
-                        super::foreach_rev(|v| v.on_post_update())?;
+
+                        {
+                            let _scope = super::scope("update");
+                            super::foreach(|v| v.on_update())?;
+                        }
+
+                        {
+                            let _scope = super::scope("render");
+                            super::foreach(|v| v.on_render())?;
+                        }
+
+                        {
+                            let _scope = super::scope("post_update");
+                            super::foreach_rev(|v| v.on_post_update())?;
+                        }
 
                         Ok(state.alive.load(Ordering::Relaxed))
                     },
@@ -0,0 +1,375 @@
+//! Reports of frames which took longer than expected.
+//!
+//! Rare spikes are hard to reproduce on the development machine. Once a threshold is set,
+//! every frame that exceeds it is logged along with the time spent in each profile scope,
+//! the resources requested and the garbage collections during that frame. With the
+//! `alloc-tracking` feature, the number of allocations made in each `memory::scope` is
+//! reported as well.
+//!
+//! ```rust
+//! use std::time::Duration;
+//! use crayon::prelude::*;
+//! application::oneshot().unwrap();
+//!
+//! application::set_hitch_threshold(Duration::from_millis(50));
+//!
+//! {
+//!     let _scope = application::scope("physics");
+//!     // ...
+//! }
+//!
+//! for report in application::hitches() {
+//!     println!("{}", report);
+//! }
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::Duration;
+
+use crate::utils::time::Timestamp;
+
+const MAX_REPORTS: usize = 16;
+const MAX_LOADS: usize = 32;
+
+/// A frame which took longer than the threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HitchReport {
+    /// The index of this frame since the engine started.
+    pub frame: u64,
+    /// The duration of this frame.
+    pub duration: Duration,
+    /// The time spent in each profile scope during this frame, in the order they were
+    /// first entered.
+    pub scopes: Vec<(&'static str, Duration)>,
+    /// The urls of resources requested during this frame.
+    pub loads: Vec<String>,
+    /// The number of garbage collections during this frame.
+    pub gcs: usize,
+    /// The number of resources freed by the garbage collections.
+    pub freed: usize,
+    /// The number of allocations made in each memory scope during this frame. It's only
+    /// tracked with the `alloc-tracking` feature, and the scopes without allocations are
+    /// omitted.
+    pub allocs: Vec<(&'static str, usize)>,
+}
+
+impl fmt::Display for HitchReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Frame {} took {}ms.", self.frame, millis(self.duration))?;
+
+        if !self.scopes.is_empty() {
+            let scopes: Vec<_> = self
+                .scopes
+                .iter()
+                .map(|&(name, duration)| format!("{} {}ms", name, millis(duration)))
+                .collect();
+
+            write!(f, "\n  scopes: {}", scopes.join(", "))?;
+        }
+
+        if !self.loads.is_empty() {
+            write!(f, "\n  loads: {}", self.loads.join(", "))?;
+        }
+
+        if self.gcs > 0 {
+            write!(f, "\n  gc: {} runs, {} freed", self.gcs, self.freed)?;
+        }
+
+        if !self.allocs.is_empty() {
+            let allocs: Vec<_> = self
+                .allocs
+                .iter()
+                .map(|&(name, num)| format!("{} {}", name, num))
+                .collect();
+
+            write!(f, "\n  allocs: {}", allocs.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Measures the time spent from its creation to drop, and adds it to the scope with the
+/// same name of current frame.
+pub struct ProfileScope {
+    name: &'static str,
+    start: Timestamp,
+}
+
+impl ProfileScope {
+    pub(crate) fn new(name: &'static str) -> Self {
+        ProfileScope {
+            name,
+            start: Timestamp::now(),
+        }
+    }
+}
+
+impl Drop for ProfileScope {
+    fn drop(&mut self) {
+        if crate::application::valid() {
+            let duration = self.start.elapsed();
+            super::inside::time_ctx().record_scope(self.name, duration);
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct HitchDetector {
+    threshold: Option<Duration>,
+    frame: u64,
+    scopes: Vec<(&'static str, Duration)>,
+    loads: Vec<String>,
+    gcs: usize,
+    freed: usize,
+    // The allocation counters of memory scopes at the start of current frame, `None` until
+    // they are sampled once after the threshold is set.
+    allocs: Option<Vec<(&'static str, usize)>>,
+    reports: VecDeque<HitchReport>,
+}
+
+impl HitchDetector {
+    pub fn new(threshold: Option<Duration>) -> Self {
+        HitchDetector {
+            threshold,
+            ..Default::default()
+        }
+    }
+
+    pub fn set_threshold(&mut self, threshold: Option<Duration>) {
+        self.threshold = threshold;
+        if threshold.is_none() {
+            self.allocs = None;
+        }
+    }
+
+    pub fn record_scope(&mut self, name: &'static str, duration: Duration) {
+        if self.threshold.is_none() {
+            return;
+        }
+
+        match self.scopes.iter_mut().find(|v| v.0 == name) {
+            Some(v) => v.1 += duration,
+            None => self.scopes.push((name, duration)),
+        }
+    }
+
+    pub fn record_load(&mut self, url: &str) {
+        if self.threshold.is_some() && self.loads.len() < MAX_LOADS {
+            self.loads.push(url.to_owned());
+        }
+    }
+
+    pub fn record_gc(&mut self, freed: usize) {
+        if self.threshold.is_some() {
+            self.gcs += 1;
+            self.freed += freed;
+        }
+    }
+
+    /// Takes the allocation counters of memory scopes at the end of current frame, and
+    /// returns the number of allocations made in each scope since the last sample.
+    #[cfg_attr(not(feature = "alloc-tracking"), allow(dead_code))]
+    pub fn record_allocs(
+        &mut self,
+        counts: Vec<(&'static str, usize)>,
+    ) -> Vec<(&'static str, usize)> {
+        if self.threshold.is_none() {
+            return Vec::new();
+        }
+
+        let allocs = match self.allocs {
+            Some(ref prev) => counts
+                .iter()
+                .filter_map(|&(name, num)| {
+                    let prev = prev.iter().find(|v| v.0 == name).map(|v| v.1).unwrap_or(0);
+                    if num > prev {
+                        Some((name, num - prev))
+                    } else {
+                        None
+                    }
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+
+        self.allocs = Some(counts);
+        allocs
+    }
+
+    /// Finishes current frame with its duration.
+    pub fn advance(&mut self, duration: Duration) -> Option<&HitchReport> {
+        #[cfg(feature = "alloc-tracking")]
+        let allocs = match self.threshold {
+            Some(_) => self.record_allocs(
+                crate::memory::report()
+                    .into_iter()
+                    .map(|v| (v.name, v.allocs))
+                    .collect(),
+            ),
+            None => Vec::new(),
+        };
+
+        #[cfg(not(feature = "alloc-tracking"))]
+        let allocs = Vec::new();
+
+        self.advance_with(duration, allocs)
+    }
+
+    fn advance_with(
+        &mut self,
+        duration: Duration,
+        allocs: Vec<(&'static str, usize)>,
+    ) -> Option<&HitchReport> {
+        let frame = self.frame;
+        let scopes = std::mem::replace(&mut self.scopes, Vec::new());
+        let loads = std::mem::replace(&mut self.loads, Vec::new());
+        let gcs = std::mem::replace(&mut self.gcs, 0);
+        let freed = std::mem::replace(&mut self.freed, 0);
+        self.frame += 1;
+
+        // The first frame includes the time spent on setup.
+        let threshold = self.threshold?;
+        if frame == 0 || duration <= threshold {
+            return None;
+        }
+
+        if self.reports.len() >= MAX_REPORTS {
+            self.reports.pop_front();
+        }
+
+        self.reports.push_back(HitchReport {
+            frame,
+            duration,
+            scopes,
+            loads,
+            gcs,
+            freed,
+            allocs,
+        });
+
+        self.reports.back()
+    }
+
+    pub fn reports(&self) -> Vec<HitchReport> {
+        self.reports.iter().cloned().collect()
+    }
+}
+
+fn millis(duration: Duration) -> u64 {
+    duration.as_secs() * 1000 + u64::from(duration.subsec_millis())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn threshold() {
+        let mut hitches = HitchDetector::new(None);
+        hitches.advance(Duration::from_millis(10));
+        assert!(hitches.advance(Duration::from_millis(100)).is_none());
+
+        hitches.set_threshold(Some(Duration::from_millis(50)));
+        assert!(hitches.advance(Duration::from_millis(50)).is_none());
+        assert!(hitches.advance(Duration::from_millis(51)).is_some());
+        assert_eq!(hitches.reports().len(), 1);
+    }
+
+    #[test]
+    fn first_frame() {
+        let mut hitches = HitchDetector::new(Some(Duration::from_millis(50)));
+        assert!(hitches.advance(Duration::from_secs(1)).is_none());
+        assert!(hitches.advance(Duration::from_secs(1)).is_some());
+    }
+
+    #[test]
+    fn report() {
+        let mut hitches = HitchDetector::new(Some(Duration::from_millis(50)));
+        hitches.advance(Duration::from_millis(10));
+
+        hitches.record_scope("physics", Duration::from_millis(20));
+        hitches.record_scope("render", Duration::from_millis(5));
+        hitches.record_scope("physics", Duration::from_millis(40));
+        hitches.record_load("res:level.json");
+
+        let report = hitches.advance(Duration::from_millis(70)).unwrap().clone();
+        assert_eq!(report.frame, 1);
+        assert_eq!(report.duration, Duration::from_millis(70));
+        assert_eq!(
+            report.scopes,
+            [
+                ("physics", Duration::from_millis(60)),
+                ("render", Duration::from_millis(5)),
+            ]
+        );
+        assert_eq!(report.loads, ["res:level.json"]);
+
+        let text = format!("{}", report);
+        assert_eq!(
+            text,
+            "Frame 1 took 70ms.\n  scopes: physics 60ms, render 5ms\n  loads: res:level.json"
+        );
+
+        // The records are cleared once the frame finishes.
+        hitches.advance(Duration::from_millis(10));
+        let report = hitches.advance(Duration::from_millis(70)).unwrap();
+        assert!(report.scopes.is_empty());
+        assert!(report.loads.is_empty());
+    }
+
+    #[test]
+    fn gc_and_allocs() {
+        let mut hitches = HitchDetector::new(Some(Duration::from_millis(50)));
+
+        // The first sample only sets the baseline of counters.
+        let allocs = hitches.record_allocs(vec![("untagged", 100), ("video", 10)]);
+        assert!(allocs.is_empty());
+        hitches.advance_with(Duration::from_millis(10), allocs);
+
+        hitches.record_gc(3);
+        hitches.record_gc(0);
+        let allocs = hitches.record_allocs(vec![("untagged", 100), ("video", 25), ("res", 4)]);
+        assert_eq!(allocs, [("video", 15), ("res", 4)]);
+
+        let report = hitches
+            .advance_with(Duration::from_millis(70), allocs)
+            .unwrap()
+            .clone();
+        assert_eq!(report.gcs, 2);
+        assert_eq!(report.freed, 3);
+        assert_eq!(report.allocs, [("video", 15), ("res", 4)]);
+
+        let text = format!("{}", report);
+        assert_eq!(
+            text,
+            "Frame 1 took 70ms.\n  gc: 2 runs, 3 freed\n  allocs: video 15, res 4"
+        );
+
+        let report = hitches
+            .advance_with(Duration::from_millis(70), Vec::new())
+            .unwrap();
+        assert_eq!(report.gcs, 0);
+        assert_eq!(report.freed, 0);
+
+        // Nothing is recorded without the threshold.
+        hitches.set_threshold(None);
+        hitches.record_gc(1);
+        assert!(hitches.record_allocs(vec![("video", 50)]).is_empty());
+        assert_eq!(hitches.gcs, 0);
+        assert!(hitches.allocs.is_none());
+    }
+
+    #[test]
+    fn max_reports() {
+        let mut hitches = HitchDetector::new(Some(Duration::from_millis(50)));
+        for _ in 0..MAX_REPORTS + 3 {
+            hitches.advance(Duration::from_millis(60));
+        }
+
+        let reports = hitches.reports();
+        assert_eq!(reports.len(), MAX_REPORTS);
+        assert_eq!(reports[0].frame, 3);
+    }
+}
@@ -28,14 +28,26 @@
 pub mod ins;
 pub mod sys;
 
+mod boot;
+mod budget;
 mod engine;
+mod hitch;
 mod launcher;
 mod lifecycle;
+mod settings;
+mod states;
+mod sysinfo;
 mod time;
 
 pub mod prelude {
+    pub use super::boot::{BootParams, Splash};
+    pub use super::budget::EffectsBudget;
+    pub use super::hitch::{HitchReport, ProfileScope};
     pub use super::launcher::Launcher;
     pub use super::lifecycle::{LifecycleListener, LifecycleListenerHandle};
+    pub use super::settings::Settings;
+    pub use super::states::{Preload, State, StateMachine, Transition};
+    pub use super::sysinfo::SystemInfo;
     pub use super::Params;
 }
 
@@ -43,13 +55,18 @@ use crate::errors::*;
 
 use self::lifecycle::{LifecycleListener, LifecycleListenerHandle};
 
+use self::boot::BootParams;
+use self::budget::EffectsBudget;
 use self::engine::EngineSystem;
 use self::inside::{ctx, lifecycle_ctx, time_ctx, CTX, LIFECYCLE_CTX, TIME_CTX};
 use self::lifecycle::LifecycleSystem;
+use self::settings::Settings;
 use self::time::TimeSystem;
 
 use crate::input::InputParams;
 use crate::res::ResourceParams;
+use crate::sched::SchedParams;
+use crate::video::VideoParams;
 use crate::window::WindowParams;
 
 /// A structure containing configuration data for the game engine, which are
@@ -69,12 +86,25 @@ pub struct Params {
     pub max_inactive_fps: u32,
     /// Set how many frames to average for timestep smoothing.
     pub time_smooth_step: u32,
+    /// Frames which take longer than this are logged with a `HitchReport`.
+    pub hitch_threshold: Option<std::time::Duration>,
+    /// The global budget of optional effects, which is fed with the frame times every
+    /// frame. Effects run at the full quality if `None`.
+    pub effects_budget: Option<EffectsBudget>,
+    /// The engine-wide settings, e.g. the capacities of object pools.
+    pub settings: Settings,
     /// The setup parameters for window sub-system.
     pub window: WindowParams,
+    /// The setup parameters for video sub-system.
+    pub video: VideoParams,
     /// The setup parameters for input sub-system.
     pub input: InputParams,
     /// The setup params for resource sub-system.
     pub res: ResourceParams,
+    /// The setup params for sched sub-system.
+    pub sched: SchedParams,
+    /// The splashes shown while the engine boots.
+    pub boot: BootParams,
 }
 
 impl Default for Params {
@@ -84,9 +114,15 @@ impl Default for Params {
             max_fps: 30,
             max_inactive_fps: 0,
             time_smooth_step: 0,
+            hitch_threshold: None,
+            effects_budget: None,
+            settings: Settings::default(),
             window: WindowParams::default(),
+            video: VideoParams::default(),
             input: InputParams::default(),
             res: ResourceParams::default(),
+            sched: SchedParams::default(),
+            boot: BootParams::default(),
         }
     }
 }
@@ -100,6 +136,9 @@ impl Params {
                 self.max_fps = 0;
                 warn!("The max FPS could not be controlled in web environment.");
             }
+
+            // There are no threads in web environment.
+            self.settings.worker_threads = Some(0);
         }
     }
 }
@@ -117,6 +156,7 @@ where
         params.validate();
 
         let dirs = params.res.dirs.clone();
+        let boot = params.boot.clone();
         LIFECYCLE_CTX = Box::into_raw(Box::new(LifecycleSystem::new()));
         TIME_CTX = Box::into_raw(Box::new(TimeSystem::new(&params)));
 
@@ -127,26 +167,37 @@ where
         };
 
         let latch = crate::res::inside::load_manifests(dirs)?;
-        ctx().run(latch, closure)
+        ctx().run(latch, boot, closure)
     }
 }
 
 #[doc(hidden)]
 pub fn oneshot() -> Result<()> {
+    setup_headless(Params::default())?;
+    advance()
+}
+
+/// Setup the core system in headless mode, without running the main loop.
+pub(crate) fn setup_headless(mut params: Params) -> Result<()> {
     unsafe {
         debug_assert!(LIFECYCLE_CTX.is_null(), "duplicated setup of crayon.");
 
-        let params = Params::default();
-
         sys::init();
+        params.validate();
+
         LIFECYCLE_CTX = Box::into_raw(Box::new(LifecycleSystem::new()));
         TIME_CTX = Box::into_raw(Box::new(TimeSystem::new(&params)));
         CTX = Box::into_raw(Box::new(EngineSystem::new_headless(params)?));
-
-        ctx().run_oneshot()
+        Ok(())
     }
 }
 
+/// Runs a single frame.
+#[inline]
+pub(crate) fn advance() -> Result<()> {
+    ctx().run_oneshot()
+}
+
 /// Discard the core system.
 #[inline]
 pub fn discard() {
@@ -157,11 +208,13 @@ pub(crate) unsafe fn late_discard() {
     drop(Box::from_raw(CTX as *mut EngineSystem));
     CTX = std::ptr::null();
 
-    drop(Box::from_raw(TIME_CTX as *mut TimeSystem));
-    TIME_CTX = std::ptr::null();
-
+    // `valid` checks the lifecycle system, so it goes first to make sure the time system
+    // is still alive while the engine is considered valid.
     drop(Box::from_raw(LIFECYCLE_CTX as *mut LifecycleSystem));
     LIFECYCLE_CTX = std::ptr::null();
+
+    drop(Box::from_raw(TIME_CTX as *mut TimeSystem));
+    TIME_CTX = std::ptr::null();
 }
 
 /// Checks if the engine is enabled.
@@ -224,6 +277,20 @@ pub fn set_time_smoothing_step(step: u32) {
     time_ctx().set_time_smoothing_step(step);
 }
 
+pub(crate) use self::sysinfo::cpu_cores;
+
+/// Collects the information of the device, e.g. the OS, CPU cores, memory and GPU.
+#[inline]
+pub fn system_info() -> self::sysinfo::SystemInfo {
+    self::sysinfo::SystemInfo::collect()
+}
+
+/// Gets the engine-wide settings the application was set up with.
+#[inline]
+pub fn settings() -> Settings {
+    ctx().settings()
+}
+
 /// Gets current fps.
 #[inline]
 pub fn fps() -> u32 {
@@ -236,6 +303,73 @@ pub fn frame_duration() -> ::std::time::Duration {
     time_ctx().frame_duration()
 }
 
+/// Sets the threshold of frame duration, over which the frame is logged as a hitch along
+/// with the time spent in each profile scope, the resources requested, the garbage
+/// collections and the allocations of memory scopes. `None` disables the detection.
+#[inline]
+pub fn set_hitch_threshold<T: Into<Option<::std::time::Duration>>>(threshold: T) {
+    time_ctx().set_hitch_threshold(threshold.into());
+}
+
+/// Gets the recent hitch reports, from the oldest to the newest.
+#[inline]
+pub fn hitches() -> Vec<self::hitch::HitchReport> {
+    time_ctx().hitches()
+}
+
+/// Creates a profile scope, which measures the time until it's dropped. The breakdown of
+/// scopes is included in hitch reports.
+#[inline]
+pub fn scope(name: &'static str) -> self::hitch::ProfileScope {
+    self::hitch::ProfileScope::new(name)
+}
+
+/// Sets the global budget of optional effects, which is fed with the frame times by the
+/// engine. `None` keeps effects at the full quality.
+#[inline]
+pub fn set_effects_budget<T: Into<Option<EffectsBudget>>>(budget: T) {
+    time_ctx().set_effects_budget(budget.into());
+}
+
+/// Gets a snapshot of the global effects budget.
+#[inline]
+pub fn effects_budget() -> Option<EffectsBudget> {
+    time_ctx().effects_budget()
+}
+
+/// Gets the current quality of the global effects budget as a factor in `(0, 1]`, which
+/// scales particle spawn rates or the quality of post effects. It's always `1.0` without
+/// a budget.
+#[inline]
+pub fn effects_scale() -> f32 {
+    time_ctx().effects_scale()
+}
+
+/// Scales the maximum count of something, e.g. decals, by the current quality of the
+/// global effects budget.
+#[inline]
+pub fn effects_scale_count(max: usize) -> usize {
+    (max as f32 * effects_scale()).round() as usize
+}
+
+/// Forces the quality level of the global effects budget, e.g. from the user settings.
+#[inline]
+pub fn set_effects_level(level: u32) {
+    time_ctx().set_effects_level(level);
+}
+
+pub(crate) fn record_load(url: &str) {
+    if valid() {
+        time_ctx().record_load(url);
+    }
+}
+
+pub(crate) fn record_gc(freed: usize) {
+    if valid() {
+        time_ctx().record_gc(freed);
+    }
+}
+
 #[inline]
 fn foreach<T>(func: T) -> Result<()>
 where
@@ -0,0 +1,38 @@
+/// The engine-wide settings, which bound the number of worker threads and the memory
+/// footprint of the object pools.
+///
+/// The pools grow as needed by default. Setting a non-zero capacity allocates the pool
+/// up-front and never grows it, which gives memory-constrained platforms a predictable
+/// footprint. Creating objects in a exhausted pool fails with `OutOfCapacity` errors.
+#[derive(Debug, Clone, Copy)]
+pub struct Settings {
+    /// The number of worker threads of the sched system. It's detected from the number of
+    /// CPU cores with `SchedParams::reserved_cores` and `SchedParams::max_workers` if `None`.
+    pub worker_threads: Option<u32>,
+    /// The maximum number of surfaces, unbounded if zero.
+    pub max_surfaces: usize,
+    /// The maximum number of shaders, unbounded if zero.
+    pub max_shaders: usize,
+    /// The maximum number of meshes, unbounded if zero.
+    pub max_meshes: usize,
+    /// The maximum number of textures, unbounded if zero.
+    pub max_textures: usize,
+    /// The maximum number of render textures, unbounded if zero.
+    pub max_render_textures: usize,
+    /// The maximum number of entities in each scene, unbounded if zero.
+    pub max_entities: usize,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            worker_threads: None,
+            max_surfaces: 0,
+            max_shaders: 0,
+            max_meshes: 0,
+            max_textures: 0,
+            max_render_textures: 0,
+            max_entities: 0,
+        }
+    }
+}
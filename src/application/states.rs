@@ -0,0 +1,495 @@
+use std::time::Duration;
+
+use crate::application::prelude::LifecycleListener;
+use crate::errors::*;
+use crate::math::prelude::Color;
+use crate::sched::prelude::LatchProbe;
+use crate::video::prelude::*;
+
+/// A screen of the game, e.g. main menu, gameplay or pause menu. The states are managed
+/// in a stack by `StateMachine`, and only the topmost one is updated.
+pub trait State {
+    /// Called when the state is pushed onto the stack.
+    fn on_enter(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called when the state is popped from the stack.
+    fn on_exit(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called when another state is pushed on top of this one.
+    fn on_pause(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called when this state becomes the topmost one again.
+    fn on_resume(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called every frame if this state is the topmost one. The returned transition
+    /// is applied right after.
+    fn on_update(&mut self) -> Result<Transition> {
+        Ok(Transition::None)
+    }
+
+    /// Called every frame after `on_update` if this state is the topmost one.
+    fn on_render(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Checks if the state is ready to be shown, e.g. its resources are loaded. The screen
+    /// stays black after fading out with `Transition::Fade` until it's ready.
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
+
+/// The transitions between states.
+pub enum Transition {
+    /// Stays in the current state.
+    None,
+    /// Pauses the current state, and pushes a new one on top of it.
+    Push(Box<dyn State + Send>),
+    /// Pops the current state, and resumes the one below it.
+    Pop,
+    /// Replaces the current state with a new one.
+    Switch(Box<dyn State + Send>),
+    /// Fades out to black in the given seconds, replaces the current state with a new one, and
+    /// fades back in once the topmost state is ready.
+    Fade(f32, Box<dyn State + Send>),
+    /// Pops all the states, and shuts down the engine.
+    Quit,
+}
+
+/// A stack of states layered over `LifecycleListener`, which could be returned from the
+/// closure of `application::setup` directly.
+///
+/// ```rust,ignore
+/// struct Menu;
+///
+/// impl State for Menu {
+///     fn on_update(&mut self) -> Result<Transition> {
+///         if input::is_key_press(Key::Return) {
+///             return Ok(Transition::Switch(Box::new(Gameplay::new()?)));
+///         }
+///
+///         Ok(Transition::None)
+///     }
+/// }
+///
+/// application::setup(params, || Ok(StateMachine::new(Menu)))?;
+/// ```
+pub struct StateMachine {
+    stack: Vec<Box<dyn State + Send>>,
+    pending: Option<Box<dyn State + Send>>,
+    fading: Option<Fading>,
+    overlay: Option<Overlay>,
+}
+
+impl StateMachine {
+    /// Creates a new `StateMachine` with the initial state, which is entered in the
+    /// first frame.
+    pub fn new<T: State + Send + 'static>(state: T) -> Self {
+        StateMachine {
+            stack: Vec::new(),
+            pending: Some(Box::new(state)),
+            fading: None,
+            overlay: None,
+        }
+    }
+
+    /// Updates the topmost state and applies its transition, and advances the fading by
+    /// `dt`. It's called every frame with the frame duration.
+    pub fn update(&mut self, dt: Duration) -> Result<()> {
+        if let Some(state) = self.pending.take() {
+            self.push(state)?;
+        }
+
+        let transition = match self.stack.last_mut() {
+            Some(v) => v.on_update()?,
+            None => Transition::None,
+        };
+
+        self.apply(transition)?;
+        self.advance_fading(dt)
+    }
+
+    /// Pauses the topmost state, and pushes a new one on top of it.
+    pub fn push(&mut self, mut state: Box<dyn State + Send>) -> Result<()> {
+        if let Some(v) = self.stack.last_mut() {
+            v.on_pause()?;
+        }
+
+        state.on_enter()?;
+        self.stack.push(state);
+        Ok(())
+    }
+
+    /// Pops the topmost state, and resumes the one below it.
+    pub fn pop(&mut self) -> Result<Option<Box<dyn State + Send>>> {
+        let mut state = match self.stack.pop() {
+            Some(state) => state,
+            None => return Ok(None),
+        };
+
+        state.on_exit()?;
+
+        if let Some(v) = self.stack.last_mut() {
+            v.on_resume()?;
+        }
+
+        Ok(Some(state))
+    }
+
+    /// Replaces the topmost state with a new one.
+    pub fn switch(&mut self, mut state: Box<dyn State + Send>) -> Result<()> {
+        if let Some(mut v) = self.stack.pop() {
+            v.on_exit()?;
+        }
+
+        state.on_enter()?;
+        self.stack.push(state);
+        Ok(())
+    }
+
+    /// Fades out to black in `duration` seconds, and replaces the topmost state with a new
+    /// one. The screen fades back in once the topmost state is ready, so the new state is
+    /// usually a `Preload` which streams the resources behind the black screen.
+    pub fn fade(&mut self, duration: f32, state: Box<dyn State + Send>) {
+        self.fading = Some(Fading::new(duration, state));
+    }
+
+    /// Gets the opacity of the black overlay drawn over the states while fading, from 0
+    /// for not fading to 1 for fully black.
+    pub fn fade_alpha(&self) -> f32 {
+        self.fading.as_ref().map_or(0.0, |v| v.alpha())
+    }
+
+    /// Pops all the states.
+    pub fn clear(&mut self) -> Result<()> {
+        while let Some(mut v) = self.stack.pop() {
+            v.on_exit()?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of states in the stack.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Checks if the stack is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    fn apply(&mut self, transition: Transition) -> Result<()> {
+        match transition {
+            Transition::None => {}
+            Transition::Push(state) => self.push(state)?,
+            Transition::Pop => {
+                self.pop()?;
+            }
+            Transition::Switch(state) => self.switch(state)?,
+            Transition::Fade(duration, state) => self.fade(duration, state),
+            Transition::Quit => {
+                self.clear()?;
+            }
+        }
+
+        if self.stack.is_empty() && crate::application::valid() {
+            crate::application::discard();
+        }
+
+        Ok(())
+    }
+
+    fn advance_fading(&mut self, dt: Duration) -> Result<()> {
+        let mut fading = match self.fading.take() {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+
+        if fading.phase == FadePhase::Out {
+            fading.elapsed += dt;
+            if fading.elapsed >= fading.duration {
+                if let Some(state) = fading.target.take() {
+                    self.switch(state)?;
+                }
+
+                fading.phase = FadePhase::Hold;
+            }
+        }
+
+        if fading.phase == FadePhase::Hold {
+            if self.stack.last().map_or(true, |v| v.is_ready()) {
+                fading.phase = FadePhase::In;
+                fading.elapsed = Duration::from_secs(0);
+            }
+        } else if fading.phase == FadePhase::In {
+            fading.elapsed += dt;
+            if fading.elapsed >= fading.duration {
+                return Ok(());
+            }
+        }
+
+        self.fading = Some(fading);
+        Ok(())
+    }
+}
+
+impl LifecycleListener for StateMachine {
+    fn on_update(&mut self) -> Result<()> {
+        // The time is only needed while fading.
+        let dt = if self.fading.is_some() {
+            crate::application::frame_duration()
+        } else {
+            Duration::from_secs(0)
+        };
+
+        self.update(dt)
+    }
+
+    fn on_render(&mut self) -> Result<()> {
+        if let Some(v) = self.stack.last_mut() {
+            v.on_render()?;
+        }
+
+        // The overlay is drawn after everything the topmost state submitted.
+        let alpha = self.fade_alpha();
+        if alpha > 0.0 && crate::video::valid() {
+            if self.overlay.is_none() {
+                self.overlay = Some(Overlay::new()?);
+            }
+
+            self.overlay.as_mut().unwrap().draw(alpha)?;
+        }
+
+        Ok(())
+    }
+
+    fn on_exit(&mut self) -> Result<()> {
+        self.clear()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FadePhase {
+    /// Fades out the current state.
+    Out,
+    /// Stays black until the topmost state is ready.
+    Hold,
+    /// Fades in the new state.
+    In,
+}
+
+struct Fading {
+    duration: Duration,
+    elapsed: Duration,
+    phase: FadePhase,
+    target: Option<Box<dyn State + Send>>,
+}
+
+impl Fading {
+    fn new(duration: f32, state: Box<dyn State + Send>) -> Self {
+        Fading {
+            duration: Duration::from_millis((duration.max(0.0) * 1000.0) as u64),
+            elapsed: Duration::from_secs(0),
+            phase: FadePhase::Out,
+            target: Some(state),
+        }
+    }
+
+    fn alpha(&self) -> f32 {
+        let nanos = |v: Duration| v.as_secs() as f64 * 1e9 + f64::from(v.subsec_nanos());
+        let progress = if self.duration > Duration::from_secs(0) {
+            (nanos(self.elapsed) / nanos(self.duration)).min(1.0) as f32
+        } else {
+            1.0
+        };
+
+        match self.phase {
+            FadePhase::Out => progress,
+            FadePhase::Hold => 1.0,
+            FadePhase::In => 1.0 - progress,
+        }
+    }
+}
+
+crate::impl_vertex! {
+    OverlayVertex {
+        position => [Position; Float; 2; false],
+    }
+}
+
+const OVERLAY_VS: &str = r#"
+#version 100
+precision lowp float;
+
+attribute vec2 Position;
+
+void main(){
+    gl_Position = vec4(Position, 0.0, 1.0);
+}
+"#;
+
+const OVERLAY_FS: &str = r#"
+#version 100
+precision lowp float;
+
+uniform float u_Alpha;
+
+void main() {
+    gl_FragColor = vec4(0.0, 0.0, 0.0, u_Alpha);
+}
+"#;
+
+/// Draws a black quad over the window, which is blended over the frame.
+struct Overlay {
+    surface: SurfaceHandle,
+    shader: ShaderHandle,
+    mesh: MeshHandle,
+    cmds: CommandBuffer,
+}
+
+impl Overlay {
+    fn new() -> Result<Self> {
+        // Draws into the window without clearing it.
+        let mut params = SurfaceParams::default();
+        params.set_clear(None::<Color<f32>>, None::<f32>, None::<i32>);
+        let surface = crate::video::create_surface(params)?;
+
+        let mut params = ShaderParams::default();
+        params.attributes = OverlayVertex::attributes();
+        params.uniforms = UniformVariableLayout::build()
+            .with("u_Alpha", UniformVariableType::F32)
+            .finish();
+        params.state.color_blend = Some((
+            Equation::Add,
+            BlendFactor::Value(BlendValue::SourceAlpha),
+            BlendFactor::OneMinusValue(BlendValue::SourceAlpha),
+        ));
+        let vs = OVERLAY_VS.to_owned();
+        let fs = OVERLAY_FS.to_owned();
+        let shader = crate::video::create_shader(params, vs, fs)?;
+
+        let verts: [OverlayVertex; 4] = [
+            OverlayVertex::new([-1.0, -1.0]),
+            OverlayVertex::new([1.0, -1.0]),
+            OverlayVertex::new([1.0, 1.0]),
+            OverlayVertex::new([-1.0, 1.0]),
+        ];
+        let idxes: [u16; 6] = [0, 1, 2, 0, 2, 3];
+
+        let mut params = MeshParams::default();
+        params.layout = OverlayVertex::layout();
+        params.num_verts = 4;
+        params.num_idxes = 6;
+
+        let data = MeshData {
+            vptr: OverlayVertex::encode(&verts[..]).into(),
+            iptr: IndexFormat::encode(&idxes).into(),
+        };
+        let mesh = crate::video::create_mesh(params, Some(data))?;
+
+        Ok(Overlay {
+            surface,
+            shader,
+            mesh,
+            cmds: CommandBuffer::new(),
+        })
+    }
+
+    fn draw(&mut self, alpha: f32) -> Result<()> {
+        let mut dc = Draw::new(self.shader, self.mesh);
+        dc.set_uniform_variable("u_Alpha", alpha);
+        self.cmds.draw(dc);
+        self.cmds.submit(self.surface)?;
+        Ok(())
+    }
+}
+
+impl Drop for Overlay {
+    fn drop(&mut self) {
+        if crate::video::valid() {
+            crate::video::delete_mesh(self.mesh);
+            crate::video::delete_shader(self.shader);
+            crate::video::delete_surface(self.surface);
+        }
+    }
+}
+
+type Constructor<T> = Box<dyn FnMut(&T) -> Result<Box<dyn State + Send>> + Send>;
+
+/// A state which waits until the resources are loaded, and switches to the state created
+/// by the closure then. It's usually pushed with `Transition::Fade`, which keeps the
+/// screen black until the resources are loaded, or `Transition::Switch` with a loading
+/// screen drawn in `on_render` of the states, to swap scenes without blocking the main
+/// thread.
+///
+/// If the closure fails, the error is returned from every `on_update` after.
+///
+/// ```rust,ignore
+/// // The latch is set once all the resources of next level are loaded.
+/// let latch = Arc::new(CountLatch::new());
+/// Level::preload(latch.clone())?;
+///
+/// Ok(Transition::Fade(0.5, Box::new(Preload::new(latch, |_| {
+///     Ok(Box::new(Level::new()?))
+/// }))))
+/// ```
+pub struct Preload<T: LatchProbe> {
+    resources: T,
+    closure: Constructor<T>,
+    error: Option<String>,
+}
+
+impl<T: LatchProbe> Preload<T> {
+    pub fn new<F>(resources: T, closure: F) -> Self
+    where
+        F: FnOnce(&T) -> Result<Box<dyn State + Send>> + Send + 'static,
+    {
+        let mut v = Some(closure);
+        let closure: Constructor<T> = Box::new(move |r| match v.take() {
+            Some(w) => w(r),
+            None => bail!("Preload is finished already."),
+        });
+
+        Preload {
+            resources,
+            closure,
+            error: None,
+        }
+    }
+}
+
+impl<T: LatchProbe> State for Preload<T> {
+    fn on_update(&mut self) -> Result<Transition> {
+        if let Some(ref err) = self.error {
+            bail!("{}", err);
+        }
+
+        if !self.resources.is_set() {
+            return Ok(Transition::None);
+        }
+
+        match (self.closure)(&self.resources) {
+            Ok(state) => Ok(Transition::Switch(state)),
+            Err(err) => {
+                self.error = Some(err.to_string());
+                Err(err)
+            }
+        }
+    }
+
+    /// Checks if the resources are loaded.
+    fn is_ready(&self) -> bool {
+        self.resources.is_set()
+    }
+}
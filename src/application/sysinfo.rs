@@ -0,0 +1,183 @@
+use std::fmt;
+
+/// The information of the device running the application, which is logged at startup and
+/// could be attached to the reports of bugs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemInfo {
+    /// The name and version of the operating system.
+    pub os: String,
+    /// The number of logical CPU cores.
+    pub cpu_cores: Option<usize>,
+    /// The total amount of physical memory in bytes.
+    pub total_memory: Option<u64>,
+    /// The amount of memory in bytes which is available for new allocations.
+    pub available_memory: Option<u64>,
+    /// The name of the GPU renderer, which is `None` if the video system is not set up.
+    pub renderer: Option<String>,
+}
+
+impl SystemInfo {
+    /// Collects the information. Fields which are not available on current platform are
+    /// left as `None`.
+    pub fn collect() -> Self {
+        let (total_memory, available_memory) = platform::memory();
+
+        SystemInfo {
+            os: platform::os(),
+            cpu_cores: platform::cpu_cores(),
+            total_memory,
+            available_memory,
+            renderer: if crate::video::valid() {
+                Some(crate::video::renderer())
+            } else {
+                None
+            },
+        }
+    }
+}
+
+impl fmt::Display for SystemInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fn mb(v: Option<u64>) -> String {
+            v.map(|v| format!("{}MB", v / (1024 * 1024)))
+                .unwrap_or_else(|| "unknown".to_owned())
+        }
+
+        let renderer = self.renderer.as_ref().map_or("unknown", |v| v.as_str());
+        let cores = self
+            .cpu_cores
+            .map(|v| v.to_string())
+            .unwrap_or_else(|| "unknown".to_owned());
+
+        write!(
+            f,
+            "OS: {}, CPU cores: {}, Memory: {} ({} available), Renderer: {}",
+            self.os,
+            cores,
+            mb(self.total_memory),
+            mb(self.available_memory),
+            renderer
+        )
+    }
+}
+
+pub(crate) fn cpu_cores() -> Option<usize> {
+    platform::cpu_cores()
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod platform {
+    use std::fs;
+
+    pub fn os() -> String {
+        let name = fs::read_to_string("/etc/os-release")
+            .ok()
+            .and_then(|v| {
+                v.lines()
+                    .find(|v| v.starts_with("PRETTY_NAME="))
+                    .map(|v| v["PRETTY_NAME=".len()..].trim_matches('"').to_owned())
+            })
+            .unwrap_or_else(|| std::env::consts::OS.to_owned());
+
+        match fs::read_to_string("/proc/sys/kernel/osrelease") {
+            Ok(kernel) => format!("{} ({})", name, kernel.trim()),
+            Err(_) => name,
+        }
+    }
+
+    pub fn cpu_cores() -> Option<usize> {
+        let cpuinfo = fs::read_to_string("/proc/cpuinfo").ok()?;
+        let n = cpuinfo
+            .lines()
+            .filter(|v| v.starts_with("processor"))
+            .count();
+
+        if n > 0 {
+            Some(n)
+        } else {
+            None
+        }
+    }
+
+    pub fn memory() -> (Option<u64>, Option<u64>) {
+        let meminfo = match fs::read_to_string("/proc/meminfo") {
+            Ok(v) => v,
+            Err(_) => return (None, None),
+        };
+
+        // The values are in kB, e.g. "MemTotal:       16314248 kB".
+        let field = |name: &str| {
+            meminfo
+                .lines()
+                .find(|v| v.starts_with(name))
+                .and_then(|v| v[name.len()..].split_whitespace().next())
+                .and_then(|v| v.parse::<u64>().ok())
+                .map(|v| v * 1024)
+        };
+
+        (field("MemTotal:"), field("MemAvailable:"))
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::process::Command;
+
+    fn sysctl(name: &str) -> Option<String> {
+        let output = Command::new("sysctl").arg("-n").arg(name).output().ok()?;
+        let v = String::from_utf8(output.stdout).ok()?;
+        Some(v.trim().to_owned())
+    }
+
+    pub fn os() -> String {
+        match sysctl("kern.osproductversion") {
+            Some(ref v) if !v.is_empty() => format!("macOS {}", v),
+            _ => "macOS".to_owned(),
+        }
+    }
+
+    pub fn cpu_cores() -> Option<usize> {
+        sysctl("hw.logicalcpu").and_then(|v| v.parse().ok())
+    }
+
+    pub fn memory() -> (Option<u64>, Option<u64>) {
+        (sysctl("hw.memsize").and_then(|v| v.parse().ok()), None)
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    pub fn os() -> String {
+        "Windows".to_owned()
+    }
+
+    pub fn cpu_cores() -> Option<usize> {
+        std::env::var("NUMBER_OF_PROCESSORS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+    }
+
+    pub fn memory() -> (Option<u64>, Option<u64>) {
+        (None, None)
+    }
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    windows
+)))]
+mod platform {
+    pub fn os() -> String {
+        std::env::consts::OS.to_owned()
+    }
+
+    pub fn cpu_cores() -> Option<usize> {
+        None
+    }
+
+    pub fn memory() -> (Option<u64>, Option<u64>) {
+        (None, None)
+    }
+}
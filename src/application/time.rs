@@ -2,12 +2,14 @@
 
 use std;
 use std::collections::VecDeque;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::Duration;
 
 use crate::application::{LifecycleListener, LifecycleListenerHandle};
 use crate::utils::time::Timestamp;
 
+use super::budget::EffectsBudget;
+use super::hitch::{HitchDetector, HitchReport};
 use super::Params;
 
 pub struct TimeSystem {
@@ -21,6 +23,8 @@ struct TimeStateShared {
     max_inactive_fps: RwLock<u32>,
     smoothing_step: RwLock<usize>,
     timestep: RwLock<Duration>,
+    hitches: Mutex<HitchDetector>,
+    effects: RwLock<Option<EffectsBudget>>,
 }
 
 struct TimeState {
@@ -58,6 +62,18 @@ impl LifecycleListener for TimeState {
         let mut elapsed = self.last_frame_timepoint.elapsed();
         self.last_frame_timepoint = Timestamp::now();
 
+        if let Some(report) = self.shared.hitches.lock().unwrap().advance(elapsed) {
+            warn!("[Hitch] {}", report);
+        }
+
+        // The budget is fed with the real frame time, before it's clamped by `min_fps`.
+        if let Some(ref mut budget) = *self.shared.effects.write().unwrap() {
+            budget.advance(elapsed);
+        }
+
+        #[cfg(feature = "alloc-tracking")]
+        crate::memory::check_budgets();
+
         // If fps lower than minimum, simply clamp it.
         if self.min_fps > 0 {
             elapsed = std::cmp::min(
@@ -103,6 +119,8 @@ impl TimeSystem {
             max_inactive_fps: RwLock::new(setup.max_inactive_fps),
             smoothing_step: RwLock::new(setup.time_smooth_step as usize),
             timestep: RwLock::new(Duration::new(0, 0)),
+            hitches: Mutex::new(HitchDetector::new(setup.hitch_threshold)),
+            effects: RwLock::new(setup.effects_budget.clone()),
         });
 
         let state = TimeState {
@@ -166,4 +184,60 @@ impl TimeSystem {
     pub fn frame_duration(&self) -> Duration {
         *self.shared.timestep.read().unwrap()
     }
+
+    /// Sets the threshold of frame duration, over which the frame is reported as a hitch.
+    #[inline]
+    pub fn set_hitch_threshold(&self, threshold: Option<Duration>) {
+        self.shared.hitches.lock().unwrap().set_threshold(threshold);
+    }
+
+    /// Gets the recent hitch reports.
+    #[inline]
+    pub fn hitches(&self) -> Vec<HitchReport> {
+        self.shared.hitches.lock().unwrap().reports()
+    }
+
+    #[inline]
+    pub fn set_effects_budget(&self, budget: Option<EffectsBudget>) {
+        *self.shared.effects.write().unwrap() = budget;
+    }
+
+    #[inline]
+    pub fn effects_budget(&self) -> Option<EffectsBudget> {
+        self.shared.effects.read().unwrap().clone()
+    }
+
+    #[inline]
+    pub fn effects_scale(&self) -> f32 {
+        match *self.shared.effects.read().unwrap() {
+            Some(ref budget) => budget.scale(),
+            None => 1.0,
+        }
+    }
+
+    #[inline]
+    pub fn set_effects_level(&self, level: u32) {
+        if let Some(ref mut budget) = *self.shared.effects.write().unwrap() {
+            budget.set_level(level);
+        }
+    }
+
+    #[inline]
+    pub fn record_scope(&self, name: &'static str, duration: Duration) {
+        self.shared
+            .hitches
+            .lock()
+            .unwrap()
+            .record_scope(name, duration);
+    }
+
+    #[inline]
+    pub fn record_load(&self, url: &str) {
+        self.shared.hitches.lock().unwrap().record_load(url);
+    }
+
+    #[inline]
+    pub fn record_gc(&self, freed: usize) {
+        self.shared.hitches.lock().unwrap().record_gc(freed);
+    }
 }
@@ -0,0 +1,225 @@
+//! Named actions and axes mapped onto the raw inputs.
+//!
+//! Games usually check for "jump" or "move horizontally" instead of specific keys, and
+//! let players rebind them in a settings menu. An `ActionMap` binds names to keys, mouse
+//! and gamepad inputs, and could be serialized to persist the bindings.
+//!
+//! ```rust
+//! use crayon::prelude::*;
+//! application::oneshot().unwrap();
+//!
+//! let mut actions = ActionMap::default();
+//! actions.bind_action("jump", ActionBinding::Key(Key::Space));
+//! actions.bind_action("jump", ActionBinding::Mouse(MouseButton::Left));
+//! actions.bind_axis("horizontal", AxisBinding::new(AxisSource::Keys(Key::A, Key::D)));
+//!
+//! if actions.is_action_press("jump") {
+//!     // ...
+//! }
+//!
+//! let x = actions.axis("horizontal");
+//! ```
+
+use crate::utils::prelude::FastHashMap;
+
+use super::gamepad::{GamepadAxis, GamepadButton};
+use super::keyboard::Key;
+use super::mouse::MouseButton;
+
+/// A raw input that triggers an action.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ActionBinding {
+    Key(Key),
+    Mouse(MouseButton),
+    /// A button on any of the connected gamepads.
+    Gamepad(GamepadButton),
+}
+
+/// A raw input that drives an axis.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AxisSource {
+    /// A pair of keys which drives the axis to -1 and 1 respectively.
+    Keys(Key, Key),
+    /// The horizontal movement of mouse since last frame.
+    MouseX,
+    /// The vertical movement of mouse since last frame.
+    MouseY,
+    /// The horizontal scroll movement of mouse.
+    ScrollX,
+    /// The vertical scroll movement of mouse.
+    ScrollY,
+    /// An axis of the connected gamepads, the one with the largest magnitude is used.
+    Gamepad(GamepadAxis),
+}
+
+/// A raw input that drives an axis, with its scale and dead zone.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AxisBinding {
+    pub source: AxisSource,
+    /// Multiplies the raw value.
+    pub scale: f32,
+    /// Raw values whose magnitudes are less than the dead zone are treated as zero. It's
+    /// ignored by gamepad axes, which are filtered by `GamepadParams::dead_zone` already.
+    pub dead_zone: f32,
+}
+
+impl AxisBinding {
+    pub fn new(source: AxisSource) -> Self {
+        AxisBinding {
+            source,
+            scale: 1.0,
+            dead_zone: 0.0,
+        }
+    }
+
+    fn value(&self) -> f32 {
+        let v = match self.source {
+            AxisSource::Keys(negative, positive) => {
+                let mut v = 0.0;
+                if super::is_key_down(negative) {
+                    v -= 1.0;
+                }
+
+                if super::is_key_down(positive) {
+                    v += 1.0;
+                }
+
+                v
+            }
+            AxisSource::MouseX => super::mouse_movement().x,
+            AxisSource::MouseY => super::mouse_movement().y,
+            AxisSource::ScrollX => super::mouse_scroll().x,
+            AxisSource::ScrollY => super::mouse_scroll().y,
+            AxisSource::Gamepad(axis) => super::gamepads()
+                .into_iter()
+                .map(|pad| pad.axis(axis))
+                .fold(0.0, |acc, v| if v.abs() > acc.abs() { v } else { acc }),
+        };
+
+        self.filter(v)
+    }
+
+    fn filter(&self, v: f32) -> f32 {
+        let dead_zone = match self.source {
+            AxisSource::Gamepad(_) => 0.0,
+            _ => self.dead_zone,
+        };
+
+        if v.abs() < dead_zone {
+            0.0
+        } else {
+            v * self.scale
+        }
+    }
+}
+
+/// The bindings of named actions and axes, which could be changed at runtime.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ActionMap {
+    actions: FastHashMap<String, Vec<ActionBinding>>,
+    axes: FastHashMap<String, Vec<AxisBinding>>,
+}
+
+impl ActionMap {
+    /// Binds a raw input to the action. An action could have multiple bindings, and is
+    /// triggered by any of them.
+    pub fn bind_action<T: Into<String>>(&mut self, name: T, binding: ActionBinding) {
+        let bindings = self.actions.entry(name.into()).or_insert_with(Vec::new);
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+
+    /// Removes all the bindings of the action.
+    pub fn unbind_action<T: AsRef<str>>(&mut self, name: T) {
+        self.actions.remove(name.as_ref());
+    }
+
+    /// Gets the bindings of the action.
+    pub fn action_bindings<T: AsRef<str>>(&self, name: T) -> &[ActionBinding] {
+        self.actions
+            .get(name.as_ref())
+            .map(|v| v.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Binds a raw input to the axis. The value of axis is the sum of all its bindings.
+    pub fn bind_axis<T: Into<String>>(&mut self, name: T, binding: AxisBinding) {
+        self.axes
+            .entry(name.into())
+            .or_insert_with(Vec::new)
+            .push(binding);
+    }
+
+    /// Removes all the bindings of the axis.
+    pub fn unbind_axis<T: AsRef<str>>(&mut self, name: T) {
+        self.axes.remove(name.as_ref());
+    }
+
+    /// Gets the bindings of the axis.
+    pub fn axis_bindings<T: AsRef<str>>(&self, name: T) -> &[AxisBinding] {
+        self.axes
+            .get(name.as_ref())
+            .map(|v| v.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Checks if any binding of the action is held down.
+    pub fn is_action_down<T: AsRef<str>>(&self, name: T) -> bool {
+        self.action_bindings(name).iter().any(|v| match *v {
+            ActionBinding::Key(key) => super::is_key_down(key),
+            ActionBinding::Mouse(button) => super::is_mouse_down(button),
+            ActionBinding::Gamepad(button) => super::gamepads()
+                .into_iter()
+                .any(|pad| pad.is_button_down(button)),
+        })
+    }
+
+    /// Checks if any binding of the action has been pressed during the last frame.
+    pub fn is_action_press<T: AsRef<str>>(&self, name: T) -> bool {
+        self.action_bindings(name).iter().any(|v| match *v {
+            ActionBinding::Key(key) => super::is_key_press(key),
+            ActionBinding::Mouse(button) => super::is_mouse_press(button),
+            ActionBinding::Gamepad(button) => super::gamepads()
+                .into_iter()
+                .any(|pad| pad.is_button_press(button)),
+        })
+    }
+
+    /// Checks if any binding of the action has been released during the last frame.
+    pub fn is_action_release<T: AsRef<str>>(&self, name: T) -> bool {
+        self.action_bindings(name).iter().any(|v| match *v {
+            ActionBinding::Key(key) => super::is_key_release(key),
+            ActionBinding::Mouse(button) => super::is_mouse_release(button),
+            ActionBinding::Gamepad(button) => super::gamepads()
+                .into_iter()
+                .any(|pad| pad.is_button_release(button)),
+        })
+    }
+
+    /// Gets the value of the axis, zero if it has no bindings.
+    pub fn axis<T: AsRef<str>>(&self, name: T) -> f32 {
+        self.axis_bindings(name).iter().map(|v| v.value()).sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn filter() {
+        let mut binding = AxisBinding::new(AxisSource::MouseX);
+        binding.scale = 2.0;
+        binding.dead_zone = 0.25;
+
+        assert_eq!(binding.filter(0.2), 0.0);
+        assert_eq!(binding.filter(-0.2), 0.0);
+        assert_eq!(binding.filter(0.5), 1.0);
+        assert_eq!(binding.filter(-0.5), -1.0);
+
+        // Gamepad axes are filtered by the dead zone of `GamepadParams` only.
+        binding.source = AxisSource::Gamepad(GamepadAxis::LeftStickX);
+        assert_eq!(binding.filter(0.2), 0.4);
+    }
+}
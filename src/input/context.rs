@@ -0,0 +1,148 @@
+//! Ordered input contexts, which decide who handles the inputs of a frame.
+//!
+//! It's common that the UI and the gameplay are both interested in the same click. The
+//! contexts are kept in a stack, and a context could consume the inputs of a device once
+//! it handled them, which hides them from all the contexts below it in this frame.
+//!
+//! ```rust
+//! use crayon::prelude::*;
+//! application::oneshot().unwrap();
+//!
+//! let gameplay = input::push_context();
+//! let ui = input::push_context();
+//!
+//! // The UI handles the click on a button.
+//! if input::is_mouse_press(MouseButton::Left) {
+//!     input::consume(ui, InputDevice::Pointer);
+//! }
+//!
+//! // And the gameplay will not react to it.
+//! if input::is_available(gameplay, InputDevice::Pointer) {
+//!     // ...
+//! }
+//!
+//! input::pop_context(ui);
+//! input::pop_context(gameplay);
+//! ```
+
+use crate::utils::prelude::HandlePool;
+
+impl_handle!(InputContextHandle);
+
+/// The kinds of inputs that could be consumed by contexts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputDevice {
+    /// Mouse and touch inputs.
+    Pointer,
+    /// Keyboard inputs, including the captured text.
+    Keyboard,
+    /// Gamepad inputs.
+    Gamepad,
+}
+
+impl InputDevice {
+    #[inline]
+    fn mask(self) -> u8 {
+        match self {
+            InputDevice::Pointer => 0x1,
+            InputDevice::Keyboard => 0x2,
+            InputDevice::Gamepad => 0x4,
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct InputContexts {
+    handles: HandlePool<InputContextHandle>,
+    // Contexts from the bottom to the top, along with the devices they consumed.
+    stack: Vec<(InputContextHandle, u8)>,
+}
+
+impl InputContexts {
+    pub fn push(&mut self) -> InputContextHandle {
+        let handle = self.handles.create();
+        self.stack.push((handle, 0));
+        handle
+    }
+
+    pub fn pop(&mut self, handle: InputContextHandle) {
+        if self.handles.free(handle) {
+            self.stack.retain(|&(v, _)| v != handle);
+        }
+    }
+
+    pub fn consume(&mut self, handle: InputContextHandle, device: InputDevice) {
+        if let Some(v) = self.stack.iter_mut().find(|v| v.0 == handle) {
+            v.1 |= device.mask();
+        }
+    }
+
+    pub fn is_available(&self, handle: InputContextHandle, device: InputDevice) -> bool {
+        for &(v, consumed) in self.stack.iter().rev() {
+            if v == handle {
+                return true;
+            }
+
+            if consumed & device.mask() != 0 {
+                return false;
+            }
+        }
+
+        false
+    }
+
+    pub fn is_consumed(&self, device: InputDevice) -> bool {
+        self.stack.iter().any(|v| v.1 & device.mask() != 0)
+    }
+
+    pub fn advance(&mut self) {
+        for v in &mut self.stack {
+            v.1 = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn consume() {
+        let mut contexts = InputContexts::default();
+        let gameplay = contexts.push();
+        let ui = contexts.push();
+
+        assert!(contexts.is_available(ui, InputDevice::Pointer));
+        assert!(contexts.is_available(gameplay, InputDevice::Pointer));
+
+        contexts.consume(ui, InputDevice::Pointer);
+        assert!(contexts.is_consumed(InputDevice::Pointer));
+        assert!(contexts.is_available(ui, InputDevice::Pointer));
+        assert!(!contexts.is_available(gameplay, InputDevice::Pointer));
+        assert!(contexts.is_available(gameplay, InputDevice::Keyboard));
+
+        // The consumed devices are available again in the next frame.
+        contexts.advance();
+        assert!(!contexts.is_consumed(InputDevice::Pointer));
+        assert!(contexts.is_available(gameplay, InputDevice::Pointer));
+    }
+
+    #[test]
+    fn pop() {
+        let mut contexts = InputContexts::default();
+        let gameplay = contexts.push();
+        let ui = contexts.push();
+
+        contexts.consume(ui, InputDevice::Keyboard);
+        assert!(!contexts.is_available(gameplay, InputDevice::Keyboard));
+
+        contexts.pop(ui);
+        assert!(!contexts.is_available(ui, InputDevice::Keyboard));
+        assert!(!contexts.is_consumed(InputDevice::Keyboard));
+        assert!(contexts.is_available(gameplay, InputDevice::Keyboard));
+
+        // Consuming with a popped context does nothing.
+        contexts.consume(ui, InputDevice::Keyboard);
+        assert!(contexts.is_available(gameplay, InputDevice::Keyboard));
+    }
+}
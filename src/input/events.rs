@@ -1,10 +1,11 @@
 use crate::math::prelude::Vector2;
 
+use super::gamepad::{GamepadAxis, GamepadButton};
 use super::keyboard::Key;
 use super::mouse::MouseButton;
 use super::touchpad::TouchState;
 
-/// Input device event, supports mouse and keyboard only.
+/// Input device event, supports mouse, keyboard, touch and gamepads.
 #[derive(Debug, Clone, Copy)]
 pub enum InputEvent {
     /// The cursor has moved on the window.
@@ -39,4 +40,19 @@ pub enum InputEvent {
         state: TouchState,
         position: Vector2<f32>,
     },
+
+    /// A gamepad has been connected. The ids of disconnected gamepads might be reused.
+    GamepadConnected { id: usize },
+    /// A gamepad has been disconnected.
+    GamepadDisconnected { id: usize },
+    /// Pressed event on gamepad has been received.
+    GamepadPressed { id: usize, button: GamepadButton },
+    /// Released event from gamepad has been received.
+    GamepadReleased { id: usize, button: GamepadButton },
+    /// The value of an analog axis of gamepad has changed.
+    GamepadAxisChanged {
+        id: usize,
+        axis: GamepadAxis,
+        value: f32,
+    },
 }
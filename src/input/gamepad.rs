@@ -0,0 +1,300 @@
+//! Gamepads, which are identified by the order they are connected in.
+//!
+//! The gamepads are polled through [gilrs](https://gitlab.com/gilrs-project/gilrs) on
+//! desktop platforms, which requires the `gamepad` feature. Connecting or disconnecting
+//! a gamepad emits `InputEvent::GamepadConnected` and `InputEvent::GamepadDisconnected`
+//! to the window event listeners, and the states of buttons and axes are updated every
+//! frame like the other input devices.
+//!
+//! ```rust
+//! use crayon::prelude::*;
+//! application::oneshot().unwrap();
+//!
+//! let pad = input::gamepad(0);
+//! if pad.is_button_press(GamepadButton::South) {
+//!     pad.rumble(0.5, std::time::Duration::from_millis(200));
+//! }
+//!
+//! let x = pad.axis(GamepadAxis::LeftStickX);
+//! ```
+
+use std::time::Duration;
+
+use crate::utils::hash::FastHashSet;
+
+use super::MAX_GAMEPADS;
+
+/// The setup parameters of gamepads.
+#[derive(Debug, Clone, Copy)]
+pub struct GamepadParams {
+    /// The values of sticks whose magnitudes are less than the dead zone are treated as
+    /// zero, and the rest are rescaled to cover the full range.
+    pub dead_zone: f32,
+    /// The dead zone of analog triggers.
+    pub trigger_dead_zone: f32,
+}
+
+impl Default for GamepadParams {
+    fn default() -> Self {
+        GamepadParams {
+            dead_zone: 0.15,
+            trigger_dead_zone: 0.05,
+        }
+    }
+}
+
+/// Describes a button of gamepad, named after its position on the layout of Xbox
+/// controllers.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum GamepadButton {
+    /// The bottom action button, e.g. A on Xbox controllers.
+    South,
+    /// The right action button, e.g. B on Xbox controllers.
+    East,
+    /// The top action button, e.g. Y on Xbox controllers.
+    North,
+    /// The left action button, e.g. X on Xbox controllers.
+    West,
+    LeftBumper,
+    RightBumper,
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    Mode,
+    LeftThumb,
+    RightThumb,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+/// Describes an analog axis of gamepad. Sticks range from -1 to 1 with positive values
+/// pointing right and up, and triggers range from 0 to 1.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftTrigger,
+    RightTrigger,
+}
+
+impl GamepadAxis {
+    #[inline]
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    #[inline]
+    fn is_trigger(self) -> bool {
+        self == GamepadAxis::LeftTrigger || self == GamepadAxis::RightTrigger
+    }
+}
+
+/// A gamepad slot, which is valid even if nothing is connected to it. All the queries
+/// return the rest states if the gamepad is disconnected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gamepad {
+    id: usize,
+}
+
+impl Gamepad {
+    #[inline]
+    pub(crate) fn new(id: usize) -> Self {
+        Gamepad { id }
+    }
+
+    /// Gets the index of this gamepad.
+    #[inline]
+    pub fn id(self) -> usize {
+        self.id
+    }
+
+    /// Checks if the gamepad is connected.
+    #[inline]
+    pub fn is_connected(self) -> bool {
+        super::inside::ctx().is_gamepad_connected(self.id)
+    }
+
+    /// Checks if a button is currently held down.
+    #[inline]
+    pub fn is_button_down(self, button: GamepadButton) -> bool {
+        super::inside::ctx().is_gamepad_button_down(self.id, button)
+    }
+
+    /// Checks if a button has been pressed during the last frame.
+    #[inline]
+    pub fn is_button_press(self, button: GamepadButton) -> bool {
+        super::inside::ctx().is_gamepad_button_press(self.id, button)
+    }
+
+    /// Checks if a button has been released during the last frame.
+    #[inline]
+    pub fn is_button_release(self, button: GamepadButton) -> bool {
+        super::inside::ctx().is_gamepad_button_release(self.id, button)
+    }
+
+    /// Gets the value of axis with the dead zone applied.
+    #[inline]
+    pub fn axis(self, axis: GamepadAxis) -> f32 {
+        super::inside::ctx().gamepad_axis(self.id, axis)
+    }
+
+    /// Vibrates the gamepad with `strength` from 0 to 1 for `duration`. It has no effect
+    /// if force feedback is not supported by the gamepad or platform.
+    #[inline]
+    pub fn rumble(self, strength: f32, duration: Duration) {
+        super::inside::ctx().rumble(self.id, strength, duration)
+    }
+}
+
+#[derive(Default)]
+struct GamepadState {
+    connected: bool,
+    downs: FastHashSet<GamepadButton>,
+    presses: FastHashSet<GamepadButton>,
+    releases: FastHashSet<GamepadButton>,
+    axes: [f32; 6],
+}
+
+impl GamepadState {
+    fn reset(&mut self) {
+        self.downs.clear();
+        self.presses.clear();
+        self.releases.clear();
+        self.axes = [0.0; 6];
+    }
+}
+
+pub struct Gamepads {
+    params: GamepadParams,
+    pads: Vec<GamepadState>,
+}
+
+impl Gamepads {
+    pub fn new(params: GamepadParams) -> Self {
+        Gamepads {
+            params,
+            pads: (0..MAX_GAMEPADS).map(|_| GamepadState::default()).collect(),
+        }
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        for v in &mut self.pads {
+            v.reset();
+        }
+    }
+
+    #[inline]
+    pub fn advance(&mut self) {
+        for v in &mut self.pads {
+            v.presses.clear();
+            v.releases.clear();
+        }
+    }
+
+    #[inline]
+    pub fn on_connected(&mut self, id: usize) {
+        if let Some(v) = self.pads.get_mut(id) {
+            v.reset();
+            v.connected = true;
+        } else {
+            warn!(
+                "[Input] Gamepad {} is ignored, since at most {} gamepads are tracked.",
+                id, MAX_GAMEPADS
+            );
+        }
+    }
+
+    #[inline]
+    pub fn on_disconnected(&mut self, id: usize) {
+        if let Some(v) = self.pads.get_mut(id) {
+            v.reset();
+            v.connected = false;
+        }
+    }
+
+    #[inline]
+    pub fn on_button_pressed(&mut self, id: usize, button: GamepadButton) {
+        if let Some(v) = self.pads.get_mut(id) {
+            if v.downs.insert(button) {
+                v.presses.insert(button);
+            }
+        }
+    }
+
+    #[inline]
+    pub fn on_button_released(&mut self, id: usize, button: GamepadButton) {
+        if let Some(v) = self.pads.get_mut(id) {
+            if v.downs.remove(&button) {
+                v.releases.insert(button);
+            }
+        }
+    }
+
+    #[inline]
+    pub fn on_axis_changed(&mut self, id: usize, axis: GamepadAxis, value: f32) {
+        if let Some(v) = self.pads.get_mut(id) {
+            v.axes[axis.index()] = value;
+        }
+    }
+
+    /// Gets the ids of connected gamepads.
+    pub fn connected(&self) -> Vec<usize> {
+        (0..self.pads.len())
+            .filter(|&i| self.pads[i].connected)
+            .collect()
+    }
+
+    #[inline]
+    pub fn is_connected(&self, id: usize) -> bool {
+        self.pads.get(id).map(|v| v.connected).unwrap_or(false)
+    }
+
+    #[inline]
+    pub fn is_button_down(&self, id: usize, button: GamepadButton) -> bool {
+        self.pads
+            .get(id)
+            .map(|v| v.downs.contains(&button))
+            .unwrap_or(false)
+    }
+
+    #[inline]
+    pub fn is_button_press(&self, id: usize, button: GamepadButton) -> bool {
+        self.pads
+            .get(id)
+            .map(|v| v.presses.contains(&button))
+            .unwrap_or(false)
+    }
+
+    #[inline]
+    pub fn is_button_release(&self, id: usize, button: GamepadButton) -> bool {
+        self.pads
+            .get(id)
+            .map(|v| v.releases.contains(&button))
+            .unwrap_or(false)
+    }
+
+    pub fn axis(&self, id: usize, axis: GamepadAxis) -> f32 {
+        let v = match self.pads.get(id) {
+            Some(pad) => pad.axes[axis.index()],
+            None => return 0.0,
+        };
+
+        let dead_zone = if axis.is_trigger() {
+            self.params.trigger_dead_zone
+        } else {
+            self.params.dead_zone
+        };
+
+        if v.abs() <= dead_zone || dead_zone >= 1.0 {
+            0.0
+        } else {
+            v.signum() * ((v.abs() - dead_zone) / (1.0 - dead_zone)).min(1.0)
+        }
+    }
+}
@@ -159,21 +159,42 @@
 //!
 //! Notes we also have APIs with `_in_points` suffix to works in logical points.
 //!
+//! # Gamepad Inputs
+//!
+//! Gamepads are accessed by the order they are connected in, and the states of their
+//! buttons and analog axes could be checked the same way as other devices:
+//!
+//! ```rust
+//! use crayon::prelude::*;
+//! application::oneshot().unwrap();
+//!
+//! // Checks if a button of the first gamepad has been pressed during last frame.
+//! input::gamepad(0).is_button_press(GamepadButton::South);
+//!
+//! // Gets the horizontal value of left stick, with the dead zone applied.
+//! input::gamepad(0).axis(GamepadAxis::LeftStickX);
+//! ```
+//!
 //! # Others Inputs
 //!
 //! Somethings that nice to have, but not implemented right now:
 //!
 //! 1. Device sensor inputs;
-//! 2. Game pad inputs;
-//! 3. More touch gesture like `Pinching`.
+//! 2. More touch gesture like `Pinching`.
 
+pub mod actions;
+pub mod context;
 pub mod events;
+pub mod gamepad;
 pub mod keyboard;
 pub mod mouse;
 pub mod touchpad;
 
 pub mod prelude {
+    pub use super::actions::{ActionBinding, ActionMap, AxisBinding, AxisSource};
+    pub use super::context::{InputContextHandle, InputDevice};
     pub use super::events::InputEvent;
+    pub use super::gamepad::{Gamepad, GamepadAxis, GamepadButton, GamepadParams};
     pub use super::keyboard::{Key, KeyboardParams};
     pub use super::mouse::{MouseButton, MouseParams};
     pub use super::touchpad::{GesturePan, GestureTap, TouchPadParams};
@@ -185,8 +206,13 @@ mod system;
 /// Maximum touches that would be tracked at sametime.
 pub const MAX_TOUCHES: usize = 4;
 
+/// Maximum gamepads that would be tracked at sametime.
+pub const MAX_GAMEPADS: usize = 8;
+
 use crate::math::prelude::Vector2;
 
+use self::context::{InputContextHandle, InputDevice};
+use self::gamepad::{Gamepad, GamepadParams};
 use self::inside::{ctx, CTX};
 use self::keyboard::{Key, KeyboardParams};
 use self::mouse::{MouseButton, MouseParams};
@@ -199,6 +225,7 @@ pub struct InputParams {
     pub keyboard: KeyboardParams,
     pub mouse: MouseParams,
     pub touchpad: TouchPadParams,
+    pub gamepad: GamepadParams,
 }
 
 /// Checks if the resource system is enabled.
@@ -213,6 +240,38 @@ pub fn reset() {
     ctx().reset();
 }
 
+/// Pushes a new input context on top of the others. Contexts near the top have
+/// higher priority to handle the inputs.
+#[inline]
+pub fn push_context() -> InputContextHandle {
+    ctx().push_context()
+}
+
+/// Removes the input context.
+#[inline]
+pub fn pop_context(handle: InputContextHandle) {
+    ctx().pop_context(handle)
+}
+
+/// Marks the inputs of device in current frame as handled by the context, which hides
+/// them from the contexts below it.
+#[inline]
+pub fn consume(handle: InputContextHandle, device: InputDevice) {
+    ctx().consume(handle, device)
+}
+
+/// Checks if the inputs of device are not consumed by any context above this one.
+#[inline]
+pub fn is_available(handle: InputContextHandle, device: InputDevice) -> bool {
+    ctx().is_available(handle, device)
+}
+
+/// Checks if the inputs of device are consumed by any context in current frame.
+#[inline]
+pub fn is_consumed(device: InputDevice) -> bool {
+    ctx().is_consumed(device)
+}
+
 /// Returns true if a keyboard is attached
 #[inline]
 pub fn has_keyboard_attached() -> bool {
@@ -339,6 +398,25 @@ pub fn finger_pan() -> GesturePan {
     ctx().finger_pan()
 }
 
+/// Returns true if any gamepad is connected.
+#[inline]
+pub fn has_gamepad_attached() -> bool {
+    ctx().has_gamepad_attached()
+}
+
+/// Gets the `n`th gamepad. The ids are assigned in the order gamepads are first
+/// connected in.
+#[inline]
+pub fn gamepad(n: usize) -> Gamepad {
+    Gamepad::new(n)
+}
+
+/// Gets all the connected gamepads.
+#[inline]
+pub fn gamepads() -> Vec<Gamepad> {
+    ctx().gamepads()
+}
+
 pub(crate) mod inside {
     use super::system::InputSystem;
     use super::InputParams;
@@ -1,9 +1,12 @@
 use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
 use crate::application::prelude::{LifecycleListener, LifecycleListenerHandle};
 use crate::window::prelude::{Event, EventListener, EventListenerHandle};
 
+use super::context::{InputContextHandle, InputContexts, InputDevice};
 use super::events::InputEvent;
+use super::gamepad::{Gamepad, GamepadAxis, GamepadButton, Gamepads};
 use super::keyboard::{Key, Keyboard};
 use super::mouse::{Mouse, MouseButton};
 use super::touchpad::{GesturePan, GestureTap, TouchPad, TouchState};
@@ -25,6 +28,8 @@ struct InputState {
     mouse: RwLock<Mouse>,
     keyboard: RwLock<Keyboard>,
     touchpad: RwLock<TouchPad>,
+    gamepads: RwLock<Gamepads>,
+    contexts: RwLock<InputContexts>,
 }
 
 impl EventListener for Arc<InputState> {
@@ -93,6 +98,30 @@ impl EventListener for Arc<InputState> {
                 } => {
                     self.touchpad.write().unwrap().on_touch(id, state, position);
                 }
+
+                InputEvent::GamepadConnected { id } => {
+                    self.gamepads.write().unwrap().on_connected(id)
+                }
+
+                InputEvent::GamepadDisconnected { id } => {
+                    self.gamepads.write().unwrap().on_disconnected(id)
+                }
+
+                InputEvent::GamepadPressed { id, button } => {
+                    self.gamepads.write().unwrap().on_button_pressed(id, button)
+                }
+
+                InputEvent::GamepadReleased { id, button } => self
+                    .gamepads
+                    .write()
+                    .unwrap()
+                    .on_button_released(id, button),
+
+                InputEvent::GamepadAxisChanged { id, axis, value } => self
+                    .gamepads
+                    .write()
+                    .unwrap()
+                    .on_axis_changed(id, axis, value),
             }
         }
 
@@ -105,6 +134,8 @@ impl LifecycleListener for Arc<InputState> {
         self.mouse.write().unwrap().advance();
         self.keyboard.write().unwrap().advance();
         self.touchpad.write().unwrap().advance();
+        self.gamepads.write().unwrap().advance();
+        self.contexts.write().unwrap().advance();
         Ok(())
     }
 }
@@ -126,6 +157,8 @@ impl InputSystem {
             mouse: RwLock::new(Mouse::new(setup.mouse)),
             keyboard: RwLock::new(Keyboard::new(setup.keyboard)),
             touchpad: RwLock::new(TouchPad::new(setup.touchpad)),
+            gamepads: RwLock::new(Gamepads::new(setup.gamepad)),
+            contexts: RwLock::new(InputContexts::default()),
         });
 
         InputSystem {
@@ -140,10 +173,46 @@ impl InputSystem {
         self.state.mouse.write().unwrap().reset();
         self.state.keyboard.write().unwrap().reset();
         self.state.touchpad.write().unwrap().reset();
+        self.state.gamepads.write().unwrap().reset();
 
         *self.state.touch_emulation_button.write().unwrap() = None;
     }
 
+    /// Pushes a new input context on top of the others.
+    #[inline]
+    pub fn push_context(&self) -> InputContextHandle {
+        self.state.contexts.write().unwrap().push()
+    }
+
+    /// Removes the input context.
+    #[inline]
+    pub fn pop_context(&self, handle: InputContextHandle) {
+        self.state.contexts.write().unwrap().pop(handle);
+    }
+
+    /// Marks the inputs of device in current frame as handled by the context, which
+    /// hides them from the contexts below it.
+    #[inline]
+    pub fn consume(&self, handle: InputContextHandle, device: InputDevice) {
+        self.state.contexts.write().unwrap().consume(handle, device);
+    }
+
+    /// Checks if the inputs of device are not consumed by any context above this one.
+    #[inline]
+    pub fn is_available(&self, handle: InputContextHandle, device: InputDevice) -> bool {
+        self.state
+            .contexts
+            .read()
+            .unwrap()
+            .is_available(handle, device)
+    }
+
+    /// Checks if the inputs of device are consumed by any context.
+    #[inline]
+    pub fn is_consumed(&self, device: InputDevice) -> bool {
+        self.state.contexts.read().unwrap().is_consumed(device)
+    }
+
     /// Returns true if a keyboard is attached
     #[inline]
     pub fn has_keyboard_attached(&self) -> bool {
@@ -277,4 +346,67 @@ impl InputSystem {
     pub fn finger_pan(&self) -> GesturePan {
         self.state.touchpad.read().unwrap().pan()
     }
+
+    /// Returns true if any gamepad is connected.
+    #[inline]
+    pub fn has_gamepad_attached(&self) -> bool {
+        !self.state.gamepads.read().unwrap().connected().is_empty()
+    }
+
+    /// Gets all the connected gamepads.
+    #[inline]
+    pub fn gamepads(&self) -> Vec<Gamepad> {
+        let gamepads = self.state.gamepads.read().unwrap();
+        gamepads.connected().into_iter().map(Gamepad::new).collect()
+    }
+
+    /// Checks if the gamepad is connected.
+    #[inline]
+    pub fn is_gamepad_connected(&self, id: usize) -> bool {
+        self.state.gamepads.read().unwrap().is_connected(id)
+    }
+
+    /// Checks if a button of gamepad is currently held down.
+    #[inline]
+    pub fn is_gamepad_button_down(&self, id: usize, button: GamepadButton) -> bool {
+        self.state
+            .gamepads
+            .read()
+            .unwrap()
+            .is_button_down(id, button)
+    }
+
+    /// Checks if a button of gamepad has been pressed during the last frame.
+    #[inline]
+    pub fn is_gamepad_button_press(&self, id: usize, button: GamepadButton) -> bool {
+        self.state
+            .gamepads
+            .read()
+            .unwrap()
+            .is_button_press(id, button)
+    }
+
+    /// Checks if a button of gamepad has been released during the last frame.
+    #[inline]
+    pub fn is_gamepad_button_release(&self, id: usize, button: GamepadButton) -> bool {
+        self.state
+            .gamepads
+            .read()
+            .unwrap()
+            .is_button_release(id, button)
+    }
+
+    /// Gets the value of an axis of gamepad with the dead zone applied.
+    #[inline]
+    pub fn gamepad_axis(&self, id: usize, axis: GamepadAxis) -> f32 {
+        self.state.gamepads.read().unwrap().axis(id, axis)
+    }
+
+    /// Vibrates the gamepad if force feedback is available.
+    #[inline]
+    pub fn rumble(&self, id: usize, strength: f32, duration: Duration) {
+        if self.is_gamepad_connected(id) {
+            crate::window::inside::rumble(id, strength, duration);
+        }
+    }
 }
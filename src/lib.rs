@@ -65,14 +65,20 @@ pub use log::{error, info, warn};
 pub mod errors;
 #[macro_use]
 pub mod utils;
+pub mod achievements;
 pub mod application;
 #[macro_use]
 pub mod video;
 pub mod input;
 pub mod math;
+#[cfg(feature = "alloc-tracking")]
+pub mod memory;
 pub mod prelude;
+#[cfg(all(feature = "discord", not(target_arch = "wasm32")))]
+pub mod presence;
 pub mod res;
 pub mod sched;
+pub mod testing;
 pub mod window;
 
 #[macro_export]
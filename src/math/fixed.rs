@@ -0,0 +1,796 @@
+//! Deterministic fixed-point numbers for gameplay simulation.
+//!
+//! Floating-point results could differ across compilers, instruction sets and platforms,
+//! which breaks lockstep games where every peer runs the same simulation. `Fixed` is a
+//! signed 48.16 fixed-point number whose arithmetic only uses integer operations, so the
+//! results are bit-identical everywhere. The simulation is written with `Fixed`,
+//! `FixedVector2`, `FixedVector3`, `FixedQuaternion`, `FixedMatrix3` and `FixedTransform`,
+//! and converted into floats only for rendering.
+//!
+//! ```rust
+//! use crayon::math::fixed::{Fixed, FixedVector2};
+//!
+//! let velocity = FixedVector2::new(Fixed::from(3), Fixed::from(4));
+//! assert_eq!(velocity.magnitude(), Fixed::from(5));
+//!
+//! let position = velocity * Fixed::from_f32(0.5);
+//! assert_eq!(position.to_f32(), [1.5, 2.0].into());
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+
+use cgmath::{Matrix3, Matrix4, Quaternion, Vector2, Vector3};
+
+/// The number of fractional bits of `Fixed`.
+pub const FRAC_BITS: u32 = 16;
+
+/// A signed 48.16 fixed-point number.
+#[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Fixed(i64);
+
+impl Fixed {
+    pub const ZERO: Fixed = Fixed(0);
+    pub const ONE: Fixed = Fixed(1 << FRAC_BITS);
+    pub const HALF: Fixed = Fixed(1 << (FRAC_BITS - 1));
+    pub const PI: Fixed = Fixed(205_887);
+    pub const FRAC_PI_2: Fixed = Fixed(102_944);
+    pub const FRAC_PI_4: Fixed = Fixed(51_472);
+    pub const TWO_PI: Fixed = Fixed(411_775);
+    /// The smallest positive value.
+    pub const EPSILON: Fixed = Fixed(1);
+
+    /// Creates a `Fixed` from its raw bits.
+    #[inline]
+    pub const fn from_bits(bits: i64) -> Self {
+        Fixed(bits)
+    }
+
+    /// Gets the raw bits.
+    #[inline]
+    pub fn to_bits(self) -> i64 {
+        self.0
+    }
+
+    /// Creates a `Fixed` from a float, which is rounded to the nearest representable
+    /// value. It should only be used with constants or inputs that are the same on every
+    /// peer, as the float itself might already differ.
+    #[inline]
+    pub fn from_f32(v: f32) -> Self {
+        Fixed((f64::from(v) * f64::from(1 << FRAC_BITS)).round() as i64)
+    }
+
+    /// Converts into a float, e.g. for rendering.
+    #[inline]
+    pub fn to_f32(self) -> f32 {
+        (self.0 as f64 / f64::from(1 << FRAC_BITS)) as f32
+    }
+
+    /// Creates a `Fixed` from the fraction `num / den`.
+    #[inline]
+    pub fn from_ratio(num: i32, den: i32) -> Self {
+        Fixed::from(num) / Fixed::from(den)
+    }
+
+    /// Returns the largest integer less than or equal to this number.
+    #[inline]
+    pub fn floor(self) -> Self {
+        Fixed(self.0 & !(Self::ONE.0 - 1))
+    }
+
+    /// Returns the smallest integer greater than or equal to this number.
+    #[inline]
+    pub fn ceil(self) -> Self {
+        (self + Fixed(Self::ONE.0 - 1)).floor()
+    }
+
+    /// Returns the nearest integer, rounding half-way cases away from zero.
+    #[inline]
+    pub fn round(self) -> Self {
+        if self.0 >= 0 {
+            (self + Self::HALF).floor()
+        } else {
+            -(-self + Self::HALF).floor()
+        }
+    }
+
+    /// Returns the fractional part, which is always positive.
+    #[inline]
+    pub fn fract(self) -> Self {
+        self - self.floor()
+    }
+
+    /// Returns the integer part, truncated towards negative infinity.
+    #[inline]
+    pub fn to_int(self) -> i64 {
+        self.0 >> FRAC_BITS
+    }
+
+    #[inline]
+    pub fn abs(self) -> Self {
+        Fixed(self.0.abs())
+    }
+
+    #[inline]
+    pub fn signum(self) -> Self {
+        match self.0.cmp(&0) {
+            Ordering::Less => -Self::ONE,
+            Ordering::Equal => Self::ZERO,
+            Ordering::Greater => Self::ONE,
+        }
+    }
+
+    #[inline]
+    pub fn min(self, rhs: Self) -> Self {
+        std::cmp::min(self, rhs)
+    }
+
+    #[inline]
+    pub fn max(self, rhs: Self) -> Self {
+        std::cmp::max(self, rhs)
+    }
+
+    #[inline]
+    pub fn clamp(self, min: Self, max: Self) -> Self {
+        self.max(min).min(max)
+    }
+
+    /// Linear interpolates between `self` and `rhs`.
+    #[inline]
+    pub fn lerp(self, rhs: Self, t: Self) -> Self {
+        self + (rhs - self) * t
+    }
+
+    /// Returns the square root, or zero for negative numbers.
+    pub fn sqrt(self) -> Self {
+        if self.0 <= 0 {
+            return Self::ZERO;
+        }
+
+        Fixed(isqrt((self.0 as u128) << FRAC_BITS) as i64)
+    }
+
+    /// Returns the sine of an angle in radians.
+    pub fn sin(self) -> Self {
+        // Reduces the angle into [-PI, PI], and then into [-PI/2, PI/2] with
+        // sin(x) = sin(PI - x).
+        let mut x = (self + Self::PI) % Self::TWO_PI;
+        if x.0 < 0 {
+            x += Self::TWO_PI;
+        }
+
+        x -= Self::PI;
+        if x > Self::FRAC_PI_2 {
+            x = Self::PI - x;
+        } else if x < -Self::FRAC_PI_2 {
+            x = -Self::PI - x;
+        }
+
+        // Taylor series, which converges quickly enough in [-PI/2, PI/2].
+        let x2 = x * x;
+        let mut term = x;
+        let mut sum = x;
+        for i in 1..8 {
+            term = -term * x2 / Fixed::from((2 * i) * (2 * i + 1));
+            sum += term;
+        }
+
+        sum.clamp(-Self::ONE, Self::ONE)
+    }
+
+    /// Returns the cosine of an angle in radians.
+    #[inline]
+    pub fn cos(self) -> Self {
+        (self + Self::FRAC_PI_2).sin()
+    }
+
+    /// Returns the angle in radians of point `(x, self)`, which is in `[-PI, PI]`.
+    pub fn atan2(self, x: Self) -> Self {
+        let y = self;
+        if x.0 == 0 && y.0 == 0 {
+            return Self::ZERO;
+        }
+
+        // atan(z) for z in [0, 1] with a rational approximation, and the rest of
+        // octants are mapped into it.
+        let (ax, ay) = (x.abs(), y.abs());
+        let z = if ax >= ay { ay / ax } else { ax / ay };
+        let mut a = Self::FRAC_PI_4 * z - z * (z - Self::ONE) * (Fixed(16_037) + Fixed(4_345) * z);
+
+        if ay > ax {
+            a = Self::FRAC_PI_2 - a;
+        }
+
+        if x.0 < 0 {
+            a = Self::PI - a;
+        }
+
+        if y.0 < 0 {
+            -a
+        } else {
+            a
+        }
+    }
+}
+
+fn isqrt(v: u128) -> u128 {
+    let mut rem = v;
+    let mut root = 0;
+    let mut bit = 1u128 << 126;
+
+    while bit > rem {
+        bit >>= 2;
+    }
+
+    while bit != 0 {
+        if rem >= root + bit {
+            rem -= root + bit;
+            root = (root >> 1) + bit;
+        } else {
+            root >>= 1;
+        }
+
+        bit >>= 2;
+    }
+
+    root
+}
+
+impl From<i32> for Fixed {
+    #[inline]
+    fn from(v: i32) -> Self {
+        Fixed(i64::from(v) << FRAC_BITS)
+    }
+}
+
+impl fmt::Debug for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Fixed({})", self.to_f32())
+    }
+}
+
+impl fmt::Display for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_f32())
+    }
+}
+
+impl Add for Fixed {
+    type Output = Fixed;
+
+    #[inline]
+    fn add(self, rhs: Fixed) -> Fixed {
+        Fixed(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Fixed;
+
+    #[inline]
+    fn sub(self, rhs: Fixed) -> Fixed {
+        Fixed(self.0 - rhs.0)
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Fixed;
+
+    #[inline]
+    fn mul(self, rhs: Fixed) -> Fixed {
+        Fixed(((i128::from(self.0) * i128::from(rhs.0)) >> FRAC_BITS) as i64)
+    }
+}
+
+impl Div for Fixed {
+    type Output = Fixed;
+
+    /// Panics if `rhs` is zero.
+    #[inline]
+    fn div(self, rhs: Fixed) -> Fixed {
+        Fixed(((i128::from(self.0) << FRAC_BITS) / i128::from(rhs.0)) as i64)
+    }
+}
+
+impl Rem for Fixed {
+    type Output = Fixed;
+
+    #[inline]
+    fn rem(self, rhs: Fixed) -> Fixed {
+        Fixed(self.0 % rhs.0)
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Fixed;
+
+    #[inline]
+    fn neg(self) -> Fixed {
+        Fixed(-self.0)
+    }
+}
+
+macro_rules! impl_assign_ops {
+    ($ty: ty, $rhs: ty, $($trait: ident, $func: ident, $op: tt;)*) => {
+        $(
+            impl $trait<$rhs> for $ty {
+                #[inline]
+                fn $func(&mut self, rhs: $rhs) {
+                    *self = *self $op rhs;
+                }
+            }
+        )*
+    };
+}
+
+impl_assign_ops!(Fixed, Fixed,
+    AddAssign, add_assign, +;
+    SubAssign, sub_assign, -;
+    MulAssign, mul_assign, *;
+    DivAssign, div_assign, /;
+);
+
+/// A 2D vector of `Fixed`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FixedVector2 {
+    pub x: Fixed,
+    pub y: Fixed,
+}
+
+impl FixedVector2 {
+    #[inline]
+    pub fn new(x: Fixed, y: Fixed) -> Self {
+        FixedVector2 { x, y }
+    }
+
+    #[inline]
+    pub fn zero() -> Self {
+        FixedVector2::default()
+    }
+
+    #[inline]
+    pub fn dot(self, rhs: Self) -> Fixed {
+        self.x * rhs.x + self.y * rhs.y
+    }
+
+    /// Returns the z component of the cross product of `self` and `rhs` on the xy plane.
+    #[inline]
+    pub fn perp_dot(self, rhs: Self) -> Fixed {
+        self.x * rhs.y - self.y * rhs.x
+    }
+
+    #[inline]
+    pub fn magnitude2(self) -> Fixed {
+        self.dot(self)
+    }
+
+    #[inline]
+    pub fn magnitude(self) -> Fixed {
+        self.magnitude2().sqrt()
+    }
+
+    /// Returns the vector with the same direction and a length of one, or zero if the
+    /// vector is zero.
+    pub fn normalize(self) -> Self {
+        let len = self.magnitude();
+        if len == Fixed::ZERO {
+            return self;
+        }
+
+        FixedVector2::new(self.x / len, self.y / len)
+    }
+
+    /// Rotates the vector counter-clockwise by an angle in radians.
+    pub fn rotate(self, angle: Fixed) -> Self {
+        let (sin, cos) = (angle.sin(), angle.cos());
+        FixedVector2::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
+
+    /// Converts into a float vector, e.g. for rendering.
+    #[inline]
+    pub fn to_f32(self) -> Vector2<f32> {
+        Vector2::new(self.x.to_f32(), self.y.to_f32())
+    }
+}
+
+/// A 3D vector of `Fixed`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FixedVector3 {
+    pub x: Fixed,
+    pub y: Fixed,
+    pub z: Fixed,
+}
+
+impl FixedVector3 {
+    #[inline]
+    pub fn new(x: Fixed, y: Fixed, z: Fixed) -> Self {
+        FixedVector3 { x, y, z }
+    }
+
+    #[inline]
+    pub fn zero() -> Self {
+        FixedVector3::default()
+    }
+
+    #[inline]
+    pub fn dot(self, rhs: Self) -> Fixed {
+        self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
+    }
+
+    #[inline]
+    pub fn cross(self, rhs: Self) -> Self {
+        FixedVector3::new(
+            self.y * rhs.z - self.z * rhs.y,
+            self.z * rhs.x - self.x * rhs.z,
+            self.x * rhs.y - self.y * rhs.x,
+        )
+    }
+
+    #[inline]
+    pub fn magnitude2(self) -> Fixed {
+        self.dot(self)
+    }
+
+    #[inline]
+    pub fn magnitude(self) -> Fixed {
+        self.magnitude2().sqrt()
+    }
+
+    /// Returns the vector with the same direction and a length of one, or zero if the
+    /// vector is zero.
+    pub fn normalize(self) -> Self {
+        let len = self.magnitude();
+        if len == Fixed::ZERO {
+            return self;
+        }
+
+        FixedVector3::new(self.x / len, self.y / len, self.z / len)
+    }
+
+    /// Rotates the vector around the y axis by an angle in radians, which is the usual
+    /// heading of characters.
+    pub fn rotate_y(self, angle: Fixed) -> Self {
+        let (sin, cos) = (angle.sin(), angle.cos());
+        FixedVector3::new(
+            self.x * cos + self.z * sin,
+            self.y,
+            self.z * cos - self.x * sin,
+        )
+    }
+
+    /// Converts into a float vector, e.g. for rendering.
+    #[inline]
+    pub fn to_f32(self) -> Vector3<f32> {
+        Vector3::new(self.x.to_f32(), self.y.to_f32(), self.z.to_f32())
+    }
+}
+
+macro_rules! impl_vector_ops {
+    ($ty: ident, $($field: ident),*) => {
+        impl Add for $ty {
+            type Output = $ty;
+
+            #[inline]
+            fn add(self, rhs: $ty) -> $ty {
+                $ty { $($field: self.$field + rhs.$field),* }
+            }
+        }
+
+        impl Sub for $ty {
+            type Output = $ty;
+
+            #[inline]
+            fn sub(self, rhs: $ty) -> $ty {
+                $ty { $($field: self.$field - rhs.$field),* }
+            }
+        }
+
+        impl Mul<Fixed> for $ty {
+            type Output = $ty;
+
+            #[inline]
+            fn mul(self, rhs: Fixed) -> $ty {
+                $ty { $($field: self.$field * rhs),* }
+            }
+        }
+
+        impl Div<Fixed> for $ty {
+            type Output = $ty;
+
+            #[inline]
+            fn div(self, rhs: Fixed) -> $ty {
+                $ty { $($field: self.$field / rhs),* }
+            }
+        }
+
+        impl Neg for $ty {
+            type Output = $ty;
+
+            #[inline]
+            fn neg(self) -> $ty {
+                $ty { $($field: -self.$field),* }
+            }
+        }
+
+        impl_assign_ops!($ty, $ty,
+            AddAssign, add_assign, +;
+            SubAssign, sub_assign, -;
+        );
+
+        impl_assign_ops!($ty, Fixed,
+            MulAssign, mul_assign, *;
+            DivAssign, div_assign, /;
+        );
+    };
+}
+
+impl_vector_ops!(FixedVector2, x, y);
+impl_vector_ops!(FixedVector3, x, y, z);
+
+/// A rotation quaternion of `Fixed`, with the scalar part `s` and the vector part `v`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FixedQuaternion {
+    pub s: Fixed,
+    pub v: FixedVector3,
+}
+
+impl Default for FixedQuaternion {
+    fn default() -> Self {
+        FixedQuaternion::one()
+    }
+}
+
+impl FixedQuaternion {
+    #[inline]
+    pub fn new(s: Fixed, v: FixedVector3) -> Self {
+        FixedQuaternion { s, v }
+    }
+
+    /// Creates the identity rotation.
+    #[inline]
+    pub fn one() -> Self {
+        FixedQuaternion::new(Fixed::ONE, FixedVector3::zero())
+    }
+
+    /// Creates a rotation around the normalized `axis` by an angle in radians.
+    pub fn from_axis_angle(axis: FixedVector3, angle: Fixed) -> Self {
+        let half = angle * Fixed::HALF;
+        FixedQuaternion::new(half.cos(), axis * half.sin())
+    }
+
+    /// Creates a rotation around the y axis by an angle in radians, which is the same as
+    /// `FixedVector3::rotate_y`.
+    #[inline]
+    pub fn from_angle_y(angle: Fixed) -> Self {
+        let axis = FixedVector3::new(Fixed::ZERO, Fixed::ONE, Fixed::ZERO);
+        FixedQuaternion::from_axis_angle(axis, angle)
+    }
+
+    #[inline]
+    pub fn magnitude(self) -> Fixed {
+        (self.s * self.s + self.v.magnitude2()).sqrt()
+    }
+
+    /// Returns the quaternion with a length of one. The rounding errors of fixed-point
+    /// products accumulate when rotations are combined every tick, so they should be
+    /// normalized once in a while.
+    pub fn normalize(self) -> Self {
+        let len = self.magnitude();
+        if len == Fixed::ZERO {
+            return FixedQuaternion::one();
+        }
+
+        FixedQuaternion::new(self.s / len, self.v / len)
+    }
+
+    /// Returns the inverse rotation, which is the conjugate of the normalized quaternion.
+    #[inline]
+    pub fn invert(self) -> Self {
+        FixedQuaternion::new(self.s, -self.v)
+    }
+
+    /// Rotates a vector.
+    #[inline]
+    pub fn rotate_vector(self, v: FixedVector3) -> FixedVector3 {
+        let tmp = self.v.cross(v) + v * self.s;
+        self.v.cross(tmp) * Fixed::from(2) + v
+    }
+
+    /// Converts into a float quaternion, e.g. for rendering.
+    #[inline]
+    pub fn to_f32(self) -> Quaternion<f32> {
+        Quaternion::from_sv(self.s.to_f32(), self.v.to_f32())
+    }
+}
+
+impl Mul for FixedQuaternion {
+    type Output = FixedQuaternion;
+
+    fn mul(self, rhs: FixedQuaternion) -> FixedQuaternion {
+        FixedQuaternion::new(
+            self.s * rhs.s - self.v.dot(rhs.v),
+            rhs.v * self.s + self.v * rhs.s + self.v.cross(rhs.v),
+        )
+    }
+}
+
+impl Mul<FixedVector3> for FixedQuaternion {
+    type Output = FixedVector3;
+
+    #[inline]
+    fn mul(self, rhs: FixedVector3) -> FixedVector3 {
+        self.rotate_vector(rhs)
+    }
+}
+
+/// A column-major 3x3 matrix of `Fixed`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FixedMatrix3 {
+    pub x: FixedVector3,
+    pub y: FixedVector3,
+    pub z: FixedVector3,
+}
+
+impl Default for FixedMatrix3 {
+    fn default() -> Self {
+        FixedMatrix3::identity()
+    }
+}
+
+impl FixedMatrix3 {
+    /// Creates a matrix from its columns.
+    #[inline]
+    pub fn from_cols(x: FixedVector3, y: FixedVector3, z: FixedVector3) -> Self {
+        FixedMatrix3 { x, y, z }
+    }
+
+    #[inline]
+    pub fn identity() -> Self {
+        FixedMatrix3::from_scale(Fixed::ONE)
+    }
+
+    /// Creates a uniform scale matrix.
+    #[inline]
+    pub fn from_scale(scale: Fixed) -> Self {
+        FixedMatrix3::from_cols(
+            FixedVector3::new(scale, Fixed::ZERO, Fixed::ZERO),
+            FixedVector3::new(Fixed::ZERO, scale, Fixed::ZERO),
+            FixedVector3::new(Fixed::ZERO, Fixed::ZERO, scale),
+        )
+    }
+
+    #[inline]
+    pub fn transpose(self) -> Self {
+        FixedMatrix3::from_cols(
+            FixedVector3::new(self.x.x, self.y.x, self.z.x),
+            FixedVector3::new(self.x.y, self.y.y, self.z.y),
+            FixedVector3::new(self.x.z, self.y.z, self.z.z),
+        )
+    }
+
+    /// Converts into a float matrix, e.g. for rendering.
+    #[inline]
+    pub fn to_f32(self) -> Matrix3<f32> {
+        Matrix3::from_cols(self.x.to_f32(), self.y.to_f32(), self.z.to_f32())
+    }
+}
+
+impl From<FixedQuaternion> for FixedMatrix3 {
+    fn from(q: FixedQuaternion) -> Self {
+        let two = Fixed::from(2);
+        let (x2, y2, z2) = (q.v.x * two, q.v.y * two, q.v.z * two);
+
+        let (xx2, xy2, xz2) = (x2 * q.v.x, x2 * q.v.y, x2 * q.v.z);
+        let (yy2, yz2, zz2) = (y2 * q.v.y, y2 * q.v.z, z2 * q.v.z);
+        let (sx2, sy2, sz2) = (x2 * q.s, y2 * q.s, z2 * q.s);
+
+        FixedMatrix3::from_cols(
+            FixedVector3::new(Fixed::ONE - yy2 - zz2, xy2 + sz2, xz2 - sy2),
+            FixedVector3::new(xy2 - sz2, Fixed::ONE - xx2 - zz2, yz2 + sx2),
+            FixedVector3::new(xz2 + sy2, yz2 - sx2, Fixed::ONE - xx2 - yy2),
+        )
+    }
+}
+
+impl Mul<FixedVector3> for FixedMatrix3 {
+    type Output = FixedVector3;
+
+    #[inline]
+    fn mul(self, rhs: FixedVector3) -> FixedVector3 {
+        self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
+    }
+}
+
+impl Mul for FixedMatrix3 {
+    type Output = FixedMatrix3;
+
+    #[inline]
+    fn mul(self, rhs: FixedMatrix3) -> FixedMatrix3 {
+        FixedMatrix3::from_cols(self * rhs.x, self * rhs.y, self * rhs.z)
+    }
+}
+
+/// The position, rotation and uniform scale of a simulated object, which mirrors the
+/// `Transform` of the world module. It's converted into floats with `to_f32` once the
+/// tick is done.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FixedTransform {
+    pub scale: Fixed,
+    pub position: FixedVector3,
+    pub rotation: FixedQuaternion,
+}
+
+impl Default for FixedTransform {
+    fn default() -> Self {
+        FixedTransform {
+            scale: Fixed::ONE,
+            position: FixedVector3::zero(),
+            rotation: FixedQuaternion::one(),
+        }
+    }
+}
+
+impl Mul for FixedTransform {
+    type Output = FixedTransform;
+
+    fn mul(self, rhs: FixedTransform) -> FixedTransform {
+        FixedTransform {
+            position: self.rotation * (rhs.position * self.scale) + self.position,
+            rotation: self.rotation * rhs.rotation,
+            scale: self.scale * rhs.scale,
+        }
+    }
+}
+
+impl FixedTransform {
+    /// Returns a transform that "un-does" this one, or `None` if the scale is not positive.
+    pub fn inverse(self) -> Option<Self> {
+        if self.scale <= Fixed::ZERO {
+            return None;
+        }
+
+        let s = Fixed::ONE / self.scale;
+        let r = self.rotation.invert();
+
+        Some(FixedTransform {
+            scale: s,
+            rotation: r,
+            position: r.rotate_vector(self.position) * -s,
+        })
+    }
+
+    /// Transforms direction from local space to transform's space, which is not affected
+    /// by scale or position.
+    #[inline]
+    pub fn transform_direction(&self, v: FixedVector3) -> FixedVector3 {
+        self.rotation * v
+    }
+
+    /// Transforms vector from local space to transform's space, which is not affected by
+    /// position.
+    #[inline]
+    pub fn transform_vector(&self, v: FixedVector3) -> FixedVector3 {
+        self.rotation * (v * self.scale)
+    }
+
+    /// Transforms points from local space to transform's space.
+    #[inline]
+    pub fn transform_point(&self, v: FixedVector3) -> FixedVector3 {
+        self.rotation * (v * self.scale) + self.position
+    }
+
+    /// Returns the rotation and scale part of the matrix representation.
+    #[inline]
+    pub fn matrix3(&self) -> FixedMatrix3 {
+        FixedMatrix3::from(self.rotation) * FixedMatrix3::from_scale(self.scale)
+    }
+
+    /// Converts into the float matrix representation, e.g. for rendering.
+    pub fn to_f32(self) -> Matrix4<f32> {
+        // M = T * R * S
+        let mut m: Matrix4<f32> = self.matrix3().to_f32().into();
+        m.w = self.position.to_f32().extend(1.0);
+        m
+    }
+}
@@ -2,12 +2,16 @@
 
 pub mod aabb;
 pub mod color;
+pub mod fixed;
 pub mod frustum;
 pub mod plane;
 
 pub mod prelude {
     pub use super::aabb::{Aabb2, Aabb3};
     pub use super::color::Color;
+    pub use super::fixed::{
+        Fixed, FixedMatrix3, FixedQuaternion, FixedTransform, FixedVector2, FixedVector3,
+    };
     pub use super::frustum::{Frustum, FrustumPoints, Projection};
     pub use super::plane::{Plane, PlaneBound, PlaneRelation};
 
@@ -15,4 +19,5 @@ pub mod prelude {
     pub use cgmath::prelude::{One, Zero};
     pub use cgmath::{Angle, Deg, Euler, Quaternion, Rad, Rotation};
     pub use cgmath::{Matrix, Matrix2, Matrix3, Matrix4, SquareMatrix, Vector2, Vector3, Vector4};
+    pub use cgmath::{Point2, Point3};
 }
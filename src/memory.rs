@@ -0,0 +1,286 @@
+//! Tracks the memory allocated by each subsystem, to find out which one keeps growing over
+//! long sessions.
+//!
+//! `TrackingAllocator` wraps the system allocator, and attributes every allocation to the
+//! innermost `memory::scope` of the thread it's made on. The live and peak bytes of scopes,
+//! along with their number of allocations, could be inspected with `memory::report`, and a
+//! warning is logged once a scope exceeds its soft budget. The hitch reports include the
+//! allocations made by each scope during the slow frames.
+//!
+//! The engine opens the scopes `video`, `res` and `sched` around its own work, and so do the
+//! `world` and `audio` modules with their `alloc-tracking` features. The workers of `sched`
+//! are always inside the `sched` scope, so the jobs which are not scoped on their own are
+//! attributed to it.
+//!
+//! This module is only available with the `alloc-tracking` feature, and it's up to the
+//! application to register the allocator.
+//!
+//! ```rust,ignore
+//! #[global_allocator]
+//! static ALLOCATOR: crayon::memory::TrackingAllocator = crayon::memory::TrackingAllocator;
+//!
+//! memory::set_budget("world", 64 * 1024 * 1024);
+//!
+//! {
+//!     let _scope = memory::scope("world");
+//!     scene.load(prefab)?;
+//! }
+//!
+//! for v in memory::report() {
+//!     info!("{}: {} bytes, {} bytes at peak.", v.name, v.live, v.peak);
+//! }
+//! ```
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering};
+
+/// The maximum number of scopes, including the untagged one.
+pub const MAX_SCOPES: usize = 32;
+
+const UNTAGGED: &str = "untagged";
+
+/// The allocator which tracks the allocations by scopes.
+pub struct TrackingAllocator;
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        allocate(layout, |v| System.alloc(v))
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        allocate(layout, |v| System.alloc_zeroed(v))
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let offset = header(&layout);
+        let tag = std::ptr::read_unaligned(ptr.sub(TAG_SIZE) as *const usize);
+        SLOTS[tag].live.fetch_sub(layout.size(), Ordering::Relaxed);
+
+        let layout = Layout::from_size_align_unchecked(layout.size() + offset, layout.align());
+        System.dealloc(ptr.sub(offset), layout);
+    }
+}
+
+const TAG_SIZE: usize = std::mem::size_of::<usize>();
+
+/// The tag is stored right before the returned memory, in a header which keeps the alignment.
+fn header(layout: &Layout) -> usize {
+    std::cmp::max(layout.align(), TAG_SIZE)
+}
+
+unsafe fn allocate<F: FnOnce(Layout) -> *mut u8>(layout: Layout, func: F) -> *mut u8 {
+    let offset = header(&layout);
+    let ptr = match Layout::from_size_align(layout.size() + offset, layout.align()) {
+        Ok(v) => func(v),
+        Err(_) => return std::ptr::null_mut(),
+    };
+
+    if ptr.is_null() {
+        return ptr;
+    }
+
+    let tag = CURRENT.try_with(|v| v.get()).unwrap_or(0);
+    let ptr = ptr.add(offset);
+    std::ptr::write_unaligned(ptr.sub(TAG_SIZE) as *mut usize, tag);
+    SLOTS[tag].grow(layout.size());
+    ptr
+}
+
+// The slot is initialised as constant, so accessing it never allocates, which would
+// re-enter the allocator. `try_with` fails only while the thread is being torn down, in
+// which case the allocation is untagged.
+thread_local!(static CURRENT: Cell<usize> = const { Cell::new(0) });
+
+struct Slot {
+    name: AtomicPtr<u8>,
+    len: AtomicUsize,
+    live: AtomicUsize,
+    peak: AtomicUsize,
+    allocs: AtomicUsize,
+    budget: AtomicUsize,
+    exceeded: AtomicBool,
+}
+
+impl Slot {
+    const fn new() -> Self {
+        Slot {
+            name: AtomicPtr::new(std::ptr::null_mut()),
+            len: AtomicUsize::new(0),
+            live: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+            allocs: AtomicUsize::new(0),
+            budget: AtomicUsize::new(0),
+            exceeded: AtomicBool::new(false),
+        }
+    }
+
+    fn grow(&self, size: usize) {
+        self.allocs.fetch_add(1, Ordering::Relaxed);
+        let live = self.live.fetch_add(size, Ordering::Relaxed) + size;
+        let mut peak = self.peak.load(Ordering::Relaxed);
+        while live > peak {
+            match self
+                .peak
+                .compare_exchange_weak(peak, live, Ordering::Relaxed, Ordering::Relaxed)
+            {
+                Ok(_) => break,
+                Err(v) => peak = v,
+            }
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        let ptr = self.name.load(Ordering::Acquire);
+        if ptr.is_null() {
+            return UNTAGGED;
+        }
+
+        unsafe {
+            let bytes = std::slice::from_raw_parts(ptr, self.len.load(Ordering::Relaxed));
+            std::str::from_utf8_unchecked(bytes)
+        }
+    }
+}
+
+macro_rules! slots {
+    (@slot $i: expr) => {
+        Slot::new()
+    };
+    ($($i: expr),*) => {
+        [$(slots!(@slot $i)),*]
+    };
+}
+
+static SLOTS: [Slot; MAX_SCOPES] = slots!(
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
+    26, 27, 28, 29, 30, 31
+);
+
+static NUM_SLOTS: AtomicUsize = AtomicUsize::new(1);
+static REGISTERING: AtomicBool = AtomicBool::new(false);
+
+/// Finds the slot of scope, or claims a new one. All the scopes exceeding `MAX_SCOPES` are
+/// tracked as untagged.
+fn register(name: &'static str) -> usize {
+    let num = NUM_SLOTS.load(Ordering::Acquire);
+    if let Some(i) = (1..num).find(|&i| SLOTS[i].name() == name) {
+        return i;
+    }
+
+    while REGISTERING
+        .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+        .is_err()
+    {
+        std::thread::yield_now();
+    }
+
+    // Checks again, as the scope might be registered by other threads in the meantime.
+    let num = NUM_SLOTS.load(Ordering::Acquire);
+    let index = match (1..num).find(|&i| SLOTS[i].name() == name) {
+        Some(i) => i,
+        None if num < MAX_SCOPES => {
+            SLOTS[num].len.store(name.len(), Ordering::Relaxed);
+            SLOTS[num]
+                .name
+                .store(name.as_ptr() as *mut u8, Ordering::Release);
+            NUM_SLOTS.store(num + 1, Ordering::Release);
+            num
+        }
+        None => 0,
+    };
+
+    REGISTERING.store(false, Ordering::Release);
+
+    if index == 0 {
+        warn!(
+            "[Memory] Scope {} is tracked as untagged, since there are too many scopes.",
+            name
+        );
+    }
+
+    index
+}
+
+/// A scope which attributes the allocations of current thread to it until it's dropped.
+pub struct MemoryScope {
+    prev: usize,
+}
+
+impl Drop for MemoryScope {
+    fn drop(&mut self) {
+        let prev = self.prev;
+        let _ = CURRENT.try_with(|v| v.set(prev));
+    }
+}
+
+/// The memory usage of a scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryUsage {
+    pub name: &'static str,
+    /// The bytes allocated and not freed yet.
+    pub live: usize,
+    /// The maximum of live bytes.
+    pub peak: usize,
+    /// The number of allocations made since the allocator is registered.
+    pub allocs: usize,
+    pub budget: Option<usize>,
+}
+
+/// Creates a scope, which attributes the allocations on current thread to `name` until
+/// it's dropped. Scopes could be nested.
+pub fn scope(name: &'static str) -> MemoryScope {
+    let tag = register(name);
+    let prev = CURRENT.try_with(|v| v.replace(tag)).unwrap_or(0);
+    MemoryScope { prev }
+}
+
+/// Sets the soft budget of scope in bytes. A warning is logged once its live bytes exceed
+/// the budget. `None` removes the budget.
+pub fn set_budget<T: Into<Option<usize>>>(name: &'static str, budget: T) {
+    let slot = &SLOTS[register(name)];
+    slot.budget
+        .store(budget.into().unwrap_or(0), Ordering::Relaxed);
+    slot.exceeded.store(false, Ordering::Relaxed);
+}
+
+/// Gets the memory usage of all the scopes, starting with the untagged allocations.
+pub fn report() -> Vec<MemoryUsage> {
+    (0..NUM_SLOTS.load(Ordering::Acquire))
+        .map(|i| {
+            let slot = &SLOTS[i];
+            let budget = slot.budget.load(Ordering::Relaxed);
+
+            MemoryUsage {
+                name: slot.name(),
+                live: slot.live.load(Ordering::Relaxed),
+                peak: slot.peak.load(Ordering::Relaxed),
+                allocs: slot.allocs.load(Ordering::Relaxed),
+                budget: if budget > 0 { Some(budget) } else { None },
+            }
+        })
+        .collect()
+}
+
+/// Logs the scopes which exceed their budgets. It's called every frame by the engine, and
+/// only warns once until the scope gets back under its budget.
+pub(crate) fn check_budgets() {
+    for slot in &SLOTS[..NUM_SLOTS.load(Ordering::Acquire)] {
+        let budget = slot.budget.load(Ordering::Relaxed);
+        if budget == 0 {
+            continue;
+        }
+
+        let live = slot.live.load(Ordering::Relaxed);
+        if live <= budget {
+            slot.exceeded.store(false, Ordering::Relaxed);
+        } else if !slot.exceeded.swap(true, Ordering::Relaxed) {
+            warn!(
+                "[Memory] Scope {} exceeds its budget with {} of {} bytes.",
+                slot.name(),
+                live,
+                budget
+            );
+        }
+    }
+}
@@ -1,10 +1,16 @@
+pub use crate::achievements::prelude::*;
 pub use crate::application::prelude::*;
 pub use crate::input::prelude::*;
 pub use crate::math::prelude::*;
+#[cfg(all(feature = "discord", not(target_arch = "wasm32")))]
+pub use crate::presence::prelude::*;
 pub use crate::res::prelude::*;
 pub use crate::sched::prelude::*;
 pub use crate::video::prelude::*;
 pub use crate::window::prelude::*;
-pub use crate::{application, input, main, math, res, sched, video, window};
+pub use crate::{achievements, application, input, main, math, res, sched, video, window};
+
+#[cfg(feature = "alloc-tracking")]
+pub use crate::memory;
 
 pub use crate::errors::{Error as CrError, Result as CrResult};
@@ -0,0 +1,184 @@
+//! The local IPC protocol of Discord clients, which exchanges frames of an opcode, the
+//! length of payload and a JSON payload over a unix socket or a named pipe.
+
+use std::io::{Read, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use byteorder::{ByteOrder, LittleEndian};
+use serde_json::{json, Value};
+
+use crate::errors::*;
+
+use super::Activity;
+
+const OP_HANDSHAKE: u32 = 0;
+const OP_FRAME: u32 = 1;
+const OP_CLOSE: u32 = 2;
+const OP_PING: u32 = 3;
+const OP_PONG: u32 = 4;
+
+const MAX_PAYLOAD: usize = 64 * 1024;
+
+/// A client which stops responding is treated as disconnected after it.
+const TIMEOUT_SECS: u64 = 5;
+
+#[cfg(unix)]
+type Stream = std::os::unix::net::UnixStream;
+#[cfg(windows)]
+type Stream = std::fs::File;
+
+pub struct Connection {
+    stream: Stream,
+    nonce: u64,
+}
+
+impl Connection {
+    /// Connects to the running Discord client, and completes the handshake. The sockets
+    /// are searched in `dir` instead of the temporary directories if it's specified.
+    pub fn open(client_id: &str, dir: Option<&Path>) -> Result<Self> {
+        let mut last_err = None;
+
+        for i in 0..10 {
+            match Self::connect(i, dir) {
+                Ok(stream) => {
+                    // A stale socket or another client might be listening on it, so the
+                    // next socket is tried if the handshake fails.
+                    let mut conn = Connection { stream, nonce: 0 };
+                    match conn.handshake(client_id) {
+                        Ok(_) => return Ok(conn),
+                        Err(err) => last_err = Some(err),
+                    }
+                }
+                Err(err) => last_err = Some(err),
+            }
+        }
+
+        match last_err {
+            Some(err) => Err(err),
+            None => bail!("Discord is not running."),
+        }
+    }
+
+    /// Sets or clears the activity of current process.
+    pub fn set_activity(&mut self, activity: Option<&Activity>) -> Result<()> {
+        self.nonce += 1;
+
+        let payload = json!({
+            "cmd": "SET_ACTIVITY",
+            "args": {
+                "pid": std::process::id(),
+                "activity": activity.map(|v| v.to_json()),
+            },
+            "nonce": self.nonce.to_string(),
+        });
+
+        self.write(OP_FRAME, &payload)?;
+        let response = self.read_frame()?;
+        if response["evt"] == "ERROR" {
+            bail!("Discord rejected the activity: {}", response["data"]);
+        }
+
+        Ok(())
+    }
+
+    /// Checks if the client is still responding.
+    pub fn ping(&mut self) -> Result<()> {
+        self.nonce += 1;
+        self.write(OP_PING, &json!({ "nonce": self.nonce.to_string() }))?;
+
+        loop {
+            let (op, payload) = self.read()?;
+            match op {
+                OP_PONG => return Ok(()),
+                OP_PING => self.write(OP_PONG, &payload)?,
+                OP_CLOSE => bail!("Discord closed the connection: {}", payload),
+                _ => {}
+            }
+        }
+    }
+
+    fn handshake(&mut self, client_id: &str) -> Result<()> {
+        self.write(OP_HANDSHAKE, &json!({ "v": 1, "client_id": client_id }))?;
+
+        let response = self.read_frame()?;
+        if response["evt"] != "READY" {
+            bail!("Discord refused the handshake: {}", response);
+        }
+
+        Ok(())
+    }
+
+    /// Reads the next frame, and answers the pings in between.
+    fn read_frame(&mut self) -> Result<Value> {
+        loop {
+            let (op, payload) = self.read()?;
+            match op {
+                OP_FRAME => return Ok(payload),
+                OP_PING => self.write(OP_PONG, &payload)?,
+                OP_CLOSE => bail!("Discord closed the connection: {}", payload),
+                _ => {}
+            }
+        }
+    }
+
+    fn write(&mut self, op: u32, payload: &Value) -> Result<()> {
+        let bytes = serde_json::to_vec(payload)?;
+
+        let mut header = [0; 8];
+        LittleEndian::write_u32(&mut header[0..4], op);
+        LittleEndian::write_u32(&mut header[4..8], bytes.len() as u32);
+
+        self.stream.write_all(&header)?;
+        self.stream.write_all(&bytes)?;
+        self.stream.flush()?;
+        Ok(())
+    }
+
+    fn read(&mut self) -> Result<(u32, Value)> {
+        let mut header = [0; 8];
+        self.stream.read_exact(&mut header)?;
+
+        let op = LittleEndian::read_u32(&header[0..4]);
+        let len = LittleEndian::read_u32(&header[4..8]) as usize;
+        if len > MAX_PAYLOAD {
+            bail!("Discord sent a frame with {} bytes.", len);
+        }
+
+        let mut bytes = vec![0; len];
+        self.stream.read_exact(&mut bytes)?;
+        Ok((op, serde_json::from_slice(&bytes)?))
+    }
+
+    #[cfg(unix)]
+    fn connect(index: usize, dir: Option<&Path>) -> Result<Stream> {
+        let dir = match dir {
+            Some(v) => v.to_owned(),
+            None => ["XDG_RUNTIME_DIR", "TMPDIR", "TMP", "TEMP"]
+                .iter()
+                .filter_map(|v| std::env::var_os(v))
+                .next()
+                .unwrap_or_else(|| "/tmp".into())
+                .into(),
+        };
+
+        let stream = Stream::connect(dir.join(format!("discord-ipc-{}", index)))?;
+        let timeout = Some(Duration::from_secs(TIMEOUT_SECS));
+        stream.set_read_timeout(timeout)?;
+        stream.set_write_timeout(timeout)?;
+        Ok(stream)
+    }
+
+    // The standard library could not set timeouts on pipes, so a client which stops
+    // responding blocks the worker thread until it's closed. Frames are never blocked.
+    #[cfg(windows)]
+    fn connect(index: usize, _: Option<&Path>) -> Result<Stream> {
+        let path = format!(r"\\.\pipe\discord-ipc-{}", index);
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(path)?;
+
+        Ok(file)
+    }
+}
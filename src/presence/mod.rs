@@ -0,0 +1,285 @@
+//! Discord Rich Presence, which shows what the player is doing in their Discord profile.
+//!
+//! `Presence` polls the activity provided by game every frame, and sends it to the local
+//! Discord client once it changes. The connection is managed by a background thread, so
+//! frames are never blocked by it. It reconnects automatically if Discord is started
+//! after the game or restarted, and updates are throttled to the rate limits of Discord.
+//!
+//! This module is only available with the `discord` feature.
+//!
+//! ```rust,ignore
+//! let presence = Presence::new("1234567890", move || {
+//!     Some(Activity {
+//!         state: Some("In a match".to_owned()),
+//!         party: Some((2, 4)),
+//!         start: Some(match_started),
+//!         ..Default::default()
+//!     })
+//! })?;
+//! ```
+
+mod ipc;
+
+pub mod prelude {
+    pub use super::{Activity, Presence};
+}
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde_json::{json, Map, Value};
+
+use crate::application::prelude::{LifecycleListener, LifecycleListenerHandle};
+use crate::errors::*;
+
+use self::ipc::Connection;
+
+/// Discord allows 5 updates every 20 seconds.
+const UPDATE_INTERVAL_SECS: u64 = 4;
+const RECONNECT_INTERVAL_SECS: u64 = 10;
+const TICK_MILLIS: u64 = 500;
+
+/// The activity shown in Discord.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Activity {
+    /// The current party status, e.g. "In a match".
+    pub state: Option<String>,
+    /// What the player is doing, e.g. "Ranked - Map 3".
+    pub details: Option<String>,
+    /// The current size and max size of party.
+    pub party: Option<(u32, u32)>,
+    /// Shows the elapsed time since `start`.
+    pub start: Option<SystemTime>,
+    /// Shows the remaining time until `end`.
+    pub end: Option<SystemTime>,
+    /// The key of large image uploaded to the Discord application.
+    pub large_image: Option<String>,
+    pub large_text: Option<String>,
+    /// The key of small image uploaded to the Discord application.
+    pub small_image: Option<String>,
+    pub small_text: Option<String>,
+}
+
+impl Activity {
+    fn to_json(&self) -> Value {
+        let mut activity = Map::new();
+        let mut assets = Map::new();
+        let mut timestamps = Map::new();
+
+        let insert = |map: &mut Map<String, Value>, key: &str, v: &Option<String>| {
+            if let Some(ref v) = *v {
+                map.insert(key.to_owned(), json!(v));
+            }
+        };
+
+        insert(&mut activity, "state", &self.state);
+        insert(&mut activity, "details", &self.details);
+        insert(&mut assets, "large_image", &self.large_image);
+        insert(&mut assets, "large_text", &self.large_text);
+        insert(&mut assets, "small_image", &self.small_image);
+        insert(&mut assets, "small_text", &self.small_text);
+
+        let secs = |v: SystemTime| v.duration_since(UNIX_EPOCH).map(|v| v.as_secs()).ok();
+        if let Some(v) = self.start.and_then(secs) {
+            timestamps.insert("start".to_owned(), json!(v));
+        }
+
+        if let Some(v) = self.end.and_then(secs) {
+            timestamps.insert("end".to_owned(), json!(v));
+        }
+
+        if let Some((size, max)) = self.party {
+            activity.insert("party".to_owned(), json!({ "size": [size, max] }));
+        }
+
+        if !assets.is_empty() {
+            activity.insert("assets".to_owned(), Value::Object(assets));
+        }
+
+        if !timestamps.is_empty() {
+            activity.insert("timestamps".to_owned(), Value::Object(timestamps));
+        }
+
+        Value::Object(activity)
+    }
+}
+
+/// Updates the rich presence of Discord with the activities provided by game.
+pub struct Presence {
+    lis: LifecycleListenerHandle,
+    connected: Arc<AtomicBool>,
+}
+
+impl Presence {
+    /// Creates the presence of the Discord application `client_id`. The `provider` is
+    /// invoked every frame, and the presence is cleared if it returns None.
+    pub fn new<T1, T2>(client_id: T1, provider: T2) -> Result<Self>
+    where
+        T1: Into<String>,
+        T2: FnMut() -> Option<Activity> + 'static,
+    {
+        Self::with_ipc_dir(client_id, None, provider)
+    }
+
+    /// Creates the presence which connects to the sockets in `dir`, e.g. the ones of
+    /// a fake Discord client in tests.
+    #[doc(hidden)]
+    pub fn with_ipc_dir<T1, T2>(client_id: T1, dir: Option<PathBuf>, provider: T2) -> Result<Self>
+    where
+        T1: Into<String>,
+        T2: FnMut() -> Option<Activity> + 'static,
+    {
+        let client_id = client_id.into();
+        if client_id.is_empty() || !client_id.chars().all(|v| v.is_ascii_digit()) {
+            bail!("{:?} is not a valid Discord client id.", client_id);
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let connected = Arc::new(AtomicBool::new(false));
+        let clone = connected.clone();
+
+        thread::Builder::new()
+            .name("Presence".to_string())
+            .spawn(move || worker(&client_id, dir.as_ref().map(|v| v.as_path()), &rx, &clone))?;
+
+        let lis = crate::application::attach(PresenceListener {
+            provider: Box::new(provider),
+            last: None,
+            tx,
+        });
+
+        Ok(Presence { lis, connected })
+    }
+
+    /// Checks if it's connected to the Discord client.
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for Presence {
+    fn drop(&mut self) {
+        // Drops the sender, which clears the presence and stops the worker.
+        crate::application::detach(self.lis);
+    }
+}
+
+struct PresenceListener {
+    provider: Box<dyn FnMut() -> Option<Activity>>,
+    last: Option<Option<Activity>>,
+    tx: Sender<Option<Activity>>,
+}
+
+impl LifecycleListener for PresenceListener {
+    fn on_post_update(&mut self) -> Result<()> {
+        let activity = (self.provider)();
+        if self.last.as_ref() != Some(&activity) {
+            self.last = Some(activity.clone());
+            let _ = self.tx.send(activity);
+        }
+
+        Ok(())
+    }
+}
+
+fn worker(
+    client_id: &str,
+    dir: Option<&Path>,
+    rx: &Receiver<Option<Activity>>,
+    connected: &AtomicBool,
+) {
+    let mut conn: Option<Connection> = None;
+    let mut current = None;
+    let mut dirty = false;
+    let mut last_sent: Option<Instant> = None;
+    let mut last_attempt: Option<Instant> = None;
+    let mut last_ping = Instant::now();
+
+    loop {
+        match rx.recv_timeout(Duration::from_millis(TICK_MILLIS)) {
+            Ok(v) => {
+                current = v;
+                dirty = true;
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => {
+                if let Some(mut conn) = conn {
+                    let _ = conn.set_activity(None);
+                }
+
+                connected.store(false, Ordering::Relaxed);
+                return;
+            }
+        }
+
+        // Only the latest activity matters.
+        while let Ok(v) = rx.try_recv() {
+            current = v;
+            dirty = true;
+        }
+
+        if conn.is_none() {
+            // There is nothing to show until the game provides an activity.
+            if !dirty && current.is_none() {
+                continue;
+            }
+
+            if last_attempt.map_or(false, |v| {
+                v.elapsed() < Duration::from_secs(RECONNECT_INTERVAL_SECS)
+            }) {
+                continue;
+            }
+
+            last_attempt = Some(Instant::now());
+            match Connection::open(client_id, dir) {
+                Ok(v) => {
+                    info!("[Presence] Connected to Discord.");
+                    conn = Some(v);
+                    connected.store(true, Ordering::Relaxed);
+
+                    // The activity of previous connection is gone if Discord was restarted.
+                    dirty = true;
+                    last_ping = Instant::now();
+                }
+                Err(_) => continue,
+            }
+        } else if !dirty {
+            // Nothing is sent while the activity stays the same, so the connection is
+            // checked regularly to notice a restarted Discord.
+            if last_ping.elapsed() >= Duration::from_secs(RECONNECT_INTERVAL_SECS) {
+                last_ping = Instant::now();
+                if let Err(err) = conn.as_mut().unwrap().ping() {
+                    warn!("[Presence] Lost connection to Discord. {}", err);
+                    conn = None;
+                    connected.store(false, Ordering::Relaxed);
+                }
+            }
+
+            continue;
+        }
+
+        if last_sent.map_or(false, |v| {
+            v.elapsed() < Duration::from_secs(UPDATE_INTERVAL_SECS)
+        }) {
+            continue;
+        }
+
+        let result = conn.as_mut().unwrap().set_activity(current.as_ref());
+        last_sent = Some(Instant::now());
+
+        match result {
+            Ok(_) => dirty = false,
+            Err(err) => {
+                // Keeps it dirty, so the activity is sent again after reconnection.
+                warn!("[Presence] Lost connection to Discord. {}", err);
+                conn = None;
+                connected.store(false, Ordering::Relaxed);
+            }
+        }
+    }
+}
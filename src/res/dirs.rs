@@ -0,0 +1,150 @@
+//! Platform-correct locations for the files written by games.
+//!
+//! Games should never write saves or settings next to the executable, which is usually
+//! read-only once installed through a store. `UserDirs` resolves the conventional
+//! per-user locations of every platform instead:
+//!
+//! - Linux and BSDs follow the XDG base directories, e.g. `$XDG_DATA_HOME/<app>/saves` and
+//!   `$XDG_CONFIG_HOME/<app>`, falling back to `~/.local/share` and `~/.config`.
+//! - macOS uses `~/Library/Application Support/<app>` for saves and screenshots, and
+//!   `~/Library/Preferences/<app>` and `~/Library/Logs/<app>` for config and logs.
+//! - Windows uses `%APPDATA%\<org>\<app>` for everything but logs, which are written into
+//!   `%LOCALAPPDATA%\<org>\<app>\logs` as they should not roam with the user profile.
+//!
+//! ```rust,ignore
+//! let dirs = UserDirs::new("shawnscode", "crayon")?;
+//! let path = dirs.create(UserDir::Saves)?.join("slot0.sav");
+//! ```
+
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::errors::*;
+
+/// The kinds of files written by games.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UserDir {
+    Saves,
+    Config,
+    Logs,
+    Screenshots,
+}
+
+/// The resolved per-user directories of a game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserDirs {
+    saves: PathBuf,
+    config: PathBuf,
+    logs: PathBuf,
+    screenshots: PathBuf,
+}
+
+impl UserDirs {
+    /// Resolves the directories of the application on current platform. The organization
+    /// name is only used on Windows, where it's conventional to group applications by
+    /// their publishers.
+    pub fn new<T1: AsRef<str>, T2: AsRef<str>>(org: T1, app: T2) -> Result<Self> {
+        Self::with_env(org, app, |name| env::var_os(name))
+    }
+
+    /// Resolves the directories like `new`, but reads the environment variables, e.g.
+    /// `HOME` and `XDG_DATA_HOME`, from `env` instead of the process.
+    pub fn with_env<T1, T2, F>(org: T1, app: T2, env: F) -> Result<Self>
+    where
+        T1: AsRef<str>,
+        T2: AsRef<str>,
+        F: Fn(&str) -> Option<OsString>,
+    {
+        let (org, app) = (org.as_ref(), app.as_ref());
+        if app.is_empty() {
+            bail!("The application name of UserDirs should not be empty.");
+        }
+
+        Self::platform(org, app, &env)
+    }
+
+    /// Creates directories under a custom root, e.g. the storage provided by Steam Cloud
+    /// or a console SDK.
+    pub fn with_root<T: AsRef<Path>>(root: T) -> Self {
+        let root = root.as_ref();
+        UserDirs {
+            saves: root.join("saves"),
+            config: root.join("config"),
+            logs: root.join("logs"),
+            screenshots: root.join("screenshots"),
+        }
+    }
+
+    /// Gets the path of a directory, which might not exist yet.
+    pub fn path(&self, dir: UserDir) -> &Path {
+        match dir {
+            UserDir::Saves => &self.saves,
+            UserDir::Config => &self.config,
+            UserDir::Logs => &self.logs,
+            UserDir::Screenshots => &self.screenshots,
+        }
+    }
+
+    /// Gets the path of a directory, and creates it along with its parents if missing.
+    pub fn create(&self, dir: UserDir) -> Result<PathBuf> {
+        let path = self.path(dir);
+        fs::create_dir_all(path)?;
+        Ok(path.to_owned())
+    }
+
+    #[cfg(target_os = "windows")]
+    fn platform(org: &str, app: &str, env: &dyn Fn(&str) -> Option<OsString>) -> Result<Self> {
+        let roaming = var(env, "APPDATA")?.join(org).join(app);
+        let local = var(env, "LOCALAPPDATA")?.join(org).join(app);
+
+        Ok(UserDirs {
+            saves: roaming.join("saves"),
+            config: roaming.join("config"),
+            logs: local.join("logs"),
+            screenshots: roaming.join("screenshots"),
+        })
+    }
+
+    #[cfg(target_os = "macos")]
+    fn platform(_: &str, app: &str, env: &dyn Fn(&str) -> Option<OsString>) -> Result<Self> {
+        let library = var(env, "HOME")?.join("Library");
+        let support = library.join("Application Support").join(app);
+
+        Ok(UserDirs {
+            saves: support.join("saves"),
+            config: library.join("Preferences").join(app),
+            logs: library.join("Logs").join(app),
+            screenshots: support.join("screenshots"),
+        })
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    fn platform(_: &str, app: &str, env: &dyn Fn(&str) -> Option<OsString>) -> Result<Self> {
+        let xdg = |name: &str, fallback: &str| -> Result<PathBuf> {
+            match env(name) {
+                Some(ref v) if Path::new(v).is_absolute() => Ok(PathBuf::from(v)),
+                _ => Ok(var(env, "HOME")?.join(fallback)),
+            }
+        };
+
+        let data = xdg("XDG_DATA_HOME", ".local/share")?.join(app);
+
+        Ok(UserDirs {
+            saves: data.join("saves"),
+            config: xdg("XDG_CONFIG_HOME", ".config")?.join(app),
+            logs: xdg("XDG_STATE_HOME", ".local/state")?
+                .join(app)
+                .join("logs"),
+            screenshots: data.join("screenshots"),
+        })
+    }
+}
+
+fn var(env: &dyn Fn(&str) -> Option<OsString>, name: &str) -> Result<PathBuf> {
+    match env(name) {
+        Some(ref v) if !v.is_empty() => Ok(PathBuf::from(v)),
+        _ => bail!("Could not resolve user directories without ${}.", name),
+    }
+}
@@ -0,0 +1,81 @@
+//! Frees the resources which are no longer referenced at the boundaries chosen by game, e.g.
+//! scene unloads.
+//!
+//! With deferred release enabled, a resource whose last handle is deleted is kept in its pool
+//! instead of being freed right away. If it's requested again before the next collection, the
+//! same resource is reused without loading it again, which is common for the textures and
+//! meshes shared by consecutive scenes. Turning it off collects the resources kept so far, and
+//! it's off again whenever the resource system is set up.
+//!
+//! `res::collect_garbage` runs the collectors attached by modules, and the built-in video
+//! resources are collected last, since they are usually owned by other resources like
+//! prefabs.
+//!
+//! ```rust,ignore
+//! res::set_deferred_release(true);
+//!
+//! // Unloads the current scene, and starts to load the next one.
+//! scene.clear();
+//! let next = crayon_world::create_prefab_from("res:next.prefab")?;
+//!
+//! // Frees whatever is not used by the next scene.
+//! let report = res::collect_garbage();
+//! info!("{}", report);
+//! ```
+
+use std::fmt;
+
+use crate::utils::prelude::FastHashMap;
+
+impl_handle!(GarbageCollectorHandle);
+
+/// The resources freed by `collect_garbage`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GarbageReport {
+    /// The kinds of resources, along with the names or UUIDs of the freed ones, in the order
+    /// they are freed.
+    pub freed: Vec<(&'static str, Vec<String>)>,
+}
+
+impl GarbageReport {
+    /// Records the resources of `kind` freed by a collector.
+    pub fn add(&mut self, kind: &'static str, freed: Vec<String>) {
+        if !freed.is_empty() {
+            self.freed.push((kind, freed));
+        }
+    }
+
+    /// Gets the number of freed resources.
+    pub fn len(&self) -> usize {
+        self.freed.iter().map(|v| v.1.len()).sum()
+    }
+
+    /// Checks if nothing is freed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl fmt::Display for GarbageReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut counts: FastHashMap<&'static str, usize> = FastHashMap::default();
+        let mut kinds = Vec::new();
+
+        for &(kind, ref freed) in &self.freed {
+            if !counts.contains_key(kind) {
+                kinds.push(kind);
+            }
+
+            *counts.entry(kind).or_insert(0) += freed.len();
+        }
+
+        write!(f, "Freed {} resources", self.len())?;
+        for (i, kind) in kinds.iter().enumerate() {
+            let sep = if i == 0 { ": " } else { ", " };
+            write!(f, "{}{} {}", sep, counts[kind], kind)?;
+        }
+
+        write!(f, ".")
+    }
+}
@@ -49,6 +49,7 @@ impl Manifest {
 pub struct ManfiestResolver {
     manifests: Vec<Manifest>,
     manifest_prefixs: Vec<InlinableString>,
+    manifest_locations: Vec<InlinableString>,
     uuids: FastHashMap<Uuid, (usize, usize)>,
     filenames: FastHashMap<HashValue<str>, Uuid>,
 }
@@ -58,6 +59,7 @@ impl ManfiestResolver {
         ManfiestResolver {
             manifests: Vec::new(),
             manifest_prefixs: Vec::new(),
+            manifest_locations: Vec::new(),
             uuids: FastHashMap::default(),
             filenames: FastHashMap::default(),
         }
@@ -65,13 +67,84 @@ impl ManfiestResolver {
 
     pub fn add<T: Into<InlinableString>>(&mut self, prefix: T, file: &mut dyn Read) -> Result<()> {
         let manifest = Manifest::load_from(file)?;
+        let prefix = prefix.into();
+        self.add_manifest(prefix.clone(), prefix, manifest);
+        Ok(())
+    }
 
+    /// Adds a manifest whose filenames are prefixed with `prefix`, while the resources are
+    /// stored at `location`. The filenames that were registered already are overridden,
+    /// and returned.
+    pub fn add_manifest<T1, T2>(
+        &mut self,
+        prefix: T1,
+        location: T2,
+        manifest: Manifest,
+    ) -> Vec<String>
+    where
+        T1: Into<InlinableString>,
+        T2: Into<InlinableString>,
+    {
         let mut prefix = prefix.into();
         if !prefix.ends_with('/') {
             prefix.push('/');
         }
 
-        let index = self.manifests.len();
+        let mut location = location.into();
+        if !location.ends_with('/') {
+            location.push('/');
+        }
+
+        let mut overrides = Vec::new();
+        for v in &manifest.items {
+            let filename = manifest.buf.as_str(v.filename);
+            if self.find(format!("{}{}", prefix, filename)).is_some() {
+                overrides.push(filename.to_owned());
+            }
+        }
+
+        self.manifests.push(manifest);
+        self.manifest_prefixs.push(prefix);
+        self.manifest_locations.push(location);
+
+        let index = self.manifests.len() - 1;
+        self.index(index);
+        overrides
+    }
+
+    /// Removes the manifest stored at `location`. The filenames it overrode are restored.
+    pub fn remove<T: AsRef<str>>(&mut self, location: T) -> bool {
+        let mut location = location.as_ref().to_owned();
+        if !location.ends_with('/') {
+            location.push('/');
+        }
+
+        let index = match self
+            .manifest_locations
+            .iter()
+            .position(|v| &**v == location.as_str())
+        {
+            Some(index) => index,
+            None => return false,
+        };
+
+        self.manifests.remove(index);
+        self.manifest_prefixs.remove(index);
+        self.manifest_locations.remove(index);
+
+        self.uuids.clear();
+        self.filenames.clear();
+        for i in 0..self.manifests.len() {
+            self.index(i);
+        }
+
+        true
+    }
+
+    fn index(&mut self, index: usize) {
+        let manifest = &self.manifests[index];
+        let prefix = &self.manifest_prefixs[index];
+
         for (sub_index, v) in manifest.items.iter().enumerate() {
             let filename = manifest.buf.as_str(v.filename);
             let fullname = format!("{}{}", prefix, filename);
@@ -79,10 +152,6 @@ impl ManfiestResolver {
             self.uuids.insert(v.uuid, (index, sub_index));
             self.filenames.insert(fullname.into(), v.uuid);
         }
-
-        self.manifests.push(manifest);
-        self.manifest_prefixs.push(prefix);
-        Ok(())
     }
 
     /// Checks if the uuid exists in this registry.
@@ -103,7 +172,7 @@ impl ManfiestResolver {
     pub fn resolve(&self, uuid: Uuid) -> Option<String> {
         self.uuids
             .get(&uuid)
-            .and_then(|&(index, _)| self.manifest_prefixs.get(index))
+            .and_then(|&(index, _)| self.manifest_locations.get(index))
             .map(|prefix| format!("{}/{:X}", prefix, uuid.to_simple()))
     }
 
@@ -42,14 +42,28 @@
 //! process of `crayon-cli`.
 //!
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod dirs;
+pub mod gc;
 pub mod manifest;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod mods;
 pub mod request;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod saves;
 pub mod shortcut;
 pub mod url;
 pub mod utils;
 pub mod vfs;
 
 pub mod prelude {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub use super::dirs::{UserDir, UserDirs};
+    pub use super::gc::{GarbageCollectorHandle, GarbageReport};
+    #[cfg(not(target_arch = "wasm32"))]
+    pub use super::mods::{ModInfo, ModLimits, Mods};
+    #[cfg(not(target_arch = "wasm32"))]
+    pub use super::saves::{SaveSlot, SaveSlots};
     pub use super::utils::prelude::ResourceState;
     pub use super::ResourceParams;
 }
@@ -58,6 +72,7 @@ mod system;
 
 use uuid::Uuid;
 
+use self::gc::{GarbageCollectorHandle, GarbageReport};
 use self::inside::{ctx, CTX};
 use self::request::{Request, Response};
 use self::shortcut::ShortcutResolver;
@@ -142,6 +157,52 @@ pub fn load_from<T: AsRef<str>>(filename: T) -> Result<Request, failure::Error>
     ctx().load_from(filename)
 }
 
+/// Keeps the resources which are no longer referenced until `collect_garbage` is called,
+/// instead of freeing them right away. Turning it off collects the resources kept so far.
+pub fn set_deferred_release(deferred: bool) {
+    let prev = ctx().set_deferred_release(deferred);
+    if prev && !deferred {
+        collect_garbage();
+    }
+}
+
+/// Checks if the release of resources is deferred until `collect_garbage`. It's always
+/// false without the resource system.
+#[inline]
+pub fn deferred_release() -> bool {
+    valid() && ctx().deferred_release()
+}
+
+/// Frees all the resources which are no longer referenced, and reports what was freed. The
+/// collectors attached by modules run first, and the video resources which might be owned by
+/// them are freed last.
+pub fn collect_garbage() -> GarbageReport {
+    let mut report = GarbageReport::default();
+    ctx().collect_garbage(&mut report);
+
+    if crate::video::valid() {
+        crate::video::collect_garbage(&mut report);
+    }
+
+    crate::application::record_gc(report.len());
+    report
+}
+
+/// Attaches a collector, which frees the unreferenced resources of a module in
+/// `collect_garbage`.
+#[inline]
+pub fn attach_collector<T>(func: T) -> GarbageCollectorHandle
+where
+    T: FnMut(&mut GarbageReport) + Send + 'static,
+{
+    ctx().attach_collector(func)
+}
+
+#[inline]
+pub fn detach_collector(handle: GarbageCollectorHandle) {
+    ctx().detach_collector(handle)
+}
+
 pub(crate) mod inside {
     use std::sync::Arc;
 
@@ -170,6 +231,8 @@ pub(crate) mod inside {
     pub unsafe fn setup(params: ResourceParams) -> Result<(), failure::Error> {
         debug_assert!(CTX.is_null(), "duplicated setup of resource system.");
 
+        #[cfg(feature = "alloc-tracking")]
+        let _scope = crate::memory::scope("res");
         let ctx = ResourceSystem::new(params)?;
         CTX = Box::into_raw(Box::new(ctx));
         Ok(())
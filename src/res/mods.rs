@@ -0,0 +1,282 @@
+//! Third-party asset bundles loaded at runtime.
+//!
+//! Every sub-directory of the mods directory is a bundle, which is built by the same
+//! offline pipeline as the game and contains a `MANIFEST` along with its resources.
+//! Enabling a mod mounts its manifest over a prefix of the game, e.g. `res:`, so any
+//! resource with the same filename is overridden by the one in the mod, and the others
+//! are added as new resources. The resources themselves are always read from the
+//! directory of the mod.
+//!
+//! Since bundles are untrusted, they are validated before mounting: the filenames could
+//! not escape the prefix, the resources could not be symbolic links, the number and sizes
+//! of files are limited by `ModLimits`, and the UUIDs could not collide with the resources
+//! registered already.
+//!
+//! Resources which have been loaded before a mod is enabled are cached by the modules, so
+//! mods should be enabled before loading the game content.
+//!
+//! ```rust,ignore
+//! let mut mods = Mods::new("mods", "res:");
+//! for info in mods.list()? {
+//!     println!("{} (enabled: {})", info.name, info.enabled);
+//! }
+//!
+//! for filename in mods.enable("hd-textures")? {
+//!     println!("{} is overridden.", filename);
+//! }
+//! ```
+
+use std::env;
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use crate::errors::*;
+
+use super::manifest::{Manifest, NAME};
+
+/// The limits of a mod bundle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModLimits {
+    /// The maximum number of resources in a mod.
+    pub max_files: usize,
+    /// The maximum size of a resource in bytes.
+    pub max_file_size: u64,
+    /// The maximum size of all the resources of a mod in bytes.
+    pub max_total_size: u64,
+}
+
+impl Default for ModLimits {
+    fn default() -> Self {
+        ModLimits {
+            max_files: 4096,
+            max_file_size: 64 * 1024 * 1024,
+            max_total_size: 1024 * 1024 * 1024,
+        }
+    }
+}
+
+/// The information of an available mod.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModInfo {
+    /// The name of this mod, which is the name of its directory.
+    pub name: String,
+    /// The directory of this mod.
+    pub path: PathBuf,
+    /// Is this mod enabled.
+    pub enabled: bool,
+}
+
+struct EnabledMod {
+    name: String,
+    location: String,
+    overrides: Vec<String>,
+}
+
+/// Lists, enables and disables the mods in a directory.
+pub struct Mods {
+    dir: PathBuf,
+    prefix: String,
+    limits: ModLimits,
+    enabled: Vec<EnabledMod>,
+}
+
+impl Mods {
+    /// Creates the mods in directory `dir`, which override the resources under `prefix`.
+    pub fn new<T1: AsRef<Path>, T2: Into<String>>(dir: T1, prefix: T2) -> Self {
+        Mods {
+            dir: dir.as_ref().to_owned(),
+            prefix: prefix.into(),
+            limits: ModLimits::default(),
+            enabled: Vec::new(),
+        }
+    }
+
+    /// Sets the limits of mods, which are checked when a mod is enabled.
+    #[inline]
+    pub fn set_limits(&mut self, limits: ModLimits) {
+        self.limits = limits;
+    }
+
+    /// Gets the directory of mods.
+    #[inline]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Lists the available mods in alphabetical order.
+    pub fn list(&self) -> Result<Vec<ModInfo>> {
+        let mut mods = Vec::new();
+        if !self.dir.is_dir() {
+            return Ok(mods);
+        }
+
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if !path.join(NAME).is_file() {
+                continue;
+            }
+
+            let name = match path.file_name().and_then(|v| v.to_str()) {
+                Some(v) if is_valid_name(v) => v.to_owned(),
+                _ => continue,
+            };
+
+            mods.push(ModInfo {
+                enabled: self.is_enabled(&name),
+                name,
+                path,
+            });
+        }
+
+        mods.sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name));
+        Ok(mods)
+    }
+
+    /// Checks if the mod is enabled.
+    #[inline]
+    pub fn is_enabled<T: AsRef<str>>(&self, name: T) -> bool {
+        let name = name.as_ref();
+        self.enabled.iter().any(|v| v.name == name)
+    }
+
+    /// Gets the filenames overridden by an enabled mod.
+    pub fn overrides<T: AsRef<str>>(&self, name: T) -> Option<&[String]> {
+        let name = name.as_ref();
+        self.enabled
+            .iter()
+            .find(|v| v.name == name)
+            .map(|v| &v.overrides[..])
+    }
+
+    /// Validates and enables a mod. The mods enabled later take precedence over the
+    /// earlier ones. Returns the filenames overridden by this mod.
+    pub fn enable<T: AsRef<str>>(&mut self, name: T) -> Result<&[String]> {
+        let name = validate(name.as_ref())?;
+        if self.is_enabled(name) {
+            bail!("Mod {} has been enabled already.", name);
+        }
+
+        let dir = self.dir.join(name);
+        let dir = if dir.is_absolute() {
+            dir
+        } else {
+            env::current_dir()?.join(dir)
+        };
+
+        let manifest = self.validate(name, &dir)?;
+        let location = format!("file://{}/", dir.display());
+        let overrides = super::inside::ctx().mount(&self.prefix, &location, manifest)?;
+
+        info!(
+            "[Mods] {} is enabled with {} resources overridden.",
+            name,
+            overrides.len()
+        );
+
+        self.enabled.push(EnabledMod {
+            name: name.to_owned(),
+            location,
+            overrides,
+        });
+
+        Ok(&self.enabled.last().unwrap().overrides)
+    }
+
+    /// Disables a mod, and restores the resources it overrode.
+    pub fn disable<T: AsRef<str>>(&mut self, name: T) -> Result<()> {
+        let name = name.as_ref();
+        let index = match self.enabled.iter().position(|v| v.name == name) {
+            Some(index) => index,
+            None => bail!("Mod {} is not enabled.", name),
+        };
+
+        let v = self.enabled.remove(index);
+        super::inside::ctx().unmount(&v.location);
+        info!("[Mods] {} is disabled.", name);
+        Ok(())
+    }
+
+    fn validate(&self, name: &str, dir: &Path) -> Result<Manifest> {
+        let bytes = fs::read(dir.join(NAME))?;
+        let manifest = Manifest::load_from(&mut Cursor::new(bytes))?;
+
+        if manifest.items.len() > self.limits.max_files {
+            bail!(
+                "Mod {} has {} resources, which exceeds the limit {}.",
+                name,
+                manifest.items.len(),
+                self.limits.max_files
+            );
+        }
+
+        let mut total = 0;
+        for v in &manifest.items {
+            let filename = manifest.buf.as_str(v.filename);
+            if !is_valid_filename(filename) {
+                bail!("Mod {} has an invalid filename {:?}.", name, filename);
+            }
+
+            let path = dir.join(format!("{:X}", v.uuid.to_simple()));
+            let metadata = match fs::symlink_metadata(&path) {
+                Ok(v) => v,
+                Err(_) => bail!("Mod {} has no data for {}.", name, filename),
+            };
+
+            if !metadata.is_file() {
+                bail!("Mod {} has invalid data for {}.", name, filename);
+            }
+
+            if metadata.len() > self.limits.max_file_size {
+                bail!(
+                    "{} of mod {} has {} bytes, which exceeds the limit {}.",
+                    filename,
+                    name,
+                    metadata.len(),
+                    self.limits.max_file_size
+                );
+            }
+
+            total += metadata.len();
+        }
+
+        if total > self.limits.max_total_size {
+            bail!(
+                "Mod {} has {} bytes, which exceeds the limit {}.",
+                name,
+                total,
+                self.limits.max_total_size
+            );
+        }
+
+        Ok(manifest)
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|v| v.is_ascii_alphanumeric() || v == '-' || v == '_')
+}
+
+fn validate(name: &str) -> Result<&str> {
+    if !is_valid_name(name) {
+        bail!(
+            "Mod name {:?} should only contain ASCII letters, digits, '-' and '_'.",
+            name
+        );
+    }
+
+    Ok(name)
+}
+
+/// Checks if the filename is relative, and never escapes the prefix it's mounted on.
+fn is_valid_filename(filename: &str) -> bool {
+    !filename.is_empty()
+        && !filename.starts_with('/')
+        && !filename.contains(|v: char| v == '\\' || v == ':' || v == '?' || v == '#')
+        && filename
+            .split('/')
+            .all(|v| !v.is_empty() && v != "." && v != "..")
+}
@@ -0,0 +1,230 @@
+//! Crash-safe file writes and save slots.
+//!
+//! Writing a file in place leaves a truncated file behind if the game crashes or the power
+//! goes off in the middle. `write_atomic` writes into a temporary file next to the target,
+//! flushes it to disk and renames it over the target, so readers always see either the old
+//! or the new content.
+//!
+//! `SaveSlots` manages named saves in a directory on top of it, along with optional
+//! thumbnails.
+//!
+//! ```rust,ignore
+//! let dirs = UserDirs::new("shawnscode", "crayon")?;
+//! let slots = SaveSlots::new(dirs.create(UserDir::Saves)?);
+//!
+//! slots.save("slot0", &bytes, Some(&thumbnail))?;
+//! for slot in slots.list()? {
+//!     println!("{} saved at {:?}", slot.name, slot.timestamp);
+//! }
+//! ```
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::errors::*;
+
+const SAVE_EXT: &str = "sav";
+const THUMBNAIL_EXT: &str = "thumb";
+const TEMP_EXT: &str = "tmp";
+
+/// Writes the bytes into file atomically.
+pub fn write_atomic<T: AsRef<Path>>(path: T, bytes: &[u8]) -> Result<()> {
+    let path = path.as_ref();
+    let filename = match path.file_name() {
+        Some(v) => v.to_string_lossy(),
+        None => bail!("{:?} is not a valid file path.", path),
+    };
+
+    let tmp = path.with_file_name(format!(".{}.{}", filename, TEMP_EXT));
+
+    {
+        let mut file = fs::File::create(&tmp)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+    }
+
+    if let Err(err) = fs::rename(&tmp, path) {
+        let _ = fs::remove_file(&tmp);
+        return Err(err.into());
+    }
+
+    // Makes sure the rename itself is persisted.
+    #[cfg(unix)]
+    {
+        if let Some(dir) = path.parent() {
+            let dir = if dir.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                dir
+            };
+
+            fs::File::open(dir)?.sync_all()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The metadata of a save slot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaveSlot {
+    /// The name of this slot.
+    pub name: String,
+    /// The last time this slot has been written.
+    pub timestamp: SystemTime,
+    /// The size of save data in bytes.
+    pub size: u64,
+    /// Does this slot have a thumbnail.
+    pub thumbnail: bool,
+}
+
+/// Named save slots in a directory.
+#[derive(Debug, Clone)]
+pub struct SaveSlots {
+    dir: PathBuf,
+}
+
+impl SaveSlots {
+    /// Creates save slots in the directory, which is created when the first slot is
+    /// saved.
+    pub fn new<T: AsRef<Path>>(dir: T) -> Self {
+        SaveSlots {
+            dir: dir.as_ref().to_owned(),
+        }
+    }
+
+    /// Gets the directory of slots.
+    #[inline]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Lists the slots, from the newest to the oldest.
+    pub fn list(&self) -> Result<Vec<SaveSlot>> {
+        let mut slots = Vec::new();
+        if !self.dir.is_dir() {
+            return Ok(slots);
+        }
+
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().map(|v| v == SAVE_EXT) != Some(true) {
+                continue;
+            }
+
+            let name = match path.file_stem().and_then(|v| v.to_str()) {
+                Some(v) if is_valid_name(v) => v.to_owned(),
+                _ => continue,
+            };
+
+            let metadata = fs::metadata(&path)?;
+            slots.push(SaveSlot {
+                timestamp: metadata.modified()?,
+                size: metadata.len(),
+                thumbnail: self.path(&name, THUMBNAIL_EXT).is_file(),
+                name,
+            });
+        }
+
+        slots.sort_by(|lhs, rhs| rhs.timestamp.cmp(&lhs.timestamp));
+        Ok(slots)
+    }
+
+    /// Gets the metadata of a slot, and returns None if it does not exist.
+    pub fn get<T: AsRef<str>>(&self, name: T) -> Result<Option<SaveSlot>> {
+        let name = validate(name.as_ref())?;
+        let path = self.path(name, SAVE_EXT);
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        let metadata = fs::metadata(&path)?;
+        Ok(Some(SaveSlot {
+            name: name.to_owned(),
+            timestamp: metadata.modified()?,
+            size: metadata.len(),
+            thumbnail: self.path(name, THUMBNAIL_EXT).is_file(),
+        }))
+    }
+
+    /// Writes the data of a slot atomically, along with an optional thumbnail. The
+    /// thumbnail of previous save is removed if there is none.
+    ///
+    /// The data is written first, so a crash in between leaves the new save with a stale
+    /// thumbnail at worst, instead of a new thumbnail next to the old save.
+    pub fn save<T: AsRef<str>>(
+        &self,
+        name: T,
+        data: &[u8],
+        thumbnail: Option<&[u8]>,
+    ) -> Result<()> {
+        let name = validate(name.as_ref())?;
+        fs::create_dir_all(&self.dir)?;
+
+        write_atomic(self.path(name, SAVE_EXT), data)?;
+
+        let path = self.path(name, THUMBNAIL_EXT);
+        match thumbnail {
+            Some(bytes) => write_atomic(&path, bytes)?,
+            None if path.is_file() => fs::remove_file(&path)?,
+            None => {}
+        }
+
+        Ok(())
+    }
+
+    /// Reads the data of a slot.
+    pub fn load<T: AsRef<str>>(&self, name: T) -> Result<Vec<u8>> {
+        let name = validate(name.as_ref())?;
+        Ok(fs::read(self.path(name, SAVE_EXT))?)
+    }
+
+    /// Reads the thumbnail of a slot, and returns None if it has none.
+    pub fn load_thumbnail<T: AsRef<str>>(&self, name: T) -> Result<Option<Vec<u8>>> {
+        let name = validate(name.as_ref())?;
+        let path = self.path(name, THUMBNAIL_EXT);
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        Ok(Some(fs::read(path)?))
+    }
+
+    /// Deletes a slot and its thumbnail.
+    pub fn delete<T: AsRef<str>>(&self, name: T) -> Result<()> {
+        let name = validate(name.as_ref())?;
+
+        for ext in &[SAVE_EXT, THUMBNAIL_EXT] {
+            let path = self.path(name, ext);
+            if path.is_file() {
+                fs::remove_file(path)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn path(&self, name: &str, ext: &str) -> PathBuf {
+        self.dir.join(format!("{}.{}", name, ext))
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|v| v.is_ascii_alphanumeric() || v == '-' || v == '_')
+}
+
+fn validate(name: &str) -> Result<&str> {
+    if !is_valid_name(name) {
+        bail!(
+            "Save slot name {:?} should only contain ASCII letters, digits, '-' and '_'.",
+            name
+        );
+    }
+
+    Ok(name)
+}
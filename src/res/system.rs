@@ -1,11 +1,15 @@
 use std::io::Read;
-use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 
 use uuid::Uuid;
 
 use crate::application::prelude::{LifecycleListener, LifecycleListenerHandle};
+use crate::utils::prelude::ObjectPool;
 
-use super::manifest::ManfiestResolver;
+use super::gc::{GarbageCollectorHandle, GarbageReport};
+
+use super::manifest::{ManfiestResolver, Manifest};
 use super::request::{Request, RequestQueue, Response};
 use super::shortcut::ShortcutResolver;
 use super::url::Url;
@@ -18,14 +22,20 @@ pub struct ResourceSystem {
     manifest: RwLock<ManfiestResolver>,
     requests: Arc<RequestQueue>,
     lifecycle: LifecycleListenerHandle,
+    collectors: Mutex<ObjectPool<GarbageCollectorHandle, Collector>>,
+    deferred: AtomicBool,
 }
 
+type Collector = Box<dyn FnMut(&mut GarbageReport) + Send>;
+
 struct Lifecycle {
     requests: Arc<RequestQueue>,
 }
 
 impl LifecycleListener for Lifecycle {
     fn on_post_update(&mut self) -> Result<(), failure::Error> {
+        #[cfg(feature = "alloc-tracking")]
+        let _scope = crate::memory::scope("res");
         self.requests.advance();
         Ok(())
     }
@@ -48,11 +58,44 @@ impl ResourceSystem {
             manifest: RwLock::new(ManfiestResolver::new()),
             requests: requests.clone(),
             lifecycle: crate::application::attach(Lifecycle { requests }),
+            collectors: Mutex::new(ObjectPool::new()),
+            deferred: AtomicBool::new(false),
         };
 
         Ok(sys)
     }
 
+    #[inline]
+    pub fn attach_collector<T>(&self, func: T) -> GarbageCollectorHandle
+    where
+        T: FnMut(&mut GarbageReport) + Send + 'static,
+    {
+        self.collectors.lock().unwrap().create(Box::new(func))
+    }
+
+    #[inline]
+    pub fn detach_collector(&self, handle: GarbageCollectorHandle) {
+        self.collectors.lock().unwrap().free(handle);
+    }
+
+    /// Sets whether the release of resources is deferred, and returns the previous value.
+    #[inline]
+    pub fn set_deferred_release(&self, deferred: bool) -> bool {
+        self.deferred.swap(deferred, Ordering::Relaxed)
+    }
+
+    #[inline]
+    pub fn deferred_release(&self) -> bool {
+        self.deferred.load(Ordering::Relaxed)
+    }
+
+    pub fn collect_garbage(&self, report: &mut GarbageReport) {
+        let mut collectors = self.collectors.lock().unwrap();
+        for v in collectors.values_mut() {
+            v(report);
+        }
+    }
+
     /// Attach a manifest to this registry.
     #[inline]
     pub fn attach<T>(&self, prefix: T, file: &mut dyn Read) -> Result<(), failure::Error>
@@ -67,6 +110,38 @@ impl ResourceSystem {
         self.manifest.write().unwrap().add(url, file)
     }
 
+    /// Mounts a manifest whose filenames are prefixed with `prefix`, while the resources are
+    /// stored at `location`. Returns the filenames that were overridden.
+    pub fn mount<T1, T2>(
+        &self,
+        prefix: T1,
+        location: T2,
+        manifest: Manifest,
+    ) -> Result<Vec<String>, failure::Error>
+    where
+        T1: AsRef<str>,
+        T2: AsRef<str>,
+    {
+        let prefix = prefix.as_ref();
+        let prefix = self
+            .shortcut
+            .resolve(prefix)
+            .ok_or_else(|| format_err!("Could not resolve manifest filename: {}.", prefix))?;
+
+        let mut resolver = self.manifest.write().unwrap();
+        if let Some(v) = manifest.items.iter().find(|v| resolver.contains(v.uuid)) {
+            bail!("Resource {} has been registered already.", v.uuid);
+        }
+
+        Ok(resolver.add_manifest(prefix, location.as_ref(), manifest))
+    }
+
+    /// Unmounts the manifest stored at `location`.
+    #[inline]
+    pub fn unmount<T: AsRef<str>>(&self, location: T) -> bool {
+        self.manifest.write().unwrap().remove(location)
+    }
+
     /// Resolve shortcuts in the provided string recursively and return None if not exists.
     #[inline]
     pub fn resolve<T: AsRef<str>>(&self, url: T) -> Option<String> {
@@ -121,7 +196,11 @@ impl ResourceSystem {
         self.requests.add(req, func);
 
         let vfs = self.schemas.locate(url.schema())?;
-        crate::sched::spawn(move || vfs.request(&url, state));
+        crate::sched::spawn(move || {
+            #[cfg(feature = "alloc-tracking")]
+            let _scope = crate::memory::scope("res");
+            vfs.request(&url, state)
+        });
 
         Ok(())
     }
@@ -151,13 +230,19 @@ impl ResourceSystem {
                 format_err!("Could not found resource {} in this registry.", uuid)
             })?;
 
+        crate::application::record_load(&url);
+
         let url = Url::new(url)?;
         let vfs = self.schemas.locate(url.schema())?;
 
         let state = Request::latch();
         let req = Request::new(state.clone());
 
-        crate::sched::spawn(move || vfs.request(&url, state));
+        crate::sched::spawn(move || {
+            #[cfg(feature = "alloc-tracking")]
+            let _scope = crate::memory::scope("res");
+            vfs.request(&url, state)
+        });
         Ok(req)
     }
 
@@ -24,6 +24,16 @@
 //! the resource by 1. And when you are done with the resource, its the user's responsibility to
 //! drop the ownership of the resource. And when the last ownership to a given resource is dropped,
 //! the corresponding resource is also destroyed.
+//!
+//! If deferred release is enabled with `res::set_deferred_release`, the resources are kept until
+//! `collect_garbage` instead, and could be reused if they are requested again in the meantime.
+//!
+//! ## Named Resources
+//!
+//! Resources generated at runtime, e.g. procedural textures, could be registered under a
+//! virtual path with `create_named`. Later calls of `create_from` with the same path return
+//! the registered handle and increase its reference count, just like the resources loaded
+//! from files. So materials and prefabs could refer to them without special cases.
 
 use failure::Error;
 use std::sync::{Arc, Mutex};
@@ -53,6 +63,7 @@ where
     items: ObjectPool<H, Item<Loader::Resource>>,
     requests: FastHashMap<H, Arc<Mutex<ResourceAsyncState<Loader::Intermediate>>>>,
     registry: FastHashMap<Uuid, H>,
+    names: FastHashMap<String, H>,
     loader: Loader,
 }
 
@@ -66,6 +77,18 @@ where
         ResourcePool {
             items: ObjectPool::new(),
             registry: FastHashMap::default(),
+            names: FastHashMap::default(),
+            requests: FastHashMap::default(),
+            loader,
+        }
+    }
+
+    /// Create a new and empty `ResourcePool` which holds at most `limit` resources.
+    pub fn with_limit(loader: Loader, limit: usize) -> Self {
+        ResourcePool {
+            items: ObjectPool::with_limit(limit),
+            registry: FastHashMap::default(),
+            names: FastHashMap::default(),
             requests: FastHashMap::default(),
             loader,
         }
@@ -116,23 +139,47 @@ where
     /// A associated `Handle` is returned.
     #[inline]
     pub fn create(&mut self, params: Loader::Intermediate) -> Result<H, Error> {
-        let handle = self.alloc(None);
+        let handle = self.alloc(None)?;
         match self.loader.create(handle, params) {
             Ok(value) => {
                 self.items.get_mut(handle).unwrap().resource = Some(value);
                 Ok(handle)
             }
             Err(error) => {
-                self.delete(handle);
+                self.free(handle);
                 Err(error)
             }
         }
     }
 
-    /// Create a resource from file asynchronously.
+    /// Create a resource with provided value instance, and registers it under the virtual
+    /// path `name`. It fails if there is a resource registered with the same name already.
+    pub fn create_named<T: AsRef<str>>(
+        &mut self,
+        name: T,
+        params: Loader::Intermediate,
+    ) -> Result<H, Error> {
+        let name = name.as_ref();
+        if self.names.contains_key(name) {
+            bail!("Resource '{}' has been registered already.", name);
+        }
+
+        let handle = self.create(params)?;
+        self.items.get_mut(handle).unwrap().name = Some(name.to_owned());
+        self.names.insert(name.to_owned(), handle);
+        Ok(handle)
+    }
+
+    /// Create a resource from file asynchronously. The resources registered with
+    /// `create_named` are looked up first.
     #[inline]
     pub fn create_from<T: AsRef<str>>(&mut self, url: T) -> Result<H, Error> {
         let url = url.as_ref();
+        if let Some(&handle) = self.names.get(url) {
+            self.items.get_mut(handle).unwrap().rc += 1;
+            return Ok(handle);
+        }
+
         let uuid = crate::res::find(url)
             .ok_or_else(|| format_err!("Could not found resource '{}'.", url))?;
         self.create_from_uuid(uuid)
@@ -146,7 +193,7 @@ where
             return Ok(handle);
         }
 
-        let handle = self.alloc(Some(uuid));
+        let handle = self.alloc(Some(uuid))?;
 
         let rx = Arc::new(Mutex::new(ResourceAsyncState::NotReady));
         let tx = rx.clone();
@@ -177,7 +224,7 @@ where
                 Ok(handle)
             }
             Err(err) => {
-                self.delete(handle);
+                self.free(handle);
                 Err(err)
             }
         }
@@ -185,26 +232,61 @@ where
 
     /// Deletes a resource from loadery.
     pub fn delete(&mut self, handle: H) {
+        let deferred = crate::res::deferred_release();
         let disposed = self
             .items
             .get_mut(handle)
             .map(|e| {
+                // The resource is released already, and waits for `collect_garbage`.
+                if e.rc == 0 {
+                    return false;
+                }
+
                 e.rc -= 1;
-                e.rc == 0
+                e.rc == 0 && !deferred
             })
             .unwrap_or(false);
 
         if disposed {
-            let e = self.items.free(handle).unwrap();
+            self.free(handle);
+        }
+    }
 
-            if let Some(uuid) = e.uuid {
-                self.registry.remove(&uuid);
-            }
+    /// Frees the resources which are no longer referenced but kept by deferred release, and
+    /// returns the names or UUIDs of them.
+    pub fn collect_garbage(&mut self) -> Vec<String> {
+        let released: Vec<_> = self
+            .items
+            .iter()
+            .filter(|(_, e)| e.rc == 0)
+            .map(|(handle, _)| handle)
+            .collect();
 
-            if let Some(resource) = e.resource {
-                self.loader.delete(handle, resource);
-            }
+        released.into_iter().map(|v| self.free(v)).collect()
+    }
+
+    fn free(&mut self, handle: H) -> String {
+        let e = self.items.free(handle).unwrap();
+
+        let identifier = match (e.name.as_ref(), e.uuid) {
+            (Some(name), _) => name.clone(),
+            (None, Some(uuid)) => uuid.to_string(),
+            (None, None) => format!("{:?}", handle),
+        };
+
+        if let Some(uuid) = e.uuid {
+            self.registry.remove(&uuid);
+        }
+
+        if let Some(name) = e.name {
+            self.names.remove(&name);
+        }
+
+        if let Some(resource) = e.resource {
+            self.loader.delete(handle, resource);
         }
+
+        identifier
     }
 
     /// Get the resource state.
@@ -243,27 +325,29 @@ where
     }
 
     #[inline]
-    fn alloc(&mut self, uuid: Option<Uuid>) -> H {
+    fn alloc(&mut self, uuid: Option<Uuid>) -> Result<H, Error> {
         let entry = Item {
             rc: 1,
             uuid,
+            name: None,
             resource: None,
             error: None,
         };
 
-        let handle = self.items.create(entry);
+        let handle = self.items.try_create(entry)?;
 
         if let Some(uuid) = uuid {
             self.registry.insert(uuid, handle);
         }
 
-        handle
+        Ok(handle)
     }
 }
 
 struct Item<T> {
     rc: u32,
     uuid: Option<Uuid>,
+    name: Option<String>,
     resource: Option<T>,
     error: Option<Error>,
 }
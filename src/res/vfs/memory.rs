@@ -0,0 +1,45 @@
+use std::sync::{Arc, RwLock};
+
+use crate::sched::prelude::LockLatch;
+use crate::utils::hash::FastHashMap;
+
+use super::super::request::Response;
+use super::super::url::Url;
+use super::VFS;
+
+/// A filesystem which serves files from memory, keyed by the path of urls without the
+/// leading slashes. It's mostly useful to provide resources in tests.
+#[derive(Debug, Default, Clone)]
+pub struct Memory {
+    files: Arc<RwLock<FastHashMap<String, Arc<[u8]>>>>,
+}
+
+impl Memory {
+    pub fn new() -> Self {
+        Memory::default()
+    }
+
+    /// Adds a file, or replaces the file with the same path.
+    pub fn insert<T: AsRef<str>>(&self, path: T, bytes: &[u8]) {
+        let path = path.as_ref().trim_start_matches('/').to_owned();
+        self.files.write().unwrap().insert(path, bytes.into());
+    }
+
+    /// Removes a file.
+    pub fn remove<T: AsRef<str>>(&self, path: T) {
+        let path = path.as_ref().trim_start_matches('/');
+        self.files.write().unwrap().remove(path);
+    }
+}
+
+impl VFS for Memory {
+    fn request(&self, url: &Url, state: Arc<LockLatch<Response>>) {
+        let path = url.path().trim_start_matches('/');
+        let response = match self.files.read().unwrap().get(path) {
+            Some(bytes) => Ok(bytes.to_vec().into_boxed_slice()),
+            None => Err(format_err!("Could not find {} in memory.", path)),
+        };
+
+        state.set(response);
+    }
+}
@@ -2,6 +2,7 @@
 pub mod dir;
 #[cfg(target_arch = "wasm32")]
 pub mod http;
+pub mod memory;
 
 use std::sync::Arc;
 
@@ -4,15 +4,79 @@ mod system;
 
 mod job;
 mod scheduler;
+mod thread;
 mod unwind;
 
 pub mod prelude {
     pub use super::latch::{CountLatch, Latch, LatchProbe, LockLatch, SpinLatch};
     pub use super::system::PanicHandler;
+    pub use super::thread::ThreadPriority;
+    pub use super::SchedParams;
 }
 
 use self::inside::{ctx, CTX};
 use self::scope::Scope;
+use self::thread::ThreadPriority;
+
+/// The setup parameters of sched system. The number of workers is configured with
+/// `Settings::worker_threads`, and detected from the number of CPU cores by default.
+#[derive(Debug, Clone)]
+pub struct SchedParams {
+    /// The number of cores left for the main and render threads when the number of workers
+    /// is detected. The workers are pinned after the reserved cores if `pin_workers` is set.
+    pub reserved_cores: u32,
+    /// The upper limit of detected workers, which keeps the memory and scheduling overhead
+    /// bounded on machines with lots of cores.
+    pub max_workers: u32,
+    /// The workers are named as `{name}-{index}` if provided, which shows up in debuggers
+    /// and profilers.
+    pub name: Option<String>,
+    /// The stack size of workers in bytes. The default of `std::thread` is used if `None`.
+    pub stack_size: Option<usize>,
+    /// The scheduling priority hint of workers. It's only supported on Linux/Android and
+    /// Windows, and ignored with a warning on other platforms.
+    pub priority: ThreadPriority,
+    /// Pins each worker to its own core, starting after `reserved_cores`. It's only
+    /// supported on Linux/Android and Windows, and ignored with a warning on other platforms.
+    pub pin_workers: bool,
+}
+
+impl Default for SchedParams {
+    fn default() -> Self {
+        SchedParams {
+            reserved_cores: 1,
+            max_workers: 8,
+            name: Some("sched".to_owned()),
+            stack_size: None,
+            priority: ThreadPriority::Normal,
+            pin_workers: false,
+        }
+    }
+}
+
+impl SchedParams {
+    /// Gets the number of worker threads, detecting it from the number of CPU cores
+    /// if not specified. It falls back to 4 workers if the cores could not be detected.
+    pub fn num_workers(&self, worker_threads: Option<u32>) -> u32 {
+        if let Some(num) = worker_threads {
+            return num;
+        }
+
+        match crate::application::cpu_cores() {
+            Some(cores) => (cores as u32)
+                .saturating_sub(self.reserved_cores)
+                .min(self.max_workers)
+                .max(1),
+            None => 4.min(self.max_workers).max(1),
+        }
+    }
+}
+
+/// Gets the number of worker threads.
+#[inline]
+pub fn num_workers() -> u32 {
+    ctx().num_workers()
+}
 
 /// Checks if the sched system is enabled.
 #[inline]
@@ -54,6 +118,8 @@ where
 
 pub(crate) mod inside {
     use super::system::{PanicHandler, SchedulerSystem};
+    use super::thread::ThreadPriority;
+    use super::SchedParams;
 
     pub static mut CTX: *const SchedulerSystem = std::ptr::null();
 
@@ -68,16 +134,23 @@ pub(crate) mod inside {
         }
     }
 
-    /// Setup the sched system.
+    /// Setup the sched system with `worker_threads` workers, which are detected from the
+    /// number of CPU cores if `None`.
     pub unsafe fn setup(
-        num: u32,
-        stack_size: Option<usize>,
+        params: SchedParams,
+        worker_threads: Option<u32>,
         panic_handler: Option<Box<PanicHandler>>,
     ) {
         debug_assert!(CTX.is_null(), "duplicated setup of sched system.");
 
+        let hinted = params.pin_workers || params.priority != ThreadPriority::Normal;
+        if hinted && !super::thread::supported() {
+            warn!("[Sched] Thread priority and affinity are not supported on this platform.");
+        }
+
+        let num = params.num_workers(worker_threads);
         CTX = Box::into_raw(Box::new(if num > 0 {
-            SchedulerSystem::new(num, stack_size, panic_handler)
+            SchedulerSystem::new(num, params, panic_handler)
         } else {
             SchedulerSystem::headless()
         }));
@@ -9,6 +9,7 @@ use super::job::{JobRef, StackJob};
 use super::latch::{CountLatch, Latch, LatchProbe, LatchWaitProbe, LockLatch};
 use super::system::PanicHandler;
 use super::unwind::AbortIfPanic;
+use super::SchedParams;
 
 pub struct Scheduler {
     terminator: CountLatch,
@@ -24,7 +25,7 @@ pub struct Scheduler {
 impl Scheduler {
     pub fn new(
         num: u32,
-        stack_size: Option<usize>,
+        params: SchedParams,
         panic_handler: Option<Box<PanicHandler>>,
     ) -> Arc<Self> {
         let mut stealers = Vec::new();
@@ -59,12 +60,27 @@ impl Scheduler {
             let sc = scheduler.clone();
             let mut b = thread::Builder::new();
 
-            if let Some(stack_size) = stack_size {
+            if let Some(ref name) = params.name {
+                b = b.name(format!("{}-{}", name, i));
+            }
+
+            if let Some(stack_size) = params.stack_size {
                 b = b.stack_size(stack_size);
             }
 
-            b.spawn(move || unsafe { Scheduler::main_loop(sc, i, w) })
-                .unwrap();
+            let priority = params.priority;
+            let core = if params.pin_workers {
+                let core = params.reserved_cores as usize + i;
+                Some(crate::application::cpu_cores().map_or(core, |n| core % n.max(1)))
+            } else {
+                None
+            };
+
+            b.spawn(move || unsafe {
+                super::thread::apply(priority, core);
+                Scheduler::main_loop(sc, i, w)
+            })
+            .unwrap();
         }
 
         for v in &scheduler.threads {
@@ -74,6 +90,11 @@ impl Scheduler {
         scheduler
     }
 
+    #[inline]
+    pub fn num_threads(&self) -> usize {
+        self.threads.len()
+    }
+
     /// Push a job into the "external jobs" queue; it will be taken by whatever
     /// worker has nothing to do.
     pub fn inject(&self, job: JobRef) {
@@ -206,6 +227,9 @@ impl Scheduler {
     }
 
     unsafe fn main_loop(scheduler: Arc<Scheduler>, index: usize, worker: deque::Worker<JobRef>) {
+        // Jobs are attributed to sched, unless they open scopes of their own.
+        #[cfg(feature = "alloc-tracking")]
+        let _scope = crate::memory::scope("sched");
         let worker_thread = WorkerThread {
             scheduler,
             index,
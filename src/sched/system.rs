@@ -4,6 +4,7 @@ use super::job::HeapJob;
 use super::scheduler::Scheduler;
 use super::scope::Scope;
 use super::unwind;
+use super::SchedParams;
 
 pub struct SchedulerSystem {
     scheduler: Option<Arc<Scheduler>>,
@@ -14,13 +15,9 @@ pub struct SchedulerSystem {
 pub type PanicHandler = Fn(Box<::std::any::Any + Send>) + Send + Sync;
 
 impl SchedulerSystem {
-    pub fn new(
-        num: u32,
-        stack_size: Option<usize>,
-        panic_handler: Option<Box<PanicHandler>>,
-    ) -> Self {
+    pub fn new(num: u32, params: SchedParams, panic_handler: Option<Box<PanicHandler>>) -> Self {
         SchedulerSystem {
-            scheduler: Some(Scheduler::new(num, stack_size, panic_handler)),
+            scheduler: Some(Scheduler::new(num, params, panic_handler)),
         }
     }
 
@@ -28,6 +25,13 @@ impl SchedulerSystem {
         SchedulerSystem { scheduler: None }
     }
 
+    pub fn num_workers(&self) -> u32 {
+        self.scheduler
+            .as_ref()
+            .map(|v| v.num_threads() as u32)
+            .unwrap_or(0)
+    }
+
     pub fn terminate(&self) {
         if let Some(ref scheduler) = self.scheduler {
             scheduler.terminate_dec();
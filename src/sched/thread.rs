@@ -0,0 +1,136 @@
+/// The scheduling priority hint of worker threads.
+///
+/// The hints are applied on Linux/Android and Windows, and ignored with a warning on other
+/// platforms. Raising the priority might require extra privileges, e.g. `CAP_SYS_NICE` on
+/// Linux, the workers keep running with the default priority if it's denied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThreadPriority {
+    /// Keeps the default priority of the platform.
+    Normal,
+    /// Prefers the main and render threads over workers.
+    Low,
+    /// Prefers workers over other threads of the process.
+    High,
+}
+
+impl Default for ThreadPriority {
+    fn default() -> Self {
+        ThreadPriority::Normal
+    }
+}
+
+/// Checks if the priority hints and core affinity are supported on current platform.
+pub(crate) fn supported() -> bool {
+    platform::SUPPORTED
+}
+
+/// Applies the priority hint and pins the current thread to `core` if specified.
+pub(crate) fn apply(priority: ThreadPriority, core: Option<usize>) {
+    if priority != ThreadPriority::Normal && !platform::set_priority(priority) {
+        warn!(
+            "[Sched] Failed to set the priority of {:?} to {:?}.",
+            std::thread::current().name(),
+            priority
+        );
+    }
+
+    if let Some(core) = core {
+        if !platform::set_affinity(core) {
+            warn!(
+                "[Sched] Failed to pin {:?} to core {}.",
+                std::thread::current().name(),
+                core
+            );
+        }
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod platform {
+    use super::ThreadPriority;
+
+    pub const SUPPORTED: bool = true;
+
+    const PRIO_PROCESS: i32 = 0;
+    const CPU_SETSIZE: usize = 1024;
+
+    extern "C" {
+        fn setpriority(which: i32, who: u32, prio: i32) -> i32;
+        fn sched_setaffinity(pid: i32, size: usize, mask: *const u64) -> i32;
+    }
+
+    pub fn set_priority(priority: ThreadPriority) -> bool {
+        let nice = match priority {
+            ThreadPriority::Normal => 0,
+            ThreadPriority::Low => 10,
+            ThreadPriority::High => -10,
+        };
+
+        // The nice value is per-thread on linux, and `who = 0` refers to the calling thread.
+        unsafe { setpriority(PRIO_PROCESS, 0, nice) == 0 }
+    }
+
+    pub fn set_affinity(core: usize) -> bool {
+        if core >= CPU_SETSIZE {
+            return false;
+        }
+
+        let mut mask = [0u64; CPU_SETSIZE / 64];
+        mask[core / 64] |= 1 << (core % 64);
+
+        unsafe { sched_setaffinity(0, std::mem::size_of_val(&mask), mask.as_ptr()) == 0 }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::ThreadPriority;
+
+    pub const SUPPORTED: bool = true;
+
+    const THREAD_PRIORITY_BELOW_NORMAL: i32 = -1;
+    const THREAD_PRIORITY_ABOVE_NORMAL: i32 = 1;
+
+    type Handle = *mut std::os::raw::c_void;
+
+    extern "system" {
+        fn GetCurrentThread() -> Handle;
+        fn SetThreadPriority(thread: Handle, priority: i32) -> i32;
+        fn SetThreadAffinityMask(thread: Handle, mask: usize) -> usize;
+    }
+
+    pub fn set_priority(priority: ThreadPriority) -> bool {
+        let v = match priority {
+            ThreadPriority::Normal => 0,
+            ThreadPriority::Low => THREAD_PRIORITY_BELOW_NORMAL,
+            ThreadPriority::High => THREAD_PRIORITY_ABOVE_NORMAL,
+        };
+
+        unsafe { SetThreadPriority(GetCurrentThread(), v) != 0 }
+    }
+
+    pub fn set_affinity(core: usize) -> bool {
+        if core >= std::mem::size_of::<usize>() * 8 {
+            return false;
+        }
+
+        unsafe { SetThreadAffinityMask(GetCurrentThread(), 1 << core) != 0 }
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "windows")))]
+mod platform {
+    use super::ThreadPriority;
+
+    // Neither macOS nor the web expose core pinning, and the priority of threads is
+    // managed by the QoS classes of the platform.
+    pub const SUPPORTED: bool = false;
+
+    pub fn set_priority(_: ThreadPriority) -> bool {
+        false
+    }
+
+    pub fn set_affinity(_: usize) -> bool {
+        false
+    }
+}
@@ -0,0 +1,344 @@
+//! Utilities to test gameplay code without a window or GPU.
+//!
+//! `setup` starts the engine in headless mode, where the video system accepts every command
+//! but draws nothing, and `advance` runs the lifecycle listeners for exactly one frame. It
+//! makes frame-by-frame assertions deterministic.
+//!
+//! Resources could be served from memory with `MockResources`, which builds a manifest for
+//! the files added, so the loaders are exercised the same way they are in game.
+//!
+//! Gameplay systems could be tested against a minimal `World` of entities and registered
+//! components, which needs neither the engine nor a GPU. Systems are run on the current
+//! thread with a fixed delta time, and components are visited in the order of entities.
+//!
+//! ```rust
+//! use crayon::prelude::*;
+//! use crayon::testing::{self, MockResources};
+//!
+//! let mut res = MockResources::new();
+//! let uuid = res.add("level.json", b"{}");
+//!
+//! let mut params = Params::default();
+//! params.res = res.params();
+//! testing::setup(params).unwrap();
+//!
+//! assert_eq!(crayon::res::find("res:level.json"), Some(uuid));
+//! testing::advance().unwrap();
+//! ```
+//!
+//! ```rust
+//! use std::time::Duration;
+//! use crayon::testing::World;
+//!
+//! #[derive(Debug, PartialEq)]
+//! struct Health(u32);
+//!
+//! let mut world = World::new();
+//! world.register::<Health>();
+//!
+//! let ent = world.create();
+//! world.add(ent, Health(3));
+//!
+//! let mut poison = |world: &mut World, _: Duration| {
+//!     for (_, v) in world.iter_mut::<Health>() {
+//!         v.0 = v.0.saturating_sub(1);
+//!     }
+//! };
+//!
+//! world.run(&mut poison, 2, Duration::from_millis(16));
+//! world.assert_component(ent, &Health(1));
+//! ```
+
+use std::any::{Any, TypeId};
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::time::Duration;
+
+use byteorder::{BigEndian, ByteOrder};
+use uuid::Uuid;
+
+use crate::application::Params;
+use crate::errors::*;
+use crate::res::manifest::{Manifest, ManifestItem, MAGIC, NAME};
+use crate::res::vfs::memory::Memory;
+use crate::res::ResourceParams;
+use crate::sched::prelude::LatchProbe;
+use crate::utils::handle::Handle;
+use crate::utils::handle_pool::HandlePool;
+use crate::utils::hash::FastHashMap;
+use crate::utils::time::Timestamp;
+
+const SCHEMA: &str = "mem";
+const SHORTCUT: &str = "res:";
+
+/// Setup the engine in headless mode, and waits until the manifests of resources are
+/// loaded.
+pub fn setup(params: Params) -> Result<()> {
+    let dirs = params.res.dirs.clone();
+    crate::application::setup_headless(params)?;
+
+    let latch = crate::res::inside::load_manifests(dirs)?;
+    let ts = Timestamp::now();
+
+    while !latch.is_set() {
+        if ts.elapsed() > Duration::from_secs(5) {
+            bail!("Timeout when loading the manifests of resources.");
+        }
+
+        crate::application::advance()?;
+    }
+
+    Ok(())
+}
+
+/// Runs the lifecycle listeners for exactly one frame.
+#[inline]
+pub fn advance() -> Result<()> {
+    crate::application::advance()
+}
+
+/// Runs the lifecycle listeners for frames until the closure returns true, or fails after
+/// `max_frames`.
+pub fn advance_until<T>(max_frames: usize, mut func: T) -> Result<()>
+where
+    T: FnMut() -> bool,
+{
+    for _ in 0..max_frames {
+        if func() {
+            return Ok(());
+        }
+
+        advance()?;
+    }
+
+    if func() {
+        return Ok(());
+    }
+
+    bail!(
+        "The condition is not satisfied after {} frames.",
+        max_frames
+    );
+}
+
+/// Resources served from memory, which could be located with the `res:` shortcut.
+#[derive(Debug, Clone, Default)]
+pub struct MockResources {
+    vfs: Memory,
+    manifest: Manifest,
+}
+
+impl MockResources {
+    pub fn new() -> Self {
+        MockResources::default()
+    }
+
+    /// Adds a resource with its filename, and returns the UUID assigned to it.
+    pub fn add<T: AsRef<str>>(&mut self, filename: T, bytes: &[u8]) -> Uuid {
+        // Assigns sequential UUIDs, so they are stable between runs.
+        let mut raw = [0; 16];
+        BigEndian::write_u64(&mut raw[8..], self.manifest.items.len() as u64 + 1);
+        let uuid = Uuid::from_slice(&raw).unwrap();
+
+        let no_dependencies: &[usize] = &[];
+        let item = ManifestItem {
+            filename: self.manifest.buf.extend_from_str(filename.as_ref()),
+            dependencies: self.manifest.buf.extend_from_slice(no_dependencies),
+            uuid,
+        };
+
+        self.manifest.items.push(item);
+        self.vfs.insert(format!("{:X}", uuid.to_simple()), bytes);
+        uuid
+    }
+
+    /// Creates the setup parameters of resource system, which serves the resources
+    /// added so far.
+    pub fn params(&self) -> ResourceParams {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend(bincode::serialize(&self.manifest).unwrap());
+        self.vfs.insert(NAME, &bytes);
+
+        let mut params = ResourceParams::default();
+        params.schemas.add(SCHEMA, self.vfs.clone());
+        params
+            .shortcuts
+            .add(SHORTCUT, format!("{}://{}/", SCHEMA, SCHEMA))
+            .unwrap();
+        params.dirs.push(SHORTCUT.to_owned());
+        params
+    }
+}
+
+/// The systems that could be run by `World`, which is implemented for closures.
+pub trait System {
+    fn run(&mut self, world: &mut World, dt: Duration);
+}
+
+impl<T> System for T
+where
+    T: FnMut(&mut World, Duration),
+{
+    fn run(&mut self, world: &mut World, dt: Duration) {
+        self(world, dt)
+    }
+}
+
+trait Storage {
+    fn remove(&mut self, ent: Handle);
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+// The components are sorted by entities, which keeps the order of iterations stable.
+impl<T: 'static> Storage for BTreeMap<Handle, T> {
+    fn remove(&mut self, ent: Handle) {
+        BTreeMap::remove(self, &ent);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// A minimal world of entities and components to test gameplay systems with.
+pub struct World {
+    entities: HandlePool<Handle>,
+    components: FastHashMap<TypeId, Box<dyn Storage>>,
+}
+
+impl Default for World {
+    fn default() -> Self {
+        World {
+            entities: HandlePool::new(),
+            components: FastHashMap::default(),
+        }
+    }
+}
+
+impl World {
+    pub fn new() -> Self {
+        World::default()
+    }
+
+    /// Registers the component type `T`, which must be done before adding it to entities.
+    pub fn register<T: 'static>(&mut self) -> &mut Self {
+        self.components
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(BTreeMap::<Handle, T>::new()));
+        self
+    }
+
+    /// Creates an entity without components.
+    #[inline]
+    pub fn create(&mut self) -> Handle {
+        self.entities.create()
+    }
+
+    /// Checks if the entity is alive.
+    #[inline]
+    pub fn contains(&self, ent: Handle) -> bool {
+        self.entities.contains(ent)
+    }
+
+    /// Deletes the entity and all its components.
+    pub fn delete(&mut self, ent: Handle) -> bool {
+        if !self.entities.free(ent) {
+            return false;
+        }
+
+        for v in self.components.values_mut() {
+            v.remove(ent);
+        }
+
+        true
+    }
+
+    /// Adds the component to entity, and returns the previous one if exists.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entity is dead or `T` is not registered.
+    pub fn add<T: 'static>(&mut self, ent: Handle, v: T) -> Option<T> {
+        assert!(self.entities.contains(ent), "{:?} is invalid.", ent);
+        self.storage_mut::<T>().insert(ent, v)
+    }
+
+    /// Removes the component from entity.
+    pub fn remove<T: 'static>(&mut self, ent: Handle) -> Option<T> {
+        self.storage_mut::<T>().remove(&ent)
+    }
+
+    /// Gets the component of entity.
+    pub fn get<T: 'static>(&self, ent: Handle) -> Option<&T> {
+        self.storage::<T>().get(&ent)
+    }
+
+    /// Gets the mutable component of entity.
+    pub fn get_mut<T: 'static>(&mut self, ent: Handle) -> Option<&mut T> {
+        self.storage_mut::<T>().get_mut(&ent)
+    }
+
+    /// Iterates the entities with component `T` and their components, in the order of
+    /// entities.
+    pub fn iter<T: 'static>(&self) -> impl Iterator<Item = (Handle, &T)> {
+        self.storage::<T>().iter().map(|(&k, v)| (k, v))
+    }
+
+    /// Iterates the entities with component `T` and their mutable components, in the
+    /// order of entities.
+    pub fn iter_mut<T: 'static>(&mut self) -> impl Iterator<Item = (Handle, &mut T)> {
+        self.storage_mut::<T>().iter_mut().map(|(&k, v)| (k, v))
+    }
+
+    /// Runs `system` for `frames` times on the current thread with the fixed delta time
+    /// `dt`, so the results are reproducible.
+    pub fn run<S: System>(&mut self, system: &mut S, frames: usize, dt: Duration) {
+        for _ in 0..frames {
+            system.run(self, dt);
+        }
+    }
+
+    /// Asserts the component of entity equals to `expected`.
+    ///
+    /// # Panics
+    ///
+    /// Panics with both components if they are different, or the entity does not have
+    /// the component.
+    pub fn assert_component<T>(&self, ent: Handle, expected: &T)
+    where
+        T: PartialEq + Debug + 'static,
+    {
+        match self.get::<T>(ent) {
+            Some(v) => assert!(
+                v == expected,
+                "The component of {:?} is {:?}, but {:?} is expected.",
+                ent,
+                v,
+                expected
+            ),
+            None => panic!(
+                "{:?} does not have the component, but {:?} is expected.",
+                ent, expected
+            ),
+        }
+    }
+
+    fn storage<T: 'static>(&self) -> &BTreeMap<Handle, T> {
+        self.components
+            .get(&TypeId::of::<T>())
+            .and_then(|v| v.as_any().downcast_ref())
+            .expect("The component is not registered.")
+    }
+
+    fn storage_mut<T: 'static>(&mut self) -> &mut BTreeMap<Handle, T> {
+        self.components
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|v| v.as_any_mut().downcast_mut())
+            .expect("The component is not registered.")
+    }
+}
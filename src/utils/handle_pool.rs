@@ -19,12 +19,18 @@ impl Ord for InverseHandleIndex {
     }
 }
 
+/// The error of creating handles in a pool that has reached its limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Fail)]
+#[fail(display = "The pool is out of capacity ({} at most).", _0)]
+pub struct OutOfCapacity(pub usize);
+
 /// `HandlePool` manages the manipulations of a `Handle` collection, which are
 /// created with a continuous `index` field. It also have the ability to find
 /// out the current status of a specified `Handle`.
 pub struct HandlePool<T: HandleLike> {
     versions: Vec<HandleIndex>,
     frees: BinaryHeap<InverseHandleIndex>,
+    limit: Option<usize>,
     _marker: PhantomData<T>,
 }
 
@@ -33,6 +39,7 @@ impl<T: HandleLike> Default for HandlePool<T> {
         HandlePool {
             versions: Vec::new(),
             frees: BinaryHeap::new(),
+            limit: None,
             _marker: PhantomData::default(),
         }
     }
@@ -55,13 +62,55 @@ impl<T: HandleLike> HandlePool<T> {
         HandlePool {
             versions,
             frees,
+            limit: None,
+            _marker: PhantomData::default(),
+        }
+    }
+
+    /// Constructs a new `HandlePool` which holds at most `limit` alive handles. The
+    /// memory is allocated up-front, and the pool never grows beyond it.
+    pub fn with_limit(limit: usize) -> HandlePool<T> {
+        HandlePool {
+            versions: Vec::with_capacity(limit),
+            frees: BinaryHeap::with_capacity(limit),
+            limit: Some(limit),
             _marker: PhantomData::default(),
         }
     }
 
+    /// Gets the maximum number of alive handles, or `None` if the pool is unbounded.
+    #[inline]
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    /// Checks if the pool has reached its limit.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.limit.map(|v| self.len() >= v).unwrap_or(false)
+    }
+
     /// Creates a unused `Handle`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pool has reached its limit, use `try_create` for limited pools.
     pub fn create(&mut self) -> T {
-        if !self.frees.is_empty() {
+        match self.try_create() {
+            Ok(handle) => handle,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Creates a unused `Handle`. It fails only if the pool has reached its limit.
+    pub fn try_create(&mut self) -> Result<T, OutOfCapacity> {
+        if let Some(limit) = self.limit {
+            if self.len() >= limit {
+                return Err(OutOfCapacity(limit));
+            }
+        }
+
+        let handle = if !self.frees.is_empty() {
             // If we have available free slots.
             let index = self.frees.pop().unwrap().0 as usize;
             self.versions[index] += 1;
@@ -70,7 +119,9 @@ impl<T: HandleLike> HandlePool<T> {
             // Or we just spawn a new index and corresponding version.
             self.versions.push(1);
             T::new(self.versions.len() as HandleIndex - 1, 1)
-        }
+        };
+
+        Ok(handle)
     }
 
     /// Returns true if this `Handle` was created by `HandlePool`, and has not been
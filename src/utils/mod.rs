@@ -14,7 +14,7 @@ pub mod prelude {
     pub use super::data_buf::{DataBuffer, DataBufferPtr};
     pub use super::double_buf::DoubleBuf;
     pub use super::handle::{Handle, HandleIndex, HandleLike};
-    pub use super::handle_pool::HandlePool;
+    pub use super::handle_pool::{HandlePool, OutOfCapacity};
     pub use super::hash::{FastHashMap, FastHashSet};
     pub use super::hash_value::HashValue;
     pub use super::object_pool::ObjectPool;
@@ -1,5 +1,5 @@
 use super::handle::HandleLike;
-use super::handle_pool::HandlePool;
+use super::handle_pool::{HandlePool, OutOfCapacity};
 
 /// A named object collections. Every time u create or free a handle, a
 /// attached instance `T` will be created/ freed.
@@ -31,9 +31,42 @@ impl<H: HandleLike, T: Sized> ObjectPool<H, T> {
         }
     }
 
+    /// Constructs a new `ObjectPool` which holds at most `limit` objects.
+    pub fn with_limit(limit: usize) -> Self {
+        ObjectPool {
+            handles: HandlePool::with_limit(limit),
+            entries: Vec::with_capacity(limit),
+        }
+    }
+
+    /// Gets the maximum number of objects, or `None` if the pool is unbounded.
+    #[inline]
+    pub fn limit(&self) -> Option<usize> {
+        self.handles.limit()
+    }
+
+    /// Checks if the pool has reached its limit.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.handles.is_full()
+    }
+
     /// Creates a `T` and named it with `Handle`.
-    pub fn create(&mut self, mut value: T) -> H {
-        let handle = self.handles.create();
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pool has reached its limit, use `try_create` for limited pools.
+    pub fn create(&mut self, value: T) -> H {
+        match self.try_create(value) {
+            Ok(handle) => handle,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Creates a `T` and named it with `Handle`. It fails only if the pool has reached
+    /// its limit.
+    pub fn try_create(&mut self, mut value: T) -> Result<H, OutOfCapacity> {
+        let handle = self.handles.try_create()?;
 
         if handle.index() >= self.entries.len() as u32 {
             self.entries.push(value);
@@ -42,7 +75,7 @@ impl<H: HandleLike, T: Sized> ObjectPool<H, T> {
             ::std::mem::forget(value);
         }
 
-        handle
+        Ok(handle)
     }
 
     /// Returns mutable reference to internal value with name `Handle`.
@@ -0,0 +1,109 @@
+//! Decodes PNG and JPEG images at runtime, e.g. avatars or custom skins provided by
+//! players, which never go through the offline pipeline.
+
+use crate::errors::*;
+
+use super::texture::{TextureData, TextureFormat, TextureParams};
+
+/// The maximum width and height of decoded images. Images provided by players are not
+/// trusted, so the dimensions in headers are checked before anything is allocated.
+pub const MAX_DIMENSIONS: u32 = 8192;
+
+/// Decodes a PNG or JPEG image into RGBA8 pixels. The format is guessed from the content.
+pub fn decode(bytes: &[u8]) -> Result<(TextureParams, TextureData)> {
+    match dimensions(bytes) {
+        Some((w, h)) if w <= MAX_DIMENSIONS && h <= MAX_DIMENSIONS => {}
+        Some((w, h)) => bail!(
+            "[ImageDecoder] The dimensions {}x{} of image exceed the limit {}.",
+            w,
+            h,
+            MAX_DIMENSIONS
+        ),
+        None => bail!("[ImageDecoder] Failed to read the header of image."),
+    }
+
+    let image = match image::load_from_memory(bytes) {
+        Ok(v) => v.to_rgba(),
+        Err(err) => bail!("[ImageDecoder] Failed to decode image: {}.", err),
+    };
+
+    let mut params = TextureParams::default();
+    params.format = TextureFormat::RGBA8;
+    params.dimensions = image.dimensions().into();
+
+    let data = TextureData {
+        bytes: vec![image.into_raw().into_boxed_slice()],
+    };
+
+    Ok((params, data))
+}
+
+/// Reads the width and height from the header of a PNG or JPEG image.
+fn dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    const PNG_SIGNATURE: [u8; 8] = [0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a];
+
+    fn be16(v: &[u8]) -> u32 {
+        (u32::from(v[0]) << 8) | u32::from(v[1])
+    }
+
+    fn be32(v: &[u8]) -> u32 {
+        (be16(&v[0..2]) << 16) | be16(&v[2..4])
+    }
+
+    // The IHDR chunk always comes first.
+    if bytes.starts_with(&PNG_SIGNATURE) {
+        if bytes.len() < 24 || &bytes[12..16] != b"IHDR" {
+            return None;
+        }
+
+        return Some((be32(&bytes[16..20]), be32(&bytes[20..24])));
+    }
+
+    // Walks the segments until the start of frame.
+    if bytes.starts_with(&[0xff, 0xd8]) {
+        let mut i = 2;
+        while i + 4 <= bytes.len() {
+            if bytes[i] != 0xff {
+                return None;
+            }
+
+            let marker = bytes[i + 1];
+            match marker {
+                // Fill bytes before markers.
+                0xff => i += 1,
+                // Markers without payloads.
+                0x01 | 0xd0..=0xd7 => i += 2,
+                // SOF0-SOF15, except DHT, JPG and DAC.
+                0xc0..=0xcf if marker != 0xc4 && marker != 0xc8 && marker != 0xcc => {
+                    if i + 9 > bytes.len() {
+                        return None;
+                    }
+
+                    return Some((be16(&bytes[i + 7..i + 9]), be16(&bytes[i + 5..i + 7])));
+                }
+                _ => i += 2 + be16(&bytes[i + 2..i + 4]) as usize,
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn jpeg_dimensions() {
+        let mut bytes = vec![0xff, 0xd8];
+        // APP0 with 2 bytes of payload, and a fill byte.
+        bytes.extend_from_slice(&[0xff, 0xe0, 0x00, 0x04, 0x00, 0x00, 0xff]);
+        // SOF0 of a 640x480 image.
+        bytes.extend_from_slice(&[0xff, 0xc0, 0x00, 0x11, 0x08, 0x01, 0xe0, 0x02, 0x80]);
+        assert_eq!(dimensions(&bytes), Some((640, 480)));
+
+        assert_eq!(dimensions(&bytes[..10]), None);
+        assert_eq!(dimensions(&[0xff, 0xd8, 0x00, 0x00, 0x00]), None);
+        assert_eq!(dimensions(b"GIF89a"), None);
+    }
+}
@@ -27,6 +27,12 @@ pub struct MeshParams {
     pub sub_mesh_offsets: SmallVec<[usize; 8]>,
     /// Trivial bounding box of vertices.
     pub aabb: Aabb3<f32>,
+    /// Restarts the assembly of strips at the maximum value of `index_format` if the
+    /// platform supports it. This is a runtime option and is never serialized, so the
+    /// meshes loaded from files always disable it. Notes that WebGL2 always enables
+    /// primitive restart.
+    #[serde(skip)]
+    pub primitive_restart: bool,
 }
 
 /// Continuous data of vertices and its indices.
@@ -49,6 +55,7 @@ impl Default for MeshParams {
             num_idxes: 0,
             aabb: Aabb3::zero(),
             sub_mesh_offsets: SmallVec::new(),
+            primitive_restart: false,
         }
     }
 }
@@ -134,6 +141,16 @@ impl MeshPrimitive {
         }
     }
 
+    /// The number of vertices captured by transform feedback, when `indices` are drawn
+    /// with this primitive. Strips are decomposed into separated primitives.
+    pub fn assemble_feedback_verts(self, indices: u32) -> u32 {
+        match self {
+            MeshPrimitive::Points => indices,
+            MeshPrimitive::Lines | MeshPrimitive::LineStrip => self.assemble(indices) * 2,
+            MeshPrimitive::Triangles | MeshPrimitive::TriangleStrip => self.assemble(indices) * 3,
+        }
+    }
+
     pub fn assemble_triangles(self, indices: u32) -> u32 {
         match self {
             MeshPrimitive::Points | MeshPrimitive::Lines | MeshPrimitive::LineStrip => 0,
@@ -146,6 +163,15 @@ impl MeshPrimitive {
 /// Vertex indices can be either 16- or 32-bit. You should always prefer
 /// 16-bit indices over 32-bit indices, since the latter may have performance
 /// penalties on some platforms, and they take up twice as much memory.
+///
+/// 32-bit indices are always available on desktop OpenGL and WebGL2, but requires
+/// `GL_OES_element_index_uint` on OpenGL ES 2.0. Creating a mesh with `U32` indices on
+/// platforms without it fails.
+///
+/// When drawing strips of meshes with `MeshParams::primitive_restart`, the maximum value
+/// of the index format (`0xFFFF` or `0xFFFFFFFF`) restarts the primitive if the platform
+/// supports primitive restart. This makes it possible to render multiple strips with one
+/// draw call.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
 pub enum IndexFormat {
     U16,
@@ -160,6 +186,14 @@ impl IndexFormat {
         }
     }
 
+    /// Gets the special index value which restarts the assembly of strip primitives.
+    pub fn primitive_restart_index(self) -> u32 {
+        match self {
+            IndexFormat::U16 => u32::from(u16::max_value()),
+            IndexFormat::U32 => u32::max_value(),
+        }
+    }
+
     pub fn encode<T>(values: &[T]) -> &[u8]
     where
         T: Copy,
@@ -368,6 +402,28 @@ mod test {
         assert_eq!(element.normalized, true);
         assert_eq!(layout.element(Attribute::Normal), None);
     }
+
+    #[test]
+    fn feedback_verts() {
+        assert_eq!(MeshPrimitive::Points.assemble_feedback_verts(5), 5);
+        assert_eq!(MeshPrimitive::Lines.assemble_feedback_verts(4), 4);
+        assert_eq!(MeshPrimitive::LineStrip.assemble_feedback_verts(4), 6);
+        assert_eq!(MeshPrimitive::Triangles.assemble_feedback_verts(6), 6);
+        assert_eq!(MeshPrimitive::TriangleStrip.assemble_feedback_verts(5), 9);
+    }
+
+    #[test]
+    fn primitive_restart() {
+        let mut params = MeshParams::default();
+        assert!(!params.primitive_restart);
+
+        let bytes = bincode::serialize(&params).unwrap();
+        params.primitive_restart = true;
+        assert_eq!(bincode::serialize(&params).unwrap(), bytes);
+
+        let params: MeshParams = bincode::deserialize(&bytes).unwrap();
+        assert!(!params.primitive_restart);
+    }
 }
 
 #[macro_use]
@@ -1,13 +1,23 @@
+#[cfg(feature = "image")]
+pub mod image_decoder;
+pub mod query;
+pub mod sampler;
 pub mod shader;
 pub mod surface;
 pub mod texture;
+pub mod texture_array;
 pub mod texture_loader;
 #[macro_use]
 pub mod mesh;
 pub mod mesh_loader;
 
 pub mod prelude {
-    pub use super::surface::{SurfaceHandle, SurfaceParams, SurfaceScissor, SurfaceViewport};
+    pub use super::query::{OcclusionQueryHandle, OcclusionQueryParams};
+    pub use super::sampler::{SamplerHandle, SamplerParams};
+
+    pub use super::surface::{
+        DebugMode, SurfaceHandle, SurfaceParams, SurfaceScissor, SurfaceViewport,
+    };
 
     pub use super::shader::{
         Attribute, AttributeLayout, AttributeLayoutBuilder, BlendFactor, BlendValue, Comparison,
@@ -20,6 +30,8 @@ pub mod prelude {
         TextureFormat, TextureHandle, TextureHint, TextureParams, TextureWrap,
     };
 
+    pub use super::texture_array::{TextureArrayHandle, TextureArrayParams};
+
     pub use super::mesh::{
         IndexFormat, MeshData, MeshHandle, MeshHint, MeshIndex, MeshParams, MeshPrimitive,
         VertexFormat, VertexLayout,
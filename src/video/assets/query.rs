@@ -0,0 +1,22 @@
+//! Occlusion query object which counts whether any samples of the draw calls issued
+//! between its begin and end pass the depth test.
+//!
+//! The result of a query could be used to predicate later draw calls on GPU-side with
+//! `CommandBuffer::begin_conditional_render`, without reading it back on CPU.
+
+impl_handle!(OcclusionQueryHandle);
+
+/// The parameters of an occlusion query object.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct OcclusionQueryParams {
+    /// Allows the implementation to use a less precise test, which might report that
+    /// samples passed when they did not. It's usually faster, and good enough for
+    /// bounding-volume tests.
+    pub conservative: bool,
+}
+
+impl Default for OcclusionQueryParams {
+    fn default() -> Self {
+        OcclusionQueryParams { conservative: true }
+    }
+}
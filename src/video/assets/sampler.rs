@@ -0,0 +1,36 @@
+//! Sampler state object which describes how a texture is sampled, decoupled from the
+//! texture object itself. The same texture can be bound with different samplers.
+use crate::video::assets::shader::Comparison;
+use crate::video::assets::texture::{TextureFilter, TextureWrap};
+
+impl_handle!(SamplerHandle);
+
+/// The parameters of a sampler object.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SamplerParams {
+    /// Sets the wrap parameter for texture coordinates.
+    pub wrap: TextureWrap,
+    /// Specify how the texture is used whenever the pixel being sampled.
+    pub filter: TextureFilter,
+    /// Samples between mipmap levels. It should be disabled when sampling textures without
+    /// mipmaps, otherwise the texture is incomplete.
+    pub mipmap: bool,
+    /// A fixed bias value that is to be added to the level-of-detail parameter. It has no
+    /// effect on OpenGL ES and WebGL.
+    pub lod_bias: f32,
+    /// Compares the sampled depth value with the reference value, instead of returning
+    /// it directly. This is used to sample shadow maps.
+    pub compare: Option<Comparison>,
+}
+
+impl Default for SamplerParams {
+    fn default() -> Self {
+        SamplerParams {
+            wrap: TextureWrap::Clamp,
+            filter: TextureFilter::Linear,
+            mipmap: false,
+            lod_bias: 0.0,
+            compare: None,
+        }
+    }
+}
@@ -6,7 +6,9 @@ use std::str::FromStr;
 use crate::math::prelude::{Matrix2, Matrix3, Matrix4, Vector2, Vector3, Vector4};
 use crate::utils::prelude::{FastHashMap, HashValue};
 use crate::video::assets::mesh::VertexLayout;
+use crate::video::assets::sampler::SamplerHandle;
 use crate::video::assets::texture::{RenderTextureHandle, TextureHandle};
+use crate::video::assets::texture_array::TextureArrayHandle;
 use crate::video::errors::{Error, Result};
 use crate::video::{MAX_UNIFORM_VARIABLES, MAX_VERTEX_ATTRIBUTES};
 
@@ -19,6 +21,10 @@ pub struct ShaderParams {
     pub attributes: AttributeLayout,
     pub uniforms: UniformVariableLayout,
     pub state: RenderState,
+    /// The output variables of vertex shader that are captured with transform feedback.
+    /// They are written interleaved in the specified order, so the vertex layout of the
+    /// destination mesh should match them.
+    pub varyings: Vec<String>,
 }
 
 impl ShaderParams {
@@ -148,6 +154,76 @@ impl AttributeLayout {
 
         true
     }
+
+    /// Validates the active attributes reflected from a linked program, which are
+    /// described with their names and numbers of components. Every active attribute
+    /// should be declared in this layout with the same size.
+    pub fn validate_active_attributes<'a, T>(&self, active: T) -> Result<()>
+    where
+        T: IntoIterator<Item = (&'a str, u8)>,
+    {
+        for (name, size) in active {
+            // Skips built-in variables like `gl_VertexID`.
+            if name.starts_with("gl_") {
+                continue;
+            }
+
+            let attribute: Attribute = name.parse().map_err(|_| {
+                Error::ShaderInvalid(format!(
+                    "Attribute({}) is used in shader sources, but it's not a pre-defined `Attribute`.",
+                    name
+                ))
+            })?;
+
+            match self.iter().find(|v| v.0 == attribute) {
+                Some((_, declared, _)) => {
+                    if size != 0 && size != declared {
+                        return Err(Error::AttributeIncompatible {
+                            name: name.into(),
+                            expected: format!("{} components", declared),
+                            provided: format!("{} components in shader sources", size),
+                        });
+                    }
+                }
+                None => {
+                    return Err(Error::ShaderInvalid(format!(
+                        "Attribute({}) is used in shader sources, but not declared in `AttributeLayout`.",
+                        name
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validates that the `VertexLayout` provides all the required attributes with enough
+    /// components. Returns a descriptive error otherwise.
+    pub fn validate(&self, layout: &VertexLayout) -> Result<()> {
+        for (name, size, required) in self.iter() {
+            let element = layout.element(name).filter(|v| v.size > 0);
+
+            if let Some(element) = element {
+                if element.size < size {
+                    let name: &'static str = name.into();
+                    return Err(Error::AttributeIncompatible {
+                        name: name.into(),
+                        expected: format!("{} components", size),
+                        provided: format!("{} components of {:?}", element.size, element.format),
+                    });
+                }
+            } else if required {
+                let name: &'static str = name.into();
+                return Err(Error::AttributeIncompatible {
+                    name: name.into(),
+                    expected: format!("{} components", size),
+                    provided: "nothing".into(),
+                });
+            }
+        }
+
+        Ok(())
+    }
 }
 
 pub struct AttributeLayoutIter<'a> {
@@ -302,6 +378,7 @@ impl Default for RenderState {
 pub enum UniformVariableType {
     Texture,
     RenderTexture,
+    TextureArray,
     I32,
     F32,
     Vector2f,
@@ -312,12 +389,30 @@ pub enum UniformVariableType {
     Matrix4f,
 }
 
+impl UniformVariableType {
+    /// Checks if the two types could be bound to the same variable in shader sources.
+    /// Both `Texture` and `RenderTexture` are samplers.
+    pub fn is_compatible(self, rhs: UniformVariableType) -> bool {
+        match (self, rhs) {
+            (UniformVariableType::Texture, UniformVariableType::RenderTexture) => true,
+            (UniformVariableType::RenderTexture, UniformVariableType::Texture) => true,
+            (lhs, rhs) => lhs == rhs,
+        }
+    }
+}
+
 /// Uniform variable for video program object. Each matrix based `UniformVariable`
 /// is assumed to be supplied in row major order with a optional transpose.
 #[derive(Debug, Copy, Clone)]
 pub enum UniformVariable {
     Texture(TextureHandle),
     RenderTexture(RenderTextureHandle),
+    TextureArray(TextureArrayHandle),
+    /// Texture which is sampled with a separate sampler object, instead of the sampling
+    /// parameters of itself.
+    SampledTexture(TextureHandle, SamplerHandle),
+    /// Render texture which is sampled with a separate sampler object.
+    SampledRenderTexture(RenderTextureHandle, SamplerHandle),
     I32(i32),
     F32(f32),
     Vector2f([f32; 2]),
@@ -333,6 +428,9 @@ impl UniformVariable {
         match *self {
             UniformVariable::RenderTexture(_) => UniformVariableType::RenderTexture,
             UniformVariable::Texture(_) => UniformVariableType::Texture,
+            UniformVariable::TextureArray(_) => UniformVariableType::TextureArray,
+            UniformVariable::SampledRenderTexture(_, _) => UniformVariableType::RenderTexture,
+            UniformVariable::SampledTexture(_, _) => UniformVariableType::Texture,
             UniformVariable::I32(_) => UniformVariableType::I32,
             UniformVariable::F32(_) => UniformVariableType::F32,
             UniformVariable::Vector2f(_) => UniformVariableType::Vector2f,
@@ -357,6 +455,24 @@ impl Into<UniformVariable> for RenderTextureHandle {
     }
 }
 
+impl Into<UniformVariable> for TextureArrayHandle {
+    fn into(self) -> UniformVariable {
+        UniformVariable::TextureArray(self)
+    }
+}
+
+impl Into<UniformVariable> for (TextureHandle, SamplerHandle) {
+    fn into(self) -> UniformVariable {
+        UniformVariable::SampledTexture(self.0, self.1)
+    }
+}
+
+impl Into<UniformVariable> for (RenderTextureHandle, SamplerHandle) {
+    fn into(self) -> UniformVariable {
+        UniformVariable::SampledRenderTexture(self.0, self.1)
+    }
+}
+
 impl Into<UniformVariable> for i32 {
     fn into(self) -> UniformVariable {
         UniformVariable::I32(self)
@@ -477,6 +593,58 @@ impl UniformVariableLayout {
     {
         self.variables.get(&field.into()).map(|v| v.0.as_ref())
     }
+
+    /// Validates the active uniforms reflected from a linked program, which are described
+    /// with their names and types. Uniforms whose types could not be represented with
+    /// `UniformVariableType` should be passed with `None`, and are skipped.
+    pub fn validate_active_uniforms<'a, T>(&self, active: T) -> Result<()>
+    where
+        T: IntoIterator<Item = (&'a str, Option<UniformVariableType>)>,
+    {
+        for (name, tp) in active {
+            let name = name.trim_end_matches("[0]");
+            if let (Some(declared), Some(tp)) = (self.variable_type(name), tp) {
+                if !declared.is_compatible(tp) {
+                    return Err(Error::UniformIncompatible {
+                        name: name.into(),
+                        expected: format!("{:?}", declared),
+                        provided: format!("{:?} in shader sources", tp),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validates the uniform variables of a draw call. Variables with mismatched types are
+    /// always rejected, and the unknown fields are rejected only in `strict` mode.
+    pub fn validate(
+        &self,
+        variables: &[(HashValue<str>, UniformVariable)],
+        strict: bool,
+    ) -> Result<()> {
+        for &(field, variable) in variables {
+            match self.variables.get(&field) {
+                Some((name, tp)) => {
+                    if !tp.is_compatible(variable.variable_type()) {
+                        return Err(Error::UniformIncompatible {
+                            name: name.clone(),
+                            expected: format!("{:?}", tp),
+                            provided: format!("{:?}", variable.variable_type()),
+                        });
+                    }
+                }
+                None => {
+                    if strict {
+                        return Err(Error::UniformUndefined(format!("{:?}", field)));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Default)]
@@ -503,3 +671,89 @@ impl UniformVariableLayoutBuilder {
         self.0
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::video::assets::mesh::VertexFormat;
+
+    #[test]
+    fn validate() {
+        let attributes = AttributeLayout::build()
+            .with(Attribute::Position, 3)
+            .with_optional(Attribute::Texcoord0, 2)
+            .finish();
+
+        let layout = VertexLayout::build()
+            .with(Attribute::Position, VertexFormat::Float, 3, false)
+            .finish();
+        assert!(attributes.validate(&layout).is_ok());
+
+        let layout = VertexLayout::build()
+            .with(Attribute::Position, VertexFormat::Float, 2, false)
+            .with(Attribute::Texcoord0, VertexFormat::Float, 2, false)
+            .finish();
+        assert!(attributes.validate(&layout).is_err());
+
+        let layout = VertexLayout::build()
+            .with(Attribute::Normal, VertexFormat::Float, 3, false)
+            .finish();
+        assert!(attributes.validate(&layout).is_err());
+    }
+
+    #[test]
+    fn validate_active_attributes() {
+        let attributes = AttributeLayout::build()
+            .with(Attribute::Position, 3)
+            .with_optional(Attribute::Texcoord0, 2)
+            .finish();
+
+        let active = vec![("Position", 3), ("Texcoord0", 2), ("gl_VertexID", 0)];
+        assert!(attributes.validate_active_attributes(active).is_ok());
+
+        let active = vec![("Position", 2)];
+        assert!(attributes.validate_active_attributes(active).is_err());
+
+        let active = vec![("Normal", 3)];
+        assert!(attributes.validate_active_attributes(active).is_err());
+
+        let active = vec![("a_Position", 3)];
+        assert!(attributes.validate_active_attributes(active).is_err());
+    }
+
+    #[test]
+    fn validate_uniforms() {
+        let uniforms = UniformVariableLayout::build()
+            .with("u_MVPMatrix", UniformVariableType::Matrix4f)
+            .with("u_Texture", UniformVariableType::Texture)
+            .finish();
+
+        let active = vec![
+            ("u_MVPMatrix", Some(UniformVariableType::Matrix4f)),
+            ("u_Texture", Some(UniformVariableType::Texture)),
+            ("u_Unknown", None),
+        ];
+        assert!(uniforms.validate_active_uniforms(active).is_ok());
+
+        let active = vec![("u_MVPMatrix", Some(UniformVariableType::Matrix3f))];
+        assert!(uniforms.validate_active_uniforms(active).is_err());
+
+        let vars = [(
+            "u_MVPMatrix".into(),
+            UniformVariable::Matrix4f([[0.0; 4]; 4], false),
+        )];
+        assert!(uniforms.validate(&vars, true).is_ok());
+
+        let vars = [("u_MVPMatrix".into(), UniformVariable::F32(0.0))];
+        assert!(uniforms.validate(&vars, false).is_err());
+
+        let vars = [("u_MVPMatrx".into(), UniformVariable::F32(0.0))];
+        assert!(uniforms.validate(&vars, false).is_ok());
+        assert!(uniforms.validate(&vars, true).is_err());
+
+        // Both textures and render textures could be bound to samplers.
+        let rt = RenderTextureHandle::default();
+        let vars = [("u_Texture".into(), UniformVariable::RenderTexture(rt))];
+        assert!(uniforms.validate(&vars, true).is_ok());
+    }
+}
@@ -18,6 +18,8 @@ pub struct SurfaceParams {
     pub(crate) clear_color: Option<Color<f32>>,
     pub(crate) clear_depth: Option<f32>,
     pub(crate) clear_stencil: Option<i32>,
+    pub(crate) amortization: u32,
+    pub(crate) debug_mode: Option<DebugMode>,
 }
 
 impl Default for SurfaceParams {
@@ -28,6 +30,8 @@ impl Default for SurfaceParams {
             clear_color: Some(Color::black()),
             clear_depth: Some(1.0),
             clear_stencil: None,
+            amortization: 1,
+            debug_mode: None,
         }
     }
 }
@@ -76,6 +80,48 @@ impl SurfaceParams {
         self.clear_depth = depth.into();
         self.clear_stencil = stentil.into();
     }
+
+    /// Spreads the draw calls submitted to this surface across `frames` frames. Each
+    /// frame only a slice of the submitted draw calls is executed, and the surface is
+    /// cleared only at the start of a round, so the results of expensive passes (like
+    /// shadow cascades or reflection probes) accumulate over several frames.
+    ///
+    /// Use `video::is_surface_completed` to check if a round has been finished.
+    #[inline]
+    pub fn set_amortization(&mut self, frames: u32) {
+        self.amortization = frames.max(1);
+    }
+
+    /// Sets the debug visualization of this surface, which overrides the global one set
+    /// by `video::set_debug_mode`. `None` follows the global one.
+    #[inline]
+    pub fn set_debug_mode<T: Into<Option<DebugMode>>>(&mut self, mode: T) {
+        self.debug_mode = mode.into();
+    }
+}
+
+/// The debug visualizations of draw calls, which help to find out the content that is
+/// expensive to render.
+///
+/// # Platform-specific
+///
+/// OpenGL ES and WebGL have no `glPolygonMode`, where the wireframe is drawn by variants of
+/// shaders with barycentric coordinates. The meshes are read back and expanded for them,
+/// which requires OpenGL ES 3.0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DebugMode {
+    /// Draws as usual.
+    None,
+    /// Draws the edges of triangles only.
+    Wireframe,
+    /// Replaces the surface with a heat map of how many times each pixel is drawn, from
+    /// dark blue for once to white for 8 times or more. The render target must have a
+    /// stencil buffer.
+    Overdraw,
+    /// Replaces the textures with ones whose mipmap levels are filled with red, yellow,
+    /// green, cyan, blue and magenta, starting from level 0. Stretched or blurry textures
+    /// show up as the colors of wrong levels.
+    MipLevels,
 }
 
 /// Defines a rectangle, called the scissor box, in window coordinates. The test is
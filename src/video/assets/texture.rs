@@ -64,6 +64,10 @@ pub struct RenderTextureParams {
     pub filter: TextureFilter,
     pub dimensions: Vector2<u32>,
     pub sampler: bool,
+    /// Fits the dimensions to the framebuffer of window in pixels. The render texture is
+    /// recreated whenever the window is resized, or `video::reconfigure` is called with new
+    /// dimensions.
+    pub fit_window: bool,
 }
 
 impl Default for RenderTextureParams {
@@ -74,6 +78,7 @@ impl Default for RenderTextureParams {
             filter: TextureFilter::Linear,
             dimensions: Vector2::new(0, 0),
             sampler: true,
+            fit_window: false,
         }
     }
 }
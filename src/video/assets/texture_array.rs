@@ -0,0 +1,63 @@
+//! Array of same-sized 2D textures. All the layers are bound with one texture unit, and
+//! addressed by index in shaders with `sampler2DArray`.
+use crate::math::prelude::Vector2;
+use crate::video::assets::texture::{TextureFilter, TextureFormat, TextureWrap};
+use crate::video::errors::{Error, Result};
+
+impl_handle!(TextureArrayHandle);
+
+/// The parameters of a texture array object.
+#[derive(Debug, Copy, Clone)]
+pub struct TextureArrayParams {
+    /// Sets the wrap parameter for all the layers.
+    pub wrap: TextureWrap,
+    /// Specify how the texture is used whenever the pixel being sampled.
+    pub filter: TextureFilter,
+    /// Sets the format of data, compressed formats are not supported.
+    pub format: TextureFormat,
+    /// Sets the dimensions of each layer.
+    pub dimensions: Vector2<u32>,
+    /// The number of layers.
+    pub layers: u32,
+}
+
+impl Default for TextureArrayParams {
+    fn default() -> Self {
+        TextureArrayParams {
+            format: TextureFormat::RGBA8,
+            wrap: TextureWrap::Clamp,
+            filter: TextureFilter::Linear,
+            dimensions: Vector2::new(0, 0),
+            layers: 0,
+        }
+    }
+}
+
+impl TextureArrayParams {
+    pub fn validate(&self) -> Result<()> {
+        if self.format.compressed() {
+            return Err(Error::TextureArrayInvalid(format!(
+                "Compressed format {:?} is not supported.",
+                self.format
+            )));
+        }
+
+        if self.layers == 0 || self.dimensions.x == 0 || self.dimensions.y == 0 {
+            return Err(Error::TextureArrayInvalid(
+                "Dimensions and layers should be non-zero.".into(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Validates the data that is going to be uploaded to `layer`, which should cover the
+    /// whole layer exactly.
+    pub fn validate_layer(&self, layer: u32, data: &[u8]) -> Result<()> {
+        if layer >= self.layers || data.len() != self.format.size(self.dimensions) as usize {
+            return Err(Error::OutOfBounds);
+        }
+
+        Ok(())
+    }
+}
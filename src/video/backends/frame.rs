@@ -3,6 +3,7 @@ use crate::math::prelude::{Aabb2, Vector2};
 use crate::utils::prelude::{DataBuffer, DataBufferPtr, HashValue};
 
 use super::super::assets::prelude::*;
+use super::super::FrameLatency;
 use super::Visitor;
 
 type VarsPtr = DataBufferPtr<[(HashValue<str>, UniformVariable)]>;
@@ -10,10 +11,19 @@ type BytesPtr = DataBufferPtr<[u8]>;
 
 #[derive(Debug, Clone)]
 pub enum Command {
-    Bind(SurfaceHandle),
+    Bind(SurfaceHandle, bool),
     Draw(ShaderHandle, MeshHandle, MeshIndex, VarsPtr),
+    Capture(ShaderHandle, MeshHandle, MeshIndex, VarsPtr, MeshHandle),
     UpdateScissor(SurfaceScissor),
     UpdateViewport(SurfaceViewport),
+    UpdateTextureUploadBudget(usize),
+    UpdateFrameLatency(FrameLatency),
+    UpdateStrictUniforms(bool),
+    UpdateDebugMode(DebugMode),
+    BeginOcclusionQuery(OcclusionQueryHandle),
+    EndOcclusionQuery,
+    BeginConditionalRender(OcclusionQueryHandle),
+    EndConditionalRender,
 
     CreateSurface(Box<(SurfaceHandle, SurfaceParams)>),
     DeleteSurface(SurfaceHandle),
@@ -28,6 +38,16 @@ pub enum Command {
     CreateRenderTexture(Box<(RenderTextureHandle, RenderTextureParams)>),
     DeleteRenderTexture(RenderTextureHandle),
 
+    CreateTextureArray(Box<(TextureArrayHandle, TextureArrayParams)>),
+    UpdateTextureArray(TextureArrayHandle, u32, BytesPtr),
+    DeleteTextureArray(TextureArrayHandle),
+
+    CreateSampler(Box<(SamplerHandle, SamplerParams)>),
+    DeleteSampler(SamplerHandle),
+
+    CreateOcclusionQuery(OcclusionQueryHandle, OcclusionQueryParams),
+    DeleteOcclusionQuery(OcclusionQueryHandle),
+
     CreateMesh(Box<(MeshHandle, MeshParams, Option<MeshData>)>),
     UpdateVertexBuffer(MeshHandle, usize, BytesPtr),
     UpdateIndexBuffer(MeshHandle, usize, BytesPtr),
@@ -70,8 +90,8 @@ impl Frame {
             let (mut dc, mut tris) = (0, 0);
             for v in self.cmds.drain(..) {
                 match v {
-                    Command::Bind(surface) => {
-                        visitor.bind(surface, dimensions)?;
+                    Command::Bind(surface, clear) => {
+                        visitor.bind(surface, dimensions, clear)?;
                     }
 
                     Command::Draw(shader, mesh, mesh_index, ptr) => {
@@ -80,6 +100,12 @@ impl Frame {
                         tris += visitor.draw(shader, mesh, mesh_index, vars)?;
                     }
 
+                    Command::Capture(shader, mesh, mesh_index, ptr, dst) => {
+                        let vars = self.bufs.as_slice(ptr);
+                        dc += 1;
+                        visitor.capture(shader, mesh, mesh_index, vars, dst)?;
+                    }
+
                     Command::UpdateScissor(scissor) => {
                         visitor.update_surface_scissor(scissor)?;
                     }
@@ -88,6 +114,38 @@ impl Frame {
                         visitor.update_surface_viewport(view)?;
                     }
 
+                    Command::UpdateTextureUploadBudget(bytes) => {
+                        visitor.update_texture_upload_budget(bytes)?;
+                    }
+
+                    Command::UpdateFrameLatency(latency) => {
+                        visitor.update_frame_latency(latency)?;
+                    }
+
+                    Command::UpdateStrictUniforms(strict) => {
+                        visitor.update_strict_uniforms(strict)?;
+                    }
+
+                    Command::UpdateDebugMode(mode) => {
+                        visitor.update_debug_mode(mode)?;
+                    }
+
+                    Command::BeginOcclusionQuery(handle) => {
+                        visitor.begin_occlusion_query(handle)?;
+                    }
+
+                    Command::EndOcclusionQuery => {
+                        visitor.end_occlusion_query()?;
+                    }
+
+                    Command::BeginConditionalRender(handle) => {
+                        visitor.begin_conditional_render(handle)?;
+                    }
+
+                    Command::EndConditionalRender => {
+                        visitor.end_conditional_render()?;
+                    }
+
                     Command::CreateSurface(v) => {
                         visitor.create_surface(v.0, v.1)?;
                     }
@@ -125,6 +183,35 @@ impl Frame {
                         visitor.delete_render_texture(handle)?;
                     }
 
+                    Command::CreateTextureArray(v) => {
+                        visitor.create_texture_array(v.0, v.1)?;
+                    }
+
+                    Command::UpdateTextureArray(handle, layer, ptr) => {
+                        let data = self.bufs.as_slice(ptr);
+                        visitor.update_texture_array(handle, layer, data)?;
+                    }
+
+                    Command::DeleteTextureArray(handle) => {
+                        visitor.delete_texture_array(handle)?;
+                    }
+
+                    Command::CreateSampler(v) => {
+                        visitor.create_sampler(v.0, v.1)?;
+                    }
+
+                    Command::DeleteSampler(handle) => {
+                        visitor.delete_sampler(handle)?;
+                    }
+
+                    Command::CreateOcclusionQuery(handle, params) => {
+                        visitor.create_occlusion_query(handle, params)?;
+                    }
+
+                    Command::DeleteOcclusionQuery(handle) => {
+                        visitor.delete_occlusion_query(handle)?;
+                    }
+
                     Command::CreateMesh(v) => {
                         visitor.create_mesh(v.0, v.1, v.2)?;
                     }
@@ -171,6 +171,20 @@ extensions! {
     "GL_ARB_ES3_compatibility" => gl_arb_es3_compatibility,
     "GL_OES_compressed_ETC2_RGB8_texture" => gl_oes_compressed_etc2_rgb8_texture,
     "GL_OES_compressed_ETC2_RGBA8_texture" => gl_oes_compressed_etc2_rgba8_texture,
+    "GL_OES_element_index_uint" => gl_oes_element_index_uint,
+    "GL_ARB_sampler_objects" => gl_arb_sampler_objects,
+    "GL_ARB_pixel_buffer_object" => gl_arb_pixel_buffer_object,
+    "GL_EXT_texture_array" => gl_ext_texture_array,
+    "GL_ARB_sync" => gl_arb_sync,
+}
+
+/// Describes how the primitive restart is supported.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PrimitiveRestart {
+    /// The maximum value of index type is used as restart index (`GL_PRIMITIVE_RESTART_FIXED_INDEX`).
+    FixedIndex,
+    /// The restart index must be specified with `glPrimitiveRestartIndex`.
+    Index,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -272,6 +286,97 @@ impl Capabilities {
         }
     }
 
+    /// Checks if 32-bit unsigned integer indices are supported.
+    pub fn has_u32_index(&self) -> bool {
+        match self.version {
+            Version::GL(_, _) => true,
+            Version::ES(major, _) => major >= 3 || self.extensions.gl_oes_element_index_uint,
+        }
+    }
+
+    /// Checks if sampler objects are supported.
+    pub fn has_sampler_objects(&self) -> bool {
+        self.version >= Version::GL(3, 3)
+            || self.version >= Version::ES(3, 0)
+            || self.extensions.gl_arb_sampler_objects
+    }
+
+    /// Checks if 2D texture arrays are supported.
+    pub fn has_texture_array(&self) -> bool {
+        self.version >= Version::GL(3, 0)
+            || self.version >= Version::ES(3, 0)
+            || self.extensions.gl_ext_texture_array
+    }
+
+    /// Checks if pixel data could be transfered from buffer objects.
+    pub fn has_pixel_buffer_objects(&self) -> bool {
+        self.version >= Version::GL(2, 1)
+            || self.version >= Version::ES(3, 0)
+            || self.extensions.gl_arb_pixel_buffer_object
+    }
+
+    /// Checks if polygons could be rasterized as lines with `glPolygonMode`.
+    pub fn has_polygon_mode(&self) -> bool {
+        match self.version {
+            Version::GL(_, _) => true,
+            _ => false,
+        }
+    }
+
+    /// Checks if the contents of buffers could be read back with `glMapBufferRange`.
+    pub fn has_map_buffer_range(&self) -> bool {
+        self.version >= Version::GL(3, 0)
+            || self.version >= Version::ES(3, 0)
+            || self.extensions.gl_arb_map_buffer_range
+    }
+
+    /// Checks if fences could be inserted into the command stream to wait on.
+    pub fn has_fence_sync(&self) -> bool {
+        self.version >= Version::GL(3, 2)
+            || self.version >= Version::ES(3, 0)
+            || self.extensions.gl_arb_sync
+    }
+
+    /// Checks if the vertices processed by vertex shaders could be captured into buffers.
+    /// GLES 3.0 and 3.1 forbid indexed draw calls while transform feedback is active, which
+    /// all the draw calls of meshes are, so it requires GLES 3.2 there.
+    pub fn has_transform_feedback(&self) -> bool {
+        self.version >= Version::GL(3, 0) || self.version >= Version::ES(3, 2)
+    }
+
+    /// Checks if the draw calls could be predicated on the results of occlusion queries.
+    pub fn has_conditional_render(&self) -> bool {
+        self.version >= Version::GL(3, 0)
+    }
+
+    /// Gets the target of occlusion queries, `None` if not available.
+    pub fn occlusion_query_target(&self, conservative: bool) -> Option<GLenum> {
+        if conservative && (self.version >= Version::GL(4, 3) || self.version >= Version::ES(3, 0))
+        {
+            Some(gl::ANY_SAMPLES_PASSED_CONSERVATIVE)
+        } else if self.version >= Version::GL(3, 3) || self.version >= Version::ES(3, 0) {
+            Some(gl::ANY_SAMPLES_PASSED)
+        } else if let Version::GL(_, _) = self.version {
+            Some(gl::SAMPLES_PASSED)
+        } else {
+            None
+        }
+    }
+
+    /// Gets the way primitive restart is supported, `None` if not available.
+    pub fn primitive_restart(&self) -> Option<PrimitiveRestart> {
+        if self.version >= Version::GL(4, 3)
+            || self.version >= Version::ES(3, 0)
+            || self.extensions.gl_arb_es3_compatibility
+        {
+            Some(PrimitiveRestart::FixedIndex)
+        } else if self.version >= Version::GL(3, 1) {
+            Some(PrimitiveRestart::Index)
+        } else {
+            None
+        }
+    }
+
     #[inline]
     unsafe fn parse_str(id: GLenum) -> Result<String> {
         let s = gl::GetString(gl::RENDERER);
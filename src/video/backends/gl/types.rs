@@ -89,6 +89,57 @@ impl From<IndexFormat> for GLenum {
     }
 }
 
+/// Gets the number of components of a vertex attribute type, 0 if its not a float vector.
+pub fn components(tp: GLenum) -> u8 {
+    match tp {
+        gl::FLOAT => 1,
+        gl::FLOAT_VEC2 => 2,
+        gl::FLOAT_VEC3 => 3,
+        gl::FLOAT_VEC4 => 4,
+        _ => 0,
+    }
+}
+
+/// Gets the primitive mode of transform feedback, strips are captured as separated
+/// primitives.
+pub fn feedback_primitive(primitive: MeshPrimitive) -> GLenum {
+    match primitive {
+        MeshPrimitive::Points => gl::POINTS,
+        MeshPrimitive::Lines | MeshPrimitive::LineStrip => gl::LINES,
+        MeshPrimitive::Triangles | MeshPrimitive::TriangleStrip => gl::TRIANGLES,
+    }
+}
+
+/// Gets the size in bytes of a transform feedback varying type, 0 if its not supported.
+pub fn varying_bytes(tp: GLenum) -> usize {
+    match tp {
+        gl::FLOAT | gl::INT | gl::UNSIGNED_INT => 4,
+        gl::FLOAT_VEC2 | gl::INT_VEC2 | gl::UNSIGNED_INT_VEC2 => 8,
+        gl::FLOAT_VEC3 | gl::INT_VEC3 | gl::UNSIGNED_INT_VEC3 => 12,
+        gl::FLOAT_VEC4 | gl::INT_VEC4 | gl::UNSIGNED_INT_VEC4 | gl::FLOAT_MAT2 => 16,
+        gl::FLOAT_MAT3 => 36,
+        gl::FLOAT_MAT4 => 64,
+        _ => 0,
+    }
+}
+
+/// Gets the `UniformVariableType` of a uniform type, `None` if its not supported.
+pub fn uniform_variable_type(tp: GLenum) -> Option<UniformVariableType> {
+    match tp {
+        gl::SAMPLER_2D => Some(UniformVariableType::Texture),
+        gl::SAMPLER_2D_ARRAY => Some(UniformVariableType::TextureArray),
+        gl::INT | gl::BOOL => Some(UniformVariableType::I32),
+        gl::FLOAT => Some(UniformVariableType::F32),
+        gl::FLOAT_VEC2 => Some(UniformVariableType::Vector2f),
+        gl::FLOAT_VEC3 => Some(UniformVariableType::Vector3f),
+        gl::FLOAT_VEC4 => Some(UniformVariableType::Vector4f),
+        gl::FLOAT_MAT2 => Some(UniformVariableType::Matrix2f),
+        gl::FLOAT_MAT3 => Some(UniformVariableType::Matrix3f),
+        gl::FLOAT_MAT4 => Some(UniformVariableType::Matrix4f),
+        _ => None,
+    }
+}
+
 pub fn texture_format(format: TextureFormat, caps: &Capabilities) -> (GLenum, GLenum, GLenum) {
     let sized = match caps.version {
         Version::GL(_, _) => true,
@@ -10,11 +10,85 @@ use crate::utils::hash::{FastHashMap, FastHashSet};
 use crate::utils::hash_value::HashValue;
 
 use super::super::super::assets::prelude::*;
-use super::super::utils::DataVec;
+use super::super::super::FrameLatency;
+use super::super::utils::{
+    expand_wireframe, wireframe_sources, DataVec, StagedUpload, UploadQueue, DEFAULT_UPLOAD_BUDGET,
+    MIP_COLORS, OVERDRAW_COLORS, STAGING_THRESHOLD, WIREFRAME_ATTRIBUTE,
+};
 use super::super::{UniformVar, Visitor};
-use super::capabilities::{Capabilities, Version};
+use super::capabilities::{Capabilities, PrimitiveRestart, Version};
 use super::types;
 
+/// The maximum nanoseconds to wait on the fence of previous frame.
+const FENCE_TIMEOUT: GLuint64 = 1_000_000_000;
+// The sources of heat map shaders in GLSL ES 1.00, GLSL 1.10 and GLSL 1.50, since the core
+// profile of desktop GL rejects `#version 100`.
+const HEAT_MAP_VS_100: &str = r#"
+#version 100
+precision lowp float;
+
+attribute vec2 Position;
+
+void main() {
+    gl_Position = vec4(Position, 0.0, 1.0);
+}
+"#;
+
+const HEAT_MAP_FS_100: &str = r#"
+#version 100
+precision lowp float;
+
+uniform vec4 u_Color;
+
+void main() {
+    gl_FragColor = u_Color;
+}
+"#;
+
+const HEAT_MAP_VS_110: &str = r#"
+#version 110
+
+attribute vec2 Position;
+
+void main() {
+    gl_Position = vec4(Position, 0.0, 1.0);
+}
+"#;
+
+const HEAT_MAP_FS_110: &str = r#"
+#version 110
+
+uniform vec4 u_Color;
+
+void main() {
+    gl_FragColor = u_Color;
+}
+"#;
+
+const HEAT_MAP_VS_150: &str = r#"
+#version 150
+
+in vec2 Position;
+
+void main() {
+    gl_Position = vec4(Position, 0.0, 1.0);
+}
+"#;
+
+const HEAT_MAP_FS_150: &str = r#"
+#version 150
+
+uniform vec4 u_Color;
+out vec4 o_Color;
+
+void main() {
+    o_Color = u_Color;
+}
+"#;
+
+/// The number of pixel buffer objects in the staging ring, one for each frame in flight.
+const STAGING_FRAMES: usize = 3;
+
 #[derive(Debug, Clone)]
 struct GLSurfaceData {
     handle: SurfaceHandle,
@@ -30,6 +104,11 @@ struct GLShaderData {
     params: ShaderParams,
     uniforms: RefCell<FastHashMap<HashValue<str>, GLint>>,
     attributes: RefCell<FastHashMap<HashValue<str>, GLint>>,
+    /// The size in bytes of the captured varyings of a vertex.
+    feedback_stride: usize,
+    /// The source of vertex shader, which is kept to make the wireframe variant on backends
+    /// without `glPolygonMode`.
+    vs: Option<String>,
 }
 
 impl GLShaderData {
@@ -83,6 +162,7 @@ struct GLTextureData {
     handle: TextureHandle,
     id: GLuint,
     params: TextureParams,
+    levels: u32,
     allocated: RefCell<bool>,
 }
 
@@ -93,10 +173,30 @@ struct GLRenderTextureData {
     params: RenderTextureParams,
 }
 
+#[derive(Debug, Clone)]
+struct GLTextureArrayData {
+    id: GLuint,
+    params: TextureArrayParams,
+}
+
+#[derive(Debug, Copy, Clone)]
+struct GLSamplerData {
+    id: GLuint,
+}
+
+#[derive(Debug, Clone)]
+struct GLOcclusionQueryData {
+    id: GLuint,
+    target: GLenum,
+    issued: bool,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 enum Sampler {
     RenderTexture(RenderTextureHandle),
     Texture(TextureHandle),
+    TextureArray(TextureArrayHandle),
+    MipLevels(TextureHandle),
 }
 
 struct GLMutableState {
@@ -110,16 +210,116 @@ struct GLMutableState {
     binded_vao: Option<(ShaderHandle, MeshHandle)>,
     binded_texture_index: usize,
     binded_textures: SmallVec<[Option<Sampler>; 8]>,
+    binded_samplers: SmallVec<[GLuint; 8]>,
+    binded_restart_index: Option<u32>,
+    binded_primitive_restart: bool,
+    active_query: Option<(OcclusionQueryHandle, GLenum)>,
+    conditional_render: Option<bool>,
+    discard_draws: bool,
+    capture: Option<MeshHandle>,
+}
+
+struct GLDebug {
+    mode: DebugMode,
+    active: DebugMode,
+    viewport: SurfaceViewport,
+    heat_map: Option<GLHeatMap>,
+    mip_textures: FastHashMap<(u32, u32, u32), GLuint>,
+    warned: bool,
+}
+
+/// The barycentric fallback of `DebugMode::Wireframe` on OpenGL ES. The variants of shaders
+/// and the expanded meshes are made lazily while the mode is active.
+#[derive(Default)]
+struct GLWireframe {
+    shaders: FastHashMap<ShaderHandle, Option<GLShaderData>>,
+    meshes: FastHashMap<MeshHandle, GLWireframeMesh>,
+    vaos: FastHashMap<(ShaderHandle, MeshHandle), GLuint>,
+}
+
+struct GLWireframeMesh {
+    vbo: GLuint,
+    barycentric: GLuint,
+}
+
+impl GLWireframe {
+    unsafe fn free_shader(&mut self, handle: ShaderHandle) {
+        self.free_vaos(|(s, _)| s == handle);
+
+        if let Some(Some(shader)) = self.shaders.remove(&handle) {
+            gl::DeleteProgram(shader.id);
+        }
+    }
+
+    unsafe fn free_mesh(&mut self, handle: MeshHandle) {
+        self.free_vaos(|(_, m)| m == handle);
+
+        if let Some(mesh) = self.meshes.remove(&handle) {
+            gl::DeleteBuffers(1, &mesh.vbo);
+            gl::DeleteBuffers(1, &mesh.barycentric);
+        }
+    }
+
+    unsafe fn clear(&mut self) {
+        self.free_vaos(|_| true);
+
+        for (_, shader) in self.shaders.drain() {
+            if let Some(shader) = shader {
+                gl::DeleteProgram(shader.id);
+            }
+        }
+
+        for (_, mesh) in self.meshes.drain() {
+            gl::DeleteBuffers(1, &mesh.vbo);
+            gl::DeleteBuffers(1, &mesh.barycentric);
+        }
+    }
+
+    unsafe fn free_vaos<T>(&mut self, predicate: T)
+    where
+        T: Fn((ShaderHandle, MeshHandle)) -> bool,
+    {
+        self.vaos.retain(|&k, vao| {
+            if predicate(k) {
+                gl::DeleteVertexArrays(1, vao as *mut u32);
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+struct GLHeatMap {
+    program: GLuint,
+    vao: GLuint,
+    vbo: GLuint,
+    color: GLint,
+}
+
+struct GLStaging {
+    index: usize,
+    pbos: [GLuint; STAGING_FRAMES],
+    queue: UploadQueue,
 }
 
 pub struct GLVisitor {
     state: GLMutableState,
+    staging: GLStaging,
     capabilities: Capabilities,
     surfaces: DataVec<GLSurfaceData>,
     shaders: DataVec<GLShaderData>,
     meshes: DataVec<GLMeshData>,
     textures: DataVec<GLTextureData>,
     render_textures: DataVec<GLRenderTextureData>,
+    texture_arrays: DataVec<GLTextureArrayData>,
+    samplers: DataVec<GLSamplerData>,
+    occlusion_queries: DataVec<GLOcclusionQueryData>,
+    latency: FrameLatency,
+    fence: Option<GLsync>,
+    strict_uniforms: bool,
+    debug: GLDebug,
+    wireframe: GLWireframe,
 }
 
 impl GLVisitor {
@@ -142,16 +342,48 @@ impl GLVisitor {
             binded_vao: None,
             binded_texture_index: 0,
             binded_textures: SmallVec::new(),
+            binded_samplers: SmallVec::new(),
+            binded_restart_index: None,
+            binded_primitive_restart: false,
+            active_query: None,
+            conditional_render: None,
+            discard_draws: false,
+            capture: None,
+        };
+
+        let staging = GLStaging {
+            index: 0,
+            pbos: [0; STAGING_FRAMES],
+            queue: UploadQueue::new(DEFAULT_UPLOAD_BUDGET),
         };
 
         let mut visitor = GLVisitor {
             state,
+            staging,
             capabilities,
             surfaces: DataVec::new(),
             shaders: DataVec::new(),
             meshes: DataVec::new(),
             textures: DataVec::new(),
             render_textures: DataVec::new(),
+            texture_arrays: DataVec::new(),
+            samplers: DataVec::new(),
+            occlusion_queries: DataVec::new(),
+            latency: FrameLatency::Finish,
+            fence: None,
+            strict_uniforms: true,
+            debug: GLDebug {
+                mode: DebugMode::None,
+                active: DebugMode::None,
+                viewport: SurfaceViewport {
+                    position: Vector2::new(0, 0),
+                    size: Vector2::new(0, 0),
+                },
+                heat_map: None,
+                mip_textures: FastHashMap::default(),
+                warned: false,
+            },
+            wireframe: GLWireframe::default(),
         };
 
         Self::reset_render_state(&mut visitor.state)?;
@@ -163,9 +395,70 @@ impl Visitor for GLVisitor {
     unsafe fn advance(&mut self) -> Result<()> {
         self.state.cleared_surfaces.clear();
         self.state.binded_surface = None;
+        self.upload_staged_textures()
+    }
+
+    unsafe fn update_texture_upload_budget(&mut self, bytes: usize) -> Result<()> {
+        self.staging.queue.budget = bytes;
+        Ok(())
+    }
+
+    unsafe fn update_frame_latency(&mut self, latency: FrameLatency) -> Result<()> {
+        self.latency = match latency {
+            FrameLatency::OneFrame if !self.capabilities.has_fence_sync() => {
+                warn!("Fences are not supported, falls back to `FrameLatency::Finish`.");
+                FrameLatency::Finish
+            }
+            _ => latency,
+        };
+
+        if self.latency != FrameLatency::OneFrame {
+            if let Some(fence) = self.fence.take() {
+                gl::DeleteSync(fence);
+            }
+        }
+
+        Ok(())
+    }
+
+    unsafe fn update_strict_uniforms(&mut self, strict: bool) -> Result<()> {
+        self.strict_uniforms = strict;
         Ok(())
     }
 
+    unsafe fn update_debug_mode(&mut self, mode: DebugMode) -> Result<()> {
+        self.debug.mode = mode;
+
+        if mode != DebugMode::Wireframe {
+            self.wireframe.clear();
+        }
+
+        if mode != DebugMode::MipLevels {
+            for v in self.state.binded_textures.iter_mut() {
+                if let Some(Sampler::MipLevels(_)) = *v {
+                    *v = None;
+                }
+            }
+
+            for (_, id) in self.debug.mip_textures.drain() {
+                gl::DeleteTextures(1, &id);
+            }
+        }
+
+        check()
+    }
+
+    fn uploaded_texture_bytes(&self) -> usize {
+        self.staging.queue.uploaded()
+    }
+
+    fn renderer(&self) -> String {
+        format!(
+            "{} ({})",
+            self.capabilities.renderer, self.capabilities.vendor
+        )
+    }
+
     unsafe fn create_surface(
         &mut self,
         handle: SurfaceHandle,
@@ -301,9 +594,19 @@ impl Visitor for GLVisitor {
         vs: &str,
         fs: &str,
     ) -> Result<()> {
+        if !params.varyings.is_empty() && !self.capabilities.has_transform_feedback() {
+            bail!("Transform feedback is not supported.");
+        }
+
+        let source = if self.capabilities.has_polygon_mode() {
+            None
+        } else {
+            Some(vs.to_owned())
+        };
+
         let vs = Self::compile(gl::VERTEX_SHADER, vs)?;
         let fs = Self::compile(gl::FRAGMENT_SHADER, fs)?;
-        let id = Self::link(&[vs, fs])?;
+        let id = Self::link(&[vs, fs], &params.varyings)?;
 
         gl::DetachShader(id, vs);
         gl::DeleteShader(vs);
@@ -311,12 +614,15 @@ impl Visitor for GLVisitor {
         gl::DeleteShader(fs);
         check()?;
 
+        let feedback_stride = Self::feedback_stride(id)?;
         let shader = GLShaderData {
             handle,
             id,
             params,
             uniforms: RefCell::new(FastHashMap::default()),
             attributes: RefCell::new(FastHashMap::default()),
+            feedback_stride,
+            vs: source,
         };
 
         for (name, _, _) in shader.params.attributes.iter() {
@@ -336,6 +642,20 @@ impl Visitor for GLVisitor {
             }
         }
 
+        let active = Self::active_attributes(id)?;
+        let active = active.iter().map(|(name, size)| (name.as_str(), *size));
+        if let Err(err) = shader.params.attributes.validate_active_attributes(active) {
+            gl::DeleteProgram(id);
+            bail!(err);
+        }
+
+        let active = Self::active_uniforms(id)?;
+        let active = active.iter().map(|(name, tp)| (name.as_str(), *tp));
+        if let Err(err) = shader.params.uniforms.validate_active_uniforms(active) {
+            gl::DeleteProgram(id);
+            bail!(err);
+        }
+
         self.shaders.create(handle, shader);
         Ok(())
     }
@@ -360,6 +680,7 @@ impl Visitor for GLVisitor {
             self.state.binded_shader = None;
         }
 
+        self.wireframe.free_shader(handle);
         gl::DeleteProgram(shader.id);
         check()
     }
@@ -385,6 +706,7 @@ impl Visitor for GLVisitor {
         let (internal_format, format, pixel_type) =
             types::texture_format(params.format, &self.capabilities);
         let compressed = params.format.compressed();
+        let levels = data.as_ref().map(|v| v.bytes.len().max(1)).unwrap_or(1) as u32;
         let mut allocated = false;
 
         if let Some(mut data) = data {
@@ -398,7 +720,38 @@ impl Visitor for GLVisitor {
                     params.dimensions.y as GLsizei,
                 );
 
-                if compressed {
+                let staged = !compressed
+                    && self.capabilities.has_pixel_buffer_objects()
+                    && data.bytes.iter().map(|v| v.len()).sum::<usize>() >= STAGING_THRESHOLD;
+
+                if staged {
+                    // Allocates the storage only, the pixels are uploaded in the following
+                    // frames through pixel buffer objects.
+                    for (i, v) in data.bytes.drain(..).enumerate() {
+                        gl::TexImage2D(
+                            gl::TEXTURE_2D,
+                            i as GLint,
+                            internal_format as GLint,
+                            dims.0,
+                            dims.1,
+                            0,
+                            format,
+                            pixel_type,
+                            ::std::ptr::null(),
+                        );
+
+                        self.staging.queue.push(StagedUpload {
+                            handle,
+                            level: i as u32,
+                            position: (0, 0),
+                            dimensions: (dims.0 as u32, dims.1 as u32),
+                            bytes: v,
+                        });
+
+                        dims.0 = (dims.0 / 2).max(1);
+                        dims.1 = (dims.1 / 2).max(1);
+                    }
+                } else if compressed {
                     for (i, v) in data.bytes.drain(..).enumerate() {
                         gl::CompressedTexImage2D(
                             gl::TEXTURE_2D,
@@ -445,6 +798,7 @@ impl Visitor for GLVisitor {
                 handle,
                 id,
                 params,
+                levels,
                 allocated: RefCell::new(allocated),
             },
         );
@@ -506,6 +860,21 @@ impl Visitor for GLVisitor {
             *texture.allocated.borrow_mut() = true;
         }
 
+        // Keeps the order of uploads if there are staged uploads of this texture.
+        if self.capabilities.has_pixel_buffer_objects()
+            && (data.len() >= STAGING_THRESHOLD || self.staging.queue.contains(handle))
+        {
+            self.staging.queue.push(StagedUpload {
+                handle,
+                level: 0,
+                position: (area.min.x, area.min.y),
+                dimensions: (area.dim().x, area.dim().y),
+                bytes: data.to_vec(),
+            });
+
+            return check();
+        }
+
         gl::TexSubImage2D(
             gl::TEXTURE_2D,
             0,
@@ -528,11 +897,12 @@ impl Visitor for GLVisitor {
             .ok_or_else(|| format_err!("{:?} is invalid.", handle))?;
 
         for v in self.state.binded_textures.iter_mut() {
-            if *v == Some(Sampler::Texture(handle)) {
+            if *v == Some(Sampler::Texture(handle)) || *v == Some(Sampler::MipLevels(handle)) {
                 *v = None;
             }
         }
 
+        self.staging.queue.remove(handle);
         gl::DeleteTextures(1, &texture.id);
         check()
     }
@@ -609,110 +979,353 @@ impl Visitor for GLVisitor {
         check()
     }
 
-    unsafe fn create_mesh(
+    unsafe fn create_texture_array(
         &mut self,
-        handle: MeshHandle,
-        params: MeshParams,
-        data: Option<MeshData>,
+        handle: TextureArrayHandle,
+        params: TextureArrayParams,
     ) -> Result<()> {
-        let vbo = self.create_buffer(
-            gl::ARRAY_BUFFER,
-            params.hint,
-            params.vertex_buffer_len(),
-            data.as_ref().map(|v| v.vptr.as_ref()),
-        )?;
+        if !self.capabilities.has_texture_array() {
+            bail!("Texture arrays are not supported.");
+        }
 
-        let ibo = self.create_buffer(
-            gl::ELEMENT_ARRAY_BUFFER,
-            params.hint,
-            params.index_buffer_len(),
-            data.as_ref().map(|v| v.iptr.as_ref()),
-        )?;
+        if !params.format.is_support(&self.capabilities) {
+            bail!(
+                "The GL Context does not support the texture format {:?}.",
+                params.format
+            );
+        }
 
-        self.meshes.create(
-            handle,
-            GLMeshData {
-                handle,
-                vbo,
-                ibo,
-                params,
-            },
-        );
+        let mut id = 0;
+        gl::GenTextures(1, &mut id);
+        assert!(id != 0);
 
-        Ok(())
-    }
+        let sampler = Some(Sampler::TextureArray(handle));
+        Self::bind_texture(&mut self.state, sampler, 0, id)?;
 
-    unsafe fn update_vertex_buffer(
-        &mut self,
-        handle: MeshHandle,
-        offset: usize,
-        data: &[u8],
-    ) -> Result<()> {
-        let vbo = {
-            let mesh = self
-                .meshes
-                .get(handle)
-                .ok_or_else(|| format_err!("{:?} is invalid.", handle))?;
+        let wrap: GLenum = params.wrap.into();
+        let filter = match params.filter {
+            TextureFilter::Nearest => gl::NEAREST,
+            TextureFilter::Linear => gl::LINEAR,
+        };
 
-            if mesh.params.hint == MeshHint::Immutable {
-                bail!("Trying to update immutable buffer");
-            }
+        let (wrap, filter) = (wrap as GLint, filter as GLint);
+        gl::TexParameteri(gl::TEXTURE_2D_ARRAY, gl::TEXTURE_WRAP_S, wrap);
+        gl::TexParameteri(gl::TEXTURE_2D_ARRAY, gl::TEXTURE_WRAP_T, wrap);
+        gl::TexParameteri(gl::TEXTURE_2D_ARRAY, gl::TEXTURE_MIN_FILTER, filter);
+        gl::TexParameteri(gl::TEXTURE_2D_ARRAY, gl::TEXTURE_MAG_FILTER, filter);
 
-            mesh.vbo
-        };
+        let (internal_format, format, pixel_type) =
+            types::texture_format(params.format, &self.capabilities);
 
-        Self::update_buffer(gl::ARRAY_BUFFER, vbo, offset, data)?;
+        gl::TexImage3D(
+            gl::TEXTURE_2D_ARRAY,
+            0,
+            internal_format as GLint,
+            params.dimensions.x as GLsizei,
+            params.dimensions.y as GLsizei,
+            params.layers as GLsizei,
+            0,
+            format,
+            pixel_type,
+            ::std::ptr::null(),
+        );
+
+        check()?;
+        self.texture_arrays
+            .create(handle, GLTextureArrayData { id, params });
         Ok(())
     }
 
-    unsafe fn update_index_buffer(
+    unsafe fn update_texture_array(
         &mut self,
-        handle: MeshHandle,
-        offset: usize,
+        handle: TextureArrayHandle,
+        layer: u32,
         data: &[u8],
     ) -> Result<()> {
-        let ibo = {
-            let mesh = self
-                .meshes
-                .get(handle)
-                .ok_or_else(|| format_err!("{:?} is invalid.", handle))?;
+        let texture = self
+            .texture_arrays
+            .get(handle)
+            .ok_or_else(|| format_err!("{:?} is invalid.", handle))?;
 
-            if mesh.params.hint == MeshHint::Immutable {
-                bail!("Trying to update immutable buffer");
-            }
+        texture.params.validate_layer(layer, data)?;
 
-            mesh.ibo
-        };
+        let sampler = Some(Sampler::TextureArray(handle));
+        Self::bind_texture(&mut self.state, sampler, 0, texture.id)?;
 
-        Self::update_buffer(gl::ELEMENT_ARRAY_BUFFER, ibo, offset, data)?;
-        Ok(())
+        let (_, format, pixel_type) =
+            types::texture_format(texture.params.format, &self.capabilities);
+
+        gl::TexSubImage3D(
+            gl::TEXTURE_2D_ARRAY,
+            0,
+            0,
+            0,
+            layer as GLint,
+            texture.params.dimensions.x as GLsizei,
+            texture.params.dimensions.y as GLsizei,
+            1,
+            format,
+            pixel_type,
+            data.as_ptr() as *const ::std::os::raw::c_void,
+        );
+
+        check()
     }
 
-    unsafe fn delete_mesh(&mut self, handle: MeshHandle) -> Result<()> {
-        let mesh = self
-            .meshes
+    unsafe fn delete_texture_array(&mut self, handle: TextureArrayHandle) -> Result<()> {
+        let texture = self
+            .texture_arrays
             .free(handle)
             .ok_or_else(|| format_err!("{:?} is invalid.", handle))?;
 
-        // Removes deprecated `VertexArrayObject`s.
-        self.state.vaos.retain(|&(_, h), vao| {
-            if h == mesh.handle {
-                gl::DeleteVertexArrays(1, vao as *mut u32);
-                false
-            } else {
-                true
+        for v in self.state.binded_textures.iter_mut() {
+            if *v == Some(Sampler::TextureArray(handle)) {
+                *v = None;
             }
-        });
+        }
 
-        gl::DeleteBuffers(1, &mesh.vbo);
-        gl::DeleteBuffers(1, &mesh.ibo);
+        gl::DeleteTextures(1, &texture.id);
         check()
     }
 
-    unsafe fn bind(&mut self, handle: SurfaceHandle, dimensions: Vector2<u32>) -> Result<()> {
-        if self.state.binded_surface == Some(handle) {
-            return Ok(());
-        }
+    unsafe fn create_sampler(
+        &mut self,
+        handle: SamplerHandle,
+        params: SamplerParams,
+    ) -> Result<()> {
+        let mut id = 0;
+
+        if self.capabilities.has_sampler_objects() {
+            gl::GenSamplers(1, &mut id);
+            assert!(id != 0);
+
+            let wrap: GLenum = params.wrap.into();
+            gl::SamplerParameteri(id, gl::TEXTURE_WRAP_S, wrap as GLint);
+            gl::SamplerParameteri(id, gl::TEXTURE_WRAP_T, wrap as GLint);
+
+            let (min_filter, mag_filter) = match (params.filter, params.mipmap) {
+                (TextureFilter::Nearest, false) => (gl::NEAREST, gl::NEAREST),
+                (TextureFilter::Nearest, true) => (gl::NEAREST_MIPMAP_NEAREST, gl::NEAREST),
+                (TextureFilter::Linear, false) => (gl::LINEAR, gl::LINEAR),
+                (TextureFilter::Linear, true) => (gl::LINEAR_MIPMAP_LINEAR, gl::LINEAR),
+            };
+
+            gl::SamplerParameteri(id, gl::TEXTURE_MIN_FILTER, min_filter as GLint);
+            gl::SamplerParameteri(id, gl::TEXTURE_MAG_FILTER, mag_filter as GLint);
+
+            if let Version::GL(_, _) = self.capabilities.version {
+                gl::SamplerParameterf(id, gl::TEXTURE_LOD_BIAS, params.lod_bias);
+            }
+
+            if let Some(cmp) = params.compare {
+                let cmp: GLenum = cmp.into();
+                let mode = gl::COMPARE_REF_TO_TEXTURE as GLint;
+                gl::SamplerParameteri(id, gl::TEXTURE_COMPARE_MODE, mode);
+                gl::SamplerParameteri(id, gl::TEXTURE_COMPARE_FUNC, cmp as GLint);
+            }
+
+            check()?;
+        } else {
+            warn!(
+                "Sampler objects are not supported, {:?} falls back to the sampling parameters of textures.",
+                handle
+            );
+        }
+
+        self.samplers.create(handle, GLSamplerData { id });
+        Ok(())
+    }
+
+    unsafe fn delete_sampler(&mut self, handle: SamplerHandle) -> Result<()> {
+        let sampler = self
+            .samplers
+            .free(handle)
+            .ok_or_else(|| format_err!("{:?} is invalid.", handle))?;
+
+        if sampler.id != 0 {
+            for v in self.state.binded_samplers.iter_mut() {
+                if *v == sampler.id {
+                    *v = 0;
+                }
+            }
+
+            gl::DeleteSamplers(1, &sampler.id);
+        }
+
+        check()
+    }
+
+    unsafe fn create_occlusion_query(
+        &mut self,
+        handle: OcclusionQueryHandle,
+        params: OcclusionQueryParams,
+    ) -> Result<()> {
+        let mut id = 0;
+
+        let conservative = params.conservative;
+        let target = match self.capabilities.occlusion_query_target(conservative) {
+            Some(target) => {
+                gl::GenQueries(1, &mut id);
+                assert!(id != 0);
+                target
+            }
+            None => {
+                warn!(
+                    "Occlusion queries are not supported, draw calls predicated on {:?} are always executed.",
+                    handle
+                );
+                0
+            }
+        };
+
+        let data = GLOcclusionQueryData {
+            id,
+            target,
+            issued: false,
+        };
+
+        self.occlusion_queries.create(handle, data);
+        check()
+    }
+
+    unsafe fn delete_occlusion_query(&mut self, handle: OcclusionQueryHandle) -> Result<()> {
+        let query = self
+            .occlusion_queries
+            .free(handle)
+            .ok_or_else(|| format_err!("{:?} is invalid.", handle))?;
+
+        if let Some((active, target)) = self.state.active_query {
+            if active == handle {
+                gl::EndQuery(target);
+                self.state.active_query = None;
+            }
+        }
+
+        if query.id != 0 {
+            gl::DeleteQueries(1, &query.id);
+        }
+
+        check()
+    }
+
+    unsafe fn create_mesh(
+        &mut self,
+        handle: MeshHandle,
+        params: MeshParams,
+        data: Option<MeshData>,
+    ) -> Result<()> {
+        if params.index_format == IndexFormat::U32 && !self.capabilities.has_u32_index() {
+            bail!("32-bit indices are not supported by this OpenGL implementation.");
+        }
+
+        let vbo = self.create_buffer(
+            gl::ARRAY_BUFFER,
+            params.hint,
+            params.vertex_buffer_len(),
+            data.as_ref().map(|v| v.vptr.as_ref()),
+        )?;
+
+        let ibo = self.create_buffer(
+            gl::ELEMENT_ARRAY_BUFFER,
+            params.hint,
+            params.index_buffer_len(),
+            data.as_ref().map(|v| v.iptr.as_ref()),
+        )?;
+
+        self.meshes.create(
+            handle,
+            GLMeshData {
+                handle,
+                vbo,
+                ibo,
+                params,
+            },
+        );
+
+        Ok(())
+    }
+
+    unsafe fn update_vertex_buffer(
+        &mut self,
+        handle: MeshHandle,
+        offset: usize,
+        data: &[u8],
+    ) -> Result<()> {
+        let vbo = {
+            let mesh = self
+                .meshes
+                .get(handle)
+                .ok_or_else(|| format_err!("{:?} is invalid.", handle))?;
+
+            if mesh.params.hint == MeshHint::Immutable {
+                bail!("Trying to update immutable buffer");
+            }
+
+            mesh.vbo
+        };
+
+        self.wireframe.free_mesh(handle);
+        Self::update_buffer(gl::ARRAY_BUFFER, vbo, offset, data)?;
+        Ok(())
+    }
+
+    unsafe fn update_index_buffer(
+        &mut self,
+        handle: MeshHandle,
+        offset: usize,
+        data: &[u8],
+    ) -> Result<()> {
+        let ibo = {
+            let mesh = self
+                .meshes
+                .get(handle)
+                .ok_or_else(|| format_err!("{:?} is invalid.", handle))?;
+
+            if mesh.params.hint == MeshHint::Immutable {
+                bail!("Trying to update immutable buffer");
+            }
+
+            mesh.ibo
+        };
+
+        self.wireframe.free_mesh(handle);
+        Self::update_buffer(gl::ELEMENT_ARRAY_BUFFER, ibo, offset, data)?;
+        Ok(())
+    }
+
+    unsafe fn delete_mesh(&mut self, handle: MeshHandle) -> Result<()> {
+        let mesh = self
+            .meshes
+            .free(handle)
+            .ok_or_else(|| format_err!("{:?} is invalid.", handle))?;
+
+        // Removes deprecated `VertexArrayObject`s.
+        self.state.vaos.retain(|&(_, h), vao| {
+            if h == mesh.handle {
+                gl::DeleteVertexArrays(1, vao as *mut u32);
+                false
+            } else {
+                true
+            }
+        });
+
+        self.wireframe.free_mesh(handle);
+        gl::DeleteBuffers(1, &mesh.vbo);
+        gl::DeleteBuffers(1, &mesh.ibo);
+        check()
+    }
+
+    unsafe fn bind(
+        &mut self,
+        handle: SurfaceHandle,
+        dimensions: Vector2<u32>,
+        clear: bool,
+    ) -> Result<()> {
+        if self.state.binded_surface == Some(handle) {
+            return Ok(());
+        }
+
+        self.end_debug_mode()?;
 
         let surface = self
             .surfaces
@@ -733,7 +1346,7 @@ impl Visitor for GLVisitor {
         Self::set_viewport(&mut self.state, vp)?;
         Self::set_scissor(&mut self.state, SurfaceScissor::Disable)?;
 
-        if !self.state.cleared_surfaces.contains(&handle) {
+        if clear && !self.state.cleared_surfaces.contains(&handle) {
             // Sets depth write enable to make sure that we can clear depth buffer properly.
             if surface.params.clear_depth.is_some() {
                 self.state.binded_shader = None;
@@ -750,6 +1363,9 @@ impl Visitor for GLVisitor {
             self.state.cleared_surfaces.insert(handle);
         }
 
+        let mode = surface.params.debug_mode.unwrap_or(self.debug.mode);
+        self.begin_debug_mode(mode, vp)?;
+
         self.state.binded_surface = Some(handle);
         Ok(())
     }
@@ -762,6 +1378,77 @@ impl Visitor for GLVisitor {
         Self::set_viewport(&mut self.state, vp)
     }
 
+    unsafe fn begin_occlusion_query(&mut self, handle: OcclusionQueryHandle) -> Result<()> {
+        if self.state.active_query.is_some() {
+            bail!("Occlusion queries could not be nested.");
+        }
+
+        let query = self
+            .occlusion_queries
+            .get_mut(handle)
+            .ok_or_else(|| format_err!("{:?} is invalid.", handle))?;
+
+        if query.id != 0 {
+            gl::BeginQuery(query.target, query.id);
+            query.issued = true;
+            self.state.active_query = Some((handle, query.target));
+        }
+
+        check()
+    }
+
+    unsafe fn end_occlusion_query(&mut self) -> Result<()> {
+        if let Some((_, target)) = self.state.active_query.take() {
+            gl::EndQuery(target);
+        }
+
+        check()
+    }
+
+    unsafe fn begin_conditional_render(&mut self, handle: OcclusionQueryHandle) -> Result<()> {
+        if self.state.conditional_render.is_some() {
+            bail!("Conditional rendering could not be nested.");
+        }
+
+        let query = self
+            .occlusion_queries
+            .get(handle)
+            .ok_or_else(|| format_err!("{:?} is invalid.", handle))?;
+
+        // Draws unconditionally if the query has never been issued.
+        if !query.issued {
+            self.state.conditional_render = Some(false);
+            return Ok(());
+        }
+
+        if self.capabilities.has_conditional_render() {
+            gl::BeginConditionalRender(query.id, gl::QUERY_NO_WAIT);
+            self.state.conditional_render = Some(true);
+        } else {
+            let mut available = 0;
+            gl::GetQueryObjectuiv(query.id, gl::QUERY_RESULT_AVAILABLE, &mut available);
+
+            if available != 0 {
+                let mut passed = 0;
+                gl::GetQueryObjectuiv(query.id, gl::QUERY_RESULT, &mut passed);
+                self.state.discard_draws = passed == 0;
+            }
+
+            self.state.conditional_render = Some(false);
+        }
+
+        check()
+    }
+
+    unsafe fn end_conditional_render(&mut self) -> Result<()> {
+        if self.state.conditional_render.take() == Some(true) {
+            gl::EndConditionalRender();
+        }
+
+        self.state.discard_draws = false;
+        check()
+    }
+
     unsafe fn draw(
         &mut self,
         shader: ShaderHandle,
@@ -769,11 +1456,24 @@ impl Visitor for GLVisitor {
         mesh_index: MeshIndex,
         uniforms: &[UniformVar],
     ) -> Result<u32> {
+        if self.state.discard_draws {
+            return Ok(0);
+        }
+
+        let wireframe = self.prepare_wireframe(shader, mesh)?;
+        if wireframe {
+            // The variant shares the handle of its shader, which bypasses the cached bindings.
+            self.state.binded_shader = None;
+            self.state.binded_vao = None;
+        }
+
         // Bind program and associated uniforms and textures.
-        let shader = self
-            .shaders
-            .get(shader)
-            .ok_or_else(|| format_err!("{:?} is invalid.", shader))?;
+        let shader = if wireframe {
+            self.wireframe.shaders.get(&shader).and_then(|v| v.as_ref())
+        } else {
+            self.shaders.get(shader)
+        }
+        .ok_or_else(|| format_err!("{:?} is invalid.", shader))?;
 
         Self::bind_shader(&mut self.state, &shader)?;
 
@@ -791,25 +1491,47 @@ impl Visitor for GLVisitor {
                 }
 
                 let location = shader.hash_uniform_location(field).unwrap();
+
+                let sampler = match variable {
+                    UniformVariable::SampledTexture(_, v)
+                    | UniformVariable::SampledRenderTexture(_, v) => {
+                        self.samplers.get(v).map(|v| v.id).unwrap_or(0)
+                    }
+                    _ => 0,
+                };
+
                 match variable {
-                    UniformVariable::Texture(handle) => {
+                    UniformVariable::Texture(handle)
+                    | UniformVariable::SampledTexture(handle, _) => {
                         let v = UniformVariable::I32(index as i32);
                         Self::bind_uniform_variable(location, &v)?;
 
                         if let Some(texture) = self.textures.get(handle) {
-                            Self::bind_texture(
-                                &mut self.state,
-                                Some(Sampler::Texture(handle)),
-                                index,
-                                texture.id,
-                            )?;
+                            if self.debug.active == DebugMode::MipLevels {
+                                let id =
+                                    Self::mip_texture(&mut self.debug, &mut self.state, texture)?;
+                                let sampler = Some(Sampler::MipLevels(handle));
+                                Self::bind_texture(&mut self.state, sampler, index, id)?;
+                            } else {
+                                Self::bind_texture(
+                                    &mut self.state,
+                                    Some(Sampler::Texture(handle)),
+                                    index,
+                                    texture.id,
+                                )?;
+                            }
                         } else {
                             Self::bind_texture(&mut self.state, None, index, 0)?;
                         }
 
+                        if self.capabilities.has_sampler_objects() {
+                            Self::bind_sampler(&mut self.state, index, sampler)?;
+                        }
+
                         index += 1;
                     }
-                    UniformVariable::RenderTexture(handle) => {
+                    UniformVariable::RenderTexture(handle)
+                    | UniformVariable::SampledRenderTexture(handle, _) => {
                         let v = UniformVariable::I32(index as i32);
                         Self::bind_uniform_variable(location, &v)?;
 
@@ -828,20 +1550,50 @@ impl Visitor for GLVisitor {
                             Self::bind_texture(&mut self.state, None, index, 0)?;
                         }
 
+                        if self.capabilities.has_sampler_objects() {
+                            Self::bind_sampler(&mut self.state, index, sampler)?;
+                        }
+
+                        index += 1;
+                    }
+                    UniformVariable::TextureArray(handle) => {
+                        let v = UniformVariable::I32(index as i32);
+                        Self::bind_uniform_variable(location, &v)?;
+
+                        if let Some(texture) = self.texture_arrays.get(handle) {
+                            Self::bind_texture(
+                                &mut self.state,
+                                Some(Sampler::TextureArray(handle)),
+                                index,
+                                texture.id,
+                            )?;
+                        } else {
+                            Self::bind_texture(&mut self.state, None, index, 0)?;
+                        }
+
+                        if self.capabilities.has_sampler_objects() {
+                            Self::bind_sampler(&mut self.state, index, 0)?;
+                        }
+
                         index += 1;
                     }
                     _ => {
                         Self::bind_uniform_variable(location, &variable)?;
                     }
                 }
-            } else {
+            } else if self.strict_uniforms {
                 bail!("Undefined uniform field {:?}.", field);
             }
         }
 
         if let Some(mesh) = self.meshes.get(mesh) {
             // Bind vertex buffer and vertex array object.
-            Self::bind_mesh(&mut self.state, &shader, &mesh)?;
+            if wireframe {
+                let meshes = &self.wireframe.meshes;
+                Self::bind_wireframe_mesh(&mut self.wireframe.vaos, meshes, &shader, &mesh)?;
+            } else {
+                Self::bind_mesh(&mut self.state, &shader, &mesh)?;
+            }
 
             let (from, len) = match mesh_index {
                 MeshIndex::Ptr(from, len) => {
@@ -870,12 +1622,71 @@ impl Visitor for GLVisitor {
                 MeshIndex::All => (0, mesh.params.num_idxes),
             };
 
-            gl::DrawElements(
-                mesh.params.primitive.into(),
-                len as i32,
-                mesh.params.index_format.into(),
-                from as *const u32 as *const ::std::os::raw::c_void,
-            );
+            if let Some(restart) = self.capabilities.primitive_restart() {
+                let cap = match restart {
+                    PrimitiveRestart::FixedIndex => gl::PRIMITIVE_RESTART_FIXED_INDEX,
+                    PrimitiveRestart::Index => gl::PRIMITIVE_RESTART,
+                };
+
+                if self.state.binded_primitive_restart != mesh.params.primitive_restart {
+                    if mesh.params.primitive_restart {
+                        gl::Enable(cap);
+                    } else {
+                        gl::Disable(cap);
+                    }
+
+                    self.state.binded_primitive_restart = mesh.params.primitive_restart;
+                }
+
+                if mesh.params.primitive_restart && restart == PrimitiveRestart::Index {
+                    let v = mesh.params.index_format.primitive_restart_index();
+                    if self.state.binded_restart_index != Some(v) {
+                        gl::PrimitiveRestartIndex(v);
+                        self.state.binded_restart_index = Some(v);
+                    }
+                }
+            }
+
+            if let Some(dst) = self.state.capture {
+                let dst = self
+                    .meshes
+                    .get(dst)
+                    .ok_or_else(|| format_err!("{:?} is invalid.", dst))?;
+
+                let num = mesh.params.primitive.assemble_feedback_verts(len as u32);
+                if num as usize > dst.params.num_verts {
+                    bail!(
+                        "{:?} is out of capacity to capture {} vertices.",
+                        dst.handle,
+                        num
+                    );
+                }
+
+                gl::Enable(gl::RASTERIZER_DISCARD);
+                gl::BindBufferBase(gl::TRANSFORM_FEEDBACK_BUFFER, 0, dst.vbo);
+                gl::BeginTransformFeedback(types::feedback_primitive(mesh.params.primitive));
+            }
+
+            if wireframe {
+                let first = from / mesh.params.index_format.stride();
+                gl::DrawArrays(gl::TRIANGLES, first as GLint, len as GLsizei);
+
+                self.state.binded_shader = None;
+                self.state.binded_vao = None;
+            } else {
+                gl::DrawElements(
+                    mesh.params.primitive.into(),
+                    len as i32,
+                    mesh.params.index_format.into(),
+                    from as *const u32 as *const ::std::os::raw::c_void,
+                );
+            }
+
+            if self.state.capture.is_some() {
+                gl::EndTransformFeedback();
+                gl::BindBufferBase(gl::TRANSFORM_FEEDBACK_BUFFER, 0, 0);
+                gl::Disable(gl::RASTERIZER_DISCARD);
+            }
 
             check()?;
             Ok(mesh.params.primitive.assemble(len as u32))
@@ -884,16 +1695,111 @@ impl Visitor for GLVisitor {
         }
     }
 
+    unsafe fn capture(
+        &mut self,
+        shader: ShaderHandle,
+        mesh: MeshHandle,
+        mesh_index: MeshIndex,
+        uniforms: &[UniformVar],
+        dst: MeshHandle,
+    ) -> Result<u32> {
+        if !self.capabilities.has_transform_feedback() {
+            bail!("Transform feedback is not supported.");
+        }
+
+        let feedback_stride = match self.shaders.get(shader) {
+            Some(v) if v.params.varyings.is_empty() => {
+                bail!("{:?} does not have any varyings to capture.", shader);
+            }
+            Some(v) => v.feedback_stride,
+            None => bail!("{:?} is invalid.", shader),
+        };
+
+        let stride = match self.meshes.get(dst) {
+            Some(v) => v.params.layout.stride() as usize,
+            None => bail!("{:?} is invalid.", dst),
+        };
+
+        // The varyings are written interleaved, so they must fill exactly one vertex of
+        // the destination mesh.
+        if feedback_stride != stride {
+            bail!(
+                "{:?} captures {} bytes per vertex, but the stride of {:?} is {}.",
+                shader,
+                feedback_stride,
+                dst,
+                stride
+            );
+        }
+
+        self.state.capture = Some(dst);
+        let result = self.draw(shader, mesh, mesh_index, uniforms);
+        self.state.capture = None;
+        result
+    }
+
     unsafe fn flush(&mut self) -> Result<()> {
+        self.end_occlusion_query()?;
+        self.end_conditional_render()?;
+        self.end_debug_mode()?;
+
         if self.state.cleared_surfaces.is_empty() {
             Self::clear(Color::black(), None, None)?;
         }
 
-        gl::Finish();
+        match self.latency {
+            FrameLatency::Finish => gl::Finish(),
+            FrameLatency::OneFrame => {
+                // Waits until the GPU finishes the previous frame, so at most one frame is
+                // queued behind the one being rendered.
+                if let Some(fence) = self.fence.take() {
+                    match gl::ClientWaitSync(fence, gl::SYNC_FLUSH_COMMANDS_BIT, FENCE_TIMEOUT) {
+                        gl::TIMEOUT_EXPIRED => {
+                            warn!("Timed out waiting for the previous frame, calls `glFinish`.");
+                            gl::Finish();
+                        }
+                        gl::WAIT_FAILED => {
+                            warn!("Failed to wait for the previous frame, calls `glFinish`.");
+                            gl::Finish();
+                        }
+                        _ => {}
+                    }
+
+                    gl::DeleteSync(fence);
+                }
+
+                self.fence = Some(gl::FenceSync(gl::SYNC_GPU_COMMANDS_COMPLETE, 0));
+                gl::Flush();
+            }
+            FrameLatency::Unlimited => gl::Flush(),
+        }
+
         check()
     }
 }
 
+impl Drop for GLVisitor {
+    fn drop(&mut self) {
+        unsafe {
+            if let Some(fence) = self.fence.take() {
+                gl::DeleteSync(fence);
+            }
+
+            if let Some(heat_map) = self.debug.heat_map.take() {
+                gl::DeleteVertexArrays(1, &heat_map.vao);
+                gl::DeleteBuffers(1, &heat_map.vbo);
+                gl::DeleteProgram(heat_map.program);
+            }
+
+            for (_, id) in self.debug.mip_textures.drain() {
+                gl::DeleteTextures(1, &id);
+            }
+
+            self.wireframe.clear();
+        }
+    }
+}
+
 impl GLVisitor {
     unsafe fn bind_shader(state: &mut GLMutableState, shader: &GLShaderData) -> Result<()> {
         if state.binded_shader == Some(shader.handle) {
@@ -919,6 +1825,9 @@ impl GLVisitor {
         match *variable {
             UniformVariable::Texture(_) => unreachable!(),
             UniformVariable::RenderTexture(_) => unreachable!(),
+            UniformVariable::TextureArray(_) => unreachable!(),
+            UniformVariable::SampledTexture(_, _) => unreachable!(),
+            UniformVariable::SampledRenderTexture(_, _) => unreachable!(),
             UniformVariable::I32(v) => gl::Uniform1i(location, v),
             UniformVariable::F32(v) => gl::Uniform1f(location, v),
             UniformVariable::Vector2f(v) => gl::Uniform2f(location, v[0], v[1]),
@@ -957,8 +1866,92 @@ impl GLVisitor {
         }
 
         if state.binded_textures[index] != sampler {
+            // Unbinds the previous texture array, so it would not be sampled accidently.
+            if let Some(Sampler::TextureArray(_)) = state.binded_textures[index] {
+                gl::BindTexture(gl::TEXTURE_2D_ARRAY, 0);
+            }
+
             state.binded_textures[index] = sampler;
-            gl::BindTexture(gl::TEXTURE_2D, id);
+            match sampler {
+                Some(Sampler::TextureArray(_)) => gl::BindTexture(gl::TEXTURE_2D_ARRAY, id),
+                _ => gl::BindTexture(gl::TEXTURE_2D, id),
+            }
+        }
+
+        check()
+    }
+
+    /// Uploads the staged texture data through pixel buffer objects within the budget of
+    /// current frame. At least one upload is issued every frame to make progress.
+    unsafe fn upload_staged_textures(&mut self) -> Result<()> {
+        let (size, batch) = self.staging.queue.batch();
+        if batch.is_empty() {
+            return Ok(());
+        }
+
+        let index = self.staging.index;
+        self.staging.index = (index + 1) % STAGING_FRAMES;
+
+        if self.staging.pbos[index] == 0 {
+            gl::GenBuffers(1, &mut self.staging.pbos[index]);
+            assert!(self.staging.pbos[index] != 0);
+        }
+
+        // Orphans the storage of pixel buffer object, so we never wait for the transfers
+        // that are still in flight.
+        gl::BindBuffer(gl::PIXEL_UNPACK_BUFFER, self.staging.pbos[index]);
+        gl::BufferData(
+            gl::PIXEL_UNPACK_BUFFER,
+            size as GLsizeiptr,
+            ::std::ptr::null(),
+            gl::STREAM_DRAW,
+        );
+
+        for (offset, v) in batch {
+            gl::BufferSubData(
+                gl::PIXEL_UNPACK_BUFFER,
+                offset as GLintptr,
+                v.bytes.len() as GLsizeiptr,
+                v.bytes.as_ptr() as *const ::std::os::raw::c_void,
+            );
+
+            if let Some(texture) = self.textures.get(v.handle) {
+                let (_, format, pixel_type) =
+                    types::texture_format(texture.params.format, &self.capabilities);
+
+                Self::bind_texture(
+                    &mut self.state,
+                    Some(Sampler::Texture(v.handle)),
+                    0,
+                    texture.id,
+                )?;
+
+                gl::TexSubImage2D(
+                    gl::TEXTURE_2D,
+                    v.level as GLint,
+                    v.position.0 as GLint,
+                    v.position.1 as GLint,
+                    v.dimensions.0 as GLsizei,
+                    v.dimensions.1 as GLsizei,
+                    format,
+                    pixel_type,
+                    offset as *const ::std::os::raw::c_void,
+                );
+            }
+        }
+
+        gl::BindBuffer(gl::PIXEL_UNPACK_BUFFER, 0);
+        check()
+    }
+
+    unsafe fn bind_sampler(state: &mut GLMutableState, index: usize, id: GLuint) -> Result<()> {
+        if state.binded_samplers.len() <= index {
+            state.binded_samplers.resize(index + 1, 0);
+        }
+
+        if state.binded_samplers[index] != id {
+            state.binded_samplers[index] = id;
+            gl::BindSampler(index as GLuint, id);
         }
 
         check()
@@ -982,17 +1975,10 @@ impl GLVisitor {
                 gl::BindVertexArray(vao);
                 gl::BindBuffer(gl::ARRAY_BUFFER, mesh.vbo);
 
-                for (name, size, required) in shader.params.attributes.iter() {
-                    if let Some(element) = mesh.params.layout.element(name) {
-                        if element.size < size {
-                            bail!(
-                                "Vertex buffer has incompatible attribute `{:?}` [{:?} - {:?}].",
-                                name,
-                                element.size,
-                                size
-                            );
-                        }
+                shader.params.attributes.validate(&mesh.params.layout)?;
 
+                for (name, _, _) in shader.params.attributes.iter() {
+                    if let Some(element) = mesh.params.layout.element(name) {
                         let offset = mesh.params.layout.offset(name).unwrap();
                         let stride = mesh.params.layout.stride();
 
@@ -1006,11 +1992,6 @@ impl GLVisitor {
                             GLsizei::from(stride),
                             offset as *const u8 as *const ::std::os::raw::c_void,
                         );
-                    } else if required {
-                        bail!(
-                            "Can't find attribute {:?} description in vertex buffer.",
-                            name
-                        );
                     }
                 }
 
@@ -1026,6 +2007,386 @@ impl GLVisitor {
     }
 }
 
+impl GLVisitor {
+    unsafe fn begin_debug_mode(&mut self, mode: DebugMode, vp: SurfaceViewport) -> Result<()> {
+        match mode {
+            DebugMode::Wireframe => {
+                if self.capabilities.has_polygon_mode() {
+                    gl::PolygonMode(gl::FRONT_AND_BACK, gl::LINE);
+                } else if !self.capabilities.has_map_buffer_range() && !self.debug.warned {
+                    warn!("Wireframe requires OpenGL ES 3.0 to read meshes back.");
+                    self.debug.warned = true;
+                }
+            }
+            DebugMode::Overdraw => {
+                // Counts how many times each pixel is drawn with the stencil buffer.
+                gl::Enable(gl::STENCIL_TEST);
+                gl::StencilMask(0xFF);
+                gl::StencilFunc(gl::ALWAYS, 0, 0xFF);
+                gl::StencilOp(gl::KEEP, gl::INCR, gl::INCR);
+                gl::ClearStencil(0);
+                gl::Clear(gl::STENCIL_BUFFER_BIT);
+            }
+            DebugMode::MipLevels | DebugMode::None => {}
+        }
+
+        self.debug.active = mode;
+        self.debug.viewport = vp;
+        check()
+    }
+
+    unsafe fn end_debug_mode(&mut self) -> Result<()> {
+        match self.debug.active {
+            DebugMode::Wireframe => {
+                if self.capabilities.has_polygon_mode() {
+                    gl::PolygonMode(gl::FRONT_AND_BACK, gl::FILL);
+                }
+            }
+            DebugMode::Overdraw => {
+                self.draw_heat_map()?;
+                gl::Disable(gl::STENCIL_TEST);
+            }
+            DebugMode::MipLevels | DebugMode::None => {}
+        }
+
+        self.debug.active = DebugMode::None;
+        check()
+    }
+
+    /// Draws full-screen quads with the colors of overdraw counts in the stencil buffer.
+    unsafe fn draw_heat_map(&mut self) -> Result<()> {
+        if self.debug.heat_map.is_none() {
+            self.debug.heat_map = Some(Self::create_heat_map(self.capabilities.version)?);
+        }
+
+        let heat_map = self.debug.heat_map.as_ref().unwrap();
+        Self::set_viewport(&mut self.state, self.debug.viewport)?;
+        Self::set_scissor(&mut self.state, SurfaceScissor::Disable)?;
+        Self::set_cull_face(&mut self.state, CullFace::Nothing)?;
+        Self::set_depth_test(&mut self.state, false, Comparison::Always)?;
+        Self::set_depth_write_offset(&mut self.state, None)?;
+        Self::set_color_blend(&mut self.state, None)?;
+        Self::set_color_write(&mut self.state, (true, true, true, true))?;
+
+        gl::UseProgram(heat_map.program);
+        gl::BindVertexArray(heat_map.vao);
+        self.state.binded_shader = None;
+        self.state.binded_vao = None;
+
+        gl::StencilOp(gl::KEEP, gl::KEEP, gl::KEEP);
+        for i in 0..=OVERDRAW_COLORS.len() {
+            let (func, color) = if i == 0 {
+                (gl::EQUAL, [0.0, 0.0, 0.0])
+            } else if i < OVERDRAW_COLORS.len() {
+                (gl::EQUAL, OVERDRAW_COLORS[i - 1])
+            } else {
+                // The reference value is less than or equal to the count.
+                (gl::LEQUAL, OVERDRAW_COLORS[i - 1])
+            };
+
+            gl::StencilFunc(func, i as GLint, 0xFF);
+            gl::Uniform4f(heat_map.color, color[0], color[1], color[2], 1.0);
+            gl::DrawArrays(gl::TRIANGLE_STRIP, 0, 4);
+        }
+
+        gl::BindVertexArray(0);
+        check()
+    }
+
+    unsafe fn create_heat_map(version: Version) -> Result<GLHeatMap> {
+        let (vs, fs) = match version {
+            Version::ES(_, _) => (HEAT_MAP_VS_100, HEAT_MAP_FS_100),
+            _ if version >= Version::GL(3, 2) => (HEAT_MAP_VS_150, HEAT_MAP_FS_150),
+            _ => (HEAT_MAP_VS_110, HEAT_MAP_FS_110),
+        };
+
+        let vs = Self::compile(gl::VERTEX_SHADER, vs)?;
+        let fs = Self::compile(gl::FRAGMENT_SHADER, fs)?;
+        let program = Self::link(&[vs, fs], &[])?;
+
+        gl::DetachShader(program, vs);
+        gl::DeleteShader(vs);
+        gl::DetachShader(program, fs);
+        gl::DeleteShader(fs);
+
+        let name = ::std::ffi::CString::new("u_Color").unwrap();
+        let color = gl::GetUniformLocation(program, name.as_ptr());
+        let name = ::std::ffi::CString::new("Position").unwrap();
+        let position = gl::GetAttribLocation(program, name.as_ptr()) as GLuint;
+
+        let verts: [f32; 8] = [-1.0, -1.0, 1.0, -1.0, -1.0, 1.0, 1.0, 1.0];
+
+        let mut vao = 0;
+        gl::GenVertexArrays(1, &mut vao);
+        gl::BindVertexArray(vao);
+
+        let mut vbo = 0;
+        gl::GenBuffers(1, &mut vbo);
+        gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
+        gl::BufferData(
+            gl::ARRAY_BUFFER,
+            ::std::mem::size_of_val(&verts) as GLsizeiptr,
+            verts.as_ptr() as *const ::std::os::raw::c_void,
+            gl::STATIC_DRAW,
+        );
+
+        gl::EnableVertexAttribArray(position);
+        gl::VertexAttribPointer(position, 2, gl::FLOAT, gl::FALSE, 0, ::std::ptr::null());
+        gl::BindVertexArray(0);
+        check()?;
+
+        Ok(GLHeatMap {
+            program,
+            vao,
+            vbo,
+            color,
+        })
+    }
+
+    /// Gets the texture with colored mipmap levels, which has the same dimensions and
+    /// number of levels as `texture`.
+    unsafe fn mip_texture(
+        debug: &mut GLDebug,
+        state: &mut GLMutableState,
+        texture: &GLTextureData,
+    ) -> Result<GLuint> {
+        let dims = texture.params.dimensions;
+        let key = (dims.x, dims.y, texture.levels);
+        if let Some(&id) = debug.mip_textures.get(&key) {
+            return Ok(id);
+        }
+
+        let mut id = 0;
+        gl::GenTextures(1, &mut id);
+        gl::BindTexture(gl::TEXTURE_2D, id);
+
+        // The texture unit is not binded to any tracked texture anymore.
+        if let Some(v) = state.binded_textures.get_mut(state.binded_texture_index) {
+            *v = None;
+        }
+
+        let (mut w, mut h) = (dims.x.max(1), dims.y.max(1));
+        for level in 0..texture.levels {
+            let color = MIP_COLORS[level as usize % MIP_COLORS.len()];
+            let bytes: Vec<u8> = (0..w * h).flat_map(|_| color.iter().cloned()).collect();
+
+            gl::TexImage2D(
+                gl::TEXTURE_2D,
+                level as GLint,
+                gl::RGBA as GLint,
+                w as GLsizei,
+                h as GLsizei,
+                0,
+                gl::RGBA,
+                gl::UNSIGNED_BYTE,
+                bytes.as_ptr() as *const ::std::os::raw::c_void,
+            );
+
+            w = (w / 2).max(1);
+            h = (h / 2).max(1);
+        }
+
+        let params = &texture.params;
+        Self::bind_texture_params(params.wrap, params.filter, texture.levels)?;
+        check()?;
+
+        debug.mip_textures.insert(key, id);
+        Ok(id)
+    }
+
+    /// Prepares the variant of `shader` and the expanded `mesh` if the draw call should be
+    /// drawn with the barycentric fallback of `DebugMode::Wireframe`.
+    unsafe fn prepare_wireframe(&mut self, shader: ShaderHandle, mesh: MeshHandle) -> Result<bool> {
+        if self.debug.active != DebugMode::Wireframe
+            || self.capabilities.has_polygon_mode()
+            || !self.capabilities.has_map_buffer_range()
+            || self.state.capture.is_some()
+        {
+            return Ok(false);
+        }
+
+        // Lines and points are drawn as they are.
+        let mesh = match self.meshes.get(mesh) {
+            Some(v) if v.params.primitive == MeshPrimitive::Triangles => v,
+            _ => return Ok(false),
+        };
+
+        if !self.wireframe.shaders.contains_key(&shader) {
+            let v = match self.shaders.get(shader) {
+                Some(v) => Self::create_wireframe_shader(v)?,
+                None => return Ok(false),
+            };
+
+            if v.is_none() {
+                warn!(
+                    "The wireframe of {:?} is not available without `main`.",
+                    shader
+                );
+            }
+
+            self.wireframe.shaders.insert(shader, v);
+        }
+
+        if self.wireframe.shaders[&shader].is_none() {
+            return Ok(false);
+        }
+
+        if !self.wireframe.meshes.contains_key(&mesh.handle) {
+            let v = Self::create_wireframe_mesh(mesh)?;
+            self.wireframe.meshes.insert(mesh.handle, v);
+        }
+
+        Ok(true)
+    }
+
+    unsafe fn create_wireframe_shader(shader: &GLShaderData) -> Result<Option<GLShaderData>> {
+        let (vs, fs) = match shader.vs.as_ref().and_then(|v| wireframe_sources(v)) {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        let vs = Self::compile(gl::VERTEX_SHADER, &vs)?;
+        let fs = Self::compile(gl::FRAGMENT_SHADER, &fs)?;
+        let id = Self::link(&[vs, fs], &[])?;
+
+        gl::DetachShader(id, vs);
+        gl::DeleteShader(vs);
+        gl::DetachShader(id, fs);
+        gl::DeleteShader(fs);
+        check()?;
+
+        let variant = GLShaderData {
+            handle: shader.handle,
+            id,
+            params: shader.params.clone(),
+            uniforms: RefCell::new(FastHashMap::default()),
+            attributes: RefCell::new(FastHashMap::default()),
+            feedback_stride: 0,
+            vs: None,
+        };
+
+        // The uniforms that are only used by the fragment shader are inactive in the variant,
+        // whose locations are -1 and ignored silently.
+        for &(ref name, _) in variant.params.uniforms.iter() {
+            variant.uniform_location(name)?;
+        }
+
+        Ok(Some(variant))
+    }
+
+    unsafe fn create_wireframe_mesh(mesh: &GLMeshData) -> Result<GLWireframeMesh> {
+        let verts = Self::read_buffer(mesh.vbo, mesh.params.vertex_buffer_len())?;
+        let idxes = Self::read_buffer(mesh.ibo, mesh.params.index_buffer_len())?;
+
+        let stride = mesh.params.layout.stride() as usize;
+        let (verts, barycentric) =
+            expand_wireframe(&verts, stride, &idxes, mesh.params.index_format);
+
+        let mut ids = [0; 2];
+        gl::GenBuffers(2, ids.as_mut_ptr());
+
+        gl::BindBuffer(gl::ARRAY_BUFFER, ids[0]);
+        gl::BufferData(
+            gl::ARRAY_BUFFER,
+            verts.len() as GLsizeiptr,
+            verts.as_ptr() as *const ::std::os::raw::c_void,
+            gl::STATIC_DRAW,
+        );
+
+        gl::BindBuffer(gl::ARRAY_BUFFER, ids[1]);
+        gl::BufferData(
+            gl::ARRAY_BUFFER,
+            (barycentric.len() * 4) as GLsizeiptr,
+            barycentric.as_ptr() as *const ::std::os::raw::c_void,
+            gl::STATIC_DRAW,
+        );
+
+        check()?;
+        Ok(GLWireframeMesh {
+            vbo: ids[0],
+            barycentric: ids[1],
+        })
+    }
+
+    /// Reads the contents of buffer back. It's binded to `GL_COPY_READ_BUFFER`, which leaves
+    /// the element arrays of vertex array objects untouched.
+    unsafe fn read_buffer(id: GLuint, len: usize) -> Result<Vec<u8>> {
+        gl::BindBuffer(gl::COPY_READ_BUFFER, id);
+
+        let ptr = gl::MapBufferRange(gl::COPY_READ_BUFFER, 0, len as GLsizeiptr, gl::MAP_READ_BIT);
+        if ptr.is_null() {
+            gl::BindBuffer(gl::COPY_READ_BUFFER, 0);
+            check()?;
+            bail!("Failed to map buffer {} to read.", id);
+        }
+
+        let bytes = ::std::slice::from_raw_parts(ptr as *const u8, len).to_vec();
+        gl::UnmapBuffer(gl::COPY_READ_BUFFER);
+        gl::BindBuffer(gl::COPY_READ_BUFFER, 0);
+
+        check()?;
+        Ok(bytes)
+    }
+
+    unsafe fn bind_wireframe_mesh(
+        vaos: &mut FastHashMap<(ShaderHandle, MeshHandle), GLuint>,
+        meshes: &FastHashMap<MeshHandle, GLWireframeMesh>,
+        shader: &GLShaderData,
+        mesh: &GLMeshData,
+    ) -> Result<()> {
+        let k = (shader.handle, mesh.handle);
+        if let Some(&vao) = vaos.get(&k) {
+            gl::BindVertexArray(vao);
+            return check();
+        }
+
+        let expanded = &meshes[&mesh.handle];
+
+        let mut vao = 0;
+        gl::GenVertexArrays(1, &mut vao);
+        gl::BindVertexArray(vao);
+        gl::BindBuffer(gl::ARRAY_BUFFER, expanded.vbo);
+
+        for (name, _, _) in shader.params.attributes.iter() {
+            if let Some(element) = mesh.params.layout.element(name) {
+                let offset = mesh.params.layout.offset(name).unwrap();
+                let stride = mesh.params.layout.stride();
+
+                let location = shader.attribute_location(name.into())?;
+                if location == -1 {
+                    continue;
+                }
+
+                gl::EnableVertexAttribArray(location as GLuint);
+                gl::VertexAttribPointer(
+                    location as GLuint,
+                    GLsizei::from(element.size),
+                    element.format.into(),
+                    element.normalized as u8,
+                    GLsizei::from(stride),
+                    offset as *const u8 as *const ::std::os::raw::c_void,
+                );
+            }
+        }
+
+        let location = shader.attribute_location(WIREFRAME_ATTRIBUTE)?;
+        gl::BindBuffer(gl::ARRAY_BUFFER, expanded.barycentric);
+        gl::EnableVertexAttribArray(location as GLuint);
+        gl::VertexAttribPointer(
+            location as GLuint,
+            3,
+            gl::FLOAT,
+            gl::FALSE,
+            0,
+            ::std::ptr::null(),
+        );
+
+        check()?;
+        vaos.insert(k, vao);
+        Ok(())
+    }
+}
+
 impl GLVisitor {
     unsafe fn reset_render_state(state: &mut GLMutableState) -> Result<()> {
         gl::Disable(gl::CULL_FACE);
@@ -1368,7 +2729,98 @@ impl GLVisitor {
         }
     }
 
-    unsafe fn link<'a, T>(shaders: T) -> Result<GLuint>
+    /// Reflects the size in bytes of the varyings captured by transform feedback per vertex.
+    unsafe fn feedback_stride(program: GLuint) -> Result<usize> {
+        let mut num = 0;
+        gl::GetProgramiv(program, gl::TRANSFORM_FEEDBACK_VARYINGS, &mut num);
+
+        let mut stride = 0;
+        let mut buf = [0u8; 64];
+        for i in 0..num {
+            let mut size = 0;
+            let mut tp = 0;
+            gl::GetTransformFeedbackVarying(
+                program,
+                i as GLuint,
+                buf.len() as GLsizei,
+                ::std::ptr::null_mut(),
+                &mut size,
+                &mut tp,
+                buf.as_mut_ptr() as *mut GLchar,
+            );
+
+            stride += size as usize * types::varying_bytes(tp);
+        }
+
+        check()?;
+        Ok(stride)
+    }
+
+    /// Reflects the names and numbers of components of active attributes in program.
+    unsafe fn active_attributes(program: GLuint) -> Result<Vec<(String, u8)>> {
+        let mut num = 0;
+        gl::GetProgramiv(program, gl::ACTIVE_ATTRIBUTES, &mut num);
+        let mut max_len = 0;
+        gl::GetProgramiv(program, gl::ACTIVE_ATTRIBUTE_MAX_LENGTH, &mut max_len);
+
+        let mut attributes = Vec::with_capacity(num as usize);
+        let mut buf = vec![0u8; max_len.max(1) as usize];
+        for i in 0..num {
+            let mut len = 0;
+            let mut size = 0;
+            let mut tp = 0;
+            gl::GetActiveAttrib(
+                program,
+                i as GLuint,
+                buf.len() as GLsizei,
+                &mut len,
+                &mut size,
+                &mut tp,
+                buf.as_mut_ptr() as *mut GLchar,
+            );
+
+            let name = String::from_utf8_lossy(&buf[0..len as usize]).into_owned();
+            attributes.push((name, types::components(tp)));
+        }
+
+        check()?;
+        Ok(attributes)
+    }
+
+    /// Reflects the names and types of active uniforms in program.
+    unsafe fn active_uniforms(
+        program: GLuint,
+    ) -> Result<Vec<(String, Option<UniformVariableType>)>> {
+        let mut num = 0;
+        gl::GetProgramiv(program, gl::ACTIVE_UNIFORMS, &mut num);
+        let mut max_len = 0;
+        gl::GetProgramiv(program, gl::ACTIVE_UNIFORM_MAX_LENGTH, &mut max_len);
+
+        let mut uniforms = Vec::with_capacity(num as usize);
+        let mut buf = vec![0u8; max_len.max(1) as usize];
+        for i in 0..num {
+            let mut len = 0;
+            let mut size = 0;
+            let mut tp = 0;
+            gl::GetActiveUniform(
+                program,
+                i as GLuint,
+                buf.len() as GLsizei,
+                &mut len,
+                &mut size,
+                &mut tp,
+                buf.as_mut_ptr() as *mut GLchar,
+            );
+
+            let name = String::from_utf8_lossy(&buf[0..len as usize]).into_owned();
+            uniforms.push((name, types::uniform_variable_type(tp)));
+        }
+
+        check()?;
+        Ok(uniforms)
+    }
+
+    unsafe fn link<'a, T>(shaders: T, varyings: &[String]) -> Result<GLuint>
     where
         T: IntoIterator<Item = &'a GLuint>,
     {
@@ -1377,6 +2829,21 @@ impl GLVisitor {
             gl::AttachShader(program, *shader)
         }
 
+        if !varyings.is_empty() {
+            let names: Vec<_> = varyings
+                .iter()
+                .map(|v| ::std::ffi::CString::new(v.as_bytes()).unwrap())
+                .collect();
+            let ptrs: Vec<_> = names.iter().map(|v| v.as_ptr()).collect();
+
+            gl::TransformFeedbackVaryings(
+                program,
+                ptrs.len() as GLsizei,
+                ptrs.as_ptr(),
+                gl::INTERLEAVED_ATTRIBS,
+            );
+        }
+
         gl::LinkProgram(program);
         // Get the link status
         let mut status = GLint::from(gl::FALSE);
@@ -1,14 +1,22 @@
 use super::super::assets::prelude::*;
+use super::super::FrameLatency;
+use super::utils::{StagedUpload, UploadQueue, DEFAULT_UPLOAD_BUDGET, STAGING_THRESHOLD};
 use super::{UniformVar, Visitor};
 
 use crate::errors::*;
 use crate::math::prelude::{Aabb2, Vector2};
 
-pub struct HeadlessVisitor {}
+/// The visitor which draws nothing. The large texture uploads go through the same staging
+/// queue as the OpenGL backend, so the upload budget behaves the same without a GPU.
+pub struct HeadlessVisitor {
+    staging: UploadQueue,
+}
 
 impl HeadlessVisitor {
     pub fn new() -> Self {
-        HeadlessVisitor {}
+        HeadlessVisitor {
+            staging: UploadQueue::new(DEFAULT_UPLOAD_BUDGET),
+        }
     }
 }
 
@@ -37,18 +45,53 @@ impl Visitor for HeadlessVisitor {
 
     unsafe fn create_texture(
         &mut self,
-        _: TextureHandle,
-        _: TextureParams,
-        _: Option<TextureData>,
+        handle: TextureHandle,
+        params: TextureParams,
+        data: Option<TextureData>,
     ) -> Result<()> {
+        if let Some(data) = data {
+            let len = data.bytes.iter().map(|v| v.len()).sum::<usize>();
+            if !params.format.compressed() && len >= STAGING_THRESHOLD {
+                let mut dims = (params.dimensions.x, params.dimensions.y);
+                for (i, v) in data.bytes.into_iter().enumerate() {
+                    self.staging.push(StagedUpload {
+                        handle,
+                        level: i as u32,
+                        position: (0, 0),
+                        dimensions: dims,
+                        bytes: v.into_vec(),
+                    });
+
+                    dims.0 = (dims.0 / 2).max(1);
+                    dims.1 = (dims.1 / 2).max(1);
+                }
+            }
+        }
+
         Ok(())
     }
 
-    unsafe fn update_texture(&mut self, _: TextureHandle, _: Aabb2<u32>, _: &[u8]) -> Result<()> {
+    unsafe fn update_texture(
+        &mut self,
+        handle: TextureHandle,
+        area: Aabb2<u32>,
+        data: &[u8],
+    ) -> Result<()> {
+        if data.len() >= STAGING_THRESHOLD || self.staging.contains(handle) {
+            self.staging.push(StagedUpload {
+                handle,
+                level: 0,
+                position: (area.min.x, area.min.y),
+                dimensions: (area.dim().x, area.dim().y),
+                bytes: data.to_vec(),
+            });
+        }
+
         Ok(())
     }
 
-    unsafe fn delete_texture(&mut self, _: TextureHandle) -> Result<()> {
+    unsafe fn delete_texture(&mut self, handle: TextureHandle) -> Result<()> {
+        self.staging.remove(handle);
         Ok(())
     }
 
@@ -64,6 +107,47 @@ impl Visitor for HeadlessVisitor {
         Ok(())
     }
 
+    unsafe fn create_texture_array(
+        &mut self,
+        _: TextureArrayHandle,
+        _: TextureArrayParams,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    unsafe fn update_texture_array(
+        &mut self,
+        _: TextureArrayHandle,
+        _: u32,
+        _: &[u8],
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    unsafe fn delete_texture_array(&mut self, _: TextureArrayHandle) -> Result<()> {
+        Ok(())
+    }
+
+    unsafe fn create_sampler(&mut self, _: SamplerHandle, _: SamplerParams) -> Result<()> {
+        Ok(())
+    }
+
+    unsafe fn delete_sampler(&mut self, _: SamplerHandle) -> Result<()> {
+        Ok(())
+    }
+
+    unsafe fn create_occlusion_query(
+        &mut self,
+        _: OcclusionQueryHandle,
+        _: OcclusionQueryParams,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    unsafe fn delete_occlusion_query(&mut self, _: OcclusionQueryHandle) -> Result<()> {
+        Ok(())
+    }
+
     unsafe fn create_mesh(
         &mut self,
         _: MeshHandle,
@@ -85,7 +169,7 @@ impl Visitor for HeadlessVisitor {
         Ok(())
     }
 
-    unsafe fn bind(&mut self, _: SurfaceHandle, _: Vector2<u32>) -> Result<()> {
+    unsafe fn bind(&mut self, _: SurfaceHandle, _: Vector2<u32>, _: bool) -> Result<()> {
         Ok(())
     }
 
@@ -99,6 +183,17 @@ impl Visitor for HeadlessVisitor {
         Ok(0)
     }
 
+    unsafe fn capture(
+        &mut self,
+        _: ShaderHandle,
+        _: MeshHandle,
+        _: MeshIndex,
+        _: &[UniformVar],
+        _: MeshHandle,
+    ) -> Result<u32> {
+        Ok(0)
+    }
+
     unsafe fn update_surface_scissor(&mut self, _: SurfaceScissor) -> Result<()> {
         Ok(())
     }
@@ -107,11 +202,53 @@ impl Visitor for HeadlessVisitor {
         Ok(())
     }
 
+    unsafe fn begin_occlusion_query(&mut self, _: OcclusionQueryHandle) -> Result<()> {
+        Ok(())
+    }
+
+    unsafe fn end_occlusion_query(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    unsafe fn begin_conditional_render(&mut self, _: OcclusionQueryHandle) -> Result<()> {
+        Ok(())
+    }
+
+    unsafe fn end_conditional_render(&mut self) -> Result<()> {
+        Ok(())
+    }
+
     unsafe fn flush(&mut self) -> Result<()> {
         Ok(())
     }
 
     unsafe fn advance(&mut self) -> Result<()> {
+        self.staging.batch();
         Ok(())
     }
+
+    unsafe fn update_texture_upload_budget(&mut self, bytes: usize) -> Result<()> {
+        self.staging.budget = bytes;
+        Ok(())
+    }
+
+    unsafe fn update_frame_latency(&mut self, _: FrameLatency) -> Result<()> {
+        Ok(())
+    }
+
+    unsafe fn update_strict_uniforms(&mut self, _: bool) -> Result<()> {
+        Ok(())
+    }
+
+    unsafe fn update_debug_mode(&mut self, _: DebugMode) -> Result<()> {
+        Ok(())
+    }
+
+    fn uploaded_texture_bytes(&self) -> usize {
+        self.staging.uploaded()
+    }
+
+    fn renderer(&self) -> String {
+        "Headless".to_owned()
+    }
 }
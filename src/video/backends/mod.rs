@@ -6,6 +6,7 @@ pub mod headless;
 mod utils;
 
 use super::assets::prelude::*;
+use super::FrameLatency;
 
 use crate::errors::*;
 use crate::math::prelude::{Aabb2, Vector2};
@@ -53,6 +54,35 @@ pub trait Visitor {
 
     unsafe fn delete_render_texture(&mut self, handle: RenderTextureHandle) -> Result<()>;
 
+    unsafe fn create_texture_array(
+        &mut self,
+        handle: TextureArrayHandle,
+        params: TextureArrayParams,
+    ) -> Result<()>;
+
+    /// Replaces the whole image of the layer in texture array.
+    unsafe fn update_texture_array(
+        &mut self,
+        handle: TextureArrayHandle,
+        layer: u32,
+        bytes: &[u8],
+    ) -> Result<()>;
+
+    unsafe fn delete_texture_array(&mut self, handle: TextureArrayHandle) -> Result<()>;
+
+    unsafe fn create_sampler(&mut self, handle: SamplerHandle, params: SamplerParams)
+        -> Result<()>;
+
+    unsafe fn delete_sampler(&mut self, handle: SamplerHandle) -> Result<()>;
+
+    unsafe fn create_occlusion_query(
+        &mut self,
+        handle: OcclusionQueryHandle,
+        params: OcclusionQueryParams,
+    ) -> Result<()>;
+
+    unsafe fn delete_occlusion_query(&mut self, handle: OcclusionQueryHandle) -> Result<()>;
+
     unsafe fn create_mesh(
         &mut self,
         handle: MeshHandle,
@@ -76,7 +106,14 @@ pub trait Visitor {
 
     unsafe fn delete_mesh(&mut self, handle: MeshHandle) -> Result<()>;
 
-    unsafe fn bind(&mut self, surface: SurfaceHandle, dimensions: Vector2<u32>) -> Result<()>;
+    /// Binds the surface. The surface will be cleared when its first bound in current frame,
+    /// unless `clear` is false.
+    unsafe fn bind(
+        &mut self,
+        surface: SurfaceHandle,
+        dimensions: Vector2<u32>,
+        clear: bool,
+    ) -> Result<()>;
 
     unsafe fn draw(
         &mut self,
@@ -86,16 +123,60 @@ pub trait Visitor {
         vars: &[UniformVar],
     ) -> Result<u32>;
 
+    /// Draws the mesh with rasterization discarded, and captures the processed vertices
+    /// into the vertex buffer of `dst` with transform feedback.
+    unsafe fn capture(
+        &mut self,
+        shader: ShaderHandle,
+        mesh: MeshHandle,
+        mesh_index: MeshIndex,
+        vars: &[UniformVar],
+        dst: MeshHandle,
+    ) -> Result<u32>;
+
     unsafe fn update_surface_scissor(&mut self, scissor: SurfaceScissor) -> Result<()>;
 
     unsafe fn update_surface_viewport(&mut self, vp: SurfaceViewport) -> Result<()>;
 
+    /// Starts counting the samples that pass the depth test with occlusion query.
+    unsafe fn begin_occlusion_query(&mut self, handle: OcclusionQueryHandle) -> Result<()>;
+
+    unsafe fn end_occlusion_query(&mut self) -> Result<()>;
+
+    /// Discards the following draw calls if no samples passed in the last result of
+    /// occlusion query.
+    unsafe fn begin_conditional_render(&mut self, handle: OcclusionQueryHandle) -> Result<()>;
+
+    unsafe fn end_conditional_render(&mut self) -> Result<()>;
+
     /// Blocks until all execution is complete. Such effects include all changes to render state, all
     /// changes to connection state, and all changes to the frame buffer contents.
     unsafe fn flush(&mut self) -> Result<()>;
 
     /// Advance one frame, it will be called every frames.
     unsafe fn advance(&mut self) -> Result<()>;
+
+    /// Sets the maximum number of bytes of texture data that are uploaded asynchronously
+    /// per frame.
+    unsafe fn update_texture_upload_budget(&mut self, bytes: usize) -> Result<()>;
+
+    /// Sets how many frames could be queued before they are presented. It's applied when
+    /// the frame is flushed.
+    unsafe fn update_frame_latency(&mut self, latency: FrameLatency) -> Result<()>;
+
+    /// Sets whether drawing with uniform fields that are not declared in shader fails.
+    unsafe fn update_strict_uniforms(&mut self, strict: bool) -> Result<()>;
+
+    /// Sets the debug visualization of the surfaces which do not override it. It takes
+    /// effect when the next surface is binded.
+    unsafe fn update_debug_mode(&mut self, mode: DebugMode) -> Result<()>;
+
+    /// Returns the bytes of staged texture data uploaded in current frame. It's always zero
+    /// on the backends which upload textures immediately.
+    fn uploaded_texture_bytes(&self) -> usize;
+
+    /// Returns the name of the renderer, which is usually specific to the GPU and driver.
+    fn renderer(&self) -> String;
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -1,6 +1,36 @@
 use std::borrow::Borrow;
+use std::collections::VecDeque;
+
+use byteorder::{ByteOrder, NativeEndian};
 
 use crate::utils::handle::Handle;
+use crate::video::assets::mesh::IndexFormat;
+use crate::video::assets::texture::TextureHandle;
+
+/// The colors of pixels drawn 1 to 8 or more times in `DebugMode::Overdraw`.
+pub const OVERDRAW_COLORS: [[f32; 3]; 8] = [
+    [0.0, 0.0, 0.5],
+    [0.0, 0.0, 1.0],
+    [0.0, 1.0, 1.0],
+    [0.0, 1.0, 0.0],
+    [1.0, 1.0, 0.0],
+    [1.0, 0.5, 0.0],
+    [1.0, 0.0, 0.0],
+    [1.0, 1.0, 1.0],
+];
+
+/// The colors of mipmap levels in `DebugMode::MipLevels`.
+pub const MIP_COLORS: [[u8; 4]; 6] = [
+    [255, 0, 0, 255],
+    [255, 255, 0, 255],
+    [0, 255, 0, 255],
+    [0, 255, 255, 255],
+    [0, 0, 255, 255],
+    [255, 0, 255, 255],
+];
+
+/// The attribute of barycentric coordinates in the vertex shaders of wireframe variants.
+pub const WIREFRAME_ATTRIBUTE: &str = "crayon_Barycentric";
 
 #[derive(Debug)]
 pub struct DataVec<T>
@@ -36,6 +66,20 @@ where
         None
     }
 
+    pub fn get_mut<H>(&mut self, handle: H) -> Option<&mut T>
+    where
+        H: Borrow<Handle>,
+    {
+        let index = handle.borrow().index() as usize;
+        if let Some(&v) = self.versions.get(index) {
+            if v == handle.borrow().version() {
+                return self.buf[index].as_mut();
+            }
+        }
+
+        None
+    }
+
     pub fn create<H>(&mut self, handle: H, value: T)
     where
         H: Borrow<Handle>,
@@ -66,3 +110,275 @@ where
         }
     }
 }
+
+/// Takes the leading items whose total size fits in `budget`, and returns the total size
+/// and the number of them. The first item is always taken, so the progress is made even
+/// if it exceeds the budget alone.
+pub fn budget_batch<T>(sizes: T, budget: usize) -> (usize, usize)
+where
+    T: IntoIterator<Item = usize>,
+{
+    let (mut size, mut num) = (0, 0);
+    for v in sizes {
+        if num > 0 && size + v > budget {
+            break;
+        }
+
+        size += v;
+        num += 1;
+    }
+
+    (size, num)
+}
+
+/// The default number of bytes of staged texture data that are uploaded per frame.
+pub const DEFAULT_UPLOAD_BUDGET: usize = 8 * 1024 * 1024;
+
+/// Texture uploads smaller than this are executed immediately instead of being staged.
+pub const STAGING_THRESHOLD: usize = 256 * 1024;
+
+/// The alignment of each region in the staging buffer. The offset of pixels in a pixel
+/// buffer object must be a multiple of the size of their components, which is at most 4
+/// bytes. It's only applied between regions, the rows inside a region are tightly packed
+/// with `UNPACK_ALIGNMENT` of 1, so the rows of RGB8 textures are never padded.
+pub const STAGING_ALIGNMENT: usize = 4;
+
+/// Rounds `size` up to the multiple of `STAGING_ALIGNMENT`.
+#[inline]
+pub fn staging_aligned(size: usize) -> usize {
+    (size + STAGING_ALIGNMENT - 1) & !(STAGING_ALIGNMENT - 1)
+}
+
+/// A texture upload which is staged until the budget of a later frame allows it.
+pub struct StagedUpload {
+    pub handle: TextureHandle,
+    pub level: u32,
+    pub position: (u32, u32),
+    pub dimensions: (u32, u32),
+    pub bytes: Vec<u8>,
+}
+
+/// The staged texture uploads in their submission order.
+pub struct UploadQueue {
+    pub budget: usize,
+    uploads: VecDeque<StagedUpload>,
+    uploaded: usize,
+}
+
+impl UploadQueue {
+    pub fn new(budget: usize) -> Self {
+        UploadQueue {
+            budget,
+            uploads: VecDeque::new(),
+            uploaded: 0,
+        }
+    }
+
+    #[inline]
+    pub fn push(&mut self, upload: StagedUpload) {
+        self.uploads.push_back(upload);
+    }
+
+    /// Checks if there are staged uploads of `handle`, whose later updates should be
+    /// staged as well to keep the order.
+    #[inline]
+    pub fn contains(&self, handle: TextureHandle) -> bool {
+        self.uploads.iter().any(|v| v.handle == handle)
+    }
+
+    #[inline]
+    pub fn remove(&mut self, handle: TextureHandle) {
+        self.uploads.retain(|v| v.handle != handle);
+    }
+
+    /// Takes the uploads of current frame within the budget. Returns the size of staging
+    /// buffer, and the uploads along with their aligned offsets in it.
+    pub fn batch(&mut self) -> (usize, Vec<(usize, StagedUpload)>) {
+        let sizes = self.uploads.iter().map(|v| staging_aligned(v.bytes.len()));
+        let (size, num) = budget_batch(sizes, self.budget);
+
+        let mut offset = 0;
+        let mut batch = Vec::with_capacity(num);
+        for v in self.uploads.drain(..num) {
+            let len = v.bytes.len();
+            batch.push((offset, v));
+            offset += staging_aligned(len);
+        }
+
+        self.uploaded = batch.iter().map(|v| v.1.bytes.len()).sum();
+        (size, batch)
+    }
+
+    /// Gets the bytes of texture data in the last batch.
+    #[inline]
+    pub fn uploaded(&self) -> usize {
+        self.uploaded
+    }
+}
+
+/// Makes the sources of the wireframe variant of vertex shader `vs`, which is the fallback of
+/// `DebugMode::Wireframe` on backends without `glPolygonMode`. The `main` of `vs` is renamed
+/// and wrapped to pass barycentric coordinates to a fragment shader that only draws the edges
+/// of triangles. Returns `None` if there is no `main` in `vs`.
+pub fn wireframe_sources(vs: &str) -> Option<(String, String)> {
+    let main = "void main";
+
+    let mut from = 0;
+    let at = loop {
+        let at = from + vs[from..].find(main)?;
+        if vs[at + main.len()..].trim_start().starts_with('(') {
+            break at;
+        }
+
+        from = at + main.len();
+    };
+
+    // The version of both stages must be the same.
+    let es3 = vs.trim_start().starts_with("#version 300 es");
+    let (input, output) = if es3 {
+        ("in", "out")
+    } else {
+        ("attribute", "varying")
+    };
+
+    let mut wvs = String::with_capacity(vs.len() + 256);
+    wvs.push_str(&vs[..at]);
+    wvs.push_str("void crayon_Main");
+    wvs.push_str(&vs[at + main.len()..]);
+    wvs.push_str(&format!(
+        "\n{1} vec3 {0};\n{2} vec3 v_{0};\n\nvoid main() {{\n    crayon_Main();\n    v_{0} = {0};\n}}\n",
+        WIREFRAME_ATTRIBUTE, input, output
+    ));
+
+    // The edges are one pixel wide with derivatives, which are only available in GLSL ES
+    // 1.00 with extensions.
+    let wfs = if es3 {
+        format!(
+            "#version 300 es\nprecision mediump float;\n\nin vec3 v_{0};\nout vec4 crayon_Color;\n\n\
+             void main() {{\n    vec3 d = v_{0} / fwidth(v_{0});\n    \
+             if (min(d.x, min(d.y, d.z)) > 1.0) discard;\n    crayon_Color = vec4(1.0);\n}}\n",
+            WIREFRAME_ATTRIBUTE
+        )
+    } else {
+        format!(
+            "#version 100\nprecision mediump float;\n\nvarying vec3 v_{0};\n\n\
+             void main() {{\n    vec3 d = v_{0};\n    \
+             if (min(d.x, min(d.y, d.z)) > 0.02) discard;\n    gl_FragColor = vec4(1.0);\n}}\n",
+            WIREFRAME_ATTRIBUTE
+        )
+    };
+
+    Some((wvs, wfs))
+}
+
+/// Expands the `stride` bytes vertices referenced by `indices` of triangles, so each corner
+/// gets its own vertex and barycentric coordinates. The expanded vertex of the nth index
+/// is the nth one, which keeps the ranges of `MeshIndex` valid for non-indexed draws.
+pub fn expand_wireframe(
+    verts: &[u8],
+    stride: usize,
+    indices: &[u8],
+    format: IndexFormat,
+) -> (Vec<u8>, Vec<f32>) {
+    let num = indices.len() / format.stride();
+    let mut expanded = Vec::with_capacity(num * stride);
+    let mut barycentric = Vec::with_capacity(num * 3);
+
+    for i in 0..num {
+        let bytes = &indices[i * format.stride()..];
+        let index = match format {
+            IndexFormat::U16 => NativeEndian::read_u16(bytes) as usize,
+            IndexFormat::U32 => NativeEndian::read_u32(bytes) as usize,
+        };
+
+        match verts.get(index * stride..(index + 1) * stride) {
+            Some(v) => expanded.extend_from_slice(v),
+            None => expanded.resize(expanded.len() + stride, 0),
+        }
+
+        let mut corner = [0.0; 3];
+        corner[i % 3] = 1.0;
+        barycentric.extend_from_slice(&corner);
+    }
+
+    (expanded, barycentric)
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod test {
+    use super::*;
+    use crate::utils::handle::HandleLike;
+
+    #[test]
+    fn wireframe() {
+        let vs = "#version 100\nattribute vec2 Position;\nvoid main () {\n}\n";
+        let (wvs, wfs) = wireframe_sources(vs).unwrap();
+        assert!(wvs.contains("void crayon_Main () {"));
+        assert!(wvs.contains("attribute vec3 crayon_Barycentric;"));
+        assert!(wfs.starts_with("#version 100"));
+
+        let vs = "#version 300 es\nvoid mainly() {}\nvoid main() {}\n";
+        let (wvs, wfs) = wireframe_sources(vs).unwrap();
+        assert!(wvs.contains("void mainly() {}\nvoid crayon_Main() {}"));
+        assert!(wvs.contains("in vec3 crayon_Barycentric;"));
+        assert!(wfs.starts_with("#version 300 es"));
+
+        assert!(wireframe_sources("void mainly() {}").is_none());
+
+        let verts = [0u8, 1, 2, 3, 4, 5];
+        let indices = IndexFormat::encode(&[2u16, 0, 1, 1, 9, 0]);
+        let (expanded, barycentric) = expand_wireframe(&verts, 2, indices, IndexFormat::U16);
+        assert_eq!(expanded, [4, 5, 0, 1, 2, 3, 2, 3, 0, 0, 0, 1]);
+        assert_eq!(&barycentric[..6], [1.0, 0.0, 0.0, 0.0, 1.0, 0.0]);
+        assert_eq!(&barycentric[9..], [1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn budget() {
+        assert_eq!(budget_batch(vec![], 8), (0, 0));
+        assert_eq!(budget_batch(vec![4, 4, 4], 8), (8, 2));
+        assert_eq!(budget_batch(vec![4, 5, 1], 8), (4, 1));
+        assert_eq!(budget_batch(vec![16, 1], 8), (16, 1));
+        assert_eq!(budget_batch(vec![1, 2, 3], 0), (1, 1));
+    }
+
+    #[test]
+    fn staging() {
+        assert_eq!(staging_aligned(0), 0);
+        assert_eq!(staging_aligned(27), 28);
+        assert_eq!(staging_aligned(28), 28);
+
+        let upload = |index, bytes| StagedUpload {
+            handle: TextureHandle::new(index, 1),
+            level: 0,
+            position: (0, 0),
+            dimensions: (1, 1),
+            bytes: vec![0; bytes],
+        };
+
+        // The rows of a 3x3 RGB8 region are 9 bytes, which are kept unpadded, while the
+        // regions that follow start at aligned offsets.
+        let mut queue = UploadQueue::new(40);
+        queue.push(upload(1, 27));
+        queue.push(upload(2, 8));
+        queue.push(upload(1, 4));
+        queue.push(upload(3, 64));
+        assert!(queue.contains(TextureHandle::new(1, 1)));
+
+        let (size, batch) = queue.batch();
+        assert_eq!(size, 40);
+        assert_eq!(batch.iter().map(|v| v.0).collect::<Vec<_>>(), [0, 28, 36]);
+        assert_eq!(batch[0].1.bytes.len(), 27);
+        assert_eq!(queue.uploaded(), 39);
+        assert!(!queue.contains(TextureHandle::new(1, 1)));
+
+        queue.push(upload(4, 4));
+        queue.remove(TextureHandle::new(4, 1));
+        let (size, batch) = queue.batch();
+        assert_eq!((size, batch.len()), (64, 1));
+        assert_eq!(queue.uploaded(), 64);
+
+        queue.batch();
+        assert_eq!(queue.uploaded(), 0);
+    }
+}
@@ -45,6 +45,34 @@ impl From<IndexFormat> for u32 {
     }
 }
 
+/// Gets the number of components of a vertex attribute type, 0 if its not a float vector.
+pub fn components(tp: u32) -> u8 {
+    match tp {
+        WebGL::FLOAT => 1,
+        WebGL::FLOAT_VEC2 => 2,
+        WebGL::FLOAT_VEC3 => 3,
+        WebGL::FLOAT_VEC4 => 4,
+        _ => 0,
+    }
+}
+
+/// Gets the `UniformVariableType` of a uniform type, `None` if its not supported.
+pub fn uniform_variable_type(tp: u32) -> Option<UniformVariableType> {
+    match tp {
+        WebGL::SAMPLER_2D => Some(UniformVariableType::Texture),
+        WebGL::SAMPLER_2D_ARRAY => Some(UniformVariableType::TextureArray),
+        WebGL::INT | WebGL::BOOL => Some(UniformVariableType::I32),
+        WebGL::FLOAT => Some(UniformVariableType::F32),
+        WebGL::FLOAT_VEC2 => Some(UniformVariableType::Vector2f),
+        WebGL::FLOAT_VEC3 => Some(UniformVariableType::Vector3f),
+        WebGL::FLOAT_VEC4 => Some(UniformVariableType::Vector4f),
+        WebGL::FLOAT_MAT2 => Some(UniformVariableType::Matrix2f),
+        WebGL::FLOAT_MAT3 => Some(UniformVariableType::Matrix3f),
+        WebGL::FLOAT_MAT4 => Some(UniformVariableType::Matrix4f),
+        _ => None,
+    }
+}
+
 impl From<Comparison> for u32 {
     fn from(cmp: Comparison) -> Self {
         match cmp {
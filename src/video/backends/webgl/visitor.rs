@@ -2,8 +2,9 @@ use std::cell::RefCell;
 
 use smallvec::SmallVec;
 use web_sys::{
-    self, HtmlCanvasElement, WebGlBuffer, WebGlFramebuffer, WebGlProgram, WebGlRenderbuffer,
-    WebGlShader, WebGlTexture, WebGlUniformLocation, WebGlVertexArrayObject,
+    self, HtmlCanvasElement, WebGlBuffer, WebGlFramebuffer, WebGlProgram, WebGlQuery,
+    WebGlRenderbuffer, WebGlSampler, WebGlShader, WebGlTexture, WebGlUniformLocation,
+    WebGlVertexArrayObject,
 };
 
 use wasm_bindgen::JsCast;
@@ -14,11 +15,36 @@ use crate::math::prelude::*;
 use crate::utils::hash::{FastHashMap, FastHashSet};
 use crate::utils::hash_value::HashValue;
 use crate::video::assets::prelude::*;
+use crate::video::FrameLatency;
 
-use super::super::utils::DataVec;
+use super::super::utils::{
+    expand_wireframe, wireframe_sources, DataVec, MIP_COLORS, OVERDRAW_COLORS, WIREFRAME_ATTRIBUTE,
+};
 use super::super::{UniformVar, Visitor};
 use super::capabilities::Capabilities;
 
+// WebGL 2 accepts both GLSL ES 1.00 and 3.00, the heat map is written in the latter.
+const HEAT_MAP_VS: &str = r#"#version 300 es
+precision lowp float;
+
+in vec2 Position;
+
+void main() {
+    gl_Position = vec4(Position, 0.0, 1.0);
+}
+"#;
+
+const HEAT_MAP_FS: &str = r#"#version 300 es
+precision lowp float;
+
+uniform vec4 u_Color;
+out vec4 o_Color;
+
+void main() {
+    o_Color = u_Color;
+}
+"#;
+
 #[derive(Debug, Clone)]
 struct GLSurfaceData {
     handle: SurfaceHandle,
@@ -34,6 +60,8 @@ pub struct GLShaderData {
     params: ShaderParams,
     uniforms: RefCell<FastHashMap<HashValue<str>, WebGlUniformLocation>>,
     attributes: RefCell<FastHashMap<HashValue<str>, i32>>,
+    /// The source of vertex shader, which is kept to make the wireframe variant.
+    vs: Option<String>,
 }
 
 impl GLShaderData {
@@ -85,6 +113,7 @@ struct GLTextureData {
     handle: TextureHandle,
     id: WebGlTexture,
     params: TextureParams,
+    levels: u32,
     allocated: RefCell<bool>,
 }
 
@@ -101,10 +130,30 @@ struct GLRenderTextureData {
     params: RenderTextureParams,
 }
 
+#[derive(Debug, Clone)]
+struct GLTextureArrayData {
+    id: WebGlTexture,
+    params: TextureArrayParams,
+}
+
+#[derive(Debug, Clone)]
+struct GLSamplerData {
+    id: WebGlSampler,
+}
+
+#[derive(Debug, Clone)]
+struct GLOcclusionQueryData {
+    id: WebGlQuery,
+    target: u32,
+    issued: bool,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 enum Sampler {
     RenderTexture(RenderTextureHandle),
     Texture(TextureHandle),
+    TextureArray(TextureArrayHandle),
+    MipLevels(TextureHandle),
 }
 
 #[derive(Debug, Clone)]
@@ -125,7 +174,87 @@ struct WebGLState {
     binded_shader: Option<ShaderHandle>,
     binded_texture_index: usize,
     binded_textures: SmallVec<[Option<Sampler>; 8]>,
+    binded_samplers: SmallVec<[Option<SamplerHandle>; 8]>,
     binded_vao: Option<(ShaderHandle, MeshHandle)>,
+    active_query: Option<(OcclusionQueryHandle, u32)>,
+    conditional_render: bool,
+    discard_draws: bool,
+}
+
+struct GLDebug {
+    mode: DebugMode,
+    active: DebugMode,
+    viewport: SurfaceViewport,
+    heat_map: Option<GLHeatMap>,
+    mip_textures: FastHashMap<(u32, u32, u32), WebGlTexture>,
+}
+
+struct GLHeatMap {
+    program: WebGlProgram,
+    vao: WebGlVertexArrayObject,
+    color: WebGlUniformLocation,
+}
+
+/// The barycentric fallback of `DebugMode::Wireframe`, since WebGL has no `glPolygonMode`.
+/// The variants of shaders and the expanded meshes are made lazily while the mode is active.
+#[derive(Default)]
+struct GLWireframe {
+    shaders: FastHashMap<ShaderHandle, Option<GLShaderData>>,
+    meshes: FastHashMap<MeshHandle, GLWireframeMesh>,
+    vaos: FastHashMap<(ShaderHandle, MeshHandle), WebGlVertexArrayObject>,
+}
+
+struct GLWireframeMesh {
+    vbo: WebGlBuffer,
+    barycentric: WebGlBuffer,
+}
+
+impl GLWireframe {
+    fn free_shader(&mut self, ctx: &WebGL, handle: ShaderHandle) {
+        self.free_vaos(ctx, |(s, _)| s == handle);
+
+        if let Some(Some(shader)) = self.shaders.remove(&handle) {
+            ctx.delete_program(Some(&shader.id));
+        }
+    }
+
+    fn free_mesh(&mut self, ctx: &WebGL, handle: MeshHandle) {
+        self.free_vaos(ctx, |(_, m)| m == handle);
+
+        if let Some(mesh) = self.meshes.remove(&handle) {
+            ctx.delete_buffer(Some(&mesh.vbo));
+            ctx.delete_buffer(Some(&mesh.barycentric));
+        }
+    }
+
+    fn clear(&mut self, ctx: &WebGL) {
+        self.free_vaos(ctx, |_| true);
+
+        for (_, shader) in self.shaders.drain() {
+            if let Some(shader) = shader {
+                ctx.delete_program(Some(&shader.id));
+            }
+        }
+
+        for (_, mesh) in self.meshes.drain() {
+            ctx.delete_buffer(Some(&mesh.vbo));
+            ctx.delete_buffer(Some(&mesh.barycentric));
+        }
+    }
+
+    fn free_vaos<T>(&mut self, ctx: &WebGL, predicate: T)
+    where
+        T: Fn((ShaderHandle, MeshHandle)) -> bool,
+    {
+        self.vaos.retain(|&k, vao| {
+            if predicate(k) {
+                ctx.delete_vertex_array(Some(&vao));
+                false
+            } else {
+                true
+            }
+        });
+    }
 }
 
 pub struct WebGLVisitor {
@@ -138,6 +267,12 @@ pub struct WebGLVisitor {
     meshes: DataVec<GLMeshData>,
     textures: DataVec<GLTextureData>,
     render_textures: DataVec<GLRenderTextureData>,
+    texture_arrays: DataVec<GLTextureArrayData>,
+    samplers: DataVec<GLSamplerData>,
+    occlusion_queries: DataVec<GLOcclusionQueryData>,
+    strict_uniforms: bool,
+    debug: GLDebug,
+    wireframe: GLWireframe,
 }
 
 impl WebGLVisitor {
@@ -169,8 +304,12 @@ impl WebGLVisitor {
             binded_shader: None,
             binded_texture_index: 0,
             binded_textures: SmallVec::new(),
+            binded_samplers: SmallVec::new(),
             vaos: FastHashMap::default(),
             binded_vao: None,
+            active_query: None,
+            conditional_render: false,
+            discard_draws: false,
         };
 
         Self::reset_render_state(&ctx, &mut state)?;
@@ -183,7 +322,22 @@ impl WebGLVisitor {
             shaders: DataVec::new(),
             textures: DataVec::new(),
             render_textures: DataVec::new(),
+            texture_arrays: DataVec::new(),
+            samplers: DataVec::new(),
+            occlusion_queries: DataVec::new(),
             meshes: DataVec::new(),
+            strict_uniforms: true,
+            debug: GLDebug {
+                mode: DebugMode::None,
+                active: DebugMode::None,
+                viewport: SurfaceViewport {
+                    position: Vector2::new(0, 0),
+                    size: Vector2::new(0, 0),
+                },
+                heat_map: None,
+                mip_textures: FastHashMap::default(),
+            },
+            wireframe: GLWireframe::default(),
         })
     }
 }
@@ -195,6 +349,57 @@ impl Visitor for WebGLVisitor {
         Ok(())
     }
 
+    unsafe fn update_texture_upload_budget(&mut self, _: usize) -> Result<()> {
+        // Texture data is always copied by the browser before being uploaded, there is
+        // no benefits of staging it with pixel buffer objects.
+        Ok(())
+    }
+
+    unsafe fn update_frame_latency(&mut self, _: FrameLatency) -> Result<()> {
+        // The frames are presented by the browser after the callback of animation frame
+        // returns, which could not be tuned here.
+        Ok(())
+    }
+
+    unsafe fn update_strict_uniforms(&mut self, strict: bool) -> Result<()> {
+        self.strict_uniforms = strict;
+        Ok(())
+    }
+
+    unsafe fn update_debug_mode(&mut self, mode: DebugMode) -> Result<()> {
+        self.debug.mode = mode;
+
+        if mode != DebugMode::Wireframe {
+            self.wireframe.clear(&self.ctx);
+        }
+
+        if mode != DebugMode::MipLevels {
+            for v in self.state.binded_textures.iter_mut() {
+                if let Some(Sampler::MipLevels(_)) = *v {
+                    *v = None;
+                }
+            }
+
+            for (_, id) in self.debug.mip_textures.drain() {
+                self.ctx.delete_texture(Some(&id));
+            }
+        }
+
+        check(&self.ctx)
+    }
+
+    fn uploaded_texture_bytes(&self) -> usize {
+        0
+    }
+
+    fn renderer(&self) -> String {
+        self.ctx
+            .get_parameter(WebGL::RENDERER)
+            .ok()
+            .and_then(|v| v.as_string())
+            .unwrap_or_else(|| "WebGL".to_owned())
+    }
+
     unsafe fn create_surface(
         &mut self,
         handle: SurfaceHandle,
@@ -314,6 +519,13 @@ impl Visitor for WebGLVisitor {
         vs: &str,
         fs: &str,
     ) -> Result<()> {
+        // WebGL 2 forbids indexed draw calls while transform feedback is active, which all
+        // the draw calls of meshes are.
+        if !params.varyings.is_empty() {
+            bail!("Transform feedback is not supported.");
+        }
+
+        let source = vs.to_owned();
         let vs = Self::compile(&self.ctx, WebGL::VERTEX_SHADER, vs)?;
         let fs = Self::compile(&self.ctx, WebGL::FRAGMENT_SHADER, fs)?;
         let id = Self::link(&self.ctx, &[vs, fs])?;
@@ -324,6 +536,7 @@ impl Visitor for WebGLVisitor {
             params: params,
             uniforms: RefCell::new(FastHashMap::default()),
             attributes: RefCell::new(FastHashMap::default()),
+            vs: Some(source),
         };
 
         for (name, _, _) in shader.params.attributes.iter() {
@@ -341,6 +554,20 @@ impl Visitor for WebGLVisitor {
             }
         }
 
+        let active = Self::active_attributes(&self.ctx, &shader.id);
+        let active = active.iter().map(|(name, size)| (name.as_str(), *size));
+        if let Err(err) = shader.params.attributes.validate_active_attributes(active) {
+            self.ctx.delete_program(Some(&shader.id));
+            bail!(err);
+        }
+
+        let active = Self::active_uniforms(&self.ctx, &shader.id);
+        let active = active.iter().map(|(name, tp)| (name.as_str(), *tp));
+        if let Err(err) = shader.params.uniforms.validate_active_uniforms(active) {
+            self.ctx.delete_program(Some(&shader.id));
+            bail!(err);
+        }
+
         self.shaders.create(handle, shader);
         Ok(())
     }
@@ -368,6 +595,7 @@ impl Visitor for WebGLVisitor {
             self.state.binded_shader = None;
         }
 
+        self.wireframe.free_shader(&self.ctx, handle);
         self.ctx.delete_program(Some(&shader.id));
         check(&self.ctx)
     }
@@ -387,10 +615,12 @@ impl Visitor for WebGLVisitor {
 
         let id = self.ctx.create_texture().unwrap();
         let mut allocated = false;
+        let mut levels = 1;
 
         if let Some(mut data) = data {
             let len = data.bytes.len();
             if len > 0 {
+                levels = len as u32;
                 Self::bind_texture(
                     &self.ctx,
                     &mut self.state,
@@ -453,6 +683,7 @@ impl Visitor for WebGLVisitor {
                 handle: handle,
                 id: id,
                 params: params,
+                levels: levels,
                 allocated: RefCell::new(allocated),
             },
         );
@@ -633,6 +864,223 @@ impl Visitor for WebGLVisitor {
         check(&self.ctx)
     }
 
+    unsafe fn create_texture_array(
+        &mut self,
+        handle: TextureArrayHandle,
+        params: TextureArrayParams,
+    ) -> Result<()> {
+        if !self.capabilities.support_texture_format(params.format) {
+            bail!(
+                "The GL Context does not support the texture format {:?}.",
+                params.format
+            );
+        }
+
+        let id = self.ctx.create_texture().unwrap();
+
+        Self::bind_texture(
+            &self.ctx,
+            &mut self.state,
+            Some(Sampler::TextureArray(handle)),
+            0,
+            Some(&id),
+        )?;
+
+        let wrap: u32 = params.wrap.into();
+        let filter = match params.filter {
+            TextureFilter::Nearest => WebGL::NEAREST,
+            TextureFilter::Linear => WebGL::LINEAR,
+        };
+
+        let (target, wrap, filter) = (WebGL::TEXTURE_2D_ARRAY, wrap as i32, filter as i32);
+        self.ctx.tex_parameteri(target, WebGL::TEXTURE_WRAP_S, wrap);
+        self.ctx.tex_parameteri(target, WebGL::TEXTURE_WRAP_T, wrap);
+        self.ctx
+            .tex_parameteri(target, WebGL::TEXTURE_MIN_FILTER, filter);
+        self.ctx
+            .tex_parameteri(target, WebGL::TEXTURE_MAG_FILTER, filter);
+
+        let (internal_format, format, pixel_type) = params.format.into();
+        self.ctx
+            .tex_image_3d_with_opt_u8_array(
+                WebGL::TEXTURE_2D_ARRAY,
+                0,
+                internal_format as i32,
+                params.dimensions.x as i32,
+                params.dimensions.y as i32,
+                params.layers as i32,
+                0,
+                format,
+                pixel_type,
+                None,
+            )
+            .unwrap();
+
+        check(&self.ctx)?;
+        self.texture_arrays
+            .create(handle, GLTextureArrayData { id, params });
+        Ok(())
+    }
+
+    unsafe fn update_texture_array(
+        &mut self,
+        handle: TextureArrayHandle,
+        layer: u32,
+        data: &[u8],
+    ) -> Result<()> {
+        let texture = self
+            .texture_arrays
+            .get(handle)
+            .ok_or_else(|| format_err!("{:?} is invalid.", handle))?;
+
+        texture.params.validate_layer(layer, data)?;
+
+        Self::bind_texture(
+            &self.ctx,
+            &mut self.state,
+            Some(Sampler::TextureArray(handle)),
+            0,
+            Some(&texture.id),
+        )?;
+
+        let (_, format, pixel_type) = texture.params.format.into();
+        let mv = ::std::slice::from_raw_parts_mut(data.as_ptr() as *mut u8, data.len());
+        self.ctx
+            .tex_sub_image_3d_with_opt_u8_array(
+                WebGL::TEXTURE_2D_ARRAY,
+                0,
+                0,
+                0,
+                layer as i32,
+                texture.params.dimensions.x as i32,
+                texture.params.dimensions.y as i32,
+                1,
+                format,
+                pixel_type,
+                Some(mv),
+            )
+            .unwrap();
+
+        check(&self.ctx)
+    }
+
+    unsafe fn delete_texture_array(&mut self, handle: TextureArrayHandle) -> Result<()> {
+        let texture = self
+            .texture_arrays
+            .free(handle)
+            .ok_or_else(|| format_err!("{:?} is invalid.", handle))?;
+
+        for v in self.state.binded_textures.iter_mut() {
+            if *v == Some(Sampler::TextureArray(handle)) {
+                *v = None;
+            }
+        }
+
+        self.ctx.delete_texture(Some(&texture.id));
+        check(&self.ctx)
+    }
+
+    unsafe fn create_sampler(
+        &mut self,
+        handle: SamplerHandle,
+        params: SamplerParams,
+    ) -> Result<()> {
+        let id = self
+            .ctx
+            .create_sampler()
+            .ok_or_else(|| format_err!("Unable to create sampler object."))?;
+
+        let wrap: u32 = params.wrap.into();
+        self.ctx
+            .sampler_parameteri(&id, WebGL::TEXTURE_WRAP_S, wrap as i32);
+        self.ctx
+            .sampler_parameteri(&id, WebGL::TEXTURE_WRAP_T, wrap as i32);
+
+        let (min_filter, mag_filter) = match (params.filter, params.mipmap) {
+            (TextureFilter::Nearest, false) => (WebGL::NEAREST, WebGL::NEAREST),
+            (TextureFilter::Nearest, true) => (WebGL::NEAREST_MIPMAP_NEAREST, WebGL::NEAREST),
+            (TextureFilter::Linear, false) => (WebGL::LINEAR, WebGL::LINEAR),
+            (TextureFilter::Linear, true) => (WebGL::LINEAR_MIPMAP_LINEAR, WebGL::LINEAR),
+        };
+
+        self.ctx
+            .sampler_parameteri(&id, WebGL::TEXTURE_MIN_FILTER, min_filter as i32);
+        self.ctx
+            .sampler_parameteri(&id, WebGL::TEXTURE_MAG_FILTER, mag_filter as i32);
+
+        if let Some(cmp) = params.compare {
+            let cmp: u32 = cmp.into();
+            let mode = WebGL::COMPARE_REF_TO_TEXTURE as i32;
+            self.ctx
+                .sampler_parameteri(&id, WebGL::TEXTURE_COMPARE_MODE, mode);
+            self.ctx
+                .sampler_parameteri(&id, WebGL::TEXTURE_COMPARE_FUNC, cmp as i32);
+        }
+
+        check(&self.ctx)?;
+        self.samplers.create(handle, GLSamplerData { id });
+        Ok(())
+    }
+
+    unsafe fn delete_sampler(&mut self, handle: SamplerHandle) -> Result<()> {
+        let sampler = self
+            .samplers
+            .free(handle)
+            .ok_or_else(|| format_err!("{:?} is invalid.", handle))?;
+
+        for v in self.state.binded_samplers.iter_mut() {
+            if *v == Some(handle) {
+                *v = None;
+            }
+        }
+
+        self.ctx.delete_sampler(Some(&sampler.id));
+        check(&self.ctx)
+    }
+
+    unsafe fn create_occlusion_query(
+        &mut self,
+        handle: OcclusionQueryHandle,
+        params: OcclusionQueryParams,
+    ) -> Result<()> {
+        let id = self
+            .ctx
+            .create_query()
+            .ok_or_else(|| format_err!("Unable to create query object."))?;
+
+        let target = if params.conservative {
+            WebGL::ANY_SAMPLES_PASSED_CONSERVATIVE
+        } else {
+            WebGL::ANY_SAMPLES_PASSED
+        };
+
+        let data = GLOcclusionQueryData {
+            id,
+            target,
+            issued: false,
+        };
+
+        self.occlusion_queries.create(handle, data);
+        check(&self.ctx)
+    }
+
+    unsafe fn delete_occlusion_query(&mut self, handle: OcclusionQueryHandle) -> Result<()> {
+        let query = self
+            .occlusion_queries
+            .free(handle)
+            .ok_or_else(|| format_err!("{:?} is invalid.", handle))?;
+
+        if let Some((active, target)) = self.state.active_query {
+            if active == handle {
+                self.ctx.end_query(target);
+                self.state.active_query = None;
+            }
+        }
+
+        self.ctx.delete_query(Some(&query.id));
+        check(&self.ctx)
+    }
+
     unsafe fn create_mesh(
         &mut self,
         handle: MeshHandle,
@@ -683,6 +1131,7 @@ impl Visitor for WebGLVisitor {
             bail!("Trying to update immutable buffer");
         }
 
+        self.wireframe.free_mesh(&self.ctx, handle);
         Self::update_buffer(&self.ctx, WebGL::ARRAY_BUFFER, &mesh.vbo, offset, data)
     }
 
@@ -701,6 +1150,7 @@ impl Visitor for WebGLVisitor {
             bail!("Trying to update immutable buffer");
         }
 
+        self.wireframe.free_mesh(&self.ctx, handle);
         Self::update_buffer(
             &self.ctx,
             WebGL::ELEMENT_ARRAY_BUFFER,
@@ -729,16 +1179,24 @@ impl Visitor for WebGLVisitor {
             });
         }
 
+        self.wireframe.free_mesh(&self.ctx, handle);
         self.ctx.delete_buffer(Some(&mesh.vbo));
         self.ctx.delete_buffer(Some(&mesh.ibo));
         check(&self.ctx)
     }
 
-    unsafe fn bind(&mut self, handle: SurfaceHandle, dimensions: Vector2<u32>) -> Result<()> {
+    unsafe fn bind(
+        &mut self,
+        handle: SurfaceHandle,
+        dimensions: Vector2<u32>,
+        clear: bool,
+    ) -> Result<()> {
         if self.state.binded_surface == Some(handle) {
             return Ok(());
         }
 
+        self.end_debug_mode()?;
+
         let surface = self
             .surfaces
             .get(handle)
@@ -758,7 +1216,7 @@ impl Visitor for WebGLVisitor {
         Self::set_viewport(&self.ctx, &mut self.state, vp)?;
         Self::set_scissor(&self.ctx, &mut self.state, SurfaceScissor::Disable)?;
 
-        if !self.state.cleared_surfaces.contains(&handle) {
+        if clear && !self.state.cleared_surfaces.contains(&handle) {
             // Sets depth write enable to make sure that we can clear depth buffer properly.
             if surface.params.clear_depth.is_some() {
                 self.state.binded_shader = None;
@@ -776,6 +1234,9 @@ impl Visitor for WebGLVisitor {
             self.state.cleared_surfaces.insert(handle);
         }
 
+        let mode = surface.params.debug_mode.unwrap_or(self.debug.mode);
+        self.begin_debug_mode(mode, vp)?;
+
         self.state.binded_surface = Some(handle);
         Ok(())
     }
@@ -787,11 +1248,24 @@ impl Visitor for WebGLVisitor {
         mesh_index: MeshIndex,
         uniforms: &[UniformVar],
     ) -> Result<u32> {
+        if self.state.discard_draws {
+            return Ok(0);
+        }
+
+        let wireframe = self.prepare_wireframe(shader, mesh)?;
+        if wireframe {
+            // The variant shares the handle of its shader, which bypasses the cached bindings.
+            self.state.binded_shader = None;
+            self.state.binded_vao = None;
+        }
+
         // Bind program and associated uniforms and textures.
-        let shader = self
-            .shaders
-            .get(shader)
-            .ok_or_else(|| format_err!("{:?} is invalid.", shader))?;
+        let shader = if wireframe {
+            self.wireframe.shaders.get(&shader).and_then(|v| v.as_ref())
+        } else {
+            self.shaders.get(shader)
+        }
+        .ok_or_else(|| format_err!("{:?} is invalid.", shader))?;
 
         Self::bind_shader(&self.ctx, &mut self.state, &shader)?;
 
@@ -808,27 +1282,66 @@ impl Visitor for WebGLVisitor {
                     );
                 }
 
-                let location = shader.hash_uniform_location(field).unwrap();
+                // The uniforms only used by fragment shaders are inactive in wireframe variants.
+                let location = match shader.hash_uniform_location(field) {
+                    Some(v) => v,
+                    None if wireframe => continue,
+                    None => unreachable!(),
+                };
+
+                let sampler = match variable {
+                    UniformVariable::SampledTexture(_, v)
+                    | UniformVariable::SampledRenderTexture(_, v) => Some(v),
+                    _ => None,
+                };
+
                 match variable {
-                    UniformVariable::Texture(handle) => {
+                    UniformVariable::Texture(handle)
+                    | UniformVariable::SampledTexture(handle, _) => {
                         let v = UniformVariable::I32(index as i32);
                         Self::bind_uniform_variable(&self.ctx, &location, &v)?;
 
                         if let Some(texture) = self.textures.get(handle) {
-                            Self::bind_texture(
-                                &self.ctx,
-                                &mut self.state,
-                                Some(Sampler::Texture(handle)),
-                                index,
-                                Some(&texture.id),
-                            )?;
+                            if self.debug.active == DebugMode::MipLevels {
+                                let id = Self::mip_texture(
+                                    &self.ctx,
+                                    &mut self.debug,
+                                    &mut self.state,
+                                    texture,
+                                )?;
+
+                                Self::bind_texture(
+                                    &self.ctx,
+                                    &mut self.state,
+                                    Some(Sampler::MipLevels(handle)),
+                                    index,
+                                    Some(&id),
+                                )?;
+                            } else {
+                                Self::bind_texture(
+                                    &self.ctx,
+                                    &mut self.state,
+                                    Some(Sampler::Texture(handle)),
+                                    index,
+                                    Some(&texture.id),
+                                )?;
+                            }
                         } else {
                             Self::bind_texture(&self.ctx, &mut self.state, None, index, None)?;
                         }
 
+                        Self::bind_sampler(
+                            &self.ctx,
+                            &mut self.state,
+                            &self.samplers,
+                            index,
+                            sampler,
+                        )?;
+
                         index += 1;
                     }
-                    UniformVariable::RenderTexture(handle) => {
+                    UniformVariable::RenderTexture(handle)
+                    | UniformVariable::SampledRenderTexture(handle, _) => {
                         let v = UniformVariable::I32(index as i32);
                         Self::bind_uniform_variable(&self.ctx, &location, &v)?;
 
@@ -851,20 +1364,60 @@ impl Visitor for WebGLVisitor {
                             Self::bind_texture(&self.ctx, &mut self.state, None, index, None)?;
                         }
 
+                        Self::bind_sampler(
+                            &self.ctx,
+                            &mut self.state,
+                            &self.samplers,
+                            index,
+                            sampler,
+                        )?;
+
+                        index += 1;
+                    }
+                    UniformVariable::TextureArray(handle) => {
+                        let v = UniformVariable::I32(index as i32);
+                        Self::bind_uniform_variable(&self.ctx, &location, &v)?;
+
+                        if let Some(texture) = self.texture_arrays.get(handle) {
+                            Self::bind_texture(
+                                &self.ctx,
+                                &mut self.state,
+                                Some(Sampler::TextureArray(handle)),
+                                index,
+                                Some(&texture.id),
+                            )?;
+                        } else {
+                            Self::bind_texture(&self.ctx, &mut self.state, None, index, None)?;
+                        }
+
+                        Self::bind_sampler(
+                            &self.ctx,
+                            &mut self.state,
+                            &self.samplers,
+                            index,
+                            None,
+                        )?;
+
                         index += 1;
                     }
                     _ => {
                         Self::bind_uniform_variable(&self.ctx, &location, &variable)?;
                     }
                 }
-            } else {
+            } else if self.strict_uniforms {
                 bail!("Undefined uniform field {:?}.", field);
             }
         }
 
         if let Some(mesh) = self.meshes.get(mesh) {
             // Bind vertex buffer and vertex array object.
-            Self::bind_mesh(&self.ctx, &mut self.state, &shader, &mesh)?;
+            if wireframe {
+                let meshes = &self.wireframe.meshes;
+                let vaos = &mut self.wireframe.vaos;
+                Self::bind_wireframe_mesh(&self.ctx, vaos, meshes, &shader, &mesh)?;
+            } else {
+                Self::bind_mesh(&self.ctx, &mut self.state, &shader, &mesh)?;
+            }
 
             let (from, len) = match mesh_index {
                 MeshIndex::Ptr(from, len) => {
@@ -893,12 +1446,21 @@ impl Visitor for WebGLVisitor {
                 MeshIndex::All => (0, mesh.params.num_idxes),
             };
 
-            self.ctx.draw_elements_with_i32(
-                mesh.params.primitive.into(),
-                len as i32,
-                mesh.params.index_format.into(),
-                from as i32,
-            );
+            if wireframe {
+                let first = from / mesh.params.index_format.stride();
+                self.ctx
+                    .draw_arrays(WebGL::TRIANGLES, first as i32, len as i32);
+
+                self.state.binded_shader = None;
+                self.state.binded_vao = None;
+            } else {
+                self.ctx.draw_elements_with_i32(
+                    mesh.params.primitive.into(),
+                    len as i32,
+                    mesh.params.index_format.into(),
+                    from as i32,
+                );
+            }
 
             check(&self.ctx)?;
             Ok(mesh.params.primitive.assemble(len as u32))
@@ -915,12 +1477,398 @@ impl Visitor for WebGLVisitor {
         Self::set_viewport(&self.ctx, &mut self.state, vp)
     }
 
+    unsafe fn begin_occlusion_query(&mut self, handle: OcclusionQueryHandle) -> Result<()> {
+        if self.state.active_query.is_some() {
+            bail!("Occlusion queries could not be nested.");
+        }
+
+        let query = self
+            .occlusion_queries
+            .get_mut(handle)
+            .ok_or_else(|| format_err!("{:?} is invalid.", handle))?;
+
+        self.ctx.begin_query(query.target, &query.id);
+        query.issued = true;
+        self.state.active_query = Some((handle, query.target));
+        check(&self.ctx)
+    }
+
+    unsafe fn end_occlusion_query(&mut self) -> Result<()> {
+        if let Some((_, target)) = self.state.active_query.take() {
+            self.ctx.end_query(target);
+        }
+
+        check(&self.ctx)
+    }
+
+    unsafe fn begin_conditional_render(&mut self, handle: OcclusionQueryHandle) -> Result<()> {
+        if self.state.conditional_render {
+            bail!("Conditional rendering could not be nested.");
+        }
+
+        let query = self
+            .occlusion_queries
+            .get(handle)
+            .ok_or_else(|| format_err!("{:?} is invalid.", handle))?;
+
+        // WebGL has no conditional rendering, so we take the result if it has been
+        // returned already. Results are never available in the frame they are issued.
+        if query.issued {
+            let available = self
+                .ctx
+                .get_query_parameter(&query.id, WebGL::QUERY_RESULT_AVAILABLE);
+
+            if available.as_bool() == Some(true) {
+                let passed = self.ctx.get_query_parameter(&query.id, WebGL::QUERY_RESULT);
+                self.state.discard_draws = passed.as_f64() == Some(0.0);
+            }
+        }
+
+        self.state.conditional_render = true;
+        check(&self.ctx)
+    }
+
+    unsafe fn end_conditional_render(&mut self) -> Result<()> {
+        self.state.conditional_render = false;
+        self.state.discard_draws = false;
+        Ok(())
+    }
+
+    unsafe fn capture(
+        &mut self,
+        _: ShaderHandle,
+        _: MeshHandle,
+        _: MeshIndex,
+        _: &[UniformVar],
+        _: MeshHandle,
+    ) -> Result<u32> {
+        bail!("Transform feedback is not supported.");
+    }
+
     unsafe fn flush(&mut self) -> Result<()> {
+        self.end_occlusion_query()?;
+        self.end_conditional_render()?;
+        self.end_debug_mode()?;
+
         self.ctx.finish();
         Ok(())
     }
 }
 
+impl WebGLVisitor {
+    unsafe fn begin_debug_mode(&mut self, mode: DebugMode, vp: SurfaceViewport) -> Result<()> {
+        if mode == DebugMode::Overdraw {
+            // Counts how many times each pixel is drawn with the stencil buffer.
+            self.ctx.enable(WebGL::STENCIL_TEST);
+            self.ctx.stencil_mask(0xFF);
+            self.ctx.stencil_func(WebGL::ALWAYS, 0, 0xFF);
+            self.ctx.stencil_op(WebGL::KEEP, WebGL::INCR, WebGL::INCR);
+            self.ctx.clear_stencil(0);
+            self.ctx.clear(WebGL::STENCIL_BUFFER_BIT);
+        }
+
+        self.debug.active = mode;
+        self.debug.viewport = vp;
+        check(&self.ctx)
+    }
+
+    unsafe fn end_debug_mode(&mut self) -> Result<()> {
+        if self.debug.active == DebugMode::Overdraw {
+            self.draw_heat_map()?;
+            self.ctx.disable(WebGL::STENCIL_TEST);
+        }
+
+        self.debug.active = DebugMode::None;
+        check(&self.ctx)
+    }
+
+    /// Draws full-screen quads with the colors of overdraw counts in the stencil buffer.
+    unsafe fn draw_heat_map(&mut self) -> Result<()> {
+        if self.debug.heat_map.is_none() {
+            self.debug.heat_map = Some(Self::create_heat_map(&self.ctx)?);
+        }
+
+        let ctx = &self.ctx;
+        let state = &mut self.state;
+        let heat_map = self.debug.heat_map.as_ref().unwrap();
+
+        Self::set_viewport(ctx, state, self.debug.viewport)?;
+        Self::set_scissor(ctx, state, SurfaceScissor::Disable)?;
+        Self::set_cull_face(ctx, state, CullFace::Nothing)?;
+        Self::set_depth_test(ctx, state, false, Comparison::Always)?;
+        Self::set_depth_write_offset(ctx, state, None)?;
+        Self::set_color_blend(ctx, state, None)?;
+        Self::set_color_write(ctx, state, (true, true, true, true))?;
+
+        ctx.use_program(Some(&heat_map.program));
+        ctx.bind_vertex_array(Some(&heat_map.vao));
+        state.binded_shader = None;
+        state.binded_vao = None;
+
+        ctx.stencil_op(WebGL::KEEP, WebGL::KEEP, WebGL::KEEP);
+        for i in 0..=OVERDRAW_COLORS.len() {
+            let (func, color) = if i == 0 {
+                (WebGL::EQUAL, [0.0, 0.0, 0.0])
+            } else if i < OVERDRAW_COLORS.len() {
+                (WebGL::EQUAL, OVERDRAW_COLORS[i - 1])
+            } else {
+                // The reference value is less than or equal to the count.
+                (WebGL::LEQUAL, OVERDRAW_COLORS[i - 1])
+            };
+
+            ctx.stencil_func(func, i as i32, 0xFF);
+            ctx.uniform4f(Some(&heat_map.color), color[0], color[1], color[2], 1.0);
+            ctx.draw_arrays(WebGL::TRIANGLE_STRIP, 0, 4);
+        }
+
+        ctx.bind_vertex_array(None);
+        check(ctx)
+    }
+
+    unsafe fn create_heat_map(ctx: &WebGL) -> Result<GLHeatMap> {
+        let vs = Self::compile(ctx, WebGL::VERTEX_SHADER, HEAT_MAP_VS)?;
+        let fs = Self::compile(ctx, WebGL::FRAGMENT_SHADER, HEAT_MAP_FS)?;
+        let program = Self::link(ctx, &[vs, fs])?;
+
+        let color = ctx
+            .get_uniform_location(&program, "u_Color")
+            .ok_or_else(|| format_err!("Uniform(\"u_Color\") is undefined in heat map."))?;
+        let position = ctx.get_attrib_location(&program, "Position") as u32;
+
+        let verts: [f32; 8] = [-1.0, -1.0, 1.0, -1.0, -1.0, 1.0, 1.0, 1.0];
+        let mv = ::std::slice::from_raw_parts_mut(verts.as_ptr() as *mut u8, 32);
+
+        let vao = ctx.create_vertex_array().unwrap();
+        ctx.bind_vertex_array(Some(&vao));
+
+        // The buffer is kept alive by the vertex array object.
+        let vbo = ctx.create_buffer().unwrap();
+        ctx.bind_buffer(WebGL::ARRAY_BUFFER, Some(&vbo));
+        ctx.buffer_data_with_u8_array(WebGL::ARRAY_BUFFER, mv, WebGL::STATIC_DRAW);
+
+        ctx.enable_vertex_attrib_array(position);
+        ctx.vertex_attrib_pointer_with_i32(position, 2, WebGL::FLOAT, false, 0, 0);
+        ctx.bind_vertex_array(None);
+        check(ctx)?;
+
+        Ok(GLHeatMap {
+            program,
+            vao,
+            color,
+        })
+    }
+
+    /// Gets the texture with colored mipmap levels, which has the same dimensions and
+    /// number of levels as `texture`.
+    unsafe fn mip_texture(
+        ctx: &WebGL,
+        debug: &mut GLDebug,
+        state: &mut WebGLState,
+        texture: &GLTextureData,
+    ) -> Result<WebGlTexture> {
+        let dims = texture.params.dimensions;
+        let key = (dims.x, dims.y, texture.levels);
+        if let Some(id) = debug.mip_textures.get(&key) {
+            return Ok(id.clone());
+        }
+
+        let id = ctx.create_texture().unwrap();
+        ctx.bind_texture(WebGL::TEXTURE_2D, Some(&id));
+
+        // The texture unit is not binded to any tracked texture anymore.
+        if let Some(v) = state.binded_textures.get_mut(state.binded_texture_index) {
+            *v = None;
+        }
+
+        let (mut w, mut h) = (dims.x.max(1), dims.y.max(1));
+        for level in 0..texture.levels {
+            let color = MIP_COLORS[level as usize % MIP_COLORS.len()];
+            let mut bytes: Vec<u8> = (0..w * h).flat_map(|_| color.iter().cloned()).collect();
+
+            ctx.tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+                WebGL::TEXTURE_2D,
+                level as i32,
+                WebGL::RGBA as i32,
+                w as i32,
+                h as i32,
+                0,
+                WebGL::RGBA,
+                WebGL::UNSIGNED_BYTE,
+                Some(&mut bytes[..]),
+            )
+            .unwrap();
+
+            w = (w / 2).max(1);
+            h = (h / 2).max(1);
+        }
+
+        let params = &texture.params;
+        Self::bind_texture_params(ctx, params.wrap, params.filter, texture.levels)?;
+        check(ctx)?;
+
+        debug.mip_textures.insert(key, id.clone());
+        Ok(id)
+    }
+
+    /// Prepares the variant of `shader` and the expanded `mesh` if the draw call should be
+    /// drawn with the barycentric fallback of `DebugMode::Wireframe`.
+    unsafe fn prepare_wireframe(&mut self, shader: ShaderHandle, mesh: MeshHandle) -> Result<bool> {
+        if self.debug.active != DebugMode::Wireframe {
+            return Ok(false);
+        }
+
+        // Lines and points are drawn as they are.
+        let mesh = match self.meshes.get(mesh) {
+            Some(v) if v.params.primitive == MeshPrimitive::Triangles => v,
+            _ => return Ok(false),
+        };
+
+        if !self.wireframe.shaders.contains_key(&shader) {
+            let v = match self.shaders.get(shader) {
+                Some(v) => Self::create_wireframe_shader(&self.ctx, v)?,
+                None => return Ok(false),
+            };
+
+            if v.is_none() {
+                warn!(
+                    "The wireframe of {:?} is not available without `main`.",
+                    shader
+                );
+            }
+
+            self.wireframe.shaders.insert(shader, v);
+        }
+
+        if self.wireframe.shaders[&shader].is_none() {
+            return Ok(false);
+        }
+
+        if !self.wireframe.meshes.contains_key(&mesh.handle) {
+            let v = Self::create_wireframe_mesh(&self.ctx, mesh)?;
+            self.wireframe.meshes.insert(mesh.handle, v);
+        }
+
+        Ok(true)
+    }
+
+    unsafe fn create_wireframe_shader(
+        ctx: &WebGL,
+        shader: &GLShaderData,
+    ) -> Result<Option<GLShaderData>> {
+        let (vs, fs) = match shader.vs.as_ref().and_then(|v| wireframe_sources(v)) {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        let vs = Self::compile(ctx, WebGL::VERTEX_SHADER, &vs)?;
+        let fs = Self::compile(ctx, WebGL::FRAGMENT_SHADER, &fs)?;
+        let id = Self::link(ctx, &[vs, fs])?;
+
+        let variant = GLShaderData {
+            handle: shader.handle,
+            id: id,
+            params: shader.params.clone(),
+            uniforms: RefCell::new(FastHashMap::default()),
+            attributes: RefCell::new(FastHashMap::default()),
+            vs: None,
+        };
+
+        // The uniforms that are only used by the fragment shader are inactive in the variant,
+        // which have no locations and are skipped while drawing.
+        for &(ref name, _) in variant.params.uniforms.iter() {
+            let _ = variant.uniform_location(ctx, name);
+        }
+
+        Ok(Some(variant))
+    }
+
+    unsafe fn create_wireframe_mesh(ctx: &WebGL, mesh: &GLMeshData) -> Result<GLWireframeMesh> {
+        // The buffers are binded to `COPY_READ_BUFFER`, which leaves the element arrays of
+        // vertex array objects untouched.
+        let mut verts = vec![0; mesh.params.vertex_buffer_len()];
+        ctx.bind_buffer(WebGL::COPY_READ_BUFFER, Some(&mesh.vbo));
+        ctx.get_buffer_sub_data_with_i32_and_u8_array(WebGL::COPY_READ_BUFFER, 0, &mut verts);
+
+        let mut idxes = vec![0; mesh.params.index_buffer_len()];
+        ctx.bind_buffer(WebGL::COPY_READ_BUFFER, Some(&mesh.ibo));
+        ctx.get_buffer_sub_data_with_i32_and_u8_array(WebGL::COPY_READ_BUFFER, 0, &mut idxes);
+        ctx.bind_buffer(WebGL::COPY_READ_BUFFER, None);
+        check(ctx)?;
+
+        let stride = mesh.params.layout.stride() as usize;
+        let (mut verts, barycentric) =
+            expand_wireframe(&verts, stride, &idxes, mesh.params.index_format);
+
+        let vbo = ctx.create_buffer().unwrap();
+        ctx.bind_buffer(WebGL::ARRAY_BUFFER, Some(&vbo));
+        ctx.buffer_data_with_u8_array(WebGL::ARRAY_BUFFER, &mut verts, WebGL::STATIC_DRAW);
+
+        let barycentric_vbo = ctx.create_buffer().unwrap();
+        ctx.bind_buffer(WebGL::ARRAY_BUFFER, Some(&barycentric_vbo));
+        let len = barycentric.len() * 4;
+        let mv = ::std::slice::from_raw_parts_mut(barycentric.as_ptr() as *mut u8, len);
+        ctx.buffer_data_with_u8_array(WebGL::ARRAY_BUFFER, mv, WebGL::STATIC_DRAW);
+
+        check(ctx)?;
+        Ok(GLWireframeMesh {
+            vbo: vbo,
+            barycentric: barycentric_vbo,
+        })
+    }
+
+    unsafe fn bind_wireframe_mesh(
+        ctx: &WebGL,
+        vaos: &mut FastHashMap<(ShaderHandle, MeshHandle), WebGlVertexArrayObject>,
+        meshes: &FastHashMap<MeshHandle, GLWireframeMesh>,
+        shader: &GLShaderData,
+        mesh: &GLMeshData,
+    ) -> Result<()> {
+        let k = (shader.handle, mesh.handle);
+        if let Some(vao) = vaos.get(&k) {
+            ctx.bind_vertex_array(Some(vao));
+            return check(ctx);
+        }
+
+        let expanded = &meshes[&mesh.handle];
+
+        let vao = ctx.create_vertex_array().unwrap();
+        ctx.bind_vertex_array(Some(&vao));
+        ctx.bind_buffer(WebGL::ARRAY_BUFFER, Some(&expanded.vbo));
+
+        for (name, _, _) in shader.params.attributes.iter() {
+            if let Some(element) = mesh.params.layout.element(name) {
+                let offset = mesh.params.layout.offset(name).unwrap();
+                let stride = mesh.params.layout.stride();
+
+                // The attributes only used by fragment shaders are inactive in the variant.
+                let location = match shader.attribute_location(ctx, name.into()) {
+                    Ok(v) => v,
+                    Err(_) => continue,
+                };
+
+                ctx.enable_vertex_attrib_array(location as u32);
+                ctx.vertex_attrib_pointer_with_i32(
+                    location as u32,
+                    element.size as i32,
+                    element.format.into(),
+                    element.normalized,
+                    stride as i32,
+                    offset as i32,
+                );
+            }
+        }
+
+        let location = shader.attribute_location(ctx, WIREFRAME_ATTRIBUTE)?;
+        ctx.bind_buffer(WebGL::ARRAY_BUFFER, Some(&expanded.barycentric));
+        ctx.enable_vertex_attrib_array(location as u32);
+        ctx.vertex_attrib_pointer_with_i32(location as u32, 3, WebGL::FLOAT, false, 0, 0);
+
+        check(ctx)?;
+        vaos.insert(k, vao);
+        Ok(())
+    }
+}
+
 impl WebGLVisitor {
     unsafe fn bind_surface_render_texture(
         ctx: &WebGL,
@@ -982,6 +1930,35 @@ impl WebGLVisitor {
         }
     }
 
+    /// Reflects the names and numbers of components of active attributes in program.
+    unsafe fn active_attributes(ctx: &WebGL, program: &WebGlProgram) -> Vec<(String, u8)> {
+        let num = ctx
+            .get_program_parameter(program, WebGL::ACTIVE_ATTRIBUTES)
+            .as_f64()
+            .unwrap_or(0.0) as u32;
+
+        (0..num)
+            .filter_map(|i| ctx.get_active_attrib(program, i))
+            .map(|v| (v.name(), super::types::components(v.type_())))
+            .collect()
+    }
+
+    /// Reflects the names and types of active uniforms in program.
+    unsafe fn active_uniforms(
+        ctx: &WebGL,
+        program: &WebGlProgram,
+    ) -> Vec<(String, Option<UniformVariableType>)> {
+        let num = ctx
+            .get_program_parameter(program, WebGL::ACTIVE_UNIFORMS)
+            .as_f64()
+            .unwrap_or(0.0) as u32;
+
+        (0..num)
+            .filter_map(|i| ctx.get_active_uniform(program, i))
+            .map(|v| (v.name(), super::types::uniform_variable_type(v.type_())))
+            .collect()
+    }
+
     unsafe fn link<'a, T>(ctx: &WebGL, shaders: T) -> Result<WebGlProgram>
     where
         T: IntoIterator<Item = &'a WebGlShader>,
@@ -994,6 +1971,7 @@ impl WebGLVisitor {
         for shader in shaders {
             ctx.attach_shader(&program, shader)
         }
+
         ctx.link_program(&program);
 
         if ctx
@@ -1053,17 +2031,10 @@ impl WebGLVisitor {
                 ctx.bind_vertex_array(Some(&vao));
                 ctx.bind_buffer(WebGL::ARRAY_BUFFER, Some(&mesh.vbo));
 
-                for (name, size, required) in shader.params.attributes.iter() {
-                    if let Some(element) = mesh.params.layout.element(name) {
-                        if element.size < size {
-                            bail!(
-                                "Vertex buffer has incompatible attribute `{:?}` [{:?} - {:?}].",
-                                name,
-                                element.size,
-                                size
-                            );
-                        }
+                shader.params.attributes.validate(&mesh.params.layout)?;
 
+                for (name, _, _) in shader.params.attributes.iter() {
+                    if let Some(element) = mesh.params.layout.element(name) {
                         let offset = mesh.params.layout.offset(name).unwrap();
                         let stride = mesh.params.layout.stride();
 
@@ -1077,13 +2048,6 @@ impl WebGLVisitor {
                             stride as i32,
                             offset as i32,
                         );
-                    } else {
-                        if required {
-                            bail!(
-                                "Can't find attribute {:?} description in vertex buffer.",
-                                name
-                            );
-                        }
                     }
                 }
 
@@ -1106,6 +2070,9 @@ impl WebGLVisitor {
         match *variable {
             UniformVariable::Texture(_) => unreachable!(),
             UniformVariable::RenderTexture(_) => unreachable!(),
+            UniformVariable::TextureArray(_) => unreachable!(),
+            UniformVariable::SampledTexture(_, _) => unreachable!(),
+            UniformVariable::SampledRenderTexture(_, _) => unreachable!(),
             UniformVariable::I32(v) => ctx.uniform1i(Some(&location), v),
             UniformVariable::F32(v) => ctx.uniform1f(Some(&location), v),
             UniformVariable::Vector2f(v) => ctx.uniform2f(Some(&location), v[0], v[1]),
@@ -1407,8 +2374,36 @@ impl WebGLVisitor {
         }
 
         if state.binded_textures[index] != sampler {
+            // Unbinds the previous texture array, so it would not be sampled accidently.
+            if let Some(Sampler::TextureArray(_)) = state.binded_textures[index] {
+                ctx.bind_texture(WebGL::TEXTURE_2D_ARRAY, None);
+            }
+
             state.binded_textures[index] = sampler;
-            ctx.bind_texture(WebGL::TEXTURE_2D, id);
+            match sampler {
+                Some(Sampler::TextureArray(_)) => ctx.bind_texture(WebGL::TEXTURE_2D_ARRAY, id),
+                _ => ctx.bind_texture(WebGL::TEXTURE_2D, id),
+            }
+        }
+
+        check(ctx)
+    }
+
+    unsafe fn bind_sampler(
+        ctx: &WebGL,
+        state: &mut WebGLState,
+        samplers: &DataVec<GLSamplerData>,
+        index: usize,
+        handle: Option<SamplerHandle>,
+    ) -> Result<()> {
+        if state.binded_samplers.len() <= index {
+            state.binded_samplers.resize(index + 1, None);
+        }
+
+        if state.binded_samplers[index] != handle {
+            state.binded_samplers[index] = handle;
+            let sampler = handle.and_then(|v| samplers.get(v));
+            ctx.bind_sampler(index as u32, sampler.map(|v| &v.id));
         }
 
         check(ctx)
@@ -32,6 +32,29 @@ impl CommandBuffer {
         self.cmds.push(cmd);
     }
 
+    /// Draws the mesh without rasterization, and captures the vertices processed by vertex
+    /// shader into the vertex buffer of `dst` with transform feedback. The shader should
+    /// specify the captured outputs with `ShaderParams::varyings`.
+    ///
+    /// The captured vertices could be drawn or captured again in later draw calls, which
+    /// is useful for GPU particle simulation and caching procedural meshes. Particles are
+    /// usually simulated by ping-ponging between two meshes, capturing the state of this
+    /// frame from the one captured in the last frame.
+    ///
+    /// The captured varyings are written interleaved, and their total size must equal the
+    /// vertex stride of `dst`, or the draw call fails.
+    ///
+    /// Transform feedback requires OpenGL 3.0 or GLES 3.2, since the older GLES and WebGL 2
+    /// forbid indexed draw calls while capturing. Creating a shader with varyings fails on
+    /// other contexts, which could be used to fall back to the simulation on CPU.
+    #[inline]
+    pub fn capture(&mut self, dc: Draw, dst: MeshHandle) {
+        let len = dc.uniforms_len;
+        let ptr = self.bufs.extend_from_slice(&dc.uniforms[0..len]);
+        let cmd = Command::Capture(dc.shader, dc.mesh, dc.mesh_index, ptr, dst);
+        self.cmds.push(cmd);
+    }
+
     /// Updates the scissor test of surface.
     ///
     /// The test is initially disabled. While the test is enabled, only pixels that lie within
@@ -47,6 +70,36 @@ impl CommandBuffer {
         self.cmds.push(Command::UpdateViewport(viewport));
     }
 
+    /// Starts an occlusion query. All the draw calls until `end_occlusion_query` are
+    /// counted, and only one query could be active at a time.
+    #[inline]
+    pub fn begin_occlusion_query(&mut self, query: OcclusionQueryHandle) {
+        self.cmds.push(Command::BeginOcclusionQuery(query));
+    }
+
+    /// Ends the active occlusion query.
+    #[inline]
+    pub fn end_occlusion_query(&mut self) {
+        self.cmds.push(Command::EndOcclusionQuery);
+    }
+
+    /// Skips the draw calls until `end_conditional_render` if no samples passed the
+    /// depth test in the last result of `query`. The test is done on GPU-side without
+    /// waiting for the result, so the draw calls would be executed if it's not ready.
+    ///
+    /// Backends without conditional rendering fall back to the result that is already
+    /// available on CPU-side.
+    #[inline]
+    pub fn begin_conditional_render(&mut self, query: OcclusionQueryHandle) {
+        self.cmds.push(Command::BeginConditionalRender(query));
+    }
+
+    /// Ends the conditional rendering.
+    #[inline]
+    pub fn end_conditional_render(&mut self) {
+        self.cmds.push(Command::EndConditionalRender);
+    }
+
     /// Update a contiguous subregion of an existing two-dimensional texture object.
     #[inline]
     pub fn update_texture(&mut self, id: TextureHandle, area: Aabb2<u32>, bytes: &[u8]) {
@@ -80,19 +133,55 @@ impl CommandBuffer {
     ///
     /// Notes that this method has no effect on the allocated capacity of the underlying storage.
     pub fn submit(&mut self, surface: SurfaceHandle) -> Result<()> {
+        let draws = self.cmds.iter().filter_map(|v| match *v {
+            Command::Draw(shader, _, _, ptr) => Some((shader, self.bufs.as_slice(ptr))),
+            Command::Capture(shader, _, _, ptr, _) => Some((shader, self.bufs.as_slice(ptr))),
+            _ => None,
+        });
+
+        if let Err(err) = super::inside::ctx().validate_draws(draws) {
+            self.cmds.clear();
+            self.bufs.clear();
+            return Err(err);
+        }
+
+        let num = self
+            .cmds
+            .iter()
+            .filter(|v| match v {
+                Command::Draw(_, _, _, _) => true,
+                _ => false,
+            })
+            .count();
+
+        let (clear, range) = super::inside::ctx().amortize(surface, num);
+
         let doubele_frame = unsafe { super::inside::frames() };
         let mut frame = doubele_frame.write();
-        frame.cmds.push(Command::Bind(surface));
+        frame.cmds.push(Command::Bind(surface, clear));
 
+        let mut index = 0;
         for v in self.cmds.drain(..) {
             match v {
                 Command::Draw(shader, mesh, mesh_index, ptr) => {
+                    index += 1;
+                    if !range.contains(&(index - 1)) {
+                        continue;
+                    }
+
                     let vars = self.bufs.as_slice(ptr);
                     let ptr = frame.bufs.extend_from_slice(vars);
                     let cmd = Command::Draw(shader, mesh, mesh_index, ptr);
                     frame.cmds.push(cmd);
                 }
 
+                Command::Capture(shader, mesh, mesh_index, ptr, dst) => {
+                    let vars = self.bufs.as_slice(ptr);
+                    let ptr = frame.bufs.extend_from_slice(vars);
+                    let cmd = Command::Capture(shader, mesh, mesh_index, ptr, dst);
+                    frame.cmds.push(cmd);
+                }
+
                 Command::UpdateTexture(id, area, ptr) => {
                     let ptr = frame.bufs.extend_from_slice(self.bufs.as_slice(ptr));
                     frame.cmds.push(Command::UpdateTexture(id, area, ptr));
@@ -153,12 +242,25 @@ impl<T: Ord + Copy> DrawCommandBuffer<T> {
     ///
     /// Notes that this method has no effect on the allocated capacity of the underlying storage.
     pub fn submit(&mut self, surface: SurfaceHandle) -> Result<()> {
+        let draws = self.cmds.iter().filter_map(|v| match *v {
+            (_, Command::Draw(shader, _, _, ptr)) => Some((shader, self.bufs.as_slice(ptr))),
+            _ => None,
+        });
+
+        if let Err(err) = super::inside::ctx().validate_draws(draws) {
+            self.cmds.clear();
+            self.bufs.clear();
+            return Err(err);
+        }
+
+        let (clear, range) = super::inside::ctx().amortize(surface, self.cmds.len());
+
         let doubele_frame = unsafe { super::inside::frames() };
         let mut frame = doubele_frame.write();
-        frame.cmds.push(Command::Bind(surface));
+        frame.cmds.push(Command::Bind(surface, clear));
 
         self.cmds.as_mut_slice().sort_by_key(|v| v.0);
-        for v in self.cmds.drain(..) {
+        for v in self.cmds.drain(..).skip(range.start).take(range.len()) {
             if let (_, Command::Draw(shader, mesh, mesh_index, ptr)) = v {
                 let vars = self.bufs.as_slice(ptr);
                 let ptr = frame.bufs.extend_from_slice(vars);
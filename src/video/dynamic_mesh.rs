@@ -0,0 +1,248 @@
+//! Stream mesh which is sized in advance and refilled with generated geometry every frame.
+//!
+//! Trails, ribbons and CPU particles usually produce a different set of vertices every
+//! frame. Instead of creating and deleting mesh objects each frame, a `DynamicMesh` allocates
+//! one `MeshHint::Stream` mesh with room for several frames, and treats it as a ring buffer.
+//! The geometry written in one frame goes to its own region of the buffers, so it never
+//! overwrites the vertices which might still be consumed by the frames in flight.
+//!
+//! ```rust
+//! use crayon::prelude::*;
+//! application::oneshot().unwrap();
+//!
+//! let mut params = DynamicMeshParams::default();
+//! params.layout = VertexLayout::build()
+//!     .with(Attribute::Position, VertexFormat::Float, 3, false)
+//!     .finish();
+//! params.max_verts = 128;
+//! params.max_idxes = 384;
+//!
+//! let mut mesh = DynamicMesh::new(params).unwrap();
+//!
+//! mesh.begin();
+//! let verts: [[f32; 3]; 3] = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+//! mesh.write(IndexFormat::encode(&verts), &[0, 1, 2]).unwrap();
+//! let index = mesh.end().unwrap();
+//!
+//! // Submits a draw call with `mesh.handle()` and `index` here.
+//! # let _ = index;
+//! ```
+
+use crate::errors::*;
+
+use super::assets::prelude::*;
+
+/// The setup parameters of `DynamicMesh`.
+#[derive(Debug, Clone, Copy)]
+pub struct DynamicMeshParams {
+    /// How a single vertex structure looks like.
+    pub layout: VertexLayout,
+    /// Index format.
+    pub index_format: IndexFormat,
+    /// How the input vertex data is used to assemble primitives.
+    pub primitive: MeshPrimitive,
+    /// The maximum number of vertices that could be written between `begin` and `end`.
+    pub max_verts: usize,
+    /// The maximum number of indices that could be written between `begin` and `end`.
+    pub max_idxes: usize,
+    /// The number of regions the underlying buffers are partitioned into, usually the
+    /// number of frames in flight.
+    pub frames: usize,
+}
+
+impl Default for DynamicMeshParams {
+    fn default() -> Self {
+        DynamicMeshParams {
+            layout: VertexLayout::default(),
+            index_format: IndexFormat::U16,
+            primitive: MeshPrimitive::Triangles,
+            max_verts: 0,
+            max_idxes: 0,
+            frames: 2,
+        }
+    }
+}
+
+/// A ring-buffered stream mesh for per-frame generated geometry.
+pub struct DynamicMesh {
+    handle: MeshHandle,
+    params: DynamicMeshParams,
+    region: usize,
+    num_verts: usize,
+    num_idxes: usize,
+    verts: Vec<u8>,
+    idxes: Vec<u8>,
+    writing: bool,
+}
+
+impl DynamicMesh {
+    /// Creates a new `DynamicMesh` and its underlying mesh object.
+    pub fn new(mut params: DynamicMeshParams) -> Result<Self> {
+        params.frames = params.frames.max(1);
+
+        if params.max_verts == 0 || params.layout.stride() == 0 {
+            bail!("DynamicMesh requires a non-empty vertex layout and capacity.");
+        }
+
+        // The last index is reserved for primitive restart, which is always enabled.
+        let num_verts = params.max_verts * params.frames;
+        if params.index_format == IndexFormat::U16 && num_verts >= u16::max_value() as usize {
+            bail!(
+                "{} vertices can not be addressed with 16-bit indices.",
+                num_verts
+            );
+        }
+
+        let mut mp = MeshParams::default();
+        mp.hint = MeshHint::Stream;
+        mp.layout = params.layout;
+        mp.index_format = params.index_format;
+        mp.primitive = params.primitive;
+        mp.num_verts = num_verts;
+        mp.num_idxes = params.max_idxes * params.frames;
+        mp.primitive_restart = true;
+
+        let handle = super::create_mesh(mp, None)?;
+        let stride = params.layout.stride() as usize;
+
+        Ok(DynamicMesh {
+            handle,
+            params,
+            region: params.frames - 1,
+            num_verts: 0,
+            num_idxes: 0,
+            verts: Vec::with_capacity(params.max_verts * stride),
+            idxes: Vec::with_capacity(params.max_idxes * params.index_format.stride()),
+            writing: false,
+        })
+    }
+
+    /// Gets the handle of underlying mesh object.
+    #[inline]
+    pub fn handle(&self) -> MeshHandle {
+        self.handle
+    }
+
+    /// Gets the setup parameters.
+    #[inline]
+    pub fn params(&self) -> &DynamicMeshParams {
+        &self.params
+    }
+
+    /// Starts a new frame of geometry. This discards everything written before and
+    /// moves to the next region of the ring buffer.
+    pub fn begin(&mut self) {
+        self.region = (self.region + 1) % self.params.frames;
+        self.num_verts = 0;
+        self.num_idxes = 0;
+        self.verts.clear();
+        self.idxes.clear();
+        self.writing = true;
+    }
+
+    /// Appends vertices and indices to current frame. The `idxes` are relative to the
+    /// first vertex of `verts`, so multiple batches could be written independently.
+    ///
+    /// `u32::max_value()` in `idxes` is translated into the primitive restart index of
+    /// the index format, which could be used to separate strips.
+    pub fn write(&mut self, verts: &[u8], idxes: &[u32]) -> Result<()> {
+        if !self.writing {
+            bail!("DynamicMesh::write should be called between `begin` and `end`.");
+        }
+
+        let stride = self.params.layout.stride() as usize;
+        if verts.len() % stride != 0 {
+            bail!(
+                "The length of vertex bytes ({}) is not a multiple of stride ({}).",
+                verts.len(),
+                stride
+            );
+        }
+
+        let num_verts = verts.len() / stride;
+        if self.num_verts + num_verts > self.params.max_verts
+            || self.num_idxes + idxes.len() > self.params.max_idxes
+        {
+            bail!("DynamicMesh is out of capacity.");
+        }
+
+        // Validates all the indices first, so nothing is written if any of them is invalid.
+        let restart = IndexFormat::U32.primitive_restart_index();
+        if let Some(v) = idxes
+            .iter()
+            .find(|&&v| v != restart && v as usize >= num_verts)
+        {
+            bail!("Index {} is out of bounds ({} vertices).", v, num_verts);
+        }
+
+        let base = self.region * self.params.max_verts + self.num_verts;
+        for &v in idxes {
+            let v = if v == restart {
+                self.params.index_format.primitive_restart_index() as usize
+            } else {
+                base + v as usize
+            };
+
+            match self.params.index_format {
+                IndexFormat::U16 => {
+                    let v = v as u16;
+                    self.idxes.extend_from_slice(IndexFormat::encode(&[v]));
+                }
+                IndexFormat::U32 => {
+                    let v = v as u32;
+                    self.idxes.extend_from_slice(IndexFormat::encode(&[v]));
+                }
+            }
+        }
+
+        self.verts.extend_from_slice(verts);
+        self.num_verts += num_verts;
+        self.num_idxes += idxes.len();
+        Ok(())
+    }
+
+    /// Uploads the geometry of current frame, and returns the `MeshIndex` that should
+    /// be used to draw it.
+    pub fn end(&mut self) -> Result<MeshIndex> {
+        if !self.writing {
+            bail!("DynamicMesh::end should be called after `begin`.");
+        }
+
+        self.writing = false;
+
+        let vstride = self.params.layout.stride() as usize;
+        let istride = self.params.index_format.stride();
+        let from = self.region * self.params.max_idxes;
+
+        if !self.verts.is_empty() {
+            let offset = self.region * self.params.max_verts * vstride;
+            super::update_vertex_buffer(self.handle, offset, &self.verts)?;
+        }
+
+        if !self.idxes.is_empty() {
+            super::update_index_buffer(self.handle, from * istride, &self.idxes)?;
+        }
+
+        Ok(MeshIndex::Ptr(from, self.num_idxes))
+    }
+
+    /// The number of vertices written in current frame.
+    #[inline]
+    pub fn num_verts(&self) -> usize {
+        self.num_verts
+    }
+
+    /// The number of indices written in current frame.
+    #[inline]
+    pub fn num_idxes(&self) -> usize {
+        self.num_idxes
+    }
+}
+
+impl Drop for DynamicMesh {
+    fn drop(&mut self) {
+        if super::valid() {
+            super::delete_mesh(self.handle);
+        }
+    }
+}
@@ -6,14 +6,43 @@ pub enum Error {
     HandleInvalid(String),
     #[fail(display = "Out of bounds.")]
     OutOfBounds,
+    #[fail(display = "The pool of {} is out of capacity ({} at most).", _0, _1)]
+    OutOfCapacity(&'static str, usize),
     #[fail(display = "Can NOT update immutable buffer.")]
     UpdateImmutableBuffer,
     #[fail(display = "Can NOT sample render buffer.")]
     SampleRenderBuffer,
+    #[fail(
+        display = "The multisampling level {} of window could not be changed to {}.",
+        _0, _1
+    )]
+    MultisampleFixed(u16, u16),
     #[fail(display = "Failed to create surface, errors:\n{}\n", _0)]
     SurfaceInvalid(String),
+    #[fail(display = "Failed to create texture array, errors: {}", _0)]
+    TextureArrayInvalid(String),
     #[fail(display = "Attribute({}) is undefined.", _0)]
     AttributeUndefined(String),
+    #[fail(
+        display = "Attribute({}) is incompatible, expects {} but {} is provided.",
+        name, expected, provided
+    )]
+    AttributeIncompatible {
+        name: String,
+        expected: String,
+        provided: String,
+    },
+    #[fail(display = "Uniform({}) is undefined.", _0)]
+    UniformUndefined(String),
+    #[fail(
+        display = "Uniform({}) is incompatible, expects {} but {} is provided.",
+        name, expected, provided
+    )]
+    UniformIncompatible {
+        name: String,
+        expected: String,
+        provided: String,
+    },
 }
 
 pub type Result<T> = ::std::result::Result<T, Error>;
@@ -0,0 +1,205 @@
+//! The dependency graph of the surfaces executed in one frame.
+//!
+//! Surfaces that render into render textures, and later surfaces that sample them, form
+//! a dependency graph implicitly. It's hard to tell from the code what the engine actually
+//! executed when debugging ordering problems, so a frame could be captured and exported
+//! to DOT or JSON, which could be rendered by external tools.
+//!
+//! ```rust
+//! use crayon::prelude::*;
+//! application::oneshot().unwrap();
+//!
+//! // Records the graph of next executed frame.
+//! video::capture_frame_graph();
+//!
+//! if let Some(graph) = video::frame_graph() {
+//!     println!("{}", graph.to_dot());
+//! }
+//! ```
+
+use serde_json;
+
+use crate::utils::prelude::{FastHashMap, HandleLike, ObjectPool};
+
+use super::assets::prelude::*;
+use super::backends::frame::{Command, Frame};
+
+/// A surface binding and the draw calls submitted to it.
+#[derive(Debug, Clone)]
+pub struct FramePass {
+    /// The surface of this pass.
+    pub surface: SurfaceHandle,
+    /// The number of draw calls executed in this pass.
+    pub draws: usize,
+    /// The render textures sampled by draw calls in this pass.
+    pub reads: Vec<RenderTextureHandle>,
+    /// The render textures attached to the surface.
+    pub writes: Vec<RenderTextureHandle>,
+}
+
+/// A dependency between two passes, through a render texture that is written by the
+/// first pass and sampled by the second one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameEdge {
+    pub from: usize,
+    pub to: usize,
+    pub texture: RenderTextureHandle,
+}
+
+/// The passes executed in one frame in order, and the dependencies between them.
+#[derive(Debug, Clone, Default)]
+pub struct FrameGraph {
+    pub passes: Vec<FramePass>,
+    pub edges: Vec<FrameEdge>,
+}
+
+impl FrameGraph {
+    pub(crate) fn build(
+        frame: &Frame,
+        surfaces: &ObjectPool<SurfaceHandle, SurfaceParams>,
+    ) -> Self {
+        let mut graph = FrameGraph::default();
+
+        for v in &frame.cmds {
+            match *v {
+                Command::Bind(surface, _) => {
+                    let mut writes = Vec::new();
+                    if let Some(params) = surfaces.get(surface) {
+                        writes.extend(params.colors.iter().filter_map(|v| *v));
+                        writes.extend(params.depth_stencil);
+                    }
+
+                    graph.passes.push(FramePass {
+                        surface,
+                        draws: 0,
+                        reads: Vec::new(),
+                        writes,
+                    });
+                }
+
+                Command::Draw(_, _, _, ptr) | Command::Capture(_, _, _, ptr, _) => {
+                    if let Some(pass) = graph.passes.last_mut() {
+                        pass.draws += 1;
+
+                        for &(_, variable) in frame.bufs.as_slice(ptr) {
+                            let texture = match variable {
+                                UniformVariable::RenderTexture(v) => v,
+                                UniformVariable::SampledRenderTexture(v, _) => v,
+                                _ => continue,
+                            };
+
+                            if !pass.reads.contains(&texture) {
+                                pass.reads.push(texture);
+                            }
+                        }
+                    }
+                }
+
+                _ => {}
+            }
+        }
+
+        // Links every read to the latest pass that wrote the texture before.
+        let mut writers = FastHashMap::default();
+        for (i, pass) in graph.passes.iter().enumerate() {
+            for texture in &pass.reads {
+                if let Some(&from) = writers.get(texture) {
+                    graph.edges.push(FrameEdge {
+                        from,
+                        to: i,
+                        texture: *texture,
+                    });
+                }
+            }
+
+            for texture in &pass.writes {
+                writers.insert(*texture, i);
+            }
+        }
+
+        graph
+    }
+
+    /// Exports the graph in the DOT language of graphviz.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph frame {\n    node [shape=box];\n");
+
+        for (i, pass) in self.passes.iter().enumerate() {
+            dot.push_str(&format!(
+                "    p{} [label=\"#{} surface {}\\n{} draws\"];\n",
+                i,
+                i,
+                name(pass.surface),
+                pass.draws
+            ));
+        }
+
+        for edge in &self.edges {
+            dot.push_str(&format!(
+                "    p{} -> p{} [label=\"render texture {}\"];\n",
+                edge.from,
+                edge.to,
+                name(edge.texture)
+            ));
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Exports the graph as JSON, where handles are written as `[index, version]`.
+    pub fn to_json(&self) -> String {
+        #[derive(Serialize)]
+        struct Pass {
+            surface: (u32, u32),
+            draws: usize,
+            reads: Vec<(u32, u32)>,
+            writes: Vec<(u32, u32)>,
+        }
+
+        #[derive(Serialize)]
+        struct Edge {
+            from: usize,
+            to: usize,
+            texture: (u32, u32),
+        }
+
+        #[derive(Serialize)]
+        struct Graph {
+            passes: Vec<Pass>,
+            edges: Vec<Edge>,
+        }
+
+        let graph = Graph {
+            passes: self
+                .passes
+                .iter()
+                .map(|v| Pass {
+                    surface: pair(v.surface),
+                    draws: v.draws,
+                    reads: v.reads.iter().map(|&v| pair(v)).collect(),
+                    writes: v.writes.iter().map(|&v| pair(v)).collect(),
+                })
+                .collect(),
+            edges: self
+                .edges
+                .iter()
+                .map(|v| Edge {
+                    from: v.from,
+                    to: v.to,
+                    texture: pair(v.texture),
+                })
+                .collect(),
+        };
+
+        serde_json::to_string(&graph).unwrap()
+    }
+}
+
+fn pair<H: HandleLike>(handle: H) -> (u32, u32) {
+    (handle.index(), handle.version())
+}
+
+fn name<H: HandleLike>(handle: H) -> String {
+    format!("{}:{}", handle.index(), handle.version())
+}
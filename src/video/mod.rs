@@ -197,7 +197,10 @@ pub const MAX_UNIFORM_TEXTURE_SLOTS: usize = 8;
 #[macro_use]
 pub mod assets;
 pub mod command;
+pub mod dynamic_mesh;
 pub mod errors;
+pub mod frame_graph;
+pub mod texture_slots;
 
 mod system;
 
@@ -206,17 +209,70 @@ mod backends;
 pub mod prelude {
     pub use super::assets::prelude::*;
     pub use super::command::{CommandBuffer, Draw, DrawCommandBuffer};
+    pub use super::dynamic_mesh::{DynamicMesh, DynamicMeshParams};
+    pub use super::frame_graph::{FrameEdge, FrameGraph, FramePass};
+    pub use super::texture_slots::{TextureSlot, TextureSlots};
 }
 
 use uuid::Uuid;
 
-use crate::math::prelude::Aabb2;
+use crate::math::prelude::{Aabb2, Vector2};
 use crate::prelude::CrResult;
+use crate::res::gc::GarbageReport;
 use crate::res::utils::prelude::ResourceState;
 
 use self::assets::prelude::*;
 use self::errors::*;
-use self::inside::ctx;
+use self::frame_graph::FrameGraph;
+use self::inside::{ctx, CTX};
+
+/// Controls how many frames the CPU could run ahead of the GPU.
+///
+/// Drivers are usually allowed to queue several frames, which keeps the GPU busy but adds
+/// the queued frames to the latency between input and display, especially with vsync.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameLatency {
+    /// Blocks until the GPU finishes the commands of current frame before presenting it.
+    Finish,
+    /// Lets the GPU work on current frame while the CPU prepares the next one, by waiting
+    /// on a fence of the previous frame. It falls back to `Finish` if fences are not
+    /// supported.
+    OneFrame,
+    /// Leaves the queuing to driver.
+    Unlimited,
+}
+
+/// The setup parameters of video sub-system. The capacities of object pools are set with
+/// `Settings`.
+#[derive(Debug, Clone, Copy)]
+pub struct VideoParams {
+    /// Sets how many frames could be queued before they are presented.
+    pub frame_latency: FrameLatency,
+}
+
+impl Default for VideoParams {
+    fn default() -> Self {
+        VideoParams {
+            frame_latency: FrameLatency::Finish,
+        }
+    }
+}
+
+/// The setup parameters of `video::reconfigure`.
+#[derive(Debug, Clone, Copy)]
+pub struct ReconfigureParams {
+    /// The dimensions in pixels of render textures created with `fit_window`.
+    pub dimensions: Vector2<u32>,
+    /// The multisampling level of the default framebuffer, which should be the one
+    /// returned by `window::multisample`.
+    pub multisample: u16,
+}
+
+/// Checks if the video system is enabled.
+#[inline]
+pub fn valid() -> bool {
+    unsafe { !CTX.is_null() }
+}
 
 /// Creates an surface with `SurfaceParams`.
 #[inline]
@@ -236,6 +292,13 @@ pub fn surface_state(handle: SurfaceHandle) -> ResourceState {
     ctx().surface_state(handle)
 }
 
+/// Checks if the draw calls submitted to a time-sliced surface have been executed
+/// completely in the last round.
+#[inline]
+pub fn is_surface_completed(handle: SurfaceHandle) -> bool {
+    ctx().is_surface_completed(handle)
+}
+
 /// Deletes surface object.
 #[inline]
 pub fn delete_surface(handle: SurfaceHandle) {
@@ -261,6 +324,16 @@ pub fn shader_state(handle: ShaderHandle) -> ResourceState {
     ctx().shader_state(handle)
 }
 
+/// Enables or disables the strict mode of uniform validation. Uniform variables of draw
+/// calls are checked against the `UniformVariableLayout` of shader when submitting, and
+/// its an error to bind a variable with mismatched type. In strict mode, which is enabled
+/// by default, binding a field that is not declared in the layout is also an error. The
+/// undeclared fields are ignored silently after opting out of it.
+#[inline]
+pub fn set_strict_uniforms(strict: bool) {
+    ctx().set_strict_uniforms(strict)
+}
+
 /// Delete shader state object.
 #[inline]
 pub fn delete_shader(handle: ShaderHandle) {
@@ -276,6 +349,17 @@ where
     ctx().create_mesh(params, data)
 }
 
+/// Creates a mesh object, and registers it under the virtual path `name`. Later calls
+/// of `create_mesh_from(name)` return the same handle with its reference count increased.
+#[inline]
+pub fn create_named_mesh<T1, T2>(name: T1, params: MeshParams, data: T2) -> CrResult<MeshHandle>
+where
+    T1: AsRef<str>,
+    T2: Into<Option<MeshData>>,
+{
+    ctx().create_named_mesh(name, params, data)
+}
+
 /// Creates a mesh object from file asynchronously.
 #[inline]
 pub fn create_mesh_from<T: AsRef<str>>(url: T) -> CrResult<MeshHandle> {
@@ -332,6 +416,22 @@ where
     ctx().create_texture(params, data)
 }
 
+/// Creates a texture object, and registers it under the virtual path `name`. Later calls
+/// of `create_texture_from(name)` return the same handle with its reference count
+/// increased.
+#[inline]
+pub fn create_named_texture<T1, T2>(
+    name: T1,
+    params: TextureParams,
+    data: T2,
+) -> CrResult<TextureHandle>
+where
+    T1: AsRef<str>,
+    T2: Into<Option<TextureData>>,
+{
+    ctx().create_named_texture(name, params, data)
+}
+
 /// Creates a texture object from file asynchronously.
 #[inline]
 pub fn create_texture_from<T: AsRef<str>>(url: T) -> CrResult<TextureHandle> {
@@ -344,6 +444,23 @@ pub fn create_texture_from_uuid(uuid: Uuid) -> CrResult<TextureHandle> {
     ctx().create_texture_from_uuid(uuid)
 }
 
+/// Decodes a PNG or JPEG image into RGBA8 pixels, which could be used to create or update
+/// textures. Large images could be decoded on the `sched` threads. Images wider or higher
+/// than `MAX_DIMENSIONS` of `assets::image_decoder` are rejected.
+#[cfg(feature = "image")]
+#[inline]
+pub fn decode_image(bytes: &[u8]) -> CrResult<(TextureParams, TextureData)> {
+    assets::image_decoder::decode(bytes)
+}
+
+/// Creates a texture object from the bytes of a PNG or JPEG image, e.g. the avatars or
+/// custom skins provided by players.
+#[cfg(feature = "image")]
+#[inline]
+pub fn create_texture_from_image(bytes: &[u8]) -> CrResult<TextureHandle> {
+    ctx().create_texture_from_image(bytes)
+}
+
 /// Get the resource state of specified texture.
 #[inline]
 pub fn texture_state(handle: TextureHandle) -> ResourceState {
@@ -362,6 +479,35 @@ pub fn delete_texture(handle: TextureHandle) {
     ctx().delete_texture(handle);
 }
 
+/// Sets the maximum number of bytes of texture data that are uploaded per frame. Large
+/// textures are streamed through pixel buffer objects in the following frames within
+/// this budget, so their contents might not be available immediately.
+#[inline]
+pub fn set_texture_upload_budget(bytes: usize) {
+    ctx().set_texture_upload_budget(bytes);
+}
+
+/// Gets the bytes of staged texture data uploaded in the last frame. It stays within the
+/// budget of `set_texture_upload_budget`, unless a single upload exceeds it alone.
+#[inline]
+pub fn uploaded_texture_bytes() -> usize {
+    ctx().uploaded_texture_bytes()
+}
+
+/// Sets how many frames could be queued before they are presented. It takes effect from
+/// the next frame.
+#[inline]
+pub fn set_frame_latency(latency: FrameLatency) {
+    ctx().set_frame_latency(latency);
+}
+
+/// Sets the debug visualization of all the surfaces, except the ones which override it
+/// with `SurfaceParams::set_debug_mode`.
+#[inline]
+pub fn set_debug_mode(mode: DebugMode) {
+    ctx().set_debug_mode(mode);
+}
+
 /// Create render texture object, which could be attached with a framebuffer.
 #[inline]
 pub fn create_render_texture(params: RenderTextureParams) -> Result<RenderTextureHandle> {
@@ -380,20 +526,122 @@ pub fn render_texture_state(handle: RenderTextureHandle) -> ResourceState {
     ctx().render_texture_state(handle)
 }
 
+/// Recreates the render textures created with `fit_window`, and the surfaces they are
+/// attached to, with `ReconfigureParams`. It's called by the engine whenever the window
+/// is resized, and is only needed for other resolutions, e.g. a scaled render target.
+///
+/// The multisampling level of the default framebuffer is fixed when the window is
+/// created, and render textures are never multisampled, so requesting another level
+/// fails with `Error::MultisampleFixed`. It has to be applied by restarting the
+/// application with `WindowParams::multisample`.
+#[inline]
+pub fn reconfigure(params: ReconfigureParams) -> Result<()> {
+    ctx().reconfigure(params)
+}
+
 /// Delete the render texture object.
 #[inline]
 pub fn delete_render_texture(handle: RenderTextureHandle) {
     ctx().delete_render_texture(handle)
 }
 
+/// Creates a texture array object, which contains layers of same-sized 2D textures.
+#[inline]
+pub fn create_texture_array(params: TextureArrayParams) -> Result<TextureArrayHandle> {
+    ctx().create_texture_array(params)
+}
+
+/// Gets the `TextureArrayParams` if available.
+#[inline]
+pub fn texture_array(handle: TextureArrayHandle) -> Option<TextureArrayParams> {
+    ctx().texture_array(handle)
+}
+
+/// Replaces the whole image of the `layer` in texture array.
+#[inline]
+pub fn update_texture_array(handle: TextureArrayHandle, layer: u32, data: &[u8]) -> Result<()> {
+    ctx().update_texture_array(handle, layer, data)
+}
+
+/// Deletes the texture array object.
+#[inline]
+pub fn delete_texture_array(handle: TextureArrayHandle) {
+    ctx().delete_texture_array(handle)
+}
+
+/// Creates a sampler object. Binds a texture with it in draw calls, by setting the
+/// uniform variable with `(TextureHandle, SamplerHandle)`, to override the sampling
+/// parameters of the texture.
+#[inline]
+pub fn create_sampler(params: SamplerParams) -> Result<SamplerHandle> {
+    ctx().create_sampler(params)
+}
+
+/// Gets the `SamplerParams` if available.
+#[inline]
+pub fn sampler(handle: SamplerHandle) -> Option<SamplerParams> {
+    ctx().sampler(handle)
+}
+
+/// Deletes the sampler object.
+#[inline]
+pub fn delete_sampler(handle: SamplerHandle) {
+    ctx().delete_sampler(handle)
+}
+
+/// Creates an occlusion query object. The result of the query could be used to skip
+/// draw calls with `CommandBuffer::begin_conditional_render`.
+#[inline]
+pub fn create_occlusion_query(params: OcclusionQueryParams) -> Result<OcclusionQueryHandle> {
+    ctx().create_occlusion_query(params)
+}
+
+/// Gets the `OcclusionQueryParams` if available.
+#[inline]
+pub fn occlusion_query(handle: OcclusionQueryHandle) -> Option<OcclusionQueryParams> {
+    ctx().occlusion_query(handle)
+}
+
+/// Deletes the occlusion query object.
+#[inline]
+pub fn delete_occlusion_query(handle: OcclusionQueryHandle) {
+    ctx().delete_occlusion_query(handle)
+}
+
+/// Requests to record the dependency graph of surfaces in the next executed frame. The
+/// result could be retrieved with `frame_graph` after the frame is dispatched.
+#[inline]
+pub fn capture_frame_graph() {
+    ctx().capture_frame_graph()
+}
+
+/// Gets the last captured frame graph, which could be exported to DOT or JSON.
+#[inline]
+pub fn frame_graph() -> Option<FrameGraph> {
+    ctx().frame_graph()
+}
+
+#[inline]
+pub(crate) fn collect_garbage(report: &mut GarbageReport) {
+    ctx().collect_garbage(report)
+}
+
+/// Gets the name of the renderer, which is usually specific to the GPU and driver.
+#[inline]
+pub fn renderer() -> String {
+    ctx().renderer()
+}
+
 pub(crate) mod inside {
     use std::sync::Arc;
 
+    use crate::application::prelude::Settings;
     use crate::errors::*;
     use crate::utils::double_buf::DoubleBuf;
 
     use super::backends::frame::Frame;
     use super::system::VideoSystem;
+    use super::VideoParams;
 
     pub static mut CTX: *const VideoSystem = std::ptr::null();
 
@@ -410,19 +658,23 @@ pub(crate) mod inside {
     }
 
     /// Setup the video system.
-    pub unsafe fn setup() -> Result<()> {
+    pub unsafe fn setup(params: VideoParams, settings: Settings) -> Result<()> {
         debug_assert!(CTX.is_null(), "duplicated setup of video system.");
 
-        let ctx = VideoSystem::new()?;
+        #[cfg(feature = "alloc-tracking")]
+        let _scope = crate::memory::scope("video");
+        let ctx = VideoSystem::new(params, settings)?;
         CTX = Box::into_raw(Box::new(ctx));
         Ok(())
     }
 
     /// Setup the video system.
-    pub unsafe fn headless() {
+    pub unsafe fn headless(params: VideoParams, settings: Settings) {
         debug_assert!(CTX.is_null(), "duplicated setup of video system.");
 
-        let ctx = VideoSystem::headless();
+        #[cfg(feature = "alloc-tracking")]
+        let _scope = crate::memory::scope("video");
+        let ctx = VideoSystem::headless(params, settings);
         CTX = Box::into_raw(Box::new(ctx));
     }
 
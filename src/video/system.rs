@@ -1,11 +1,16 @@
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
 use uuid::Uuid;
 
-use crate::application::prelude::{LifecycleListener, LifecycleListenerHandle};
+use crate::application::prelude::{LifecycleListener, LifecycleListenerHandle, Settings};
 use crate::math::prelude::{Aabb2, Vector2};
-use crate::prelude::CrResult;
+use crate::prelude::{CrError, CrResult};
+use crate::res::gc::GarbageReport;
 use crate::res::utils::prelude::{ResourcePool, ResourceState};
-use crate::utils::prelude::{DoubleBuf, ObjectPool};
+use crate::utils::prelude::{
+    DoubleBuf, FastHashMap, HandleLike, HashValue, ObjectPool, OutOfCapacity,
+};
 
 use super::assets::mesh_loader::MeshLoader;
 use super::assets::prelude::*;
@@ -13,6 +18,8 @@ use super::assets::texture_loader::TextureLoader;
 use super::backends::frame::*;
 use super::backends::{self, Visitor};
 use super::errors::*;
+use super::frame_graph::FrameGraph;
+use super::{FrameLatency, ReconfigureParams, VideoParams};
 
 /// The centralized management of video sub-system.
 pub struct VideoSystem {
@@ -27,24 +34,134 @@ struct VideoState {
     meshes: RwLock<ResourcePool<MeshHandle, MeshLoader>>,
     textures: RwLock<ResourcePool<TextureHandle, TextureLoader>>,
     render_textures: RwLock<ObjectPool<RenderTextureHandle, RenderTextureParams>>,
+    samplers: RwLock<ObjectPool<SamplerHandle, SamplerParams>>,
+    occlusion_queries: RwLock<ObjectPool<OcclusionQueryHandle, OcclusionQueryParams>>,
+    texture_arrays: RwLock<ObjectPool<TextureArrayHandle, TextureArrayParams>>,
+    strict_uniforms: AtomicBool,
+    amortizations: RwLock<FastHashMap<SurfaceHandle, Amortization>>,
+    capture_frame_graph: AtomicBool,
+    frame_graph: RwLock<Option<FrameGraph>>,
+    uploaded_texture_bytes: AtomicUsize,
+    renderer: String,
+}
+
+struct Amortization {
+    frames: u32,
+    slice: u32,
+    submitted: bool,
+    completed: bool,
 }
 
 impl VideoState {
-    fn new() -> Self {
+    fn new(settings: Settings, renderer: String) -> Self {
         let frames = Arc::new(DoubleBuf::new(
             Frame::with_capacity(64 * 1024),
             Frame::with_capacity(64 * 1024),
         ));
 
+        let meshes = MeshLoader::new(frames.clone());
+        let meshes = match settings.max_meshes {
+            0 => ResourcePool::new(meshes),
+            n => ResourcePool::with_limit(meshes, n),
+        };
+
+        let textures = TextureLoader::new(frames.clone());
+        let textures = match settings.max_textures {
+            0 => ResourcePool::new(textures),
+            n => ResourcePool::with_limit(textures, n),
+        };
+
         VideoState {
-            surfaces: RwLock::new(ObjectPool::new()),
-            shaders: RwLock::new(ObjectPool::new()),
-            meshes: RwLock::new(ResourcePool::new(MeshLoader::new(frames.clone()))),
-            textures: RwLock::new(ResourcePool::new(TextureLoader::new(frames.clone()))),
-            render_textures: RwLock::new(ObjectPool::new()),
+            surfaces: RwLock::new(object_pool(settings.max_surfaces)),
+            shaders: RwLock::new(object_pool(settings.max_shaders)),
+            meshes: RwLock::new(meshes),
+            textures: RwLock::new(textures),
+            render_textures: RwLock::new(object_pool(settings.max_render_textures)),
+            samplers: RwLock::new(ObjectPool::new()),
+            occlusion_queries: RwLock::new(ObjectPool::new()),
+            texture_arrays: RwLock::new(ObjectPool::new()),
+            strict_uniforms: AtomicBool::new(true),
+            amortizations: RwLock::new(FastHashMap::default()),
+            capture_frame_graph: AtomicBool::new(false),
+            frame_graph: RwLock::new(None),
+            uploaded_texture_bytes: AtomicUsize::new(0),
+            renderer,
             frames,
         }
     }
+
+    fn reconfigure(&self, dimensions: Vector2<u32>) {
+        let mut frame = self.frames.write();
+        let mut render_textures = self.render_textures.write().unwrap();
+        let surfaces = self.surfaces.read().unwrap();
+
+        let mut refits = Vec::new();
+        for (handle, params) in render_textures.iter_mut() {
+            if params.fit_window && params.dimensions != dimensions {
+                params.dimensions = dimensions;
+                refits.push((handle, *params));
+            }
+        }
+
+        if refits.is_empty() {
+            return;
+        }
+
+        let attached: Vec<_> = surfaces
+            .iter()
+            .filter(|(_, v)| {
+                v.colors
+                    .iter()
+                    .chain(Some(&v.depth_stencil))
+                    .filter_map(|&rt| rt)
+                    .any(|rt| refits.iter().any(|&(h, _)| h == rt))
+            })
+            .map(|(handle, &params)| (handle, params))
+            .collect();
+
+        // The framebuffers of surfaces hold the old render targets, so they are deleted
+        // first, and created again after the render textures.
+        for &(handle, _) in &attached {
+            frame.cmds.push(Command::DeleteSurface(handle));
+        }
+
+        for (handle, params) in refits {
+            frame.cmds.push(Command::DeleteRenderTexture(handle));
+            let cmd = Command::CreateRenderTexture(Box::new((handle, params)));
+            frame.cmds.push(cmd);
+        }
+
+        for (handle, params) in attached {
+            let cmd = Command::CreateSurface(Box::new((handle, params)));
+            frame.cmds.push(cmd);
+        }
+    }
+}
+
+fn object_pool<H: HandleLike, T>(limit: usize) -> ObjectPool<H, T> {
+    if limit == 0 {
+        ObjectPool::new()
+    } else {
+        ObjectPool::with_limit(limit)
+    }
+}
+
+/// Creates a object in the pool, which fails if the pool has reached its limit.
+fn create_object<H: HandleLike, T>(
+    pool: &mut ObjectPool<H, T>,
+    value: T,
+    name: &'static str,
+) -> Result<H> {
+    pool.try_create(value)
+        .map_err(|err| Error::OutOfCapacity(name, err.0))
+}
+
+/// Converts the `OutOfCapacity` error of resource pools into `Error::OutOfCapacity`.
+fn resource_capacity(name: &'static str) -> impl Fn(CrError) -> CrError {
+    move |err| match err.downcast::<OutOfCapacity>() {
+        Ok(v) => Error::OutOfCapacity(name, v.0).into(),
+        Err(err) => err,
+    }
 }
 
 struct Lifecycle {
@@ -55,27 +172,59 @@ struct Lifecycle {
 
 impl LifecycleListener for Lifecycle {
     fn on_pre_update(&mut self) -> CrResult<()> {
+        #[cfg(feature = "alloc-tracking")]
+        let _scope = crate::memory::scope("video");
+
         // Swap internal commands frame.
         self.state.frames.swap();
         self.state.frames.write().clear();
         self.state.meshes.write().unwrap().advance()?;
         self.state.textures.write().unwrap().advance()?;
+
+        for v in self.state.amortizations.write().unwrap().values_mut() {
+            if v.submitted {
+                v.slice = (v.slice + 1) % v.frames;
+                v.completed = v.slice == 0;
+                v.submitted = false;
+            }
+        }
+
         Ok(())
     }
 
     fn on_post_update(&mut self) -> CrResult<()> {
+        #[cfg(feature = "alloc-tracking")]
+        let _scope = crate::memory::scope("video");
+
         let dimensions = dimensions_pixels();
 
         // Resize the window, which would recreate the underlying framebuffer.
         if dimensions != self.last_dimensions {
             self.last_dimensions = dimensions;
             crate::window::inside::resize(dimensions);
+
+            // Render textures which fit the window follow it across resizes, including the
+            // switches of window modes, e.g. entering fullscreen.
+            self.state.reconfigure(dimensions);
         }
 
+        let mut frame = self.state.frames.write_back_buf();
+        if self
+            .state
+            .capture_frame_graph
+            .swap(false, Ordering::Relaxed)
+        {
+            let surfaces = self.state.surfaces.read().unwrap();
+            let graph = FrameGraph::build(&frame, &surfaces);
+            *self.state.frame_graph.write().unwrap() = Some(graph);
+        }
+
+        frame.dispatch(self.visitor.as_mut(), self.last_dimensions)?;
+
+        let uploaded = self.visitor.uploaded_texture_bytes();
         self.state
-            .frames
-            .write_back_buf()
-            .dispatch(self.visitor.as_mut(), self.last_dimensions)?;
+            .uploaded_texture_bytes
+            .store(uploaded, Ordering::Relaxed);
 
         Ok(())
     }
@@ -89,24 +238,27 @@ impl Drop for VideoSystem {
 
 impl VideoSystem {
     /// Create a new `VideoSystem`.
-    pub fn new() -> CrResult<Self> {
-        let state = Arc::new(VideoState::new());
+    pub fn new(params: VideoParams, settings: Settings) -> CrResult<Self> {
         let visitor = backends::new()?;
+        let state = Arc::new(VideoState::new(settings, visitor.renderer()));
 
-        Ok(VideoSystem {
+        let video = VideoSystem {
             state: state.clone(),
             lis: crate::application::attach(Lifecycle {
                 state,
                 visitor,
                 last_dimensions: dimensions_pixels(),
             }),
-        })
+        };
+
+        video.set_frame_latency(params.frame_latency);
+        Ok(video)
     }
 
     /// Create a headless `VideoSystem`.
-    pub fn headless() -> Self {
-        let state = Arc::new(VideoState::new());
+    pub fn headless(_: VideoParams, settings: Settings) -> Self {
         let visitor = backends::new_headless();
+        let state = Arc::new(VideoState::new(settings, visitor.renderer()));
 
         VideoSystem {
             state: state.clone(),
@@ -118,15 +270,58 @@ impl VideoSystem {
         }
     }
 
+    /// Frees the meshes and textures which are no longer referenced.
+    pub fn collect_garbage(&self, report: &mut GarbageReport) {
+        let meshes = self.state.meshes.write().unwrap().collect_garbage();
+        report.add("meshes", meshes);
+
+        let textures = self.state.textures.write().unwrap().collect_garbage();
+        report.add("textures", textures);
+    }
+
+    /// Gets the name of the renderer.
+    #[inline]
+    pub fn renderer(&self) -> String {
+        self.state.renderer.clone()
+    }
+
     pub(crate) fn frames(&self) -> Arc<DoubleBuf<Frame>> {
         self.state.frames.clone()
     }
+
+    /// Requests to record the dependency graph of surfaces in the next executed frame.
+    #[inline]
+    pub fn capture_frame_graph(&self) {
+        self.state
+            .capture_frame_graph
+            .store(true, Ordering::Relaxed);
+    }
+
+    /// Gets the last captured frame graph.
+    #[inline]
+    pub fn frame_graph(&self) -> Option<FrameGraph> {
+        self.state.frame_graph.read().unwrap().clone()
+    }
 }
 
 impl VideoSystem {
     /// Creates an surface with `SurfaceParams`.
     pub fn create_surface(&self, params: SurfaceParams) -> Result<SurfaceHandle> {
-        let handle = self.state.surfaces.write().unwrap().create(params);
+        let handle = {
+            let mut surfaces = self.state.surfaces.write().unwrap();
+            create_object(&mut surfaces, params, "surfaces")?
+        };
+
+        if params.amortization > 1 {
+            let v = Amortization {
+                frames: params.amortization,
+                slice: 0,
+                submitted: false,
+                completed: false,
+            };
+
+            self.state.amortizations.write().unwrap().insert(handle, v);
+        }
 
         {
             let cmd = Command::CreateSurface(Box::new((handle, params)));
@@ -151,9 +346,35 @@ impl VideoSystem {
         }
     }
 
+    /// Checks if the draw calls submitted to a time-sliced surface have been executed
+    /// completely in the last round. Surfaces without amortization are always completed.
+    pub fn is_surface_completed(&self, handle: SurfaceHandle) -> bool {
+        self.state
+            .amortizations
+            .read()
+            .unwrap()
+            .get(&handle)
+            .map(|v| v.completed)
+            .unwrap_or(true)
+    }
+
+    /// Gets the clear flag and the range of draw calls that should be executed in
+    /// current frame, with `num` draw calls submitted to `surface`.
+    pub(crate) fn amortize(&self, surface: SurfaceHandle, num: usize) -> (bool, Range<usize>) {
+        let mut amortizations = self.state.amortizations.write().unwrap();
+        if let Some(v) = amortizations.get_mut(&surface) {
+            let (s, n) = (v.slice as usize, v.frames as usize);
+            v.submitted = true;
+            (s == 0, (num * s / n)..(num * (s + 1) / n))
+        } else {
+            (true, 0..num)
+        }
+    }
+
     /// Deletes surface object.
     pub fn delete_surface(&self, handle: SurfaceHandle) {
         if self.state.surfaces.write().unwrap().free(handle).is_some() {
+            self.state.amortizations.write().unwrap().remove(&handle);
             let cmd = Command::DeleteSurface(handle);
             self.state.frames.write().cmds.push(cmd);
         }
@@ -171,7 +392,10 @@ impl VideoSystem {
     ) -> Result<ShaderHandle> {
         params.validate(&vs, &fs)?;
 
-        let handle = self.state.shaders.write().unwrap().create(params.clone());
+        let handle = {
+            let mut shaders = self.state.shaders.write().unwrap();
+            create_object(&mut shaders, params.clone(), "shaders")?
+        };
 
         {
             let cmd = Command::CreateShader(Box::new((handle, params, vs, fs)));
@@ -197,6 +421,37 @@ impl VideoSystem {
         }
     }
 
+    /// Enables or disables the strict mode of uniform validation. In strict mode, which is
+    /// the default, drawing with uniform fields that are not declared in shader fails.
+    pub fn set_strict_uniforms(&self, strict: bool) {
+        self.state.strict_uniforms.store(strict, Ordering::Relaxed);
+        let cmd = Command::UpdateStrictUniforms(strict);
+        self.state.frames.write().cmds.push(cmd);
+    }
+
+    /// Validates the uniform variables of draw calls against the layouts of shaders.
+    pub(crate) fn validate_draws<'a, T>(&self, draws: T) -> Result<()>
+    where
+        T: IntoIterator<Item = (ShaderHandle, &'a [(HashValue<str>, UniformVariable)])>,
+    {
+        let mut draws = draws.into_iter().peekable();
+        if draws.peek().is_none() {
+            return Ok(());
+        }
+
+        let strict = self.state.strict_uniforms.load(Ordering::Relaxed);
+        let shaders = self.state.shaders.read().unwrap();
+        for (handle, variables) in draws {
+            let params = shaders
+                .get(handle)
+                .ok_or_else(|| Error::HandleInvalid(format!("{:?}", handle)))?;
+
+            params.uniforms.validate(variables, strict)?;
+        }
+
+        Ok(())
+    }
+
     /// Delete shader state object.
     #[inline]
     pub fn delete_shader(&self, handle: ShaderHandle) {
@@ -215,21 +470,44 @@ impl VideoSystem {
         T: Into<Option<MeshData>>,
     {
         let mut meshes = self.state.meshes.write().unwrap();
-        meshes.create((params, data.into()))
+        meshes
+            .create((params, data.into()))
+            .map_err(resource_capacity("meshes"))
+    }
+
+    /// Creates a mesh object, and registers it under the virtual path `name`, so it
+    /// could be shared with `create_mesh_from` like the meshes loaded from files.
+    #[inline]
+    pub fn create_named_mesh<T1, T2>(
+        &self,
+        name: T1,
+        params: MeshParams,
+        data: T2,
+    ) -> CrResult<MeshHandle>
+    where
+        T1: AsRef<str>,
+        T2: Into<Option<MeshData>>,
+    {
+        let mut meshes = self.state.meshes.write().unwrap();
+        meshes
+            .create_named(name, (params, data.into()))
+            .map_err(resource_capacity("meshes"))
     }
 
     /// Creates a mesh object from file asynchronously.
     #[inline]
     pub fn create_mesh_from<T: AsRef<str>>(&self, url: T) -> CrResult<MeshHandle> {
         let mut meshes = self.state.meshes.write().unwrap();
-        meshes.create_from(url)
+        meshes.create_from(url).map_err(resource_capacity("meshes"))
     }
 
     /// Creates a mesh object from file asynchronously.
     #[inline]
     pub fn create_mesh_from_uuid(&self, uuid: Uuid) -> CrResult<MeshHandle> {
         let mut meshes = self.state.meshes.write().unwrap();
-        meshes.create_from_uuid(uuid)
+        meshes
+            .create_from_uuid(uuid)
+            .map_err(resource_capacity("meshes"))
     }
 
     /// Gets the `MeshParams` if available.
@@ -301,19 +579,50 @@ impl VideoSystem {
         T: Into<Option<TextureData>>,
     {
         let mut textures = self.state.textures.write().unwrap();
-        textures.create((params, data.into()))
+        textures
+            .create((params, data.into()))
+            .map_err(resource_capacity("textures"))
+    }
+
+    /// Creates a texture object, and registers it under the virtual path `name`, so it
+    /// could be shared with `create_texture_from` like the textures loaded from files.
+    pub fn create_named_texture<T1, T2>(
+        &self,
+        name: T1,
+        params: TextureParams,
+        data: T2,
+    ) -> CrResult<TextureHandle>
+    where
+        T1: AsRef<str>,
+        T2: Into<Option<TextureData>>,
+    {
+        let mut textures = self.state.textures.write().unwrap();
+        textures
+            .create_named(name, (params, data.into()))
+            .map_err(resource_capacity("textures"))
     }
 
     /// Creates a texture object from file asynchronously.
     pub fn create_texture_from<T: AsRef<str>>(&self, url: T) -> CrResult<TextureHandle> {
         let mut textures = self.state.textures.write().unwrap();
-        textures.create_from(url)
+        textures
+            .create_from(url)
+            .map_err(resource_capacity("textures"))
     }
 
     /// Creates a texture object from file asynchronously.
     pub fn create_texture_from_uuid(&self, uuid: Uuid) -> CrResult<TextureHandle> {
         let mut textures = self.state.textures.write().unwrap();
-        textures.create_from_uuid(uuid)
+        textures
+            .create_from_uuid(uuid)
+            .map_err(resource_capacity("textures"))
+    }
+
+    /// Creates a texture object from the bytes of a PNG or JPEG image.
+    #[cfg(feature = "image")]
+    pub fn create_texture_from_image(&self, bytes: &[u8]) -> CrResult<TextureHandle> {
+        let (params, data) = super::assets::image_decoder::decode(bytes)?;
+        self.create_texture(params, data)
     }
 
     /// Get the resource state of specified texture.
@@ -345,15 +654,48 @@ impl VideoSystem {
     pub fn delete_texture(&self, handle: TextureHandle) {
         self.state.textures.write().unwrap().delete(handle);
     }
+
+    /// Sets the maximum number of bytes of texture data that are uploaded per frame. Large
+    /// texture uploads are staged and spread across frames within this budget, instead of
+    /// stalling the driver in one frame. Defaults to 8 MB.
+    pub fn set_texture_upload_budget(&self, bytes: usize) {
+        let cmd = Command::UpdateTextureUploadBudget(bytes);
+        self.state.frames.write().cmds.push(cmd);
+    }
+
+    /// Gets the bytes of staged texture data uploaded in the last frame.
+    #[inline]
+    pub fn uploaded_texture_bytes(&self) -> usize {
+        self.state.uploaded_texture_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Sets how many frames could be queued before they are presented.
+    pub fn set_frame_latency(&self, latency: FrameLatency) {
+        let cmd = Command::UpdateFrameLatency(latency);
+        self.state.frames.write().cmds.push(cmd);
+    }
+
+    /// Sets the debug visualization of the surfaces which do not override it.
+    pub fn set_debug_mode(&self, mode: DebugMode) {
+        let cmd = Command::UpdateDebugMode(mode);
+        self.state.frames.write().cmds.push(cmd);
+    }
 }
 
 impl VideoSystem {
     /// Create render texture object, which could be attached with a framebuffer.
     pub fn create_render_texture(
         &self,
-        params: RenderTextureParams,
+        mut params: RenderTextureParams,
     ) -> Result<RenderTextureHandle> {
-        let handle = self.state.render_textures.write().unwrap().create(params);
+        if params.fit_window {
+            params.dimensions = dimensions_pixels();
+        }
+
+        let handle = {
+            let mut render_textures = self.state.render_textures.write().unwrap();
+            create_object(&mut render_textures, params, "render textures")?
+        };
 
         {
             let cmd = Command::CreateRenderTexture(Box::new((handle, params)));
@@ -383,6 +725,20 @@ impl VideoSystem {
         }
     }
 
+    /// Recreates the render textures that fit the window with new dimensions in pixels,
+    /// along with the surfaces they are attached to. The handles stay valid, and all the
+    /// commands are recorded into the same frame at once, so draw calls submitted later
+    /// always see the new render targets.
+    pub fn reconfigure(&self, params: ReconfigureParams) -> Result<()> {
+        let multisample = crate::window::multisample();
+        if params.multisample != multisample {
+            return Err(Error::MultisampleFixed(multisample, params.multisample));
+        }
+
+        self.state.reconfigure(params.dimensions);
+        Ok(())
+    }
+
     /// Delete the render texture object.
     pub fn delete_render_texture(&self, handle: RenderTextureHandle) {
         if self
@@ -399,6 +755,137 @@ impl VideoSystem {
     }
 }
 
+impl VideoSystem {
+    /// Creates a texture array object with uninitialized layers.
+    pub fn create_texture_array(&self, params: TextureArrayParams) -> Result<TextureArrayHandle> {
+        params.validate()?;
+
+        let handle = self.state.texture_arrays.write().unwrap().create(params);
+
+        {
+            let cmd = Command::CreateTextureArray(Box::new((handle, params)));
+            self.state.frames.write().cmds.push(cmd);
+        }
+
+        Ok(handle)
+    }
+
+    /// Gets the `TextureArrayParams` if available.
+    pub fn texture_array(&self, handle: TextureArrayHandle) -> Option<TextureArrayParams> {
+        self.state
+            .texture_arrays
+            .read()
+            .unwrap()
+            .get(handle)
+            .cloned()
+    }
+
+    /// Replaces the whole image of the `layer` in texture array.
+    pub fn update_texture_array(
+        &self,
+        handle: TextureArrayHandle,
+        layer: u32,
+        data: &[u8],
+    ) -> Result<()> {
+        let params = self
+            .texture_array(handle)
+            .ok_or_else(|| Error::HandleInvalid(format!("{:?}", handle)))?;
+
+        params.validate_layer(layer, data)?;
+
+        let mut frame = self.state.frames.write();
+        let ptr = frame.bufs.extend_from_slice(data);
+        frame
+            .cmds
+            .push(Command::UpdateTextureArray(handle, layer, ptr));
+        Ok(())
+    }
+
+    /// Deletes the texture array object.
+    pub fn delete_texture_array(&self, handle: TextureArrayHandle) {
+        if self
+            .state
+            .texture_arrays
+            .write()
+            .unwrap()
+            .free(handle)
+            .is_some()
+        {
+            let cmd = Command::DeleteTextureArray(handle);
+            self.state.frames.write().cmds.push(cmd);
+        }
+    }
+}
+
+impl VideoSystem {
+    /// Creates a sampler object, which could be bound with textures in draw calls.
+    pub fn create_sampler(&self, params: SamplerParams) -> Result<SamplerHandle> {
+        let handle = self.state.samplers.write().unwrap().create(params);
+
+        {
+            let cmd = Command::CreateSampler(Box::new((handle, params)));
+            self.state.frames.write().cmds.push(cmd);
+        }
+
+        Ok(handle)
+    }
+
+    /// Gets the `SamplerParams` if available.
+    pub fn sampler(&self, handle: SamplerHandle) -> Option<SamplerParams> {
+        self.state.samplers.read().unwrap().get(handle).cloned()
+    }
+
+    /// Deletes the sampler object.
+    pub fn delete_sampler(&self, handle: SamplerHandle) {
+        if self.state.samplers.write().unwrap().free(handle).is_some() {
+            let cmd = Command::DeleteSampler(handle);
+            self.state.frames.write().cmds.push(cmd);
+        }
+    }
+}
+
+impl VideoSystem {
+    /// Creates an occlusion query object.
+    pub fn create_occlusion_query(
+        &self,
+        params: OcclusionQueryParams,
+    ) -> Result<OcclusionQueryHandle> {
+        let handle = self.state.occlusion_queries.write().unwrap().create(params);
+
+        {
+            let cmd = Command::CreateOcclusionQuery(handle, params);
+            self.state.frames.write().cmds.push(cmd);
+        }
+
+        Ok(handle)
+    }
+
+    /// Gets the `OcclusionQueryParams` if available.
+    pub fn occlusion_query(&self, handle: OcclusionQueryHandle) -> Option<OcclusionQueryParams> {
+        self.state
+            .occlusion_queries
+            .read()
+            .unwrap()
+            .get(handle)
+            .cloned()
+    }
+
+    /// Deletes the occlusion query object.
+    pub fn delete_occlusion_query(&self, handle: OcclusionQueryHandle) {
+        if self
+            .state
+            .occlusion_queries
+            .write()
+            .unwrap()
+            .free(handle)
+            .is_some()
+        {
+            let cmd = Command::DeleteOcclusionQuery(handle);
+            self.state.frames.write().cmds.push(cmd);
+        }
+    }
+}
+
 fn dimensions_pixels() -> Vector2<u32> {
     let dimensions = crate::window::dimensions();
     let dpr = crate::window::device_pixel_ratio();
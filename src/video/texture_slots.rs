@@ -0,0 +1,139 @@
+//! Virtual texture slots which pack many same-sized textures into the layers of a
+//! texture array.
+//!
+//! Sprites and terrain tiles usually use lots of small textures with the same size and
+//! format. Binding them one by one breaks batching, since every draw call has to switch
+//! the bound texture. `TextureSlots` uploads them into the layers of one texture array
+//! instead, and hands out a `TextureSlot` whose index stays stable until it's freed. The
+//! index could be passed to shaders as a vertex attribute or an uniform variable, and
+//! used as the third texture coordinate of `sampler2DArray`.
+//!
+//! ```rust
+//! use crayon::prelude::*;
+//! application::oneshot().unwrap();
+//!
+//! let mut params = TextureArrayParams::default();
+//! params.dimensions = Vector2::new(32, 32);
+//! params.layers = 16;
+//!
+//! let mut slots = TextureSlots::new(params).unwrap();
+//! let slot = slots.alloc(&[255; 32 * 32 * 4]).unwrap();
+//! assert_eq!(slot.index(), 0);
+//!
+//! // Binds `slots.handle()` to the `sampler2DArray`, and samples the layer `slot.index()`.
+//! slots.free(slot);
+//! ```
+
+use crate::errors::*;
+
+use super::assets::prelude::*;
+
+/// A stable index of the layer in `TextureSlots`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TextureSlot(u32);
+
+impl TextureSlot {
+    /// Gets the layer index which should be used to sample the texture in shaders.
+    #[inline]
+    pub fn index(self) -> u32 {
+        self.0
+    }
+}
+
+/// Allocator of the layers in a texture array.
+pub struct TextureSlots {
+    handle: TextureArrayHandle,
+    params: TextureArrayParams,
+    frees: Vec<u32>,
+    next: u32,
+}
+
+impl TextureSlots {
+    /// Creates a new `TextureSlots` and its underlying texture array.
+    pub fn new(params: TextureArrayParams) -> Result<Self> {
+        let handle = super::create_texture_array(params)?;
+
+        Ok(TextureSlots {
+            handle,
+            params,
+            frees: Vec::new(),
+            next: 0,
+        })
+    }
+
+    /// Gets the handle of underlying texture array object.
+    #[inline]
+    pub fn handle(&self) -> TextureArrayHandle {
+        self.handle
+    }
+
+    /// Gets the parameters of underlying texture array object.
+    #[inline]
+    pub fn params(&self) -> &TextureArrayParams {
+        &self.params
+    }
+
+    /// Allocates a free slot and uploads the image into it. The image should have the
+    /// same dimensions and format of the texture array.
+    pub fn alloc(&mut self, data: &[u8]) -> Result<TextureSlot> {
+        let index = if let Some(index) = self.frees.pop() {
+            index
+        } else if self.next < self.params.layers {
+            self.next += 1;
+            self.next - 1
+        } else {
+            bail!(
+                "TextureSlots is out of capacity ({} layers).",
+                self.params.layers
+            );
+        };
+
+        let slot = TextureSlot(index);
+        if let Err(err) = self.update(slot, data) {
+            self.frees.push(index);
+            return Err(err);
+        }
+
+        Ok(slot)
+    }
+
+    /// Replaces the image of the slot.
+    #[inline]
+    pub fn update(&mut self, slot: TextureSlot, data: &[u8]) -> Result<()> {
+        super::update_texture_array(self.handle, slot.0, data)?;
+        Ok(())
+    }
+
+    /// Frees the slot, which might be reused by later allocations.
+    pub fn free(&mut self, slot: TextureSlot) {
+        if slot.0 < self.next && !self.frees.contains(&slot.0) {
+            self.frees.push(slot.0);
+        }
+    }
+
+    /// The number of slots in use.
+    #[inline]
+    pub fn len(&self) -> usize {
+        (self.next as usize) - self.frees.len()
+    }
+
+    /// Checks if there is no slot in use.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The maximum number of slots.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.params.layers as usize
+    }
+}
+
+impl Drop for TextureSlots {
+    fn drop(&mut self) {
+        if super::valid() {
+            super::delete_texture_array(self.handle);
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use std::time::{Duration, Instant};
+
+use gilrs;
+use gilrs::ff::{BaseEffect, BaseEffectType, Effect, EffectBuilder, Replay, Ticks};
+
+use crate::input::events::InputEvent;
+use crate::input::prelude::{GamepadAxis, GamepadButton};
+
+use super::super::super::events::Event;
+
+pub struct GamepadBackend {
+    gilrs: gilrs::Gilrs,
+    connected: Vec<usize>,
+    // Effects stop playing once they are dropped, so we keep them until they expire.
+    effects: Vec<(Effect, Instant)>,
+}
+
+impl GamepadBackend {
+    pub fn new() -> Option<Self> {
+        match gilrs::Gilrs::new() {
+            Ok(gilrs) => {
+                let connected = gilrs.gamepads().map(|(id, _)| id).collect();
+                Some(GamepadBackend {
+                    gilrs,
+                    connected,
+                    effects: Vec::new(),
+                })
+            }
+            Err(err) => {
+                warn!("[Window] Gamepads are not available: {}.", err);
+                None
+            }
+        }
+    }
+
+    pub fn poll_events(&mut self, events: &mut Vec<Event>) {
+        // The gamepads connected before the initialization does not emit events.
+        for id in self.connected.drain(..) {
+            events.push(Event::InputDevice(InputEvent::GamepadConnected { id }));
+        }
+
+        while let Some(gilrs::Event { id, event, .. }) = self.gilrs.next_event() {
+            if let Some(v) = from_event(id, event) {
+                events.push(Event::InputDevice(v));
+            }
+        }
+
+        let now = Instant::now();
+        self.effects.retain(|&(_, expire)| expire > now);
+    }
+
+    pub fn rumble(&mut self, id: usize, strength: f32, duration: Duration) {
+        let supported = self
+            .gilrs
+            .connected_gamepad(id)
+            .map(|v| v.is_ff_supported())
+            .unwrap_or(false);
+
+        if !supported {
+            return;
+        }
+
+        let ms = duration.as_secs() * 1000 + u64::from(duration.subsec_millis());
+        let magnitude = (strength.max(0.0).min(1.0) * f32::from(u16::max_value())) as u16;
+
+        let effect = EffectBuilder::new()
+            .add_effect(BaseEffect {
+                kind: BaseEffectType::Strong { magnitude },
+                scheduling: Replay {
+                    play_for: Ticks::from_ms(ms.min(u64::from(u32::max_value())) as u32),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .gamepads(&[id])
+            .finish(&mut self.gilrs);
+
+        match effect.and_then(|v| v.play().map(|_| v)) {
+            Ok(v) => self.effects.push((v, Instant::now() + duration)),
+            Err(err) => warn!("[Window] Failed to rumble gamepad {}: {}.", id, err),
+        }
+    }
+}
+
+fn from_event(id: usize, event: gilrs::EventType) -> Option<InputEvent> {
+    match event {
+        gilrs::EventType::Connected => Some(InputEvent::GamepadConnected { id }),
+        gilrs::EventType::Disconnected => Some(InputEvent::GamepadDisconnected { id }),
+
+        gilrs::EventType::ButtonPressed(button, _) => {
+            from_button(button).map(|button| InputEvent::GamepadPressed { id, button })
+        }
+
+        gilrs::EventType::ButtonReleased(button, _) => {
+            from_button(button).map(|button| InputEvent::GamepadReleased { id, button })
+        }
+
+        // Analog triggers are reported as buttons with values.
+        gilrs::EventType::ButtonChanged(button, value, _) => {
+            let axis = match button {
+                gilrs::Button::LeftTrigger2 => GamepadAxis::LeftTrigger,
+                gilrs::Button::RightTrigger2 => GamepadAxis::RightTrigger,
+                _ => return None,
+            };
+
+            Some(InputEvent::GamepadAxisChanged { id, axis, value })
+        }
+
+        gilrs::EventType::AxisChanged(axis, value, _) => {
+            from_axis(axis).map(|axis| InputEvent::GamepadAxisChanged { id, axis, value })
+        }
+
+        _ => None,
+    }
+}
+
+fn from_button(button: gilrs::Button) -> Option<GamepadButton> {
+    match button {
+        gilrs::Button::South => Some(GamepadButton::South),
+        gilrs::Button::East => Some(GamepadButton::East),
+        gilrs::Button::North => Some(GamepadButton::North),
+        gilrs::Button::West => Some(GamepadButton::West),
+        gilrs::Button::LeftTrigger => Some(GamepadButton::LeftBumper),
+        gilrs::Button::RightTrigger => Some(GamepadButton::RightBumper),
+        gilrs::Button::LeftTrigger2 => Some(GamepadButton::LeftTrigger),
+        gilrs::Button::RightTrigger2 => Some(GamepadButton::RightTrigger),
+        gilrs::Button::Select => Some(GamepadButton::Select),
+        gilrs::Button::Start => Some(GamepadButton::Start),
+        gilrs::Button::Mode => Some(GamepadButton::Mode),
+        gilrs::Button::LeftThumb => Some(GamepadButton::LeftThumb),
+        gilrs::Button::RightThumb => Some(GamepadButton::RightThumb),
+        gilrs::Button::DPadUp => Some(GamepadButton::DPadUp),
+        gilrs::Button::DPadDown => Some(GamepadButton::DPadDown),
+        gilrs::Button::DPadLeft => Some(GamepadButton::DPadLeft),
+        gilrs::Button::DPadRight => Some(GamepadButton::DPadRight),
+        _ => None,
+    }
+}
+
+fn from_axis(axis: gilrs::Axis) -> Option<GamepadAxis> {
+    match axis {
+        gilrs::Axis::LeftStickX => Some(GamepadAxis::LeftStickX),
+        gilrs::Axis::LeftStickY => Some(GamepadAxis::LeftStickY),
+        gilrs::Axis::RightStickX => Some(GamepadAxis::RightStickX),
+        gilrs::Axis::RightStickY => Some(GamepadAxis::RightStickY),
+        gilrs::Axis::LeftZ => Some(GamepadAxis::LeftTrigger),
+        gilrs::Axis::RightZ => Some(GamepadAxis::RightTrigger),
+        _ => None,
+    }
+}
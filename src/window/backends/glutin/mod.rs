@@ -1,3 +1,5 @@
+#[cfg(feature = "gamepad")]
+mod gamepad;
 mod types;
 mod visitor;
 
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use gl;
 use glutin;
 use glutin::GlContext;
@@ -6,23 +8,43 @@ use crate::errors::*;
 use crate::math::prelude::Vector2;
 
 use super::super::super::events::Event;
-use super::super::super::WindowParams;
+use super::super::super::{Monitor, WindowMode, WindowParams};
 use super::super::Visitor;
 use super::types;
 
+#[cfg(feature = "gamepad")]
+use super::gamepad::GamepadBackend;
+
 pub struct GlutinVisitor {
     window: glutin::GlWindow,
     events_loop: glutin::EventsLoop,
+    #[cfg(feature = "gamepad")]
+    gamepads: Option<GamepadBackend>,
 }
 
 impl GlutinVisitor {
     pub fn from(params: WindowParams) -> Result<Self> {
+        let events_loop = glutin::EventsLoop::new();
+        let fullscreen = match params.mode {
+            WindowMode::Fullscreen(index) => {
+                let monitor = match index {
+                    Some(i) => monitor(events_loop.get_available_monitors(), i)?,
+                    None => events_loop.get_primary_monitor(),
+                };
+
+                Some(monitor)
+            }
+            _ => None,
+        };
+
         let builder = glutin::WindowBuilder::new()
             .with_title(params.title)
             .with_dimensions(glutin::dpi::LogicalSize::new(
                 f64::from(params.size.x),
                 f64::from(params.size.y),
             ))
+            .with_decorations(params.mode != WindowMode::Borderless)
+            .with_fullscreen(fullscreen)
             .with_multitouch();
 
         let context = glutin::ContextBuilder::new()
@@ -31,11 +53,12 @@ impl GlutinVisitor {
             .with_gl(glutin::GlRequest::Latest)
             .with_vsync(params.vsync);
 
-        let events_loop = glutin::EventsLoop::new();
         let window = glutin::GlWindow::new(builder, context, &events_loop).unwrap();
         let mut visitor = GlutinVisitor {
             window,
             events_loop,
+            #[cfg(feature = "gamepad")]
+            gamepads: GamepadBackend::new(),
         };
 
         let size = visitor.dimensions();
@@ -96,6 +119,13 @@ impl Visitor for GlutinVisitor {
                 events.push(e);
             }
         });
+
+        #[cfg(feature = "gamepad")]
+        {
+            if let Some(ref mut gamepads) = self.gamepads {
+                gamepads.poll_events(events);
+            }
+        }
     }
 
     #[inline]
@@ -116,4 +146,57 @@ impl Visitor for GlutinVisitor {
         self.window.swap_buffers()?;
         Ok(())
     }
+
+    fn monitors(&self) -> Vec<Monitor> {
+        self.window
+            .get_available_monitors()
+            .map(|v| {
+                let size = v.get_dimensions();
+                Monitor {
+                    name: v.get_name().unwrap_or_default(),
+                    dimensions: Vector2::new(size.width as u32, size.height as u32),
+                    device_pixel_ratio: v.get_hidpi_factor() as f32,
+                }
+            })
+            .collect()
+    }
+
+    fn set_mode(&self, mode: WindowMode) -> Result<()> {
+        match mode {
+            WindowMode::Fullscreen(index) => {
+                let monitor = match index {
+                    Some(i) => monitor(self.window.get_available_monitors(), i)?,
+                    None => self.window.get_current_monitor(),
+                };
+
+                self.window.set_fullscreen(Some(monitor));
+            }
+            _ => {
+                self.window.set_fullscreen(None);
+                self.window.set_decorations(mode != WindowMode::Borderless);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "gamepad")]
+    fn rumble(&mut self, id: usize, strength: f32, duration: Duration) {
+        if let Some(ref mut gamepads) = self.gamepads {
+            gamepads.rumble(id, strength, duration);
+        }
+    }
+
+    #[cfg(not(feature = "gamepad"))]
+    fn rumble(&mut self, _: usize, _: f32, _: Duration) {}
+}
+
+fn monitor<T>(mut monitors: T, index: usize) -> Result<glutin::MonitorId>
+where
+    T: Iterator<Item = glutin::MonitorId>,
+{
+    match monitors.nth(index) {
+        Some(v) => Ok(v),
+        None => bail!("Monitor {} is not available.", index),
+    }
 }
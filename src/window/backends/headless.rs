@@ -1,7 +1,10 @@
+use std::time::Duration;
+
 use crate::errors::*;
 use crate::math::prelude::Vector2;
 
 use super::super::events::Event;
+use super::super::{Monitor, WindowMode};
 use super::Visitor;
 
 pub struct HeadlessVisitor {}
@@ -48,4 +51,17 @@ impl Visitor for HeadlessVisitor {
     fn swap_buffers(&self) -> Result<()> {
         Ok(())
     }
+
+    #[inline]
+    fn monitors(&self) -> Vec<Monitor> {
+        Vec::new()
+    }
+
+    #[inline]
+    fn set_mode(&self, _: WindowMode) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    fn rumble(&mut self, _: usize, _: f32, _: Duration) {}
 }
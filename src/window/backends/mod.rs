@@ -1,9 +1,12 @@
 mod headless;
 
+use std::time::Duration;
+
 use crate::errors::*;
 use crate::math::prelude::Vector2;
 
 use super::events::Event;
+use super::{Monitor, WindowMode};
 
 pub trait Visitor {
     fn show(&self);
@@ -16,6 +19,9 @@ pub trait Visitor {
     fn is_current(&self) -> bool;
     fn make_current(&self) -> Result<()>;
     fn swap_buffers(&self) -> Result<()>;
+    fn monitors(&self) -> Vec<Monitor>;
+    fn set_mode(&self, mode: WindowMode) -> Result<()>;
+    fn rumble(&mut self, id: usize, strength: f32, duration: Duration);
 }
 
 pub fn new_headless() -> Box<Visitor> {
@@ -1,4 +1,5 @@
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
@@ -7,7 +8,7 @@ use web_sys::{
 };
 
 use crate::input::prelude::{InputEvent, MouseButton};
-use crate::window::prelude::{Event, WindowEvent, WindowParams};
+use crate::window::prelude::{Event, Monitor, WindowEvent, WindowMode, WindowParams};
 
 use crate::math::prelude::Vector2;
 use crate::errors::*;
@@ -288,4 +289,17 @@ impl Visitor for WebVisitor {
     fn swap_buffers(&self) -> Result<()> {
         Ok(())
     }
+
+    #[inline]
+    fn monitors(&self) -> Vec<Monitor> {
+        Vec::new()
+    }
+
+    #[inline]
+    fn set_mode(&self, _: WindowMode) -> Result<()> {
+        bail!("It does not make sense to `set_mode` of window in browser.");
+    }
+
+    #[inline]
+    fn rumble(&mut self, _: usize, _: f32, _: Duration) {}
 }
@@ -4,7 +4,7 @@ pub mod events;
 pub mod prelude {
     pub use super::events::{Event, WindowEvent};
     pub use super::system::{EventListener, EventListenerHandle};
-    pub use super::WindowParams;
+    pub use super::{Monitor, WindowMode, WindowParams};
 }
 
 mod backends;
@@ -16,6 +16,27 @@ use self::system::{EventListener, EventListenerHandle};
 use crate::errors::*;
 use crate::math::prelude::Vector2;
 
+/// The modes of window on desktop platforms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowMode {
+    /// A window with title bar and borders.
+    Windowed,
+    /// A window without title bar and borders.
+    Borderless,
+    /// Covers the whole monitor at the index of `window::monitors()`, or the monitor which
+    /// the window is currently on if `None`. The display mode of monitor is kept.
+    Fullscreen(Option<usize>),
+}
+
+/// The information of a monitor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Monitor {
+    pub name: String,
+    /// The size of monitor in pixels.
+    pub dimensions: Vector2<u32>,
+    pub device_pixel_ratio: f32,
+}
+
 #[derive(Debug, Clone)]
 pub struct WindowParams {
     /// Sets the title of window.
@@ -27,6 +48,8 @@ pub struct WindowParams {
     pub multisample: u16,
     /// Specifies whether should we have vsync.
     pub vsync: bool,
+    /// Sets the mode of window.
+    pub mode: WindowMode,
 }
 
 impl Default for WindowParams {
@@ -36,6 +59,7 @@ impl Default for WindowParams {
             size: Vector2::new(640, 320),
             multisample: 2,
             vsync: false,
+            mode: WindowMode::Windowed,
         }
     }
 }
@@ -111,7 +135,45 @@ pub fn device_pixel_ratio() -> f32 {
     ctx().device_pixel_ratio()
 }
 
+/// Returns the monitors available.
+///
+/// # Platform-specific
+///
+/// Always returns an empty list on web and mobile platforms.
+#[inline]
+pub fn monitors() -> Vec<Monitor> {
+    ctx().monitors()
+}
+
+/// Returns the current mode of window.
+#[inline]
+pub fn mode() -> WindowMode {
+    ctx().mode()
+}
+
+/// Returns the multisampling level requested for the default framebuffer, which is fixed
+/// when the window is created.
+#[inline]
+pub fn multisample() -> u16 {
+    ctx().multisample()
+}
+
+/// Switches the mode of window, e.g. toggling fullscreen. The GL context is resized to the
+/// new client area, and the render textures created with `fit_window` are recreated along
+/// with it.
+///
+/// # Platform-specific
+///
+/// Returns an error in browsers, where the mode of canvas is up to the page. Has no effect
+/// on mobile platforms.
+#[inline]
+pub fn set_mode(mode: WindowMode) -> Result<()> {
+    ctx().set_mode(mode)
+}
+
 pub(crate) mod inside {
+    use std::time::Duration;
+
     use crate::errors::*;
     use crate::math::prelude::Vector2;
 
@@ -154,6 +216,11 @@ pub(crate) mod inside {
         ctx().resize(dimensions);
     }
 
+    #[inline]
+    pub fn rumble(id: usize, strength: f32, duration: Duration) {
+        ctx().rumble(id, strength, duration);
+    }
+
     /// Discard the window system.
     pub unsafe fn discard() {
         if CTX.is_null() {
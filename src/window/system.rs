@@ -1,4 +1,5 @@
 use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
 
 use crate::application::prelude::{LifecycleListener, LifecycleListenerHandle};
 use crate::errors::*;
@@ -7,7 +8,7 @@ use crate::utils::object_pool::ObjectPool;
 
 use super::backends::{self, Visitor};
 use super::events::Event;
-use super::WindowParams;
+use super::{Monitor, WindowMode, WindowParams};
 
 impl_handle!(EventListenerHandle);
 
@@ -23,6 +24,8 @@ pub struct WindowSystem {
 
 struct WindowState {
     visitor: RwLock<Box<dyn Visitor>>,
+    mode: Mutex<WindowMode>,
+    multisample: u16,
     events: Mutex<Vec<Event>>,
     last_frame_listeners: Mutex<Vec<Arc<Mutex<dyn EventListener>>>>,
     listeners: Mutex<ObjectPool<EventListenerHandle, Arc<Mutex<dyn EventListener>>>>,
@@ -75,11 +78,15 @@ impl Drop for WindowSystem {
 impl WindowSystem {
     /// Creates a new `WindowSystem` and initalize OpenGL context.
     pub fn from(params: WindowParams) -> Result<Self> {
+        let mode = params.mode;
+        let multisample = params.multisample;
         let state = Arc::new(WindowState {
             last_frame_listeners: Mutex::new(Vec::new()),
             listeners: Mutex::new(ObjectPool::new()),
             events: Mutex::new(Vec::new()),
             visitor: RwLock::new(backends::new(params)?),
+            mode: Mutex::new(mode),
+            multisample,
         });
 
         let window = WindowSystem {
@@ -97,6 +104,8 @@ impl WindowSystem {
             listeners: Mutex::new(ObjectPool::new()),
             events: Mutex::new(Vec::new()),
             visitor: RwLock::new(backends::new_headless()),
+            mode: Mutex::new(WindowMode::Windowed),
+            multisample: 0,
         });
 
         WindowSystem {
@@ -182,4 +191,44 @@ impl WindowSystem {
     pub fn resize(&self, dimensions: Vector2<u32>) {
         self.state.visitor.read().unwrap().resize(dimensions);
     }
+
+    /// Returns the monitors available.
+    #[inline]
+    pub fn monitors(&self) -> Vec<Monitor> {
+        self.state.visitor.read().unwrap().monitors()
+    }
+
+    /// Returns the current mode of window.
+    #[inline]
+    pub fn mode(&self) -> WindowMode {
+        *self.state.mode.lock().unwrap()
+    }
+
+    /// Returns the multisampling level of window.
+    #[inline]
+    pub fn multisample(&self) -> u16 {
+        self.state.multisample
+    }
+
+    /// Switches the mode of window.
+    pub fn set_mode(&self, mode: WindowMode) -> Result<()> {
+        let mut current = self.state.mode.lock().unwrap();
+        if *current == mode {
+            return Ok(());
+        }
+
+        self.state.visitor.read().unwrap().set_mode(mode)?;
+        *current = mode;
+        Ok(())
+    }
+
+    /// Vibrates the gamepad if force feedback is supported.
+    #[inline]
+    pub fn rumble(&self, id: usize, strength: f32, duration: Duration) {
+        self.state
+            .visitor
+            .write()
+            .unwrap()
+            .rumble(id, strength, duration);
+    }
 }
@@ -0,0 +1,76 @@
+extern crate crayon;
+
+use std::fs;
+use std::sync::{Arc, Mutex};
+
+use crayon::errors::Result;
+use crayon::prelude::*;
+
+struct Recorder {
+    unlocked: Arc<Mutex<Vec<String>>>,
+}
+
+impl AchievementBackend for Recorder {
+    fn unlock(&mut self, id: &str) -> Result<()> {
+        self.unlocked.lock().unwrap().push(id.to_owned());
+        Ok(())
+    }
+
+    fn is_unlocked(&self, id: &str) -> bool {
+        self.unlocked.lock().unwrap().iter().any(|v| v == id)
+    }
+
+    fn set_stat(&mut self, _: &str, _: f32) -> Result<()> {
+        Ok(())
+    }
+
+    fn stat(&self, _: &str) -> Option<f32> {
+        None
+    }
+}
+
+#[test]
+fn achievements() {
+    let dir = std::env::temp_dir().join("crayon-achievements");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("achievements.json");
+
+    // Nothing is tracked before setup.
+    assert!(!achievements::valid());
+    assert!(achievements::unlock("first_steps").is_err());
+    assert!(!achievements::is_unlocked("first_steps"));
+    assert!(achievements::set_stat("distance", 1.0).is_err());
+    assert_eq!(achievements::stat("distance"), None);
+    assert!(achievements::flush().is_err());
+
+    achievements::setup(LocalAchievements::load(&path).unwrap());
+    assert!(achievements::valid());
+    assert!(achievements::unlock("first_steps").unwrap());
+    assert!(!achievements::unlock("first_steps").unwrap());
+    assert!(achievements::is_unlocked("first_steps"));
+    assert!(!achievements::is_unlocked("marathon"));
+
+    achievements::set_stat("distance", 40.0).unwrap();
+    assert_eq!(achievements::add_stat("distance", 2.0).unwrap(), 42.0);
+    assert!(achievements::set_stat("distance", std::f32::NAN).is_err());
+    assert!(!path.exists());
+
+    achievements::flush().unwrap();
+    assert!(path.exists());
+
+    // Reloads from file.
+    achievements::setup(LocalAchievements::load(&path).unwrap());
+    assert!(achievements::is_unlocked("first_steps"));
+    assert_eq!(achievements::stat("distance"), Some(42.0));
+
+    // Plugs a platform backend in.
+    let unlocked = Arc::new(Mutex::new(Vec::new()));
+    achievements::setup(Recorder {
+        unlocked: unlocked.clone(),
+    });
+
+    assert!(!achievements::is_unlocked("first_steps"));
+    assert!(achievements::unlock("marathon").unwrap());
+    assert_eq!(*unlocked.lock().unwrap(), vec!["marathon"]);
+}
@@ -0,0 +1,61 @@
+extern crate crayon;
+extern crate serde_json;
+
+use crayon::prelude::*;
+
+#[test]
+fn rebind() {
+    let mut actions = ActionMap::default();
+    actions.bind_action("jump", ActionBinding::Key(Key::Space));
+    actions.bind_action("jump", ActionBinding::Key(Key::Space));
+    actions.bind_action("jump", ActionBinding::Gamepad(GamepadButton::South));
+    assert_eq!(
+        actions.action_bindings("jump"),
+        [
+            ActionBinding::Key(Key::Space),
+            ActionBinding::Gamepad(GamepadButton::South)
+        ]
+    );
+
+    actions.unbind_action("jump");
+    assert!(actions.action_bindings("jump").is_empty());
+
+    actions.bind_action("jump", ActionBinding::Mouse(MouseButton::Left));
+    assert_eq!(
+        actions.action_bindings("jump"),
+        [ActionBinding::Mouse(MouseButton::Left)]
+    );
+
+    let binding = AxisBinding::new(AxisSource::Keys(Key::A, Key::D));
+    actions.bind_axis("horizontal", binding);
+    assert_eq!(actions.axis_bindings("horizontal"), [binding]);
+
+    actions.unbind_axis("horizontal");
+    assert!(actions.axis_bindings("horizontal").is_empty());
+    assert_eq!(actions.axis("horizontal"), 0.0);
+}
+
+#[test]
+fn serialization() {
+    let mut actions = ActionMap::default();
+    actions.bind_action("jump", ActionBinding::Key(Key::Space));
+    actions.bind_action("fire", ActionBinding::Mouse(MouseButton::Left));
+
+    let mut binding = AxisBinding::new(AxisSource::Gamepad(GamepadAxis::LeftStickX));
+    binding.scale = -1.0;
+    binding.dead_zone = 0.2;
+    actions.bind_axis("horizontal", binding);
+
+    let json = serde_json::to_string(&actions).unwrap();
+    let loaded: ActionMap = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(
+        loaded.action_bindings("jump"),
+        actions.action_bindings("jump")
+    );
+    assert_eq!(
+        loaded.action_bindings("fire"),
+        actions.action_bindings("fire")
+    );
+    assert_eq!(loaded.axis_bindings("horizontal"), [binding]);
+}
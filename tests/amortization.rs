@@ -0,0 +1,34 @@
+extern crate crayon;
+
+use crayon::prelude::*;
+use crayon::testing;
+
+#[test]
+fn time_sliced_surface() {
+    testing::setup(Params::default()).unwrap();
+
+    let shader = video::create_shader(ShaderParams::default(), String::new(), String::new());
+    let shader = shader.unwrap();
+    let mesh = video::create_mesh(MeshParams::default(), None).unwrap();
+
+    let mut params = SurfaceParams::default();
+    params.set_amortization(3);
+    let sliced = video::create_surface(params).unwrap();
+    let surface = video::create_surface(SurfaceParams::default()).unwrap();
+
+    let mut cmds = CommandBuffer::new();
+    let mut completions = Vec::new();
+    for _ in 0..6 {
+        for _ in 0..9 {
+            cmds.draw(Draw::new(shader, mesh));
+        }
+
+        cmds.submit(sliced).unwrap();
+        testing::advance().unwrap();
+
+        completions.push(video::is_surface_completed(sliced));
+        assert!(video::is_surface_completed(surface));
+    }
+
+    assert_eq!(completions, [false, false, true, false, false, true]);
+}
@@ -0,0 +1,78 @@
+extern crate crayon;
+
+use std::time::Duration;
+
+use crayon::application;
+use crayon::application::prelude::*;
+
+fn run(budget: &mut EffectsBudget, fps: u64, frames: u32) {
+    for _ in 0..frames {
+        budget.advance(Duration::from_millis(1000 / fps));
+    }
+}
+
+#[test]
+fn degrade() {
+    let mut budget = EffectsBudget::new(60);
+    assert_eq!(budget.level(), 3);
+    assert_eq!(budget.scale(), 1.0);
+
+    run(&mut budget, 60, 100);
+    assert_eq!(budget.level(), 3);
+
+    run(&mut budget, 20, 200);
+    assert_eq!(budget.level(), 0);
+    assert_eq!(budget.scale(), 0.25);
+    assert_eq!(budget.scale_count(100), 25);
+}
+
+#[test]
+fn hysteresis() {
+    let mut budget = EffectsBudget::new(60).with_levels(2);
+    run(&mut budget, 20, 100);
+    assert_eq!(budget.level(), 0);
+
+    // Slightly under budget is not enough to recover.
+    run(&mut budget, 62, 500);
+    assert_eq!(budget.level(), 0);
+
+    // Recovering takes more frames than degrading.
+    run(&mut budget, 100, 60);
+    assert_eq!(budget.level(), 0);
+    run(&mut budget, 100, 100);
+    assert_eq!(budget.level(), 1);
+}
+
+#[test]
+fn set_level() {
+    let mut budget = EffectsBudget::new(30);
+    budget.set_level(10);
+    assert_eq!(budget.level(), 3);
+
+    budget.set_level(1);
+    assert_eq!(budget.level(), 1);
+}
+
+#[test]
+fn global() {
+    let mut params = Params::default();
+    params.effects_budget = Some(EffectsBudget::new(60));
+    crayon::testing::setup(params).unwrap();
+
+    assert_eq!(application::effects_budget().unwrap().levels(), 4);
+    application::set_effects_level(0);
+    assert_eq!(application::effects_scale(), 0.25);
+    assert_eq!(application::effects_scale_count(100), 25);
+
+    // The engine feeds the budget every frame, and recovering takes more than one frame.
+    crayon::testing::advance().unwrap();
+    assert_eq!(application::effects_budget().unwrap().level(), 0);
+
+    application::set_effects_budget(None);
+    assert_eq!(application::effects_scale(), 1.0);
+    assert_eq!(application::effects_scale_count(100), 100);
+
+    // Forcing the level is ignored without a budget.
+    application::set_effects_level(0);
+    assert!(application::effects_budget().is_none());
+}
@@ -0,0 +1,34 @@
+extern crate crayon;
+
+use crayon::prelude::*;
+use crayon::testing;
+use crayon::video::errors::Error;
+
+#[test]
+fn out_of_capacity() {
+    let mut params = Params::default();
+    params.settings.max_surfaces = 1;
+    params.settings.max_meshes = 1;
+    params.settings.max_textures = 1;
+    testing::setup(params).unwrap();
+
+    video::create_surface(SurfaceParams::default()).unwrap();
+    match video::create_surface(SurfaceParams::default()) {
+        Err(Error::OutOfCapacity("surfaces", 1)) => {}
+        v => panic!("unexpected {:?}", v),
+    }
+
+    video::create_mesh(MeshParams::default(), None).unwrap();
+    let err = video::create_mesh(MeshParams::default(), None).unwrap_err();
+    match err.downcast::<Error>() {
+        Ok(Error::OutOfCapacity("meshes", 1)) => {}
+        v => panic!("unexpected {:?}", v),
+    }
+
+    video::create_texture(TextureParams::default(), None).unwrap();
+    let err = video::create_texture(TextureParams::default(), None).unwrap_err();
+    match err.downcast::<Error>() {
+        Ok(Error::OutOfCapacity("textures", 1)) => {}
+        v => panic!("unexpected {:?}", v),
+    }
+}
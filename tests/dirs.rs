@@ -0,0 +1,61 @@
+extern crate crayon;
+
+#[cfg(target_os = "linux")]
+use std::ffi::OsString;
+use std::path::Path;
+
+use crayon::res::prelude::*;
+
+#[test]
+fn with_root() {
+    let dirs = UserDirs::with_root("/storage");
+    assert_eq!(dirs.path(UserDir::Saves), Path::new("/storage/saves"));
+    assert_eq!(dirs.path(UserDir::Config), Path::new("/storage/config"));
+    assert_eq!(dirs.path(UserDir::Logs), Path::new("/storage/logs"));
+    assert_eq!(
+        dirs.path(UserDir::Screenshots),
+        Path::new("/storage/screenshots")
+    );
+}
+
+#[test]
+fn empty_app() {
+    assert!(UserDirs::new("org", "").is_err());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn xdg() {
+    // The variables are injected instead of being set on the process, which would race
+    // with the other tests running in parallel.
+    let env = |name: &str| {
+        let v = match name {
+            "HOME" => "/home/crayon",
+            "XDG_CONFIG_HOME" => "/etc/xdg",
+            "XDG_STATE_HOME" => "relative",
+            _ => return None,
+        };
+
+        Some(OsString::from(v))
+    };
+
+    let dirs = UserDirs::with_env("org", "app", env).unwrap();
+    let saves = Path::new("/home/crayon/.local/share/app/saves");
+    assert_eq!(dirs.path(UserDir::Saves), saves);
+    assert_eq!(dirs.path(UserDir::Config), Path::new("/etc/xdg/app"));
+
+    // Relative paths are ignored as the XDG spec requires.
+    let logs = Path::new("/home/crayon/.local/state/app/logs");
+    assert_eq!(dirs.path(UserDir::Logs), logs);
+
+    assert!(UserDirs::with_env("org", "app", |_| None).is_err());
+}
+
+#[test]
+fn create() {
+    let root = std::env::temp_dir().join("crayon-dirs-test");
+    let dirs = UserDirs::with_root(&root);
+    let path = dirs.create(UserDir::Saves).unwrap();
+    assert!(path.is_dir());
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,52 @@
+extern crate crayon;
+
+use crayon::prelude::*;
+use crayon::testing;
+
+#[test]
+fn dynamic_mesh() {
+    testing::setup(Params::default()).unwrap();
+
+    let mut params = DynamicMeshParams::default();
+    params.layout = VertexLayout::build()
+        .with(Attribute::Position, VertexFormat::Float, 2, false)
+        .finish();
+    params.max_verts = 4;
+    params.max_idxes = 6;
+    params.frames = 2;
+
+    let mut mesh = DynamicMesh::new(params).unwrap();
+    let verts: [[f32; 2]; 3] = [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]];
+    assert!(mesh.write(IndexFormat::encode(&verts), &[0, 1, 2]).is_err());
+
+    mesh.begin();
+    mesh.write(IndexFormat::encode(&verts), &[0, 1, 2]).unwrap();
+
+    // Rejected writes leave the frame untouched.
+    let one = IndexFormat::encode(&verts[..1]);
+    assert!(mesh.write(one, &[0, 5]).is_err());
+    assert!(mesh.write(IndexFormat::encode(&verts), &[0]).is_err());
+    assert_eq!(mesh.num_verts(), 3);
+    assert_eq!(mesh.num_idxes(), 3);
+
+    mesh.write(one, &[0, u32::max_value(), 0]).unwrap();
+    assert_eq!(mesh.num_verts(), 4);
+    assert_eq!(mesh.num_idxes(), 6);
+    assert_eq!(mesh.end().unwrap(), MeshIndex::Ptr(0, 6));
+
+    // The regions of the ring buffer are used in turn.
+    mesh.begin();
+    mesh.write(IndexFormat::encode(&verts), &[0, 1, 2]).unwrap();
+    assert_eq!(mesh.end().unwrap(), MeshIndex::Ptr(6, 3));
+
+    mesh.begin();
+    mesh.write(IndexFormat::encode(&verts), &[2, 1, 0]).unwrap();
+    assert_eq!(mesh.end().unwrap(), MeshIndex::Ptr(0, 3));
+
+    // The last 16-bit index is the primitive restart index.
+    params.frames = 1;
+    params.max_verts = 65535;
+    assert!(DynamicMesh::new(params).is_err());
+    params.max_verts = 65534;
+    assert!(DynamicMesh::new(params).is_ok());
+}
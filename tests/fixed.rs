@@ -0,0 +1,139 @@
+extern crate crayon;
+
+use crayon::math::fixed::*;
+use crayon::math::prelude::{Vector3, Vector4};
+
+fn approx(lhs: Fixed, rhs: f32) -> bool {
+    (lhs.to_f32() - rhs).abs() < 0.001
+}
+
+fn approx3(lhs: FixedVector3, rhs: Vector3<f32>) -> bool {
+    approx(lhs.x, rhs.x) && approx(lhs.y, rhs.y) && approx(lhs.z, rhs.z)
+}
+
+#[test]
+fn arithmetic() {
+    let a = Fixed::from(3);
+    let b = Fixed::from_ratio(1, 2);
+
+    assert_eq!(a + b, Fixed::from_f32(3.5));
+    assert_eq!(a - b, Fixed::from_f32(2.5));
+    assert_eq!(a * b, Fixed::from_f32(1.5));
+    assert_eq!(a / b, Fixed::from(6));
+    assert_eq!(-a, Fixed::from(-3));
+    assert_eq!(Fixed::from(7) % a, Fixed::from(1));
+
+    let mut c = a;
+    c *= a;
+    c -= Fixed::ONE;
+    assert_eq!(c, Fixed::from(8));
+
+    assert_eq!(Fixed::from_bits(a.to_bits()), a);
+    assert_eq!(Fixed::from(-2).lerp(Fixed::from(2), b), Fixed::ZERO);
+}
+
+#[test]
+fn rounding() {
+    let v = Fixed::from_f32(-1.25);
+    assert_eq!(v.floor(), Fixed::from(-2));
+    assert_eq!(v.ceil(), Fixed::from(-1));
+    assert_eq!(v.round(), Fixed::from(-1));
+    assert_eq!(v.fract(), Fixed::from_f32(0.75));
+    assert_eq!(v.to_int(), -2);
+    assert_eq!(v.abs(), Fixed::from_f32(1.25));
+    assert_eq!(v.signum(), -Fixed::ONE);
+
+    assert_eq!(Fixed::from_f32(2.5).round(), Fixed::from(3));
+    assert_eq!(Fixed::from_f32(-2.5).round(), Fixed::from(-3));
+    assert_eq!(Fixed::from(5).clamp(Fixed::ZERO, Fixed::ONE), Fixed::ONE);
+}
+
+#[test]
+fn functions() {
+    assert_eq!(Fixed::from(16).sqrt(), Fixed::from(4));
+    assert!(approx(Fixed::from(2).sqrt(), 2f32.sqrt()));
+    assert_eq!(Fixed::from(-1).sqrt(), Fixed::ZERO);
+
+    for i in -20..20 {
+        let angle = i as f32 * 0.4;
+        let v = Fixed::from_f32(angle);
+        assert!(approx(v.sin(), angle.sin()), "sin({})", angle);
+        assert!(approx(v.cos(), angle.cos()), "cos({})", angle);
+    }
+
+    for &(y, x) in &[
+        (1.0, 2.0),
+        (2.0, 1.0),
+        (-1.0, 3.0),
+        (1.0, -3.0),
+        (-2.0, -1.0),
+    ] {
+        let v = Fixed::from_f32(y).atan2(Fixed::from_f32(x));
+        assert!(
+            (v.to_f32() - f32::atan2(y, x)).abs() < 0.01,
+            "atan2({}, {})",
+            y,
+            x
+        );
+    }
+}
+
+#[test]
+fn vectors() {
+    let v = FixedVector2::new(Fixed::from(3), Fixed::from(4));
+    assert_eq!(v.magnitude(), Fixed::from(5));
+    assert_eq!(v.dot(v), Fixed::from(25));
+    assert!(approx(v.normalize().magnitude(), 1.0));
+    assert_eq!(v * Fixed::from(2) - v, v);
+
+    let r = FixedVector2::new(Fixed::ONE, Fixed::ZERO).rotate(Fixed::FRAC_PI_2);
+    assert!(approx(r.x, 0.0) && approx(r.y, 1.0));
+
+    let x = FixedVector3::new(Fixed::ONE, Fixed::ZERO, Fixed::ZERO);
+    let y = FixedVector3::new(Fixed::ZERO, Fixed::ONE, Fixed::ZERO);
+    assert_eq!(
+        x.cross(y),
+        FixedVector3::new(Fixed::ZERO, Fixed::ZERO, Fixed::ONE)
+    );
+    assert_eq!((x + y).to_f32(), [1.0, 1.0, 0.0].into());
+}
+
+#[test]
+fn transforms() {
+    let v = FixedVector3::new(Fixed::from(1), Fixed::from(2), Fixed::from(3));
+
+    let q = FixedQuaternion::from_angle_y(Fixed::FRAC_PI_2);
+    let r = q * v;
+    assert!(approx3(r, v.rotate_y(Fixed::FRAC_PI_2).to_f32()));
+    assert!(approx(q.magnitude(), 1.0));
+    assert!(approx3((q * q.invert()) * v, v.to_f32()));
+
+    let m = FixedMatrix3::from(q);
+    assert!(approx3(m * v, r.to_f32()));
+    assert_eq!(m * FixedMatrix3::identity(), m);
+    assert!(approx3(m.transpose() * r, v.to_f32()));
+
+    let t = FixedTransform {
+        scale: Fixed::from(2),
+        position: FixedVector3::new(Fixed::from(10), Fixed::ZERO, Fixed::ZERO),
+        rotation: q,
+    };
+
+    let p = t.transform_point(v);
+    assert!(approx3(p, (r * Fixed::from(2) + t.position).to_f32()));
+    assert!(approx3(t.matrix3() * v + t.position, p.to_f32()));
+    assert!(approx3(t.inverse().unwrap().transform_point(p), v.to_f32()));
+    assert!(approx3(
+        (t * t.inverse().unwrap()).transform_point(v),
+        v.to_f32()
+    ));
+
+    let zero = FixedTransform {
+        scale: Fixed::ZERO,
+        ..t
+    };
+    assert!(zero.inverse().is_none());
+
+    let w = t.to_f32() * Vector4::new(1.0, 2.0, 3.0, 1.0);
+    assert!(approx3(p, w.truncate()));
+}
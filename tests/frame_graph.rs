@@ -0,0 +1,61 @@
+extern crate crayon;
+
+use crayon::prelude::*;
+use crayon::testing;
+
+#[test]
+fn capture() {
+    testing::setup(Params::default()).unwrap();
+
+    let mut params = RenderTextureParams::default();
+    params.dimensions = (16, 16).into();
+    let rt = video::create_render_texture(params).unwrap();
+
+    let mut params = SurfaceParams::default();
+    params.set_attachments(&[rt], None).unwrap();
+    let offscreen = video::create_surface(params).unwrap();
+    let surface = video::create_surface(SurfaceParams::default()).unwrap();
+
+    let mut params = ShaderParams::default();
+    params.uniforms = UniformVariableLayout::build()
+        .with("u_Texture", UniformVariableType::RenderTexture)
+        .finish();
+    let shader = video::create_shader(params, String::new(), String::new()).unwrap();
+    let mesh = video::create_mesh(MeshParams::default(), None).unwrap();
+
+    let mut cmds = CommandBuffer::new();
+    video::capture_frame_graph();
+
+    let mut dc = Draw::new(shader, mesh);
+    dc.set_uniform_variable("u_Texture", rt);
+    cmds.draw(Draw::new(shader, mesh));
+    cmds.submit(offscreen).unwrap();
+    cmds.draw(dc);
+    cmds.draw(dc);
+    cmds.submit(surface).unwrap();
+
+    assert!(video::frame_graph().is_none());
+    testing::advance().unwrap();
+
+    let graph = video::frame_graph().unwrap();
+    assert_eq!(graph.passes.len(), 2);
+    assert_eq!(graph.passes[0].surface, offscreen);
+    assert_eq!(graph.passes[0].draws, 1);
+    assert_eq!(graph.passes[0].writes, vec![rt]);
+    assert_eq!(graph.passes[1].surface, surface);
+    assert_eq!(graph.passes[1].draws, 2);
+    assert_eq!(graph.passes[1].reads, vec![rt]);
+
+    assert_eq!(graph.edges.len(), 1);
+    assert_eq!(graph.edges[0].from, 0);
+    assert_eq!(graph.edges[0].to, 1);
+    assert_eq!(graph.edges[0].texture, rt);
+
+    let dot = graph.to_dot();
+    assert!(dot.starts_with("digraph frame {"));
+    assert!(dot.contains("p0 -> p1"));
+
+    let json = graph.to_json();
+    assert!(json.contains("\"passes\""));
+    assert!(json.contains("\"edges\":[{\"from\":0,\"to\":1"));
+}
@@ -0,0 +1,66 @@
+extern crate crayon;
+
+use crayon::input::gamepad::Gamepads;
+use crayon::prelude::*;
+use crayon::testing;
+
+#[test]
+fn headless() {
+    testing::setup(Params::default()).unwrap();
+
+    assert!(!input::has_gamepad_attached());
+    assert!(input::gamepads().is_empty());
+
+    let pad = input::gamepad(0);
+    assert!(!pad.is_connected());
+    assert!(!pad.is_button_down(GamepadButton::South));
+    assert_eq!(pad.axis(GamepadAxis::LeftStickX), 0.0);
+    pad.rumble(1.0, std::time::Duration::from_millis(100));
+}
+
+#[test]
+fn buttons() {
+    let mut pads = Gamepads::new(GamepadParams::default());
+    pads.on_connected(1);
+    assert_eq!(pads.connected(), vec![1]);
+
+    pads.on_button_pressed(1, GamepadButton::South);
+    assert!(pads.is_button_down(1, GamepadButton::South));
+    assert!(pads.is_button_press(1, GamepadButton::South));
+    assert!(!pads.is_button_down(0, GamepadButton::South));
+
+    pads.advance();
+    assert!(pads.is_button_down(1, GamepadButton::South));
+    assert!(!pads.is_button_press(1, GamepadButton::South));
+
+    pads.on_button_released(1, GamepadButton::South);
+    assert!(!pads.is_button_down(1, GamepadButton::South));
+    assert!(pads.is_button_release(1, GamepadButton::South));
+
+    pads.on_button_pressed(1, GamepadButton::North);
+    pads.on_disconnected(1);
+    assert!(pads.connected().is_empty());
+    assert!(!pads.is_button_down(1, GamepadButton::North));
+}
+
+#[test]
+fn dead_zone() {
+    let mut params = GamepadParams::default();
+    params.dead_zone = 0.2;
+    params.trigger_dead_zone = 0.0;
+
+    let mut pads = Gamepads::new(params);
+    pads.on_connected(0);
+
+    pads.on_axis_changed(0, GamepadAxis::LeftStickX, 0.1);
+    assert_eq!(pads.axis(0, GamepadAxis::LeftStickX), 0.0);
+
+    pads.on_axis_changed(0, GamepadAxis::LeftStickX, -0.6);
+    assert!((pads.axis(0, GamepadAxis::LeftStickX) + 0.5).abs() < 1e-5);
+
+    pads.on_axis_changed(0, GamepadAxis::LeftStickX, 1.0);
+    assert_eq!(pads.axis(0, GamepadAxis::LeftStickX), 1.0);
+
+    pads.on_axis_changed(0, GamepadAxis::LeftTrigger, 0.1);
+    assert!((pads.axis(0, GamepadAxis::LeftTrigger) - 0.1).abs() < 1e-5);
+}
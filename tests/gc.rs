@@ -0,0 +1,71 @@
+extern crate crayon;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crayon::prelude::*;
+use crayon::testing;
+
+fn texture(name: &str) -> TextureHandle {
+    let mut params = TextureParams::default();
+    params.dimensions = (2, 2).into();
+
+    let data = TextureData {
+        bytes: vec![vec![255; 16].into_boxed_slice()],
+    };
+
+    video::create_named_texture(name, params, data).unwrap()
+}
+
+#[test]
+fn collect_garbage() {
+    testing::setup(Params::default()).unwrap();
+    res::set_deferred_release(true);
+
+    // Released resources are kept, and reused if requested again.
+    let handle = texture("gen:reused");
+    video::delete_texture(handle);
+    assert_eq!(video::texture_state(handle), ResourceState::Ok);
+    assert_eq!(video::create_texture_from("gen:reused").unwrap(), handle);
+
+    let orphan = texture("gen:orphan");
+    video::delete_texture(orphan);
+    video::delete_texture(orphan);
+
+    let counter = Arc::new(AtomicUsize::new(0));
+    let clone = counter.clone();
+    let collector = res::attach_collector(move |report| {
+        clone.fetch_add(1, Ordering::SeqCst);
+        report.add("tests", vec!["custom".to_owned()]);
+    });
+
+    let report = res::collect_garbage();
+    assert_eq!(counter.load(Ordering::SeqCst), 1);
+    assert_eq!(report.len(), 2);
+    assert_eq!(
+        report.freed,
+        vec![
+            ("tests", vec!["custom".to_owned()]),
+            ("textures", vec!["gen:orphan".to_owned()]),
+        ]
+    );
+
+    assert_eq!(video::texture_state(handle), ResourceState::Ok);
+    assert_eq!(video::texture_state(orphan), ResourceState::NotReady);
+    assert!(video::create_texture_from("gen:orphan").is_err());
+
+    res::detach_collector(collector);
+    assert!(res::collect_garbage().is_empty());
+
+    // Turning deferred release off frees the resources kept so far.
+    let kept = texture("gen:kept");
+    video::delete_texture(kept);
+    assert_eq!(video::texture_state(kept), ResourceState::Ok);
+    res::set_deferred_release(false);
+    assert!(!res::deferred_release());
+    assert_eq!(video::texture_state(kept), ResourceState::NotReady);
+
+    // Resources are freed right away without deferred release.
+    video::delete_texture(handle);
+    assert_eq!(video::texture_state(handle), ResourceState::NotReady);
+}
@@ -145,3 +145,20 @@ fn iter() {
         assert_eq!(*handle, iter.next().unwrap());
     }
 }
+
+#[test]
+fn limit() {
+    let mut set: HandlePool<Handle> = HandlePool::with_limit(2);
+    assert_eq!(set.limit(), Some(2));
+
+    let e1 = set.try_create().unwrap();
+    set.try_create().unwrap();
+    assert!(set.is_full());
+    assert_eq!(set.try_create(), Err(OutOfCapacity(2)));
+    assert_eq!(set.len(), 2);
+
+    set.free(e1);
+    assert!(!set.is_full());
+    let e3 = set.try_create().unwrap();
+    assert_eq!(e3.index(), e1.index());
+}
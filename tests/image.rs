@@ -0,0 +1,33 @@
+#![cfg(feature = "image")]
+
+extern crate crayon;
+
+use crayon::prelude::*;
+
+// A 2x1 RGBA image with an opaque red pixel and a half-transparent blue one.
+const PNG: [u8; 71] = [
+    0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52,
+    0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0xf4, 0x22, 0x7f,
+    0x8a, 0x00, 0x00, 0x00, 0x0e, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9c, 0x63, 0xf8, 0xcf, 0xc0, 0x00,
+    0x42, 0x0d, 0x00, 0x0f, 0x7a, 0x03, 0x7e, 0x77, 0xe9, 0x7f, 0x97, 0x00, 0x00, 0x00, 0x00, 0x49,
+    0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+];
+
+#[test]
+fn decode_png() {
+    let (params, data) = video::decode_image(&PNG).unwrap();
+    assert_eq!(params.format, TextureFormat::RGBA8);
+    assert_eq!(params.dimensions, (2, 1).into());
+    assert_eq!(data.bytes.len(), 1);
+    assert_eq!(&data.bytes[0][..], &[255, 0, 0, 255, 0, 0, 255, 128]);
+
+    assert!(video::decode_image(&PNG[..32]).is_err());
+}
+
+#[test]
+fn reject_huge_png() {
+    // Claims to be 65536 pixels wide, which is rejected before anything is allocated.
+    let mut bytes = PNG;
+    bytes[16..20].copy_from_slice(&[0x00, 0x01, 0x00, 0x00]);
+    assert!(video::decode_image(&bytes).is_err());
+}
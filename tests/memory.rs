@@ -0,0 +1,60 @@
+#![cfg(feature = "alloc-tracking")]
+
+extern crate crayon;
+
+use crayon::memory::{self, TrackingAllocator};
+
+#[global_allocator]
+static ALLOCATOR: TrackingAllocator = TrackingAllocator;
+
+fn usage(name: &str) -> memory::MemoryUsage {
+    memory::report()
+        .into_iter()
+        .find(|v| v.name == name)
+        .unwrap()
+}
+
+#[test]
+fn scopes() {
+    let (a, b) = {
+        let _scope = memory::scope("test-outer");
+        let a = vec![0u8; 4096];
+
+        let b = {
+            let _scope = memory::scope("test-inner");
+            vec![0u64; 1024]
+        };
+
+        (a, b)
+    };
+
+    assert!(usage("test-outer").live >= 4096);
+    assert!(usage("test-inner").live >= 8192);
+    assert!(usage("test-inner").allocs >= 1);
+
+    drop(b);
+    assert!(usage("test-inner").live < 8192);
+    assert!(usage("test-inner").peak >= 8192);
+
+    drop(a);
+    assert!(usage("test-outer").live < 4096);
+}
+
+#[test]
+fn alignment() {
+    #[repr(align(64))]
+    struct Aligned([u8; 64]);
+
+    let _scope = memory::scope("test-aligned");
+    let v: Vec<Aligned> = (0..16).map(|_| Aligned([0; 64])).collect();
+    assert_eq!(v.as_ptr() as usize % 64, 0);
+}
+
+#[test]
+fn budget() {
+    memory::set_budget("test-budget", 1024);
+    assert_eq!(usage("test-budget").budget, Some(1024));
+
+    memory::set_budget("test-budget", None);
+    assert_eq!(usage("test-budget").budget, None);
+}
@@ -0,0 +1,107 @@
+extern crate bincode;
+extern crate crayon;
+extern crate uuid;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use uuid::Uuid;
+
+use crayon::prelude::*;
+use crayon::res::manifest::{Manifest, ManifestItem, MAGIC, NAME};
+use crayon::testing::{self, MockResources};
+
+static SEQ: AtomicUsize = AtomicUsize::new(0);
+
+fn tmp(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(name);
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+fn bundle(dir: &Path, files: &[(&str, &[u8])]) -> Vec<Uuid> {
+    fs::create_dir_all(dir).unwrap();
+
+    let mut manifest = Manifest::new();
+    let mut uuids = Vec::new();
+    let no_dependencies: &[usize] = &[];
+
+    for &(filename, bytes) in files {
+        let mut raw = [0xFF; 16];
+        raw[15] = SEQ.fetch_add(1, Ordering::SeqCst) as u8;
+        let uuid = Uuid::from_slice(&raw).unwrap();
+        manifest.items.push(ManifestItem {
+            filename: manifest.buf.extend_from_str(filename),
+            dependencies: manifest.buf.extend_from_slice(no_dependencies),
+            uuid,
+        });
+
+        fs::write(dir.join(format!("{:X}", uuid.to_simple())), bytes).unwrap();
+        uuids.push(uuid);
+    }
+
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend(bincode::serialize(&manifest).unwrap());
+    fs::write(dir.join(NAME), bytes).unwrap();
+    uuids
+}
+
+fn load(filename: &str) -> Vec<u8> {
+    let bytes = Arc::new(Mutex::new(None));
+    let clone = bytes.clone();
+    crayon::res::load_from_with_callback(filename, move |rsp| {
+        *clone.lock().unwrap() = Some(rsp.unwrap());
+    })
+    .unwrap();
+
+    testing::advance_until(1000, || bytes.lock().unwrap().is_some()).unwrap();
+    let bytes = bytes.lock().unwrap().take().unwrap();
+    bytes.to_vec()
+}
+
+#[test]
+fn mods() {
+    let mut res = MockResources::new();
+    let a = res.add("a.txt", b"base");
+
+    let mut params = Params::default();
+    params.res = res.params();
+    testing::setup(params).unwrap();
+
+    let dir = tmp("crayon-mods");
+    let uuids = bundle(&dir.join("hd"), &[("a.txt", b"hd"), ("new/b.txt", b"new")]);
+    bundle(&dir.join("escape"), &[("../a.txt", b"escape")]);
+    bundle(&dir.join("large"), &[("a.txt", &[0; 32])]);
+    fs::create_dir_all(dir.join("empty")).unwrap();
+
+    let mut mods = Mods::new(&dir, "res:");
+    let names: Vec<_> = mods.list().unwrap().into_iter().map(|v| v.name).collect();
+    assert_eq!(names, vec!["escape", "hd", "large"]);
+
+    assert_eq!(mods.enable("hd").unwrap(), &["a.txt".to_owned()]);
+    assert!(mods.is_enabled("hd"));
+    assert!(mods.enable("hd").is_err());
+    assert_eq!(crayon::res::find("res:a.txt"), Some(uuids[0]));
+    assert_eq!(crayon::res::find("res:new/b.txt"), Some(uuids[1]));
+    assert_eq!(load("res:a.txt"), b"hd");
+    assert_eq!(load("res:new/b.txt"), b"new");
+
+    assert!(mods.enable("escape").is_err());
+    assert!(mods.enable("../hd").is_err());
+
+    let mut limits = ModLimits::default();
+    limits.max_file_size = 16;
+    mods.set_limits(limits);
+    assert!(mods.enable("large").is_err());
+    assert!(!mods.is_enabled("large"));
+
+    mods.disable("hd").unwrap();
+    assert!(mods.overrides("hd").is_none());
+    assert_eq!(crayon::res::find("res:a.txt"), Some(a));
+    assert_eq!(crayon::res::find("res:new/b.txt"), None);
+    assert_eq!(load("res:a.txt"), b"base");
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,32 @@
+extern crate crayon;
+
+use crayon::prelude::*;
+use crayon::testing;
+
+#[test]
+fn named_texture() {
+    testing::setup(Params::default()).unwrap();
+
+    let mut params = TextureParams::default();
+    params.dimensions = (2, 2).into();
+
+    let data = TextureData {
+        bytes: vec![vec![255; 16].into_boxed_slice()],
+    };
+
+    let handle = video::create_named_texture("gen:white", params, data).unwrap();
+    assert_eq!(video::texture_state(handle), ResourceState::Ok);
+    assert!(video::create_named_texture("gen:white", params, None).is_err());
+
+    let shared = video::create_texture_from("gen:white").unwrap();
+    assert_eq!(handle, shared);
+
+    video::delete_texture(handle);
+    assert_eq!(video::texture_state(shared), ResourceState::Ok);
+
+    video::delete_texture(shared);
+    assert!(video::create_texture_from("gen:white").is_err());
+
+    let handle = video::create_named_texture("gen:white", params, None).unwrap();
+    assert_eq!(video::texture_state(handle), ResourceState::Ok);
+}
@@ -41,3 +41,15 @@ fn iterator() {
         assert_eq!(v, (i + 1) as i32);
     }
 }
+
+#[test]
+fn limit() {
+    let mut set = ObjectPool::<Handle, i32>::with_limit(1);
+    let e1 = set.try_create(1).unwrap();
+    assert!(set.is_full());
+    assert_eq!(set.try_create(2), Err(OutOfCapacity(1)));
+
+    assert_eq!(set.free(e1), Some(1));
+    let e2 = set.try_create(3).unwrap();
+    assert_eq!(set.get(e2), Some(&3));
+}
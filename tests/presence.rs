@@ -0,0 +1,95 @@
+#![cfg(all(feature = "discord", unix))]
+
+extern crate crayon;
+
+use std::io::{Read, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use crayon::prelude::*;
+use crayon::testing;
+
+fn read(stream: &mut UnixStream) -> (u32, String) {
+    let mut header = [0; 8];
+    stream.read_exact(&mut header).unwrap();
+
+    let op = u32::from(header[0]) | u32::from(header[1]) << 8;
+    let len = u32::from(header[4]) | u32::from(header[5]) << 8;
+    let mut payload = vec![0; len as usize];
+    stream.read_exact(&mut payload).unwrap();
+    (op, String::from_utf8(payload).unwrap())
+}
+
+fn write(stream: &mut UnixStream, payload: &str) {
+    let len = payload.len();
+    let header = [1, 0, 0, 0, len as u8, (len >> 8) as u8, 0, 0];
+    stream.write_all(&header).unwrap();
+    stream.write_all(payload.as_bytes()).unwrap();
+}
+
+#[test]
+fn presence() {
+    let dir = std::env::temp_dir().join("crayon-presence");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    // A stale socket which closes the connections before the handshake, so the next
+    // socket is tried.
+    let stale = UnixListener::bind(dir.join("discord-ipc-0")).unwrap();
+    std::thread::spawn(move || {
+        for stream in stale.incoming() {
+            drop(stream);
+        }
+    });
+
+    // Fakes the Discord client.
+    let listener = UnixListener::bind(dir.join("discord-ipc-1")).unwrap();
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let (op, handshake) = read(&mut stream);
+        assert_eq!(op, 0);
+        assert!(handshake.contains("\"client_id\":\"42\""));
+        write(&mut stream, r#"{"cmd":"DISPATCH","evt":"READY"}"#);
+
+        loop {
+            let (op, frame) = read(&mut stream);
+            assert_eq!(op, 1);
+            write(&mut stream, r#"{"cmd":"SET_ACTIVITY","evt":null}"#);
+            tx.send(frame).unwrap();
+        }
+    });
+
+    testing::setup(Params::default()).unwrap();
+    assert!(Presence::new("not a number", || None).is_err());
+
+    let presence = Presence::with_ipc_dir("42", Some(dir), || {
+        Some(Activity {
+            state: Some("In a match".to_owned()),
+            party: Some((2, 4)),
+            ..Default::default()
+        })
+    })
+    .unwrap();
+
+    let mut frame = None;
+    for _ in 0..500 {
+        testing::advance().unwrap();
+        if let Ok(v) = rx.recv_timeout(Duration::from_millis(10)) {
+            frame = Some(v);
+            break;
+        }
+    }
+
+    let frame = frame.unwrap();
+    assert!(frame.contains("\"cmd\":\"SET_ACTIVITY\""));
+    assert!(frame.contains("\"state\":\"In a match\""));
+    assert!(frame.contains("\"size\":[2,4]"));
+    assert!(presence.is_connected());
+
+    // Clears the presence once it's dropped.
+    drop(presence);
+    let frame = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert!(frame.contains("\"activity\":null"));
+}
@@ -0,0 +1,59 @@
+extern crate crayon;
+
+use crayon::prelude::*;
+use crayon::testing;
+
+#[test]
+fn reconfigure() {
+    testing::setup(Params::default()).unwrap();
+
+    let mut params = RenderTextureParams::default();
+    params.dimensions = (16, 16).into();
+    params.fit_window = true;
+    let fit = video::create_render_texture(params).unwrap();
+
+    params.fit_window = false;
+    let fixed = video::create_render_texture(params).unwrap();
+
+    let mut params = SurfaceParams::default();
+    params.set_attachments(&[fit], None).unwrap();
+    let surface = video::create_surface(params).unwrap();
+    testing::advance().unwrap();
+
+    let mut params = video::ReconfigureParams {
+        dimensions: (64, 32).into(),
+        multisample: window::multisample(),
+    };
+
+    video::reconfigure(params).unwrap();
+    assert_eq!(
+        video::render_texture(fit).unwrap().dimensions,
+        (64, 32).into()
+    );
+    assert_eq!(
+        video::render_texture(fixed).unwrap().dimensions,
+        (16, 16).into()
+    );
+
+    // The handles stay valid, and draw calls could be submitted to the recreated surface
+    // in the same frame.
+    assert!(video::surface(surface).is_some());
+    assert_eq!(video::render_texture_state(fit), ResourceState::Ok);
+
+    let shader = video::create_shader(ShaderParams::default(), String::new(), String::new());
+    let shader = shader.unwrap();
+    let mesh = video::create_mesh(MeshParams::default(), None).unwrap();
+
+    let mut cmds = CommandBuffer::new();
+    cmds.draw(Draw::new(shader, mesh));
+    cmds.submit(surface).unwrap();
+    testing::advance().unwrap();
+
+    // Nothing is recreated if the dimensions are unchanged.
+    video::reconfigure(params).unwrap();
+    testing::advance().unwrap();
+
+    // The multisampling level of window is fixed.
+    params.multisample += 4;
+    assert!(video::reconfigure(params).is_err());
+}
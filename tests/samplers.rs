@@ -0,0 +1,38 @@
+extern crate crayon;
+
+use crayon::prelude::*;
+use crayon::testing;
+
+#[test]
+fn sampler_objects() {
+    testing::setup(Params::default()).unwrap();
+
+    let mut params = SamplerParams::default();
+    params.filter = TextureFilter::Nearest;
+    params.compare = Some(Comparison::LessOrEqual);
+
+    let sampler = video::create_sampler(params).unwrap();
+    assert_eq!(video::sampler(sampler), Some(params));
+
+    let mut params = ShaderParams::default();
+    params.uniforms = UniformVariableLayout::build()
+        .with("u_Texture", UniformVariableType::Texture)
+        .finish();
+
+    let shader = video::create_shader(params, String::new(), String::new()).unwrap();
+    let mesh = video::create_mesh(MeshParams::default(), None).unwrap();
+    let texture = video::create_texture(TextureParams::default(), None).unwrap();
+    let surface = video::create_surface(SurfaceParams::default()).unwrap();
+
+    let mut dc = Draw::new(shader, mesh);
+    dc.set_uniform_variable("u_Texture", (texture, sampler));
+
+    let mut cmds = CommandBuffer::new();
+    cmds.draw(dc);
+    cmds.submit(surface).unwrap();
+    testing::advance().unwrap();
+
+    video::delete_sampler(sampler);
+    assert_eq!(video::sampler(sampler), None);
+    testing::advance().unwrap();
+}
@@ -0,0 +1,59 @@
+extern crate crayon;
+
+use std::fs;
+use std::path::PathBuf;
+
+use crayon::res::prelude::*;
+use crayon::res::saves::write_atomic;
+
+fn tmp(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(name);
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn atomic() {
+    let dir = tmp("crayon-saves-atomic");
+    fs::create_dir_all(&dir).unwrap();
+
+    let path = dir.join("settings.json");
+    write_atomic(&path, b"old").unwrap();
+    write_atomic(&path, b"new").unwrap();
+    assert_eq!(fs::read(&path).unwrap(), b"new");
+
+    // No temporary files are left behind.
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn slots() {
+    let dir = tmp("crayon-saves-slots");
+    let slots = SaveSlots::new(&dir);
+    assert!(slots.list().unwrap().is_empty());
+
+    slots.save("slot0", b"level1", None).unwrap();
+    slots.save("slot1", b"level2", Some(b"png")).unwrap();
+    assert!(slots.save("../slot2", b"", None).is_err());
+    assert!(slots.save("", b"", None).is_err());
+
+    let list = slots.list().unwrap();
+    assert_eq!(list.len(), 2);
+
+    let slot = slots.get("slot1").unwrap().unwrap();
+    assert_eq!(slot.size, 6);
+    assert!(slot.thumbnail);
+    assert_eq!(slots.load("slot1").unwrap(), b"level2");
+    assert_eq!(slots.load_thumbnail("slot1").unwrap().unwrap(), b"png");
+
+    // Overwriting without a thumbnail removes the old one.
+    slots.save("slot1", b"level3", None).unwrap();
+    assert!(slots.load_thumbnail("slot1").unwrap().is_none());
+
+    slots.delete("slot0").unwrap();
+    assert!(slots.get("slot0").unwrap().is_none());
+    assert_eq!(slots.list().unwrap().len(), 1);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
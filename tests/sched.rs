@@ -0,0 +1,39 @@
+extern crate crayon;
+
+use std::sync::mpsc;
+
+use crayon::prelude::*;
+use crayon::testing;
+
+#[test]
+fn num_workers() {
+    let mut params = SchedParams::default();
+    assert_eq!(params.num_workers(Some(3)), 3);
+
+    params.max_workers = 2;
+    assert!(params.num_workers(None) >= 1 && params.num_workers(None) <= 2);
+
+    params.reserved_cores = 1024;
+    assert_eq!(params.num_workers(None), 1);
+}
+
+#[test]
+fn named_workers() {
+    let mut params = Params::default();
+    params.settings.worker_threads = Some(2);
+    params.sched.name = Some("worker".to_owned());
+    params.sched.priority = ThreadPriority::Low;
+    params.sched.pin_workers = true;
+    testing::setup(params).unwrap();
+
+    assert_eq!(sched::num_workers(), 2);
+
+    let (tx, rx) = mpsc::channel();
+    sched::spawn(move || {
+        let name = std::thread::current().name().map(|v| v.to_owned());
+        tx.send(name).unwrap();
+    });
+
+    let name = rx.recv().unwrap().unwrap();
+    assert!(name == "worker-0" || name == "worker-1");
+}
@@ -0,0 +1,161 @@
+extern crate crayon;
+
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crayon::application::prelude::*;
+use crayon::errors::Result;
+use crayon::sched::prelude::{Latch, SpinLatch};
+
+struct Recorder {
+    name: &'static str,
+    logs: Arc<Mutex<Vec<String>>>,
+}
+
+impl Recorder {
+    fn new(name: &'static str, logs: &Arc<Mutex<Vec<String>>>) -> Box<Self> {
+        Box::new(Recorder {
+            name,
+            logs: logs.clone(),
+        })
+    }
+
+    fn log(&self, event: &str) {
+        let v = format!("{}:{}", self.name, event);
+        self.logs.lock().unwrap().push(v);
+    }
+}
+
+impl State for Recorder {
+    fn on_enter(&mut self) -> Result<()> {
+        self.log("enter");
+        Ok(())
+    }
+
+    fn on_exit(&mut self) -> Result<()> {
+        self.log("exit");
+        Ok(())
+    }
+
+    fn on_pause(&mut self) -> Result<()> {
+        self.log("pause");
+        Ok(())
+    }
+
+    fn on_resume(&mut self) -> Result<()> {
+        self.log("resume");
+        Ok(())
+    }
+}
+
+#[test]
+fn stack() {
+    let logs = Arc::new(Mutex::new(Vec::new()));
+    let mut states = StateMachine::new(*Recorder::new("menu", &logs));
+    states.on_update().unwrap();
+    assert_eq!(states.len(), 1);
+
+    states.switch(Recorder::new("game", &logs)).unwrap();
+    states.push(Recorder::new("pause", &logs)).unwrap();
+    assert_eq!(states.len(), 2);
+
+    states.pop().unwrap();
+    states.clear().unwrap();
+    assert!(states.is_empty());
+
+    let expected = [
+        "menu:enter",
+        "menu:exit",
+        "game:enter",
+        "game:pause",
+        "pause:enter",
+        "pause:exit",
+        "game:resume",
+        "game:exit",
+    ];
+
+    assert_eq!(*logs.lock().unwrap(), expected);
+}
+
+#[test]
+fn preload() {
+    let logs = Arc::new(Mutex::new(Vec::new()));
+    let latch = Arc::new(SpinLatch::new());
+
+    let level = logs.clone();
+    let mut states = StateMachine::new(Preload::new(latch.clone(), move |_| {
+        let state: Box<dyn State + Send> = Recorder::new("level", &level);
+        Ok(state)
+    }));
+
+    states.on_update().unwrap();
+    states.on_update().unwrap();
+    assert_eq!(states.len(), 1);
+    assert!(logs.lock().unwrap().is_empty());
+
+    latch.set();
+    states.on_update().unwrap();
+    assert_eq!(states.len(), 1);
+    assert_eq!(*logs.lock().unwrap(), ["level:enter"]);
+
+    states.on_update().unwrap();
+    assert_eq!(*logs.lock().unwrap(), ["level:enter"]);
+}
+
+#[test]
+fn preload_error() {
+    let latch = Arc::new(SpinLatch::new());
+    let mut states = StateMachine::new(Preload::new(latch.clone(), |_| {
+        let err = io::Error::new(io::ErrorKind::NotFound, "Missing level.");
+        Err(err.into())
+    }));
+
+    states.on_update().unwrap();
+    latch.set();
+    assert!(states.on_update().is_err());
+    assert!(states.on_update().is_err());
+    assert_eq!(states.len(), 1);
+}
+
+#[test]
+fn fade() {
+    let logs = Arc::new(Mutex::new(Vec::new()));
+    let latch = Arc::new(SpinLatch::new());
+    let dt = Duration::from_millis(100);
+
+    let mut states = StateMachine::new(*Recorder::new("menu", &logs));
+    states.update(dt).unwrap();
+    assert_eq!(states.fade_alpha(), 0.0);
+
+    let level = logs.clone();
+    let preload = Preload::new(latch.clone(), move |_| {
+        let state: Box<dyn State + Send> = Recorder::new("level", &level);
+        Ok(state)
+    });
+
+    states.fade(0.2, Box::new(preload));
+    states.update(dt).unwrap();
+    assert_eq!(states.fade_alpha(), 0.5);
+    assert_eq!(*logs.lock().unwrap(), ["menu:enter"]);
+
+    // Stays black until the resources are loaded.
+    states.update(dt).unwrap();
+    assert_eq!(states.fade_alpha(), 1.0);
+    assert_eq!(*logs.lock().unwrap(), ["menu:enter", "menu:exit"]);
+    states.update(dt).unwrap();
+    assert_eq!(states.fade_alpha(), 1.0);
+
+    latch.set();
+    states.update(dt).unwrap();
+    assert_eq!(states.fade_alpha(), 1.0);
+    assert_eq!(
+        *logs.lock().unwrap(),
+        ["menu:enter", "menu:exit", "level:enter"]
+    );
+
+    states.update(dt).unwrap();
+    assert_eq!(states.fade_alpha(), 0.5);
+    states.update(dt).unwrap();
+    assert_eq!(states.fade_alpha(), 0.0);
+}
@@ -0,0 +1,26 @@
+extern crate crayon;
+
+use crayon::prelude::*;
+use crayon::testing;
+
+#[test]
+fn collect() {
+    let info = application::system_info();
+    assert_eq!(info.renderer, None);
+    assert!(info.to_string().contains("Renderer: unknown"));
+
+    testing::setup(Params::default()).unwrap();
+
+    let info = application::system_info();
+    assert_eq!(info.renderer, Some("Headless".to_owned()));
+    assert!(!info.os.is_empty());
+
+    #[cfg(target_os = "linux")]
+    {
+        assert!(info.cpu_cores.unwrap() > 0);
+        assert!(info.total_memory.unwrap() >= info.available_memory.unwrap());
+    }
+
+    let desc = info.to_string();
+    assert!(desc.contains("Renderer: Headless"));
+}
@@ -0,0 +1,88 @@
+extern crate crayon;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crayon::prelude::*;
+use crayon::testing::{self, MockResources, World};
+
+#[test]
+fn mock_resources() {
+    let mut res = MockResources::new();
+    let a = res.add("a.txt", b"hello");
+    let b = res.add("dir/b.txt", b"world");
+    assert_ne!(a, b);
+
+    let mut params = Params::default();
+    params.res = res.params();
+    testing::setup(params).unwrap();
+
+    assert_eq!(crayon::res::find("res:a.txt"), Some(a));
+    assert_eq!(crayon::res::find("res:dir/b.txt"), Some(b));
+    assert_eq!(crayon::res::find("res:c.txt"), None);
+
+    let bytes = Arc::new(Mutex::new(None));
+    let clone = bytes.clone();
+    crayon::res::load_from_with_callback("res:dir/b.txt", move |rsp| {
+        *clone.lock().unwrap() = Some(rsp.unwrap());
+    })
+    .unwrap();
+
+    testing::advance_until(1000, || bytes.lock().unwrap().is_some()).unwrap();
+    let bytes = bytes.lock().unwrap().take().unwrap();
+    assert_eq!(&bytes[..], b"world");
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Position(f32);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Velocity(f32);
+
+#[test]
+fn world() {
+    let mut world = World::new();
+    world.register::<Position>().register::<Velocity>();
+
+    let e1 = world.create();
+    world.add(e1, Position(0.0));
+    world.add(e1, Velocity(2.0));
+
+    let e2 = world.create();
+    world.add(e2, Position(1.0));
+
+    let mut order = Vec::new();
+    let mut movement = |world: &mut World, dt: Duration| {
+        let dt = dt.as_secs() as f32 + dt.subsec_millis() as f32 / 1000.0;
+        let velocities: Vec<_> = world.iter::<Velocity>().map(|(k, v)| (k, *v)).collect();
+        for (ent, v) in velocities {
+            if let Some(p) = world.get_mut::<Position>(ent) {
+                p.0 += v.0 * dt;
+            }
+        }
+
+        order.extend(world.iter::<Position>().map(|(k, _)| k));
+    };
+
+    world.run(&mut movement, 4, Duration::from_millis(500));
+    world.assert_component(e1, &Position(4.0));
+    world.assert_component(e2, &Position(1.0));
+    assert_eq!(order, [e1, e2, e1, e2, e1, e2, e1, e2]);
+
+    assert_eq!(world.remove::<Velocity>(e1), Some(Velocity(2.0)));
+    assert!(world.delete(e2));
+    assert!(!world.contains(e2));
+    assert_eq!(world.get::<Position>(e2), None);
+    assert_eq!(world.iter::<Position>().count(), 1);
+}
+
+#[test]
+#[should_panic]
+fn world_assert_component() {
+    let mut world = World::new();
+    world.register::<Position>();
+
+    let ent = world.create();
+    world.add(ent, Position(1.0));
+    world.assert_component(ent, &Position(2.0));
+}
@@ -0,0 +1,30 @@
+extern crate crayon;
+
+use crayon::prelude::*;
+use crayon::testing;
+
+#[test]
+fn layer_uploads() {
+    testing::setup(Params::default()).unwrap();
+
+    let mut params = TextureArrayParams::default();
+    params.dimensions = (4, 4).into();
+    params.layers = 2;
+
+    let mut slots = TextureSlots::new(params).unwrap();
+    let layer = vec![0u8; 4 * 4 * 4];
+
+    let a = slots.alloc(&layer).unwrap();
+    assert!(slots.alloc(&layer[1..]).is_err());
+    assert!(slots.alloc(&[0u8; 4 * 4 * 4 + 1]).is_err());
+    assert_eq!(slots.len(), 1);
+
+    let b = slots.alloc(&layer).unwrap();
+    assert_ne!(a, b);
+    assert!(slots.alloc(&layer).is_err());
+
+    assert!(slots.update(a, &layer[..8]).is_err());
+    assert!(video::update_texture_array(slots.handle(), 0, &[]).is_err());
+    assert!(video::update_texture_array(slots.handle(), 2, &layer).is_err());
+    testing::advance().unwrap();
+}
@@ -0,0 +1,41 @@
+extern crate crayon;
+
+use crayon::prelude::*;
+use crayon::testing;
+
+#[test]
+fn strict_uniforms() {
+    testing::setup(Params::default()).unwrap();
+
+    let mut params = ShaderParams::default();
+    params.uniforms = UniformVariableLayout::build()
+        .with("u_Color", UniformVariableType::Vector4f)
+        .finish();
+
+    let shader = video::create_shader(params, String::new(), String::new()).unwrap();
+    let mesh = video::create_mesh(MeshParams::default(), None).unwrap();
+    let surface = video::create_surface(SurfaceParams::default()).unwrap();
+
+    let mut cmds = CommandBuffer::new();
+
+    let mut dc = Draw::new(shader, mesh);
+    dc.set_uniform_variable("u_Color", [1.0f32, 1.0, 1.0, 1.0]);
+    cmds.draw(dc);
+    assert!(cmds.submit(surface).is_ok());
+
+    let mut dc = Draw::new(shader, mesh);
+    dc.set_uniform_variable("u_Colour", [1.0f32, 1.0, 1.0, 1.0]);
+    cmds.draw(dc);
+    assert!(cmds.submit(surface).is_err());
+
+    video::set_strict_uniforms(false);
+    cmds.draw(dc);
+    assert!(cmds.submit(surface).is_ok());
+
+    let mut dc = Draw::new(shader, mesh);
+    dc.set_uniform_variable("u_Color", 1.0f32);
+    cmds.draw(dc);
+    assert!(cmds.submit(surface).is_err());
+
+    testing::advance().unwrap();
+}
@@ -0,0 +1,52 @@
+extern crate crayon;
+
+use crayon::prelude::*;
+use crayon::testing;
+
+const BUDGET: usize = 600 * 1024;
+
+#[test]
+fn staged_uploads() {
+    testing::setup(Params::default()).unwrap();
+    video::set_texture_upload_budget(BUDGET);
+
+    let mut params = TextureParams::default();
+    params.hint = TextureHint::Stream;
+    params.dimensions = (512, 512).into();
+
+    let texture = video::create_texture(params, None).unwrap();
+    testing::advance_until(100, || video::texture_state(texture) == ResourceState::Ok).unwrap();
+
+    // Each band of 512x128 pixels is staged, and two of them fit in the budget of a frame.
+    let band = params.format.size((512, 128).into()) as usize;
+    let bytes = vec![0u8; band];
+    for i in 0..4 {
+        let area = Aabb2::new(Point2::new(0, i * 128), Point2::new(512, (i + 1) * 128));
+        video::update_texture(texture, area, &bytes).unwrap();
+    }
+
+    let mut uploads = Vec::new();
+    testing::advance_until(10, || {
+        let bytes = video::uploaded_texture_bytes();
+        if bytes > 0 {
+            uploads.push(bytes);
+        }
+
+        uploads.len() >= 2
+    })
+    .unwrap();
+
+    assert_eq!(uploads, [band * 2, band * 2]);
+
+    testing::advance().unwrap();
+    assert_eq!(video::uploaded_texture_bytes(), 0);
+
+    // A single upload over the budget is still made in one frame to make progress.
+    let area = Aabb2::new(Point2::new(0, 0), Point2::new(512, 512));
+    let bytes = vec![0u8; band * 4];
+    video::update_texture(texture, area, &bytes).unwrap();
+    testing::advance_until(10, || video::uploaded_texture_bytes() == band * 4).unwrap();
+
+    video::delete_texture(texture);
+    testing::advance().unwrap();
+}
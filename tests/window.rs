@@ -0,0 +1,20 @@
+extern crate crayon;
+
+use crayon::prelude::*;
+use crayon::testing;
+
+#[test]
+fn switch_mode() {
+    testing::setup(Params::default()).unwrap();
+
+    assert_eq!(window::mode(), WindowMode::Windowed);
+    assert!(window::monitors().is_empty());
+
+    window::set_mode(WindowMode::Fullscreen(None)).unwrap();
+    assert_eq!(window::mode(), WindowMode::Fullscreen(None));
+    testing::advance().unwrap();
+
+    window::set_mode(WindowMode::Borderless).unwrap();
+    assert_eq!(window::mode(), WindowMode::Borderless);
+    testing::advance().unwrap();
+}